@@ -1,6 +1,9 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
-use wasm::{hooks::HookSet, validate, RuntimeInstance, DEFAULT_MODULE};
+use wasm::{
+    hooks::{HookAction, HookSet},
+    validate, RuntimeInstance, DEFAULT_MODULE,
+};
 
 fn criterion_benchmark(c: &mut Criterion) {
     let wat = r#"
@@ -44,10 +47,16 @@ fn criterion_benchmark(c: &mut Criterion) {
     #[derive(Default)]
     struct MyCustomHookSet;
     impl HookSet for MyCustomHookSet {
-        fn instruction_hook(&mut self, bytecode: &[u8], pc: usize) {
+        fn instruction_hook(
+            &mut self,
+            _module_idx: usize,
+            bytecode: &[u8],
+            pc: usize,
+        ) -> HookAction {
             if black_box(bytecode[pc]) == 0x20 {
                 eprintln!("First instruction is a local.get");
             }
+            HookAction::Continue
         }
     }
 
@@ -2,21 +2,24 @@ use alloc::format;
 use alloc::string::{String, ToString};
 
 use crate::core::indices::GlobalIdx;
+use crate::execution::value::ExnAddr;
 use crate::validation_stack::ValidationStackEntry;
 use crate::RefType;
 use core::fmt::{Display, Formatter};
 use core::str::Utf8Error;
 
 use crate::core::reader::section_header::SectionTy;
+use crate::core::reader::types::proposal::Proposal;
 use crate::core::reader::types::ValType;
 
-use super::indices::{DataIdx, ElemIdx, FuncIdx, MemIdx, TableIdx, TypeIdx};
+use super::indices::{DataIdx, ElemIdx, FuncIdx, LabelIdx, MemIdx, TableIdx, TagIdx, TypeIdx};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum RuntimeError {
     DivideBy0,
     UnrepresentableResult,
     FunctionNotFound,
+    /// The maximum call depth or value stack size was exceeded
     StackSmash,
     // https://github.com/wasmi-labs/wasmi/blob/37d1449524a322817c55026eb21eb97dd693b9ce/crates/core/src/trap.rs#L265C5-L265C27
     BadConversionToInteger,
@@ -29,23 +32,47 @@ pub enum RuntimeError {
     ModuleNotFound,
     UnmetImport,
     UndefinedTableIndex,
+    HostFunctionSignatureMismatch,
+    OutOfFuel,
+    /// The effective address of an atomic memory access is not a multiple of the accessed size
+    UnalignedAtomic,
+    /// `memory.atomic.wait32` or `memory.atomic.wait64` was executed on a memory which is not shared
+    ExpectedSharedMemory,
+    /// `memory.atomic.wait32` or `memory.atomic.wait64` would have to block, but no
+    /// [Waiter](crate::execution::threads::Waiter) is configured
+    WaiterNotConfigured,
+    /// No memory with the given name is exported by the module, or it is not shared
+    MemoryNotFound,
+    /// An exception was thrown, but not caught by any `try_table`
+    ///
+    /// It can be decoded via [RuntimeInstance::exception](crate::RuntimeInstance::exception).
+    UncaughtException(ExnAddr),
+    /// No tag with the given name is exported by the module
+    TagNotFound,
+    /// `throw_ref` was executed with a null reference
+    NullExceptionReference,
+    /// `throw_ref` was executed with a reference to an exception which was reclaimed, as it was only referenced outside
+    /// of the running invocation, see [RuntimeInstance::exception](crate::RuntimeInstance::exception)
+    ReclaimedExceptionReference,
+    /// A [HookSet](crate::hooks::HookSet) hook returned [HookAction::Trap](crate::hooks::HookAction::Trap) with this
+    /// reason
+    HookTrap(String),
     // "undefined element" <- as-call_indirect-last
     // "unreachable"
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
-pub enum Proposal {
-    Memory64,
-    MultipleMemories,
-    Threads,
-}
-
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub enum StoreInstantiationError {
     ActiveDataWriteOutOfBounds,
     I64ValueOutOfReach(String),
     MissingValueOnTheStack,
     TooManyMemories(usize),
+    /// No module with the given name exports an entity with the given name
+    UnknownImport(String, String),
+    /// The exported entity does not match the type of the import
+    IncompatibleImportType(String, String),
+    /// The host can not provide a memory with the given minimum number of pages
+    OutOfMemory(u64),
 }
 
 #[derive(Debug, PartialEq, Eq, Clone)]
@@ -68,7 +95,7 @@ pub enum Error {
     InvalidImportDesc(u8),
     ExprMissingEnd,
     InvalidInstr(u8),
-    InvalidMultiByteInstr(u8, u8),
+    InvalidMultiByteInstr(u8, u32),
     EndInvalidValueStack,
     InvalidLocalIdx,
     InvalidValidationStackValType(Option<ValType>),
@@ -76,15 +103,22 @@ pub enum Error {
     ExpectedAnOperand,
     InvalidLimitsType(u8),
     InvalidMutType(u8),
-    MoreThanOneMemory,
     InvalidLimit,
     MemSizeTooBig,
+    /// A shared memory does not declare a maximum size
+    SharedMemoryWithoutMaximum,
     InvalidGlobalIdx(GlobalIdx),
     GlobalIsConst,
+    GlobalIsMutable,
     RuntimeError(RuntimeError),
     MemoryIsNotDefined(MemIdx),
     //           mem.align, wanted alignment
     ErroneousAlignment(u32, u32),
+    /// The offset of a memory instruction accessing a 32-bit memory does not fit into 32 bits
+    MemArgOffsetTooBig(u64),
+    /// A lane index of a vector instruction is not smaller than the number of lanes
+    //             lane idx, number of lanes
+    InvalidLaneIdx(u8, u8),
     NoDataSegments,
     DataSegmentNotFound(DataIdx),
     InvalidLabelIdx(usize),
@@ -100,11 +134,36 @@ pub enum Error {
     FunctionIsNotDefined(FuncIdx),
     ReferencingAnUnreferencedFunction(FuncIdx),
     FunctionTypeIsNotDefined(TypeIdx),
+    /// The attribute of a tag type is not `0x00`, which is the only attribute defined so far
+    InvalidTagAttribute(u8),
+    TagIsNotDefined(TagIdx),
+    /// The function type of a tag has results, although exceptions only carry parameters
+    TagTypeHasResults(TypeIdx),
+    InvalidCatchClause(u8),
+    /// The values pushed by a catch clause of a `try_table` do not match the types of the targeted label
+    CatchTypeMismatch(LabelIdx),
+    /// The callee of a tail call does not return the same types as the calling function
+    TailCallTypeMismatch,
     StoreInstantiationError(StoreInstantiationError),
     OnlyFuncRefIsAllowed,
     TypeUnificationMismatch,
     InvalidSelectTypeVector,
     TooManyLocals(usize),
+    /// The custom section `name` is malformed, which does not make the module invalid
+    MalformedNameSection,
+    /// The DWARF debug information in the custom sections is malformed, which does not make the module invalid
+    MalformedDwarf,
+    /// The module is larger than allowed by the [crate::ValidationConfig], in bytes
+    ModuleTooLarge(usize),
+    /// The module defines or imports more functions than allowed by the [crate::ValidationConfig]
+    TooManyFunctions(usize),
+    /// The table can grow beyond the size allowed by the [crate::ValidationConfig]
+    TableTooLarge(TableIdx),
+    /// The memory can grow beyond the number of pages allowed by the [crate::ValidationConfig]
+    MemoryTooLarge(MemIdx),
+    /// Blocks are nested deeper than allowed by the [crate::ValidationConfig]
+    NestingTooDeep(usize),
+    /// The module uses a proposal which is not enabled, see [crate::ValidationInfo::check_proposals]
     UnsupportedProposal(Proposal),
     Overflow,
 }
@@ -179,15 +238,20 @@ impl Display for Error {
             Error::InvalidMutType(byte) => f.write_fmt(format_args!(
                 "An invalid mut/const byte was found: {byte:#x?}"
             )),
-            Error::MoreThanOneMemory => {
-                f.write_str("As of not only one memory is allowed per module.")
-            }
             Error::InvalidLimit => f.write_str("Size minimum must not be greater than maximum"),
-            Error::MemSizeTooBig => f.write_str("Memory size must be at most 65536 pages (4GiB)"),
+            Error::MemSizeTooBig => f.write_str(
+                "Memory size must be at most 65536 pages (4GiB), or 2^48 pages for 64-bit memories",
+            ),
+            Error::SharedMemoryWithoutMaximum => {
+                f.write_str("A shared memory must declare a maximum size")
+            }
             Error::InvalidGlobalIdx(idx) => f.write_fmt(format_args!(
                 "An invalid global index `{idx}` was specified"
             )),
             Error::GlobalIsConst => f.write_str("A const global cannot be written to"),
+            Error::GlobalIsMutable => {
+                f.write_str("A mutable global cannot be read in a constant expression")
+            }
             Error::RuntimeError(err) => err.fmt(f),
             Error::ExpectedAnOperand => f.write_str("Expected a ValType"), // Error => f.write_str("Expected an operand (ValType) on the stack")
             Error::MemoryIsNotDefined(memidx) => f.write_fmt(format_args!(
@@ -200,6 +264,12 @@ impl Display for Error {
                     mem_align, minimum_wanted_alignment
                 ))
             }
+            Error::MemArgOffsetTooBig(offset) => f.write_fmt(format_args!(
+                "The offset {offset} is too big for a 32-bit memory"
+            )),
+            Error::InvalidLaneIdx(lane_idx, num_lanes) => f.write_fmt(format_args!(
+                "The lane index {lane_idx} is out of bounds for a vector of {num_lanes} lanes"
+            )),
             Error::NoDataSegments => f.write_str("Data Count is None"),
             Error::DataSegmentNotFound(data_idx) => {
                 f.write_fmt(format_args!("Data Segment {} not found", data_idx))
@@ -249,6 +319,25 @@ impl Display for Error {
                 "C.fn_types[{}] is NOT defined when it should be",
                 func_ty_idx
             )),
+            Error::InvalidTagAttribute(attribute) => f.write_fmt(format_args!(
+                "The tag attribute {attribute:#x} is invalid, only exceptions (0x00) are supported"
+            )),
+            Error::TagIsNotDefined(tag_idx) => f.write_fmt(format_args!(
+                "C.tags[{}] is NOT defined when it should be",
+                tag_idx
+            )),
+            Error::TagTypeHasResults(type_idx) => f.write_fmt(format_args!(
+                "The type {type_idx} of a tag must not have any results"
+            )),
+            Error::InvalidCatchClause(clause) => f.write_fmt(format_args!(
+                "The catch clause {clause:#x} of a try_table is invalid"
+            )),
+            Error::CatchTypeMismatch(label_idx) => f.write_fmt(format_args!(
+                "The values of a catch clause do not match the types of the label {label_idx}"
+            )),
+            Error::TailCallTypeMismatch => f.write_str(
+                "The callee of a tail call does not return the same types as the calling function",
+            ),
             Error::StoreInstantiationError(err) => err.fmt(f),
             Error::OnlyFuncRefIsAllowed => f.write_str("Only FuncRef is allowed"),
             Error::TypeUnificationMismatch => {
@@ -260,6 +349,23 @@ impl Display for Error {
             Error::TooManyLocals(x) => {
                 f.write_fmt(format_args!("Too many locals (more than 2^32-1): {}", x))
             }
+            Error::MalformedNameSection => f.write_str("The name section is malformed"),
+            Error::MalformedDwarf => f.write_str("The DWARF debug information is malformed"),
+            Error::ModuleTooLarge(size) => f.write_fmt(format_args!(
+                "The module is too large ({size} bytes)"
+            )),
+            Error::TooManyFunctions(count) => f.write_fmt(format_args!(
+                "The module has too many functions ({count})"
+            )),
+            Error::TableTooLarge(table_idx) => f.write_fmt(format_args!(
+                "The table {table_idx} may grow beyond the maximum table size"
+            )),
+            Error::MemoryTooLarge(mem_idx) => f.write_fmt(format_args!(
+                "The memory {mem_idx} may grow beyond the maximum number of pages"
+            )),
+            Error::NestingTooDeep(depth) => f.write_fmt(format_args!(
+                "Blocks are nested too deeply (more than {depth} levels)"
+            )),
             Error::UnsupportedProposal(proposal) => {
                 f.write_fmt(format_args!("Unsupported proposal: {:?}", proposal))
             }
@@ -274,7 +380,7 @@ impl Display for RuntimeError {
             RuntimeError::DivideBy0 => f.write_str("Divide by zero is not permitted"),
             RuntimeError::UnrepresentableResult => f.write_str("Result is unrepresentable"),
             RuntimeError::FunctionNotFound => f.write_str("Function not found"),
-            RuntimeError::StackSmash => f.write_str("Stack smashed"),
+            RuntimeError::StackSmash => f.write_str("Call stack exhausted"),
             RuntimeError::BadConversionToInteger => f.write_str("Bad conversion to integer"),
             RuntimeError::MemoryAccessOutOfBounds => f.write_str("Memory access out of bounds"),
             RuntimeError::TableAccessOutOfBounds => f.write_str("Table access out of bounds"),
@@ -291,6 +397,25 @@ impl Display for RuntimeError {
             RuntimeError::UndefinedTableIndex => {
                 f.write_str("Indirect call: table index out of bounds")
             }
+            RuntimeError::HostFunctionSignatureMismatch => {
+                f.write_str("A host function returned values not matching its return types")
+            }
+            RuntimeError::OutOfFuel => f.write_str("The fuel budget was exhausted"),
+            RuntimeError::UnalignedAtomic => f.write_str("Unaligned atomic memory access"),
+            RuntimeError::ExpectedSharedMemory => {
+                f.write_str("Waiting is only possible on shared memories")
+            }
+            RuntimeError::WaiterNotConfigured => {
+                f.write_str("Waiting on a shared memory requires a waiter to be configured")
+            }
+            RuntimeError::MemoryNotFound => f.write_str("No such shared memory is exported"),
+            RuntimeError::UncaughtException(_) => f.write_str("Uncaught exception"),
+            RuntimeError::TagNotFound => f.write_str("No such tag is exported"),
+            RuntimeError::NullExceptionReference => f.write_str("Null exception reference"),
+            RuntimeError::ReclaimedExceptionReference => {
+                f.write_str("Reference to a reclaimed exception")
+            }
+            RuntimeError::HookTrap(reason) => write!(f, "Trapped by a hook: {reason}"),
         }
     }
 }
@@ -312,6 +437,15 @@ impl Display for StoreInstantiationError {
             )),
             MissingValueOnTheStack => f.write_str(""),
             TooManyMemories(x) => f.write_fmt(format_args!("Too many memories (overflow): {}", x)),
+            UnknownImport(module_name, name) => f.write_fmt(format_args!(
+                "Unknown import: no module {module_name} exporting {name}"
+            )),
+            IncompatibleImportType(module_name, name) => f.write_fmt(format_args!(
+                "The export {name} of module {module_name} does not match the type of the import"
+            )),
+            OutOfMemory(pages) => f.write_fmt(format_args!(
+                "Out of memory: can not allocate a memory of {pages} pages"
+            )),
         }
     }
 }
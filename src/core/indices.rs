@@ -24,5 +24,6 @@ pub type GlobalIdx = usize;
 pub type ElemIdx = usize;
 pub type DataIdx = usize;
 pub type LocalIdx = usize;
+pub type TagIdx = usize;
 #[allow(dead_code)]
 pub type LabelIdx = usize;
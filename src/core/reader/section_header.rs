@@ -18,6 +18,7 @@ pub enum SectionTy {
     Code = 10,
     Data = 11,
     DataCount = 12,
+    Tag = 13,
 }
 
 impl WasmReadable for SectionTy {
@@ -37,6 +38,7 @@ impl WasmReadable for SectionTy {
             10 => Code,
             11 => Data,
             12 => DataCount,
+            13 => Tag,
             other => return Err(Error::InvalidSectionType(other)),
         };
 
@@ -59,6 +61,7 @@ impl WasmReadable for SectionTy {
             10 => Code,
             11 => Data,
             12 => DataCount,
+            13 => Tag,
             _ => unreachable_validated!(),
         }
     }
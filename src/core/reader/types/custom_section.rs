@@ -0,0 +1,99 @@
+//! Custom sections, which carry additional information like debug names, but do not affect the semantics of a module
+//!
+//! See <https://webassembly.github.io/spec/core/binary/modules.html#custom-section>
+
+use alloc::collections::btree_map::BTreeMap;
+
+use crate::core::indices::{FuncIdx, LocalIdx};
+use crate::core::reader::WasmReader;
+use crate::{Error, Result};
+
+/// A custom section of a module, retained as is
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CustomSection<'wasm> {
+    pub name: &'wasm str,
+    /// The bytes following the name of the section
+    pub contents: &'wasm [u8],
+}
+
+/// The debug names of a module, function and locals, which are defined by the custom section named `name`
+///
+/// See <https://webassembly.github.io/spec/core/appendix/custom.html#name-section>
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NameSection<'wasm> {
+    pub module: Option<&'wasm str>,
+    pub functions: BTreeMap<FuncIdx, &'wasm str>,
+    pub locals: BTreeMap<FuncIdx, BTreeMap<LocalIdx, &'wasm str>>,
+}
+
+impl<'wasm> NameSection<'wasm> {
+    /// The name of the custom section holding the debug names
+    pub const SECTION_NAME: &'static str = "name";
+
+    const MODULE_SUBSECTION: u8 = 0;
+    const FUNCTION_SUBSECTION: u8 = 1;
+    const LOCAL_SUBSECTION: u8 = 2;
+
+    /// Parse the contents of a name section
+    ///
+    /// Subsections other than the module, function and local names, e.g. those of the extended name section
+    /// proposal, are skipped.
+    pub fn read(contents: &'wasm [u8]) -> Result<Self> {
+        let mut wasm = WasmReader::new(contents);
+        let mut names = Self::default();
+        let mut previous_id = None;
+
+        while !wasm.remaining_bytes().is_empty() {
+            let id = wasm.read_u8()?;
+            // subsections must appear at most once and in the order of their ids
+            if previous_id.is_some_and(|previous_id| id <= previous_id) {
+                return Err(Error::MalformedNameSection);
+            }
+            previous_id = Some(id);
+
+            let size = wasm.read_var_u32()? as usize;
+            let subsection_contents = contents.get(wasm.pc..wasm.pc + size).ok_or(Error::Eof)?;
+            wasm.skip(size)?;
+            let mut subsection = WasmReader::new(subsection_contents);
+
+            match id {
+                Self::MODULE_SUBSECTION => names.module = Some(subsection.read_name()?),
+                Self::FUNCTION_SUBSECTION => names.functions = read_name_map(&mut subsection)?,
+                Self::LOCAL_SUBSECTION => {
+                    let len = subsection.read_var_u32()?;
+                    for _ in 0..len {
+                        let func_idx = subsection.read_var_u32()? as FuncIdx;
+                        let local_names = read_name_map(&mut subsection)?;
+                        names.locals.insert(func_idx, local_names);
+                    }
+                }
+                _ => continue,
+            }
+
+            if !subsection.remaining_bytes().is_empty() {
+                return Err(Error::MalformedNameSection);
+            }
+        }
+
+        Ok(names)
+    }
+
+    pub fn function_name(&self, func_idx: FuncIdx) -> Option<&'wasm str> {
+        self.functions.get(&func_idx).copied()
+    }
+
+    pub fn local_name(&self, func_idx: FuncIdx, local_idx: LocalIdx) -> Option<&'wasm str> {
+        self.locals.get(&func_idx)?.get(&local_idx).copied()
+    }
+}
+
+/// Read a `namemap`, i.e. a vector of indices and names
+fn read_name_map<'wasm>(wasm: &mut WasmReader<'wasm>) -> Result<BTreeMap<usize, &'wasm str>> {
+    let len = wasm.read_var_u32()?;
+    let mut names = BTreeMap::new();
+    for _ in 0..len {
+        let idx = wasm.read_var_u32()? as usize;
+        names.insert(idx, wasm.read_name()?);
+    }
+    Ok(names)
+}
@@ -5,14 +5,16 @@ use alloc::{format, vec::Vec};
 use crate::{
     core::{
         indices::MemIdx,
-        reader::{span::Span, WasmReadable},
+        reader::{
+            span::Span,
+            types::{global::GlobalType, MemType},
+        },
     },
     read_constant_expression::read_constant_expression,
     validation_stack::ValidationStack,
+    Error,
 };
 
-use super::UnwrapValidatedExt;
-
 pub struct DataSegment {
     pub init: Vec<u8>,
     pub mode: DataMode,
@@ -30,30 +32,21 @@ pub struct DataModeActive {
     pub offset: Span,
 }
 
-impl WasmReadable for DataSegment {
-    fn read(wasm: &mut crate::core::reader::WasmReader) -> crate::Result<Self> {
-        use crate::{NumType, ValType};
+impl DataSegment {
+    /// Here we can't implement [WasmReadable](crate::core::reader::WasmReadable) because the type of an active data
+    /// segment's offset depends on the address type of the memory it is written to. The offset may only refer to the
+    /// immutable ones of the `imported_globals`.
+    pub fn read_from_wasm(
+        wasm: &mut crate::core::reader::WasmReader,
+        memories: &[MemType],
+        imported_globals: &[GlobalType],
+    ) -> crate::Result<Self> {
         let mode = wasm.read_var_u32()?;
         let data_sec: DataSegment = match mode {
             0 => {
                 // active { memory 0, offset e }
                 trace!("Data section: active");
-                let mut valid_stack = ValidationStack::new();
-                let offset =
-                    { read_constant_expression(wasm, &mut valid_stack, None, None, None)? };
-
-                valid_stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-
-                let byte_vec = wasm.read_vec(|el| el.read_u8())?;
-
-                // WARN: we currently don't take into consideration how we act when we are dealing with globals here
-                DataSegment {
-                    mode: DataMode::Active(DataModeActive {
-                        memory_idx: 0,
-                        offset,
-                    }),
-                    init: byte_vec,
-                }
+                Self::read_active(wasm, 0, memories, imported_globals)?
             }
             1 => {
                 // passive
@@ -66,13 +59,9 @@ impl WasmReadable for DataSegment {
             }
             2 => {
                 // mode active { memory x, offset e }
-                // this hasn't been yet implemented in wasm
-                // as per docs:
-
-                // https://webassembly.github.io/spec/core/binary/modules.html#data-section
-                // The initial integer can be interpreted as a bitfield. Bit 0 indicates a passive segment, bit 1 indicates the presence of an explicit memory index for an active segment.
-                // In the current version of WebAssembly, at most one memory may be defined or imported in a single module, so all valid active data segments have a memory value of 0
-                todo!("Data section: active - with multiple memories - NOT YET IMPLEMENTED!");
+                trace!("Data section: active with explicit memory index");
+                let memory_idx = wasm.read_var_u32()? as MemIdx;
+                Self::read_active(wasm, memory_idx, memories, imported_globals)?
             }
             _ => unreachable!(),
         };
@@ -81,55 +70,30 @@ impl WasmReadable for DataSegment {
         Ok(data_sec)
     }
 
-    fn read_unvalidated(wasm: &mut crate::core::reader::WasmReader) -> Self {
-        let mode = wasm.read_var_u32().unwrap_validated();
-        let data_sec: DataSegment = match mode {
-            0 => {
-                // active { memory 0, offset e }
-                trace!("Data section: active");
-                let offset = {
-                    read_constant_expression(wasm, &mut ValidationStack::new(), None, None, None)
-                        .unwrap_validated()
-                };
-
-                let byte_vec = wasm
-                    .read_vec(|el| Ok(el.read_u8().unwrap_validated()))
-                    .unwrap_validated();
-
-                // WARN: we currently don't take into consideration how we act when we are dealing with globals here
-                DataSegment {
-                    mode: DataMode::Active(DataModeActive {
-                        memory_idx: 0,
-                        offset,
-                    }),
-                    init: byte_vec,
-                }
-            }
-            1 => {
-                // passive
-                // A passive data segment's contents can be copied into a memory using the `memory.init` instruction
-                trace!("Data section: passive");
-                DataSegment {
-                    mode: DataMode::Passive,
-                    init: wasm
-                        .read_vec(|el| Ok(el.read_u8().unwrap_validated()))
-                        .unwrap_validated(),
-                }
-            }
-            2 => {
-                // mode active { memory x, offset e }
-                // this hasn't been yet implemented in wasm
-                // as per docs:
-
-                // https://webassembly.github.io/spec/core/binary/modules.html#data-section
-                // The initial integer can be interpreted as a bitfield. Bit 0 indicates a passive segment, bit 1 indicates the presence of an explicit memory index for an active segment.
-                // In the current version of WebAssembly, at most one memory may be defined or imported in a single module, so all valid active data segments have a memory value of 0
-                todo!("Data section: active - with multiple memories - NOT YET IMPLEMENTED!");
-            }
-            _ => unreachable!(),
-        };
-
-        data_sec
+    /// Read the offset and contents of an active data segment written to the memory `memory_idx`
+    fn read_active(
+        wasm: &mut crate::core::reader::WasmReader,
+        memory_idx: MemIdx,
+        memories: &[MemType],
+        imported_globals: &[GlobalType],
+    ) -> crate::Result<Self> {
+        let mem_type = memories
+            .get(memory_idx)
+            .ok_or(Error::MemoryIsNotDefined(memory_idx))?;
+
+        let mut valid_stack = ValidationStack::new();
+        let offset =
+            read_constant_expression(wasm, &mut valid_stack, None, Some(imported_globals), None)?;
+
+        // The offset is an address, so it is an i64 for 64-bit memories
+        valid_stack.assert_pop_val_type(mem_type.addr_type.into())?;
+
+        let byte_vec = wasm.read_vec(|el| el.read_u8())?;
+
+        Ok(DataSegment {
+            mode: DataMode::Active(DataModeActive { memory_idx, offset }),
+            init: byte_vec,
+        })
     }
 }
 
@@ -0,0 +1,765 @@
+//! Source locations from the DWARF debug information of a module
+//!
+//! Compilers like clang and rustc emit DWARF into custom sections named like the ELF sections, e.g. `.debug_line`.
+//! Addresses in these sections are offsets relative to the start of the code section's contents. Only the line number
+//! programs of `.debug_line` and the functions of `.debug_info` are read, in DWARF versions 2 to 5.
+//!
+//! See <https://yurydelendik.github.io/webassembly-dwarf/> and <https://dwarfstd.org/dwarf5std.html>
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::core::reader::types::custom_section::CustomSection;
+use crate::core::reader::WasmReader;
+use crate::{Error, Result};
+
+/// A position in the source code a module was compiled from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SourceLocation {
+    /// The path of the source file, as recorded by the compiler
+    pub file: String,
+    /// The line, starting at 1
+    pub line: u32,
+    /// The column, starting at 1, or 0 if it is unknown
+    pub column: u32,
+    /// The name of the source function containing the location, if it is known
+    pub function: Option<String>,
+}
+
+impl Display for SourceLocation {
+    /// Formats the location as `file:line:column`, or `file:line` if the column is unknown
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}:{}", self.file, self.line)?;
+        if self.column != 0 {
+            write!(f, ":{}", self.column)?;
+        }
+        Ok(())
+    }
+}
+
+/// The DWARF debug information of a module, which maps positions in the module to [SourceLocation]s
+///
+/// A module without DWARF sections has empty debug information, in which no position can be found.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DwarfInfo {
+    /// The position of the code section's contents in the module, which all DWARF addresses are relative to
+    code_section_start: usize,
+    /// The paths of the files of all line number programs
+    files: Vec<String>,
+    /// The rows of all line number programs, sorted by their address
+    rows: Vec<LineRow>,
+    functions: Vec<FunctionRange>,
+}
+
+/// A row of the line number table, which applies to all addresses up to the address of the next row
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LineRow {
+    address: u64,
+    /// The index into [DwarfInfo::files], or `None` if the line number program refers to a file it does not define
+    file: Option<usize>,
+    line: u32,
+    column: u32,
+    /// Whether this row marks the first address after a sequence of instructions, i.e. it applies to no address
+    end_sequence: bool,
+}
+
+/// The addresses of a function, from `low_pc` inclusive to `high_pc` exclusive
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct FunctionRange {
+    low_pc: u64,
+    high_pc: u64,
+    name: String,
+}
+
+/// The DWARF sections a [DwarfInfo] is read from
+#[derive(Default)]
+struct Sections<'wasm> {
+    debug_abbrev: &'wasm [u8],
+    debug_addr: &'wasm [u8],
+    debug_info: &'wasm [u8],
+    debug_line: &'wasm [u8],
+    debug_line_str: &'wasm [u8],
+    debug_str: &'wasm [u8],
+    debug_str_offsets: &'wasm [u8],
+}
+
+/// The properties of a unit of `.debug_info` or `.debug_line`, which determine how its values are encoded
+#[derive(Copy, Clone)]
+struct Encoding {
+    version: u16,
+    /// 4 for 32-bit DWARF and 8 for 64-bit DWARF
+    offset_size: u8,
+    address_size: u8,
+}
+
+impl DwarfInfo {
+    /// Read the DWARF debug information from the custom sections of a module
+    ///
+    /// The `code_section_start` is the position of the code section's contents in the module.
+    pub fn read(custom_sections: &[CustomSection], code_section_start: usize) -> Result<Self> {
+        let mut sections = Sections::default();
+        for section in custom_sections {
+            let target = match section.name {
+                ".debug_abbrev" => &mut sections.debug_abbrev,
+                ".debug_addr" => &mut sections.debug_addr,
+                ".debug_info" => &mut sections.debug_info,
+                ".debug_line" => &mut sections.debug_line,
+                ".debug_line_str" => &mut sections.debug_line_str,
+                ".debug_str" => &mut sections.debug_str,
+                ".debug_str_offsets" => &mut sections.debug_str_offsets,
+                _ => continue,
+            };
+            *target = section.contents;
+        }
+
+        let mut dwarf = Self {
+            code_section_start,
+            ..Self::default()
+        };
+
+        let mut wasm = WasmReader::new(sections.debug_line);
+        while !wasm.remaining_bytes().is_empty() {
+            let (offset_size, unit) = read_unit(&mut wasm)?;
+            dwarf.read_line_program(offset_size, unit, &sections)?;
+        }
+        // sequences must not overlap, but at the address where one ends, the next one may begin
+        dwarf
+            .rows
+            .sort_by_key(|row| (row.address, !row.end_sequence));
+
+        let mut wasm = WasmReader::new(sections.debug_info);
+        while !wasm.remaining_bytes().is_empty() {
+            let (offset_size, unit) = read_unit(&mut wasm)?;
+            dwarf.read_info_unit(offset_size, unit, &sections)?;
+        }
+
+        Ok(dwarf)
+    }
+
+    /// Whether the module contains no line information at all
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Find the source location of the instruction at position `pc` in the module
+    pub fn lookup(&self, pc: usize) -> Option<SourceLocation> {
+        let address = pc.checked_sub(self.code_section_start)? as u64;
+
+        let next_row = self.rows.partition_point(|row| row.address <= address);
+        let row = self.rows.get(next_row.checked_sub(1)?)?;
+        // line 0 marks code which cannot be attributed to any line, e.g. code generated by the compiler
+        if row.end_sequence || row.line == 0 {
+            return None;
+        }
+
+        // the innermost function containing the address has the smallest range
+        let function = self
+            .functions
+            .iter()
+            .filter(|function| (function.low_pc..function.high_pc).contains(&address))
+            .min_by_key(|function| function.high_pc - function.low_pc)
+            .map(|function| function.name.clone());
+
+        Some(SourceLocation {
+            file: self.files.get(row.file?)?.clone(),
+            line: row.line,
+            column: row.column,
+            function,
+        })
+    }
+
+    /// Read one line number program of `.debug_line` and append its files and rows
+    ///
+    /// See <https://dwarfstd.org/doc/DWARF5.pdf#section.6.2>
+    fn read_line_program(
+        &mut self,
+        offset_size: u8,
+        unit: &[u8],
+        sections: &Sections,
+    ) -> Result<()> {
+        const DW_LNS_COPY: u8 = 1;
+        const DW_LNS_ADVANCE_PC: u8 = 2;
+        const DW_LNS_ADVANCE_LINE: u8 = 3;
+        const DW_LNS_SET_FILE: u8 = 4;
+        const DW_LNS_SET_COLUMN: u8 = 5;
+        const DW_LNS_CONST_ADD_PC: u8 = 8;
+        const DW_LNS_FIXED_ADVANCE_PC: u8 = 9;
+        const DW_LNE_END_SEQUENCE: u8 = 1;
+        const DW_LNE_SET_ADDRESS: u8 = 2;
+        const DW_LNE_DEFINE_FILE: u8 = 3;
+
+        let mut wasm = WasmReader::new(unit);
+        let version = read_uint(&mut wasm, 2)? as u16;
+        if !(2..=5).contains(&version) {
+            return Err(Error::MalformedDwarf);
+        }
+        let mut encoding = Encoding {
+            version,
+            offset_size,
+            // the address size of DWARF before version 5 is only known from the address operands
+            address_size: 4,
+        };
+        if version >= 5 {
+            encoding.address_size = wasm.read_u8()?;
+            let _segment_selector_size = wasm.read_u8()?;
+        }
+        let header_length = read_uint(&mut wasm, encoding.offset_size)? as usize;
+        let program_start = wasm.pc.saturating_add(header_length);
+
+        let minimum_instruction_length = u64::from(wasm.read_u8()?);
+        if version >= 4 {
+            let _maximum_operations_per_instruction = wasm.read_u8()?;
+        }
+        let _default_is_stmt = wasm.read_u8()?;
+        let line_base = i64::from(wasm.read_u8()? as i8);
+        let line_range = wasm.read_u8()?;
+        let opcode_base = wasm.read_u8()?;
+        if line_range == 0 || opcode_base == 0 {
+            return Err(Error::MalformedDwarf);
+        }
+        let standard_opcode_lengths = unit
+            .get(wasm.pc..wasm.pc + usize::from(opcode_base) - 1)
+            .ok_or(Error::Eof)?;
+        wasm.skip(standard_opcode_lengths.len())?;
+
+        let file_base = self.files.len();
+        // the directory 0 is the compilation directory, which is only contained in the table since DWARF version 5
+        let mut directories = Vec::from([""]);
+        if version >= 5 {
+            directories = read_v5_entries(&mut wasm, encoding, sections)?
+                .into_iter()
+                .map(|(path, _)| path)
+                .collect();
+            for (path, directory) in read_v5_entries(&mut wasm, encoding, sections)? {
+                let directory = directories.get(directory).copied().unwrap_or("");
+                self.files.push(join_path(directory, path));
+            }
+        } else {
+            loop {
+                match read_null_terminated(&mut wasm)? {
+                    "" => break,
+                    directory => directories.push(directory),
+                }
+            }
+            loop {
+                match read_null_terminated(&mut wasm)? {
+                    "" => break,
+                    path => self.push_v4_file(&mut wasm, &directories, path)?,
+                }
+            }
+        }
+        // files are numbered from 1 before DWARF version 5
+        let file_index = |file: u64| {
+            let file = if version >= 5 {
+                Some(file)
+            } else {
+                file.checked_sub(1)
+            };
+            file.map(|file| file_base + file as usize)
+        };
+
+        seek(&mut wasm, program_start)?;
+        let mut address = 0;
+        let mut file = 1;
+        let mut line: u64 = 1;
+        let mut column = 0;
+        let mut rows = Vec::new();
+
+        while !wasm.remaining_bytes().is_empty() {
+            let mut emit_row = false;
+            let mut end_sequence = false;
+
+            match wasm.read_u8()? {
+                0 => {
+                    let len = wasm.read_var_u64()? as usize;
+                    let operands_start = wasm.pc;
+                    match wasm.read_u8()? {
+                        DW_LNE_END_SEQUENCE => {
+                            emit_row = true;
+                            end_sequence = true;
+                        }
+                        DW_LNE_SET_ADDRESS => {
+                            encoding.address_size =
+                                len.checked_sub(1).ok_or(Error::MalformedDwarf)? as u8;
+                            address = read_uint(&mut wasm, encoding.address_size)?;
+                        }
+                        DW_LNE_DEFINE_FILE => {
+                            let path = read_null_terminated(&mut wasm)?;
+                            self.push_v4_file(&mut wasm, &directories, path)?;
+                        }
+                        // e.g. DW_LNE_set_discriminator, which does not affect the location
+                        _ => {}
+                    }
+                    seek(&mut wasm, operands_start.saturating_add(len))?;
+                }
+                DW_LNS_COPY => emit_row = true,
+                DW_LNS_ADVANCE_PC => {
+                    let advance = wasm
+                        .read_var_u64()?
+                        .wrapping_mul(minimum_instruction_length);
+                    address = address.wrapping_add(advance);
+                }
+                DW_LNS_ADVANCE_LINE => line = line.wrapping_add_signed(wasm.read_var_i64()?),
+                DW_LNS_SET_FILE => file = wasm.read_var_u64()?,
+                DW_LNS_SET_COLUMN => column = wasm.read_var_u64()?,
+                DW_LNS_CONST_ADD_PC => {
+                    let advance =
+                        u64::from((255 - opcode_base) / line_range) * minimum_instruction_length;
+                    address = address.wrapping_add(advance);
+                }
+                DW_LNS_FIXED_ADVANCE_PC => address = address.wrapping_add(read_uint(&mut wasm, 2)?),
+                special if special >= opcode_base => {
+                    let adjusted = special - opcode_base;
+                    let advance = u64::from(adjusted / line_range) * minimum_instruction_length;
+                    address = address.wrapping_add(advance);
+                    line = line.wrapping_add_signed(line_base + i64::from(adjusted % line_range));
+                    emit_row = true;
+                }
+                // other standard opcodes only affect registers which are not needed for locations, so their unsigned
+                // LEB128 operands are skipped
+                standard => {
+                    for _ in 0..standard_opcode_lengths[usize::from(standard) - 1] {
+                        wasm.read_var_u64()?;
+                    }
+                }
+            }
+
+            if emit_row {
+                rows.push(LineRow {
+                    address,
+                    file: file_index(file),
+                    line: line as u32,
+                    column: column as u32,
+                    end_sequence,
+                });
+            }
+            if end_sequence {
+                // sequences of functions removed by the linker start at a tombstone address
+                if !is_tombstone(rows[0].address) {
+                    self.rows.append(&mut rows);
+                }
+                rows.clear();
+                address = 0;
+                file = 1;
+                line = 1;
+                column = 0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Append a file of a line number program before DWARF version 5, whose directory index, modification time and
+    /// length follow its path
+    fn push_v4_file(
+        &mut self,
+        wasm: &mut WasmReader,
+        directories: &[&str],
+        path: &str,
+    ) -> Result<()> {
+        let directory = wasm.read_var_u64()? as usize;
+        let _modification_time = wasm.read_var_u64()?;
+        let _length = wasm.read_var_u64()?;
+        let directory = directories.get(directory).copied().unwrap_or("");
+        self.files.push(join_path(directory, path));
+        Ok(())
+    }
+
+    /// Read the functions of one unit of `.debug_info`
+    ///
+    /// See <https://dwarfstd.org/doc/DWARF5.pdf#section.7.5>
+    fn read_info_unit<'wasm>(
+        &mut self,
+        offset_size: u8,
+        unit: &'wasm [u8],
+        sections: &Sections<'wasm>,
+    ) -> Result<()> {
+        const DW_TAG_SUBPROGRAM: u64 = 0x2e;
+        const DW_AT_NAME: u64 = 0x03;
+        const DW_AT_LOW_PC: u64 = 0x11;
+        const DW_AT_HIGH_PC: u64 = 0x12;
+        const DW_AT_STR_OFFSETS_BASE: u64 = 0x72;
+        const DW_AT_ADDR_BASE: u64 = 0x73;
+        const DW_UT_TYPE: u8 = 0x02;
+        const DW_UT_SKELETON: u8 = 0x04;
+        const DW_UT_SPLIT_COMPILE: u8 = 0x05;
+        const DW_UT_SPLIT_TYPE: u8 = 0x06;
+
+        let mut wasm = WasmReader::new(unit);
+        let version = read_uint(&mut wasm, 2)? as u16;
+        if !(2..=5).contains(&version) {
+            return Err(Error::MalformedDwarf);
+        }
+        let (abbrev_offset, address_size) = if version >= 5 {
+            let unit_type = wasm.read_u8()?;
+            let address_size = wasm.read_u8()?;
+            let abbrev_offset = read_uint(&mut wasm, offset_size)?;
+            match unit_type {
+                DW_UT_SKELETON | DW_UT_SPLIT_COMPILE => wasm.skip(8)?,
+                DW_UT_TYPE | DW_UT_SPLIT_TYPE => wasm.skip(8 + usize::from(offset_size))?,
+                _ => {}
+            }
+            (abbrev_offset, address_size)
+        } else {
+            (read_uint(&mut wasm, offset_size)?, wasm.read_u8()?)
+        };
+        let encoding = Encoding {
+            version,
+            offset_size,
+            address_size,
+        };
+        let abbreviations = read_abbreviations(sections.debug_abbrev, abbrev_offset as usize)?;
+
+        // the bases of indexed strings and addresses are attributes of the first entry of the unit
+        let mut str_offsets_base = 0;
+        let mut addr_base = 0;
+
+        while !wasm.remaining_bytes().is_empty() {
+            let code = wasm.read_var_u64()?;
+            // the end of the children of an entry
+            if code == 0 {
+                continue;
+            }
+            let abbreviation = abbreviations.get(&code).ok_or(Error::MalformedDwarf)?;
+
+            let mut name = None;
+            let mut low_pc = None;
+            let mut high_pc = None;
+            for &(attribute, form, implicit_const) in &abbreviation.attributes {
+                let value = read_form(&mut wasm, form, implicit_const, encoding, sections)?;
+                match attribute {
+                    DW_AT_NAME => name = Some(value),
+                    DW_AT_LOW_PC => low_pc = Some(value),
+                    DW_AT_HIGH_PC => high_pc = Some(value),
+                    DW_AT_STR_OFFSETS_BASE => str_offsets_base = value.constant()?,
+                    DW_AT_ADDR_BASE => addr_base = value.constant()?,
+                    _ => {}
+                }
+            }
+
+            if abbreviation.tag != DW_TAG_SUBPROGRAM {
+                continue;
+            }
+            let (Some(name), Some(low_pc), Some(high_pc)) = (name, low_pc, high_pc) else {
+                continue;
+            };
+            let resolve_string = |value: FormValue<'wasm>| -> Result<&'wasm str> {
+                match value {
+                    FormValue::String(string) => Ok(string),
+                    FormValue::StringIndex(index) => {
+                        let offset_position = str_offsets_base
+                            .wrapping_add(index.wrapping_mul(u64::from(offset_size)));
+                        let mut wasm = reader_at(sections.debug_str_offsets, offset_position)?;
+                        string_at(sections.debug_str, read_uint(&mut wasm, offset_size)?)
+                    }
+                    _ => Err(Error::MalformedDwarf),
+                }
+            };
+            let resolve_address = |value: FormValue| match value {
+                FormValue::Address(address) => Ok(address),
+                FormValue::AddressIndex(index) => {
+                    let position =
+                        addr_base.wrapping_add(index.wrapping_mul(u64::from(address_size)));
+                    let mut wasm = reader_at(sections.debug_addr, position)?;
+                    read_uint(&mut wasm, address_size)
+                }
+                _ => Err(Error::MalformedDwarf),
+            };
+
+            let low_pc = resolve_address(low_pc)?;
+            // a constant high pc is the size of the function
+            let high_pc = match high_pc {
+                FormValue::Constant(size) => low_pc.wrapping_add(size),
+                high_pc => resolve_address(high_pc)?,
+            };
+            if !is_tombstone(low_pc) {
+                self.functions.push(FunctionRange {
+                    low_pc,
+                    high_pc,
+                    name: String::from(resolve_string(name)?),
+                });
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// An entry of `.debug_abbrev`, which describes the tag and attributes of entries of `.debug_info`
+struct Abbreviation {
+    tag: u64,
+    /// The attributes and their forms, with the value of the form `DW_FORM_implicit_const`
+    attributes: Vec<(u64, u64, i64)>,
+}
+
+/// Read the abbreviations table starting at `offset`
+fn read_abbreviations(debug_abbrev: &[u8], offset: usize) -> Result<BTreeMap<u64, Abbreviation>> {
+    const DW_FORM_IMPLICIT_CONST: u64 = 0x21;
+
+    let mut wasm = reader_at(debug_abbrev, offset as u64)?;
+    let mut abbreviations = BTreeMap::new();
+    loop {
+        let code = wasm.read_var_u64()?;
+        if code == 0 {
+            return Ok(abbreviations);
+        }
+        let tag = wasm.read_var_u64()?;
+        let _has_children = wasm.read_u8()?;
+
+        let mut attributes = Vec::new();
+        loop {
+            let attribute = wasm.read_var_u64()?;
+            let form = wasm.read_var_u64()?;
+            if (attribute, form) == (0, 0) {
+                break;
+            }
+            let implicit_const = match form {
+                DW_FORM_IMPLICIT_CONST => wasm.read_var_i64()?,
+                _ => 0,
+            };
+            attributes.push((attribute, form, implicit_const));
+        }
+        abbreviations.insert(code, Abbreviation { tag, attributes });
+    }
+}
+
+/// The value of an attribute, as far as it is needed to find the names and addresses of functions
+#[derive(Copy, Clone)]
+enum FormValue<'wasm> {
+    Address(u64),
+    /// An index into the unit's addresses in `.debug_addr`
+    AddressIndex(u64),
+    Constant(u64),
+    String(&'wasm str),
+    /// An index into the unit's string offsets in `.debug_str_offsets`
+    StringIndex(u64),
+    Other,
+}
+
+impl FormValue<'_> {
+    fn constant(self) -> Result<u64> {
+        match self {
+            FormValue::Constant(constant) => Ok(constant),
+            _ => Err(Error::MalformedDwarf),
+        }
+    }
+}
+
+/// Read the value of an attribute or of an entry of a DWARF 5 line number program header, encoded in the `form`
+///
+/// See <https://dwarfstd.org/doc/DWARF5.pdf#section.7.5.6>
+fn read_form<'wasm>(
+    wasm: &mut WasmReader<'wasm>,
+    form: u64,
+    implicit_const: i64,
+    encoding: Encoding,
+    sections: &Sections<'wasm>,
+) -> Result<FormValue<'wasm>> {
+    let value = match form {
+        // DW_FORM_addr
+        0x01 => FormValue::Address(read_uint(wasm, encoding.address_size)?),
+        // DW_FORM_block2, DW_FORM_block4, DW_FORM_block, DW_FORM_exprloc, DW_FORM_block1
+        0x03 | 0x04 | 0x09 | 0x18 | 0x0a => {
+            let len = match form {
+                0x03 => read_uint(wasm, 2)?,
+                0x04 => read_uint(wasm, 4)?,
+                0x0a => read_uint(wasm, 1)?,
+                _ => wasm.read_var_u64()?,
+            };
+            wasm.skip(len as usize)?;
+            FormValue::Other
+        }
+        // DW_FORM_data2, DW_FORM_data4, DW_FORM_data8, DW_FORM_data1
+        0x05 => FormValue::Constant(read_uint(wasm, 2)?),
+        0x06 => FormValue::Constant(read_uint(wasm, 4)?),
+        0x07 => FormValue::Constant(read_uint(wasm, 8)?),
+        0x0b => FormValue::Constant(read_uint(wasm, 1)?),
+        // DW_FORM_string
+        0x08 => FormValue::String(read_null_terminated(wasm)?),
+        // DW_FORM_flag, DW_FORM_ref1
+        0x0c | 0x11 => {
+            wasm.skip(1)?;
+            FormValue::Other
+        }
+        // DW_FORM_sdata
+        0x0d => FormValue::Constant(wasm.read_var_i64()? as u64),
+        // DW_FORM_strp
+        0x0e => FormValue::String(string_at(
+            sections.debug_str,
+            read_uint(wasm, encoding.offset_size)?,
+        )?),
+        // DW_FORM_udata, DW_FORM_sec_offset
+        0x0f => FormValue::Constant(wasm.read_var_u64()?),
+        0x17 => FormValue::Constant(read_uint(wasm, encoding.offset_size)?),
+        // DW_FORM_ref_addr, which is address sized in DWARF version 2
+        0x10 => {
+            let size = match encoding.version {
+                2 => encoding.address_size,
+                _ => encoding.offset_size,
+            };
+            wasm.skip(usize::from(size))?;
+            FormValue::Other
+        }
+        // DW_FORM_ref2, DW_FORM_ref4, DW_FORM_ref_sup4, DW_FORM_ref8, DW_FORM_ref_sig8, DW_FORM_ref_sup8,
+        // DW_FORM_data16
+        0x12 => {
+            wasm.skip(2)?;
+            FormValue::Other
+        }
+        0x13 | 0x1c => {
+            wasm.skip(4)?;
+            FormValue::Other
+        }
+        0x14 | 0x20 | 0x24 => {
+            wasm.skip(8)?;
+            FormValue::Other
+        }
+        0x1e => {
+            wasm.skip(16)?;
+            FormValue::Other
+        }
+        // DW_FORM_ref_udata, DW_FORM_loclistx, DW_FORM_rnglistx
+        0x15 | 0x22 | 0x23 => {
+            wasm.read_var_u64()?;
+            FormValue::Other
+        }
+        // DW_FORM_indirect, whose form precedes the value
+        0x16 => {
+            let form = wasm.read_var_u64()?;
+            return read_form(wasm, form, implicit_const, encoding, sections);
+        }
+        // DW_FORM_flag_present
+        0x19 => FormValue::Other,
+        // DW_FORM_strx, DW_FORM_strx1, DW_FORM_strx2, DW_FORM_strx3, DW_FORM_strx4
+        0x1a => FormValue::StringIndex(wasm.read_var_u64()?),
+        0x25..=0x28 => FormValue::StringIndex(read_uint(wasm, (form - 0x24) as u8)?),
+        // DW_FORM_addrx, DW_FORM_addrx1, DW_FORM_addrx2, DW_FORM_addrx3, DW_FORM_addrx4
+        0x1b => FormValue::AddressIndex(wasm.read_var_u64()?),
+        0x29..=0x2c => FormValue::AddressIndex(read_uint(wasm, (form - 0x28) as u8)?),
+        // DW_FORM_strp_sup, which refers to a supplementary object file
+        0x1d => {
+            wasm.skip(usize::from(encoding.offset_size))?;
+            FormValue::Other
+        }
+        // DW_FORM_line_strp
+        0x1f => FormValue::String(string_at(
+            sections.debug_line_str,
+            read_uint(wasm, encoding.offset_size)?,
+        )?),
+        // DW_FORM_implicit_const
+        0x21 => FormValue::Constant(implicit_const as u64),
+        _ => return Err(Error::MalformedDwarf),
+    };
+    Ok(value)
+}
+
+/// Read the directory or file entries of a DWARF 5 line number program header, as their paths and directory indices
+fn read_v5_entries<'wasm>(
+    wasm: &mut WasmReader<'wasm>,
+    encoding: Encoding,
+    sections: &Sections<'wasm>,
+) -> Result<Vec<(&'wasm str, usize)>> {
+    const DW_LNCT_PATH: u64 = 1;
+    const DW_LNCT_DIRECTORY_INDEX: u64 = 2;
+
+    let format_count = wasm.read_u8()?;
+    let formats = (0..format_count)
+        .map(|_| Ok((wasm.read_var_u64()?, wasm.read_var_u64()?)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let entry_count = wasm.read_var_u64()?;
+    let mut entries = Vec::new();
+    for _ in 0..entry_count {
+        let mut path = "";
+        let mut directory = 0;
+        for &(content_type, form) in &formats {
+            let value = read_form(wasm, form, 0, encoding, sections)?;
+            match (content_type, value) {
+                (DW_LNCT_PATH, FormValue::String(string)) => path = string,
+                (DW_LNCT_PATH, _) => return Err(Error::MalformedDwarf),
+                (DW_LNCT_DIRECTORY_INDEX, value) => directory = value.constant()? as usize,
+                _ => {}
+            }
+        }
+        entries.push((path, directory));
+    }
+    Ok(entries)
+}
+
+/// Read the length of a unit, and return the offset size of the unit together with its contents following the length
+fn read_unit<'wasm>(wasm: &mut WasmReader<'wasm>) -> Result<(u8, &'wasm [u8])> {
+    let (offset_size, len) = match read_uint(wasm, 4)? {
+        0xffff_ffff => (8, read_uint(wasm, 8)?),
+        0xffff_fff0.. => return Err(Error::MalformedDwarf),
+        len => (4, len),
+    };
+    let unit = wasm.full_wasm_binary[wasm.pc..]
+        .get(..len as usize)
+        .ok_or(Error::Eof)?;
+    wasm.skip(unit.len())?;
+    Ok((offset_size, unit))
+}
+
+/// Continue reading at `position`, which may be the end of the read bytes
+fn seek(wasm: &mut WasmReader, position: usize) -> Result<()> {
+    if position > wasm.full_wasm_binary.len() {
+        return Err(Error::Eof);
+    }
+    wasm.pc = position;
+    Ok(())
+}
+
+/// Read a little endian unsigned integer of `size` bytes
+fn read_uint(wasm: &mut WasmReader, size: u8) -> Result<u64> {
+    if size > 8 {
+        return Err(Error::MalformedDwarf);
+    }
+    let mut value = 0;
+    for i in 0..size {
+        value |= u64::from(wasm.read_u8()?) << (8 * i);
+    }
+    Ok(value)
+}
+
+/// Read a string terminated by a null byte
+fn read_null_terminated<'wasm>(wasm: &mut WasmReader<'wasm>) -> Result<&'wasm str> {
+    let bytes = &wasm.full_wasm_binary[wasm.pc..];
+    let len = bytes.iter().position(|byte| *byte == 0).ok_or(Error::Eof)?;
+    let string = core::str::from_utf8(&bytes[..len]).map_err(Error::MalformedUtf8String)?;
+    wasm.skip(len + 1)?;
+    Ok(string)
+}
+
+/// Read the string at `offset` in a string section like `.debug_str`
+fn string_at(section: &[u8], offset: u64) -> Result<&str> {
+    read_null_terminated(&mut reader_at(section, offset)?)
+}
+
+/// Create a reader for `section` which starts reading at `offset`
+fn reader_at(section: &[u8], offset: u64) -> Result<WasmReader<'_>> {
+    let contents = usize::try_from(offset)
+        .ok()
+        .and_then(|offset| section.get(offset..))
+        .ok_or(Error::Eof)?;
+    Ok(WasmReader::new(contents))
+}
+
+/// Join the path of a file with the path of its directory, unless the file's path is already absolute
+fn join_path(directory: &str, path: &str) -> String {
+    if directory.is_empty() || path.starts_with('/') {
+        String::from(path)
+    } else {
+        format!("{}/{path}", directory.trim_end_matches('/'))
+    }
+}
+
+/// Whether an address refers to a function which was removed by the linker
+///
+/// Older linkers use address 0 for removed functions, which is never the address of a function, as the code section
+/// starts with the number of functions. Newer linkers use -1 or, in some sections, -2.
+fn is_tombstone(address: u64) -> bool {
+    address == 0 || address >= 0xffff_fffe
+}
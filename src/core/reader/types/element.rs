@@ -1,5 +1,6 @@
 use super::RefType;
 use crate::core::reader::span::Span;
+use crate::core::reader::types::global::GlobalType;
 use crate::core::reader::WasmReader;
 use crate::read_constant_expression::read_constant_expression;
 use crate::validation_stack::ValidationStack;
@@ -46,9 +47,12 @@ impl ElemType {
     /// This comes in handy later on when we are validating the actual code of
     /// the functions so that we can make sure we are not referencing invalid
     /// functions
+    ///
+    /// The offsets and initialization expressions may only refer to the immutable ones of the `imported_globals`.
     pub fn read_from_wasm(
         wasm: &mut WasmReader,
         functions: &[usize],
+        imported_globals: &[GlobalType],
         referenced_functions: &mut BTreeSet<u32>,
         tables_length: usize,
     ) -> Result<Vec<Self>> {
@@ -85,8 +89,13 @@ impl ElemType {
                 }
 
                 let mut valid_stack = ValidationStack::new();
-                let init_expr =
-                    read_constant_expression(wasm, &mut valid_stack, None, None, Some(functions))?;
+                let init_expr = read_constant_expression(
+                    wasm,
+                    &mut valid_stack,
+                    None,
+                    Some(imported_globals),
+                    Some(functions),
+                )?;
 
                 // on top of the stack it's supposed to be the
                 valid_stack.assert_pop_val_type(super::ValType::NumType(super::NumType::I32))?;
@@ -126,7 +135,7 @@ impl ElemType {
                             w,
                             &mut valid_stack,
                             None,
-                            None,
+                            Some(imported_globals),
                             Some(functions),
                         );
 
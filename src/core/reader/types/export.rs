@@ -1,7 +1,7 @@
 use alloc::borrow::ToOwned;
 use alloc::string::String;
 
-use crate::core::indices::{FuncIdx, GlobalIdx, MemIdx, TableIdx};
+use crate::core::indices::{FuncIdx, GlobalIdx, MemIdx, TableIdx, TagIdx};
 use crate::core::reader::{WasmReadable, WasmReader};
 use crate::execution::assert_validated::UnwrapValidatedExt;
 use crate::{unreachable_validated, Error, Result};
@@ -39,6 +39,7 @@ pub enum ExportDesc {
     MemIdx(MemIdx),
     #[allow(warnings)]
     GlobalIdx(GlobalIdx),
+    TagIdx(TagIdx),
 }
 
 impl WasmReadable for ExportDesc {
@@ -51,6 +52,7 @@ impl WasmReadable for ExportDesc {
             0x01 => ExportDesc::TableIdx(desc_idx),
             0x02 => ExportDesc::MemIdx(desc_idx),
             0x03 => ExportDesc::GlobalIdx(desc_idx),
+            0x04 => ExportDesc::TagIdx(desc_idx),
             other => return Err(Error::InvalidExportDesc(other)),
         };
         Ok(desc)
@@ -65,6 +67,7 @@ impl WasmReadable for ExportDesc {
             0x01 => ExportDesc::TableIdx(desc_idx),
             0x02 => ExportDesc::MemIdx(desc_idx),
             0x03 => ExportDesc::GlobalIdx(desc_idx),
+            0x04 => ExportDesc::TagIdx(desc_idx),
             _other => unreachable_validated!(),
         }
     }
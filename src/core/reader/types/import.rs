@@ -7,7 +7,7 @@ use crate::execution::assert_validated::UnwrapValidatedExt;
 use crate::{unreachable_validated, Error, Result};
 
 use super::global::GlobalType;
-use super::{MemType, TableType};
+use super::{MemType, TableType, TagType};
 
 #[derive(Debug)]
 pub struct Import {
@@ -49,14 +49,10 @@ impl WasmReadable for Import {
 pub enum ImportDesc {
     #[allow(dead_code)]
     Func(TypeIdx),
-    #[allow(dead_code)]
     Table(TableType),
-    // TODO TableType
-    #[allow(dead_code)]
     Mem(MemType),
-    // TODO MemType
-    #[allow(dead_code)]
-    Global(GlobalType), // TODO GlobalType
+    Global(GlobalType),
+    Tag(TagType),
 }
 
 impl WasmReadable for ImportDesc {
@@ -67,6 +63,7 @@ impl WasmReadable for ImportDesc {
             0x01 => Self::Table(TableType::read(wasm)?),
             0x02 => Self::Mem(MemType::read(wasm)?),
             0x03 => Self::Global(GlobalType::read(wasm)?),
+            0x04 => Self::Tag(TagType::read(wasm)?),
             other => return Err(Error::InvalidImportDesc(other)),
         };
 
@@ -77,8 +74,9 @@ impl WasmReadable for ImportDesc {
         match wasm.read_u8().unwrap_validated() {
             0x00 => Self::Func(wasm.read_var_u32().unwrap_validated() as TypeIdx),
             0x01 => Self::Table(TableType::read_unvalidated(wasm)),
-            0x02 => todo!("read MemType"),
-            0x03 => todo!("read GlobalType"),
+            0x02 => Self::Mem(MemType::read_unvalidated(wasm)),
+            0x03 => Self::Global(GlobalType::read_unvalidated(wasm)),
+            0x04 => Self::Tag(TagType::read_unvalidated(wasm)),
             _ => unreachable_validated!(),
         }
     }
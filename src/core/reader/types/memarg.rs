@@ -1,26 +1,53 @@
 use core::fmt::Debug;
 
+use crate::core::indices::MemIdx;
 use crate::core::reader::{WasmReadable, WasmReader};
 use crate::execution::assert_validated::UnwrapValidatedExt;
 
+/// If this bit is set in the alignment field of a memarg, an explicit memory index follows the alignment
+///
+/// See <https://webassembly.github.io/multi-memory/core/binary/instructions.html#memory-instructions>
+const EXPLICIT_MEMORY_FLAG: u32 = 1 << 6;
+
 #[derive(Debug)]
 pub struct MemArg {
-    pub offset: u32,
+    /// Always fits into 32 bits for 32-bit memories
+    pub offset: u64,
     pub align: u32,
+    /// The memory accessed, which is always `0` unless the multi-memory encoding is used
+    pub memory: MemIdx,
 }
 
 impl WasmReadable for MemArg {
     fn read(wasm: &mut WasmReader) -> crate::Result<Self> {
-        let align_log2 = wasm.read_var_u32()?;
-        let offset = wasm.read_var_u32()?;
+        let mut align_log2 = wasm.read_var_u32()?;
+        let mut memory = 0;
+        if align_log2 & EXPLICIT_MEMORY_FLAG != 0 {
+            align_log2 &= !EXPLICIT_MEMORY_FLAG;
+            memory = wasm.read_var_u32()? as MemIdx;
+        }
+        let offset = wasm.read_var_u64()?;
         let align = u32::pow(2, align_log2);
-        Ok(Self { offset, align })
+        Ok(Self {
+            offset,
+            align,
+            memory,
+        })
     }
 
     fn read_unvalidated(wasm: &mut WasmReader) -> Self {
-        let align_log2 = wasm.read_var_u32().unwrap_validated();
-        let offset = wasm.read_var_u32().unwrap_validated();
+        let mut align_log2 = wasm.read_var_u32().unwrap_validated();
+        let mut memory = 0;
+        if align_log2 & EXPLICIT_MEMORY_FLAG != 0 {
+            align_log2 &= !EXPLICIT_MEMORY_FLAG;
+            memory = wasm.read_var_u32().unwrap_validated() as MemIdx;
+        }
+        let offset = wasm.read_var_u64().unwrap_validated();
         let align = u32::pow(2, align_log2);
-        Self { offset, align }
+        Self {
+            offset,
+            align,
+            memory,
+        }
     }
 }
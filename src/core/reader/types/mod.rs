@@ -5,13 +5,16 @@
 use alloc::vec::Vec;
 use core::fmt::{Debug, Formatter};
 
+use crate::core::indices::{LabelIdx, TagIdx, TypeIdx};
 use crate::core::reader::{WasmReadable, WasmReader};
 use crate::execution::assert_validated::UnwrapValidatedExt;
-use crate::value::{ExternAddr, FuncAddr, Ref};
+use crate::value::{ExnAddr, ExternAddr, FuncAddr, Ref};
 use crate::Result;
 use crate::{unreachable_validated, Error};
 
+pub mod custom_section;
 pub mod data;
+pub mod dwarf;
 pub mod element;
 pub mod export;
 pub mod function_code_header;
@@ -19,6 +22,7 @@ pub mod global;
 pub mod import;
 pub mod memarg;
 pub mod opcode;
+pub mod proposal;
 pub mod values;
 
 /// <https://webassembly.github.io/spec/core/binary/types.html#number-types>
@@ -85,6 +89,9 @@ impl WasmReadable for VecType {
 pub enum RefType {
     FuncRef,
     ExternRef,
+    /// A reference to a caught exception, see
+    /// <https://webassembly.github.io/exception-handling/core/syntax/types.html#reference-types>
+    ExnRef,
 }
 
 impl RefType {
@@ -95,6 +102,7 @@ impl RefType {
         match self {
             RefType::ExternRef => Ref::Extern(ExternAddr::null()),
             RefType::FuncRef => Ref::Func(FuncAddr::null()),
+            RefType::ExnRef => Ref::Exn(ExnAddr::null()),
         }
     }
 }
@@ -104,6 +112,7 @@ impl RefType {
         match byte {
             0x70 => Ok(RefType::FuncRef),
             0x6F => Ok(RefType::ExternRef),
+            0x69 => Ok(RefType::ExnRef),
             _ => Err(Error::InvalidRefType),
         }
     }
@@ -114,6 +123,7 @@ impl WasmReadable for RefType {
         let ty = match wasm.peek_u8()? {
             0x70 => RefType::FuncRef,
             0x6F => RefType::ExternRef,
+            0x69 => RefType::ExnRef,
             _ => return Err(Error::InvalidRefType),
         };
         let _ = wasm.read_u8();
@@ -125,6 +135,7 @@ impl WasmReadable for RefType {
         match wasm.read_u8().unwrap_validated() {
             0x70 => RefType::FuncRef,
             0x6F => RefType::ExternRef,
+            0x69 => RefType::ExnRef,
             _ => unreachable_validated!(),
         }
     }
@@ -313,22 +324,109 @@ impl BlockType {
     }
 }
 
+/// A catch clause of a `try_table`, which branches to a label if it catches an exception
+///
+/// See <https://webassembly.github.io/exception-handling/core/binary/instructions.html#control-instructions>
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CatchClause {
+    /// Catches exceptions with the given tag, passing their payload to the label
+    Catch(TagIdx, LabelIdx),
+    /// Like [CatchClause::Catch], but additionally passes a reference to the exception
+    CatchRef(TagIdx, LabelIdx),
+    /// Catches all exceptions
+    CatchAll(LabelIdx),
+    /// Like [CatchClause::CatchAll], but passes a reference to the exception
+    CatchAllRef(LabelIdx),
+}
+
+impl WasmReadable for CatchClause {
+    fn read(wasm: &mut WasmReader) -> Result<Self> {
+        let kind = wasm.read_u8()?;
+        let clause = match kind {
+            0x00 => Self::Catch(
+                wasm.read_var_u32()? as TagIdx,
+                wasm.read_var_u32()? as LabelIdx,
+            ),
+            0x01 => Self::CatchRef(
+                wasm.read_var_u32()? as TagIdx,
+                wasm.read_var_u32()? as LabelIdx,
+            ),
+            0x02 => Self::CatchAll(wasm.read_var_u32()? as LabelIdx),
+            0x03 => Self::CatchAllRef(wasm.read_var_u32()? as LabelIdx),
+            other => return Err(Error::InvalidCatchClause(other)),
+        };
+        Ok(clause)
+    }
+
+    fn read_unvalidated(wasm: &mut WasmReader) -> Self {
+        Self::read(wasm).unwrap_validated()
+    }
+}
+
+impl CatchClause {
+    /// The tag of the caught exceptions, or `None` if all exceptions are caught
+    pub fn tag_idx(&self) -> Option<TagIdx> {
+        match self {
+            Self::Catch(tag_idx, _) | Self::CatchRef(tag_idx, _) => Some(*tag_idx),
+            Self::CatchAll(_) | Self::CatchAllRef(_) => None,
+        }
+    }
+
+    /// The label which is branched to after catching an exception
+    pub fn label_idx(&self) -> LabelIdx {
+        match self {
+            Self::Catch(_, label_idx)
+            | Self::CatchRef(_, label_idx)
+            | Self::CatchAll(label_idx)
+            | Self::CatchAllRef(label_idx) => *label_idx,
+        }
+    }
+
+    /// Whether a reference to the caught exception is passed to the label
+    pub fn passes_exnref(&self) -> bool {
+        matches!(self, Self::CatchRef(..) | Self::CatchAllRef(_))
+    }
+}
+
 #[derive(Copy, Clone, PartialEq, Eq)]
 pub struct Limits {
-    pub min: u32,
-    pub max: Option<u32>,
+    pub min: u64,
+    pub max: Option<u64>,
 }
 
 impl Limits {
     // since the maximum amount of bytes is u32::MAX, the page size is 1 << 16
     // the max no. of pages = max bytes / page size = u32::MAX / (1 << 16) = 1 << 16
-    pub const MAX_MEM_PAGES: u32 = 1 << 16;
+    pub const MAX_MEM_PAGES: u64 = 1 << 16;
+    // https://webassembly.github.io/memory64/core/valid/types.html#memory-types
+    // the same calculation for 64-bit memories: u64::MAX / (1 << 16) = 1 << 48
+    pub const MAX_MEM64_PAGES: u64 = 1 << 48;
     // https://webassembly.github.io/reference-types/core/syntax/types.html#limits
     // memtype is defined in terms of limits, which go from 0 to u32::MAX
     pub const MAX_MEM_BYTES: u32 = u32::MAX;
     // https://webassembly.github.io/reference-types/core/exec/runtime.html#memory-instances
     // memory size is 65536 (1 << 16)
     pub const MEM_PAGE_SIZE: u32 = 1 << 16;
+
+    /// Read the bounds following the flags byte of limits. 64-bit memories encode their bounds as `u64`, everything
+    /// else as `u32`.
+    fn read_bounds(wasm: &mut WasmReader, has_max: bool, addr_type: AddrType) -> Result<Self> {
+        let mut read_bound = || match addr_type {
+            AddrType::I32 => wasm.read_var_u32().map(u64::from),
+            AddrType::I64 => wasm.read_var_u64(),
+        };
+
+        let min = read_bound()?;
+        let max = if has_max { Some(read_bound()?) } else { None };
+
+        if let Some(max) = max {
+            if min > max {
+                return Err(Error::InvalidLimit);
+            }
+        }
+
+        Ok(Self { min, max })
+    }
 }
 
 impl Debug for Limits {
@@ -342,45 +440,17 @@ impl Debug for Limits {
 
 impl WasmReadable for Limits {
     fn read(wasm: &mut WasmReader) -> Result<Self> {
-        let limits = match wasm.read_u8()? {
-            0x00 => {
-                let min = wasm.read_var_u32()?;
-                Self { min, max: None }
-            }
-            0x01 => {
-                let min = wasm.read_var_u32()?;
-                let max = wasm.read_var_u32()?;
-                Self {
-                    min,
-                    max: Some(max),
-                }
-            }
-            other => return Err(Error::InvalidLimitsType(other)),
-        };
-
-        if let Some(max) = limits.max {
-            if limits.min > max {
-                return Err(Error::InvalidLimit);
-            }
+        match wasm.read_u8()? {
+            0x00 => Self::read_bounds(wasm, false, AddrType::I32),
+            0x01 => Self::read_bounds(wasm, true, AddrType::I32),
+            other => Err(Error::InvalidLimitsType(other)),
         }
-
-        Ok(limits)
     }
 
     fn read_unvalidated(wasm: &mut WasmReader) -> Self {
         match wasm.read_u8().unwrap_validated() {
-            0x00 => {
-                let min = wasm.read_var_u32().unwrap_validated();
-                Self { min, max: None }
-            }
-            0x01 => {
-                let min = wasm.read_var_u32().unwrap_validated();
-                let max = wasm.read_var_u32().unwrap_validated();
-                Self {
-                    min,
-                    max: Some(max),
-                }
-            }
+            0x00 => Self::read_bounds(wasm, false, AddrType::I32).unwrap_validated(),
+            0x01 => Self::read_bounds(wasm, true, AddrType::I32).unwrap_validated(),
             _ => unreachable_validated!(),
         }
     }
@@ -398,7 +468,7 @@ impl WasmReadable for TableType {
         let et = RefType::read(wasm)?;
         let mut lim = Limits::read(wasm)?;
         if lim.max.is_none() {
-            lim.max = Some(u32::MAX)
+            lim.max = Some(u32::MAX.into())
         };
         let table_type = Self { et, lim };
         trace!("Table: {:?}", table_type);
@@ -409,38 +479,146 @@ impl WasmReadable for TableType {
         let et = RefType::read_unvalidated(wasm);
         let mut lim = Limits::read_unvalidated(wasm);
         if lim.max.is_none() {
-            lim.max = Some(u32::MAX)
+            lim.max = Some(u32::MAX.into())
         };
         Self { et, lim }
     }
 }
 
+/// The type of a tag, whose parameters are the values carried by exceptions with this tag
+///
+/// See <https://webassembly.github.io/exception-handling/core/binary/types.html#tag-types>
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TagType {
+    /// The index of a function type, which must not have any results
+    pub type_idx: TypeIdx,
+}
+
+impl WasmReadable for TagType {
+    fn read(wasm: &mut WasmReader) -> Result<Self> {
+        // the attribute is reserved for future use, the only attribute so far denotes exceptions
+        let attribute = wasm.read_u8()?;
+        if attribute != 0x00 {
+            return Err(Error::InvalidTagAttribute(attribute));
+        }
+        let type_idx = wasm.read_var_u32()? as TypeIdx;
+        Ok(Self { type_idx })
+    }
+
+    fn read_unvalidated(wasm: &mut WasmReader) -> Self {
+        let _attribute = wasm.read_u8().unwrap_validated();
+        Self {
+            type_idx: wasm.read_var_u32().unwrap_validated() as TypeIdx,
+        }
+    }
+}
+
+/// The type of the addresses used to access a memory
+///
+/// See <https://webassembly.github.io/memory64/core/syntax/types.html#memory-types>
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum AddrType {
+    I32,
+    I64,
+}
+
+impl AddrType {
+    /// The maximum number of pages a memory with this address type can have
+    pub fn max_pages(self) -> u64 {
+        match self {
+            AddrType::I32 => Limits::MAX_MEM_PAGES,
+            AddrType::I64 => Limits::MAX_MEM64_PAGES,
+        }
+    }
+}
+
+impl From<AddrType> for ValType {
+    fn from(addr_type: AddrType) -> Self {
+        match addr_type {
+            AddrType::I32 => ValType::NumType(NumType::I32),
+            AddrType::I64 => ValType::NumType(NumType::I64),
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub struct MemType {
     pub limits: Limits,
+    pub addr_type: AddrType,
+    /// Shared memories can be accessed by multiple threads at the same time, see
+    /// <https://webassembly.github.io/threads/core/syntax/types.html#memory-types>
+    pub shared: bool,
+}
+
+impl MemType {
+    /// Split the flags preceding the limits of a memory type into whether there is a maximum, whether the memory is
+    /// shared and its address type
+    ///
+    /// Bit 0 marks a maximum, bit 1 shared memories and bit 2 64-bit memories, see
+    /// <https://webassembly.github.io/memory64/core/binary/types.html#limits> and
+    /// <https://webassembly.github.io/threads/core/binary/types.html#limits>
+    fn decode_flags(flags: u8) -> Result<(bool, bool, AddrType)> {
+        if flags > 0x07 {
+            return Err(Error::InvalidLimitsType(flags));
+        }
+        let addr_type = if flags & 0x04 != 0 {
+            AddrType::I64
+        } else {
+            AddrType::I32
+        };
+        Ok((flags & 0x01 != 0, flags & 0x02 != 0, addr_type))
+    }
 }
 
 impl WasmReadable for MemType {
     fn read(wasm: &mut WasmReader) -> Result<Self> {
-        let mut limit = Limits::read(wasm)?;
-        // Memory can only grow to 65536 pages of 64kb size (4GiB)
-        if limit.min > (1 << 16) {
+        let (has_max, shared, addr_type) = Self::decode_flags(wasm.read_u8()?)?;
+        // The size of a shared memory must not change beyond a known bound, as other threads may access it
+        if shared && !has_max {
+            return Err(Error::SharedMemoryWithoutMaximum);
+        }
+        let mut limit = Limits::read_bounds(wasm, has_max, addr_type)?;
+
+        // Memory can only grow to 65536 pages of 64kb size (4GiB), or 1 << 48 pages for 64-bit memories
+        let max_pages = addr_type.max_pages();
+        if limit.min > max_pages {
             return Err(Error::MemSizeTooBig);
         }
-        if limit.max.is_none() {
-            limit.max = Some(1 << 16);
-        } else if limit.max.is_some() {
-            let max_limit = limit.max.unwrap();
-            if max_limit > (1 << 16) {
-                return Err(Error::MemSizeTooBig);
+        match limit.max {
+            None => limit.max = Some(max_pages),
+            Some(max_limit) => {
+                if max_limit > max_pages {
+                    return Err(Error::MemSizeTooBig);
+                }
             }
         }
-        Ok(Self { limits: limit })
+        Ok(Self {
+            limits: limit,
+            addr_type,
+            shared,
+        })
     }
 
     fn read_unvalidated(wasm: &mut WasmReader) -> Self {
-        Self {
-            limits: Limits::read_unvalidated(wasm),
-        }
+        // Share the decoding with `read`, which fills in the implicit maximum used when matching imports
+        Self::read(wasm).unwrap_validated()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::core::reader::types::MemType;
+    use crate::core::reader::{WasmReadable, WasmReader};
+
+    #[test]
+    fn mem_type_without_maximum() {
+        // (memory 1)
+        let bytes = [0x00, 0x01];
+
+        let validated = MemType::read(&mut WasmReader::new(&bytes)).unwrap();
+        let unvalidated = MemType::read_unvalidated(&mut WasmReader::new(&bytes));
+
+        assert_eq!(Some(65536), validated.limits.max);
+        assert_eq!(validated, unvalidated);
     }
 }
@@ -1,10 +1,23 @@
 //! All opcodes, in alphanumerical order by their numeric (hex-)value
+//!
+//! Additionally, the registry [OPCODES] (and the `OPCODES` of the extension modules) describes every opcode known to
+//! the interpreter: its name, its immediates, its stack signature and the proposal introducing it.
+
+use crate::core::reader::types::memarg::MemArg;
+use crate::core::reader::types::proposal::Proposal;
+use crate::core::reader::types::{BlockType, CatchClause};
+use crate::core::reader::{WasmReadable, WasmReader};
+use crate::execution::assert_validated::UnwrapValidatedExt;
+use crate::{NumType, RefType, ValType};
+
 pub const UNREACHABLE: u8 = 0x00;
 pub const NOP: u8 = 0x01;
 pub const BLOCK: u8 = 0x02;
 pub const LOOP: u8 = 0x03;
 pub const IF: u8 = 0x04;
 pub const ELSE: u8 = 0x05;
+pub const THROW: u8 = 0x08;
+pub const THROW_REF: u8 = 0x0A;
 pub const END: u8 = 0x0B;
 pub const BR: u8 = 0x0C;
 pub const BR_IF: u8 = 0x0D;
@@ -14,7 +27,10 @@ pub const CALL: u8 = 0x10;
 pub const DROP: u8 = 0x1A;
 pub const SELECT: u8 = 0x1B;
 pub const SELECT_T: u8 = 0x1C;
+pub const TRY_TABLE: u8 = 0x1F;
 pub const CALL_INDIRECT: u8 = 0x11;
+pub const RETURN_CALL: u8 = 0x12;
+pub const RETURN_CALL_INDIRECT: u8 = 0x13;
 pub const LOCAL_GET: u8 = 0x20;
 pub const LOCAL_SET: u8 = 0x21;
 pub const LOCAL_TEE: u8 = 0x22;
@@ -178,6 +194,8 @@ pub const REF_NULL: u8 = 0xD0;
 pub const REF_IS_NULL: u8 = 0xD1;
 pub const REF_FUNC: u8 = 0xD2;
 pub const FC_EXTENSIONS: u8 = 0xFC;
+pub const FD_EXTENSIONS: u8 = 0xFD;
+pub const FE_EXTENSIONS: u8 = 0xFE;
 pub const I32_EXTEND8_S: u8 = 0xC0;
 pub const I32_EXTEND16_S: u8 = 0xC1;
 pub const I64_EXTEND8_S: u8 = 0xC2;
@@ -185,6 +203,12 @@ pub const I64_EXTEND16_S: u8 = 0xC3;
 pub const I64_EXTEND32_S: u8 = 0xC4;
 
 pub mod fc_extensions {
+    use super::{
+        sig, Immediates as Imm, Opcode, OpcodeInfo, StackSignature, F32, F64, FC_EXTENSIONS, I32,
+        I64,
+    };
+    use crate::core::reader::types::proposal::Proposal::{self, *};
+
     pub const I32_TRUNC_SAT_F32_S: u8 = 0x00;
     pub const I32_TRUNC_SAT_F32_U: u8 = 0x01;
     pub const I32_TRUNC_SAT_F64_S: u8 = 0x02;
@@ -203,193 +227,1121 @@ pub mod fc_extensions {
     pub const TABLE_GROW: u8 = 0x0F;
     pub const TABLE_SIZE: u8 = 0x10;
     pub const TABLE_FILL: u8 = 0x11;
+
+    const fn fc(
+        sub_opcode: u8,
+        name: &'static str,
+        immediates: Imm,
+        signature: Option<StackSignature>,
+        proposal: Option<Proposal>,
+    ) -> OpcodeInfo {
+        OpcodeInfo {
+            opcode: Opcode::Prefixed(FC_EXTENSIONS, sub_opcode as u32),
+            name,
+            immediates,
+            signature,
+            proposal,
+        }
+    }
+
+    /// The registry entries of all opcodes prefixed by [`FC_EXTENSIONS`], sorted by their sub-opcode
+    #[rustfmt::skip]
+    pub static OPCODES: &[OpcodeInfo] = &[
+        fc(I32_TRUNC_SAT_F32_S, "i32.trunc_sat_f32_s", Imm::None, sig(&[F32], &[I32]), Some(NonTrappingFloatToInt)),
+        fc(I32_TRUNC_SAT_F32_U, "i32.trunc_sat_f32_u", Imm::None, sig(&[F32], &[I32]), Some(NonTrappingFloatToInt)),
+        fc(I32_TRUNC_SAT_F64_S, "i32.trunc_sat_f64_s", Imm::None, sig(&[F64], &[I32]), Some(NonTrappingFloatToInt)),
+        fc(I32_TRUNC_SAT_F64_U, "i32.trunc_sat_f64_u", Imm::None, sig(&[F64], &[I32]), Some(NonTrappingFloatToInt)),
+        fc(I64_TRUNC_SAT_F32_S, "i64.trunc_sat_f32_s", Imm::None, sig(&[F32], &[I64]), Some(NonTrappingFloatToInt)),
+        fc(I64_TRUNC_SAT_F32_U, "i64.trunc_sat_f32_u", Imm::None, sig(&[F32], &[I64]), Some(NonTrappingFloatToInt)),
+        fc(I64_TRUNC_SAT_F64_S, "i64.trunc_sat_f64_s", Imm::None, sig(&[F64], &[I64]), Some(NonTrappingFloatToInt)),
+        fc(I64_TRUNC_SAT_F64_U, "i64.trunc_sat_f64_u", Imm::None, sig(&[F64], &[I64]), Some(NonTrappingFloatToInt)),
+        fc(MEMORY_INIT, "memory.init", Imm::DataAndMemIdx, None, Some(BulkMemory)),
+        fc(DATA_DROP, "data.drop", Imm::DataIdx, None, Some(BulkMemory)),
+        fc(MEMORY_COPY, "memory.copy", Imm::MemIdxPair, None, Some(BulkMemory)),
+        fc(MEMORY_FILL, "memory.fill", Imm::MemIdx, None, Some(BulkMemory)),
+        fc(TABLE_INIT, "table.init", Imm::ElemAndTableIdx, None, Some(BulkMemory)),
+        fc(ELEM_DROP, "elem.drop", Imm::ElemIdx, None, Some(BulkMemory)),
+        fc(TABLE_COPY, "table.copy", Imm::TableIdxPair, None, Some(BulkMemory)),
+        fc(TABLE_GROW, "table.grow", Imm::TableIdx, None, Some(ReferenceTypes)),
+        fc(TABLE_SIZE, "table.size", Imm::TableIdx, None, Some(ReferenceTypes)),
+        fc(TABLE_FILL, "table.fill", Imm::TableIdx, None, Some(ReferenceTypes)),
+    ];
+}
+
+/// Opcodes of the fixed-width SIMD proposal, which are LEB128-encoded `u32`s following [`FD_EXTENSIONS`]
+///
+/// See <https://webassembly.github.io/spec/core/binary/instructions.html#vector-instructions>
+pub mod fd_extensions {
+    use super::{
+        sig, Immediates as Imm, Opcode, OpcodeInfo, StackSignature, F32, F64, FD_EXTENSIONS, I32,
+        I64, V128,
+    };
+    use crate::core::reader::types::proposal::Proposal;
+
+    pub const V128_LOAD: u32 = 0x00;
+    pub const V128_LOAD8X8_S: u32 = 0x01;
+    pub const V128_LOAD8X8_U: u32 = 0x02;
+    pub const V128_LOAD16X4_S: u32 = 0x03;
+    pub const V128_LOAD16X4_U: u32 = 0x04;
+    pub const V128_LOAD32X2_S: u32 = 0x05;
+    pub const V128_LOAD32X2_U: u32 = 0x06;
+    pub const V128_LOAD8_SPLAT: u32 = 0x07;
+    pub const V128_LOAD16_SPLAT: u32 = 0x08;
+    pub const V128_LOAD32_SPLAT: u32 = 0x09;
+    pub const V128_LOAD64_SPLAT: u32 = 0x0A;
+    pub const V128_STORE: u32 = 0x0B;
+    pub const V128_CONST: u32 = 0x0C;
+    pub const I8X16_SHUFFLE: u32 = 0x0D;
+    pub const I8X16_SWIZZLE: u32 = 0x0E;
+    pub const I8X16_SPLAT: u32 = 0x0F;
+    pub const I16X8_SPLAT: u32 = 0x10;
+    pub const I32X4_SPLAT: u32 = 0x11;
+    pub const I64X2_SPLAT: u32 = 0x12;
+    pub const F32X4_SPLAT: u32 = 0x13;
+    pub const F64X2_SPLAT: u32 = 0x14;
+    pub const I8X16_EXTRACT_LANE_S: u32 = 0x15;
+    pub const I8X16_EXTRACT_LANE_U: u32 = 0x16;
+    pub const I8X16_REPLACE_LANE: u32 = 0x17;
+    pub const I16X8_EXTRACT_LANE_S: u32 = 0x18;
+    pub const I16X8_EXTRACT_LANE_U: u32 = 0x19;
+    pub const I16X8_REPLACE_LANE: u32 = 0x1A;
+    pub const I32X4_EXTRACT_LANE: u32 = 0x1B;
+    pub const I32X4_REPLACE_LANE: u32 = 0x1C;
+    pub const I64X2_EXTRACT_LANE: u32 = 0x1D;
+    pub const I64X2_REPLACE_LANE: u32 = 0x1E;
+    pub const F32X4_EXTRACT_LANE: u32 = 0x1F;
+    pub const F32X4_REPLACE_LANE: u32 = 0x20;
+    pub const F64X2_EXTRACT_LANE: u32 = 0x21;
+    pub const F64X2_REPLACE_LANE: u32 = 0x22;
+    pub const I8X16_EQ: u32 = 0x23;
+    pub const I8X16_NE: u32 = 0x24;
+    pub const I8X16_LT_S: u32 = 0x25;
+    pub const I8X16_LT_U: u32 = 0x26;
+    pub const I8X16_GT_S: u32 = 0x27;
+    pub const I8X16_GT_U: u32 = 0x28;
+    pub const I8X16_LE_S: u32 = 0x29;
+    pub const I8X16_LE_U: u32 = 0x2A;
+    pub const I8X16_GE_S: u32 = 0x2B;
+    pub const I8X16_GE_U: u32 = 0x2C;
+    pub const I16X8_EQ: u32 = 0x2D;
+    pub const I16X8_NE: u32 = 0x2E;
+    pub const I16X8_LT_S: u32 = 0x2F;
+    pub const I16X8_LT_U: u32 = 0x30;
+    pub const I16X8_GT_S: u32 = 0x31;
+    pub const I16X8_GT_U: u32 = 0x32;
+    pub const I16X8_LE_S: u32 = 0x33;
+    pub const I16X8_LE_U: u32 = 0x34;
+    pub const I16X8_GE_S: u32 = 0x35;
+    pub const I16X8_GE_U: u32 = 0x36;
+    pub const I32X4_EQ: u32 = 0x37;
+    pub const I32X4_NE: u32 = 0x38;
+    pub const I32X4_LT_S: u32 = 0x39;
+    pub const I32X4_LT_U: u32 = 0x3A;
+    pub const I32X4_GT_S: u32 = 0x3B;
+    pub const I32X4_GT_U: u32 = 0x3C;
+    pub const I32X4_LE_S: u32 = 0x3D;
+    pub const I32X4_LE_U: u32 = 0x3E;
+    pub const I32X4_GE_S: u32 = 0x3F;
+    pub const I32X4_GE_U: u32 = 0x40;
+    pub const F32X4_EQ: u32 = 0x41;
+    pub const F32X4_NE: u32 = 0x42;
+    pub const F32X4_LT: u32 = 0x43;
+    pub const F32X4_GT: u32 = 0x44;
+    pub const F32X4_LE: u32 = 0x45;
+    pub const F32X4_GE: u32 = 0x46;
+    pub const F64X2_EQ: u32 = 0x47;
+    pub const F64X2_NE: u32 = 0x48;
+    pub const F64X2_LT: u32 = 0x49;
+    pub const F64X2_GT: u32 = 0x4A;
+    pub const F64X2_LE: u32 = 0x4B;
+    pub const F64X2_GE: u32 = 0x4C;
+    pub const V128_NOT: u32 = 0x4D;
+    pub const V128_AND: u32 = 0x4E;
+    pub const V128_ANDNOT: u32 = 0x4F;
+    pub const V128_OR: u32 = 0x50;
+    pub const V128_XOR: u32 = 0x51;
+    pub const V128_BITSELECT: u32 = 0x52;
+    pub const V128_ANY_TRUE: u32 = 0x53;
+    pub const V128_LOAD8_LANE: u32 = 0x54;
+    pub const V128_LOAD16_LANE: u32 = 0x55;
+    pub const V128_LOAD32_LANE: u32 = 0x56;
+    pub const V128_LOAD64_LANE: u32 = 0x57;
+    pub const V128_STORE8_LANE: u32 = 0x58;
+    pub const V128_STORE16_LANE: u32 = 0x59;
+    pub const V128_STORE32_LANE: u32 = 0x5A;
+    pub const V128_STORE64_LANE: u32 = 0x5B;
+    pub const V128_LOAD32_ZERO: u32 = 0x5C;
+    pub const V128_LOAD64_ZERO: u32 = 0x5D;
+    pub const F32X4_DEMOTE_F64X2_ZERO: u32 = 0x5E;
+    pub const F64X2_PROMOTE_LOW_F32X4: u32 = 0x5F;
+    pub const I8X16_ABS: u32 = 0x60;
+    pub const I8X16_NEG: u32 = 0x61;
+    pub const I8X16_POPCNT: u32 = 0x62;
+    pub const I8X16_ALL_TRUE: u32 = 0x63;
+    pub const I8X16_BITMASK: u32 = 0x64;
+    pub const I8X16_NARROW_I16X8_S: u32 = 0x65;
+    pub const I8X16_NARROW_I16X8_U: u32 = 0x66;
+    pub const F32X4_CEIL: u32 = 0x67;
+    pub const F32X4_FLOOR: u32 = 0x68;
+    pub const F32X4_TRUNC: u32 = 0x69;
+    pub const F32X4_NEAREST: u32 = 0x6A;
+    pub const I8X16_SHL: u32 = 0x6B;
+    pub const I8X16_SHR_S: u32 = 0x6C;
+    pub const I8X16_SHR_U: u32 = 0x6D;
+    pub const I8X16_ADD: u32 = 0x6E;
+    pub const I8X16_ADD_SAT_S: u32 = 0x6F;
+    pub const I8X16_ADD_SAT_U: u32 = 0x70;
+    pub const I8X16_SUB: u32 = 0x71;
+    pub const I8X16_SUB_SAT_S: u32 = 0x72;
+    pub const I8X16_SUB_SAT_U: u32 = 0x73;
+    pub const F64X2_CEIL: u32 = 0x74;
+    pub const F64X2_FLOOR: u32 = 0x75;
+    pub const I8X16_MIN_S: u32 = 0x76;
+    pub const I8X16_MIN_U: u32 = 0x77;
+    pub const I8X16_MAX_S: u32 = 0x78;
+    pub const I8X16_MAX_U: u32 = 0x79;
+    pub const F64X2_TRUNC: u32 = 0x7A;
+    pub const I8X16_AVGR_U: u32 = 0x7B;
+    pub const I16X8_EXTADD_PAIRWISE_I8X16_S: u32 = 0x7C;
+    pub const I16X8_EXTADD_PAIRWISE_I8X16_U: u32 = 0x7D;
+    pub const I32X4_EXTADD_PAIRWISE_I16X8_S: u32 = 0x7E;
+    pub const I32X4_EXTADD_PAIRWISE_I16X8_U: u32 = 0x7F;
+    pub const I16X8_ABS: u32 = 0x80;
+    pub const I16X8_NEG: u32 = 0x81;
+    pub const I16X8_Q15MULR_SAT_S: u32 = 0x82;
+    pub const I16X8_ALL_TRUE: u32 = 0x83;
+    pub const I16X8_BITMASK: u32 = 0x84;
+    pub const I16X8_NARROW_I32X4_S: u32 = 0x85;
+    pub const I16X8_NARROW_I32X4_U: u32 = 0x86;
+    pub const I16X8_EXTEND_LOW_I8X16_S: u32 = 0x87;
+    pub const I16X8_EXTEND_HIGH_I8X16_S: u32 = 0x88;
+    pub const I16X8_EXTEND_LOW_I8X16_U: u32 = 0x89;
+    pub const I16X8_EXTEND_HIGH_I8X16_U: u32 = 0x8A;
+    pub const I16X8_SHL: u32 = 0x8B;
+    pub const I16X8_SHR_S: u32 = 0x8C;
+    pub const I16X8_SHR_U: u32 = 0x8D;
+    pub const I16X8_ADD: u32 = 0x8E;
+    pub const I16X8_ADD_SAT_S: u32 = 0x8F;
+    pub const I16X8_ADD_SAT_U: u32 = 0x90;
+    pub const I16X8_SUB: u32 = 0x91;
+    pub const I16X8_SUB_SAT_S: u32 = 0x92;
+    pub const I16X8_SUB_SAT_U: u32 = 0x93;
+    pub const F64X2_NEAREST: u32 = 0x94;
+    pub const I16X8_MUL: u32 = 0x95;
+    pub const I16X8_MIN_S: u32 = 0x96;
+    pub const I16X8_MIN_U: u32 = 0x97;
+    pub const I16X8_MAX_S: u32 = 0x98;
+    pub const I16X8_MAX_U: u32 = 0x99;
+    pub const I16X8_AVGR_U: u32 = 0x9B;
+    pub const I16X8_EXTMUL_LOW_I8X16_S: u32 = 0x9C;
+    pub const I16X8_EXTMUL_HIGH_I8X16_S: u32 = 0x9D;
+    pub const I16X8_EXTMUL_LOW_I8X16_U: u32 = 0x9E;
+    pub const I16X8_EXTMUL_HIGH_I8X16_U: u32 = 0x9F;
+    pub const I32X4_ABS: u32 = 0xA0;
+    pub const I32X4_NEG: u32 = 0xA1;
+    pub const I32X4_ALL_TRUE: u32 = 0xA3;
+    pub const I32X4_BITMASK: u32 = 0xA4;
+    pub const I32X4_EXTEND_LOW_I16X8_S: u32 = 0xA7;
+    pub const I32X4_EXTEND_HIGH_I16X8_S: u32 = 0xA8;
+    pub const I32X4_EXTEND_LOW_I16X8_U: u32 = 0xA9;
+    pub const I32X4_EXTEND_HIGH_I16X8_U: u32 = 0xAA;
+    pub const I32X4_SHL: u32 = 0xAB;
+    pub const I32X4_SHR_S: u32 = 0xAC;
+    pub const I32X4_SHR_U: u32 = 0xAD;
+    pub const I32X4_ADD: u32 = 0xAE;
+    pub const I32X4_SUB: u32 = 0xB1;
+    pub const I32X4_MUL: u32 = 0xB5;
+    pub const I32X4_MIN_S: u32 = 0xB6;
+    pub const I32X4_MIN_U: u32 = 0xB7;
+    pub const I32X4_MAX_S: u32 = 0xB8;
+    pub const I32X4_MAX_U: u32 = 0xB9;
+    pub const I32X4_DOT_I16X8_S: u32 = 0xBA;
+    pub const I32X4_EXTMUL_LOW_I16X8_S: u32 = 0xBC;
+    pub const I32X4_EXTMUL_HIGH_I16X8_S: u32 = 0xBD;
+    pub const I32X4_EXTMUL_LOW_I16X8_U: u32 = 0xBE;
+    pub const I32X4_EXTMUL_HIGH_I16X8_U: u32 = 0xBF;
+    pub const I64X2_ABS: u32 = 0xC0;
+    pub const I64X2_NEG: u32 = 0xC1;
+    pub const I64X2_ALL_TRUE: u32 = 0xC3;
+    pub const I64X2_BITMASK: u32 = 0xC4;
+    pub const I64X2_EXTEND_LOW_I32X4_S: u32 = 0xC7;
+    pub const I64X2_EXTEND_HIGH_I32X4_S: u32 = 0xC8;
+    pub const I64X2_EXTEND_LOW_I32X4_U: u32 = 0xC9;
+    pub const I64X2_EXTEND_HIGH_I32X4_U: u32 = 0xCA;
+    pub const I64X2_SHL: u32 = 0xCB;
+    pub const I64X2_SHR_S: u32 = 0xCC;
+    pub const I64X2_SHR_U: u32 = 0xCD;
+    pub const I64X2_ADD: u32 = 0xCE;
+    pub const I64X2_SUB: u32 = 0xD1;
+    pub const I64X2_MUL: u32 = 0xD5;
+    pub const I64X2_EQ: u32 = 0xD6;
+    pub const I64X2_NE: u32 = 0xD7;
+    pub const I64X2_LT_S: u32 = 0xD8;
+    pub const I64X2_GT_S: u32 = 0xD9;
+    pub const I64X2_LE_S: u32 = 0xDA;
+    pub const I64X2_GE_S: u32 = 0xDB;
+    pub const I64X2_EXTMUL_LOW_I32X4_S: u32 = 0xDC;
+    pub const I64X2_EXTMUL_HIGH_I32X4_S: u32 = 0xDD;
+    pub const I64X2_EXTMUL_LOW_I32X4_U: u32 = 0xDE;
+    pub const I64X2_EXTMUL_HIGH_I32X4_U: u32 = 0xDF;
+    pub const F32X4_ABS: u32 = 0xE0;
+    pub const F32X4_NEG: u32 = 0xE1;
+    pub const F32X4_SQRT: u32 = 0xE3;
+    pub const F32X4_ADD: u32 = 0xE4;
+    pub const F32X4_SUB: u32 = 0xE5;
+    pub const F32X4_MUL: u32 = 0xE6;
+    pub const F32X4_DIV: u32 = 0xE7;
+    pub const F32X4_MIN: u32 = 0xE8;
+    pub const F32X4_MAX: u32 = 0xE9;
+    pub const F32X4_PMIN: u32 = 0xEA;
+    pub const F32X4_PMAX: u32 = 0xEB;
+    pub const F64X2_ABS: u32 = 0xEC;
+    pub const F64X2_NEG: u32 = 0xED;
+    pub const F64X2_SQRT: u32 = 0xEF;
+    pub const F64X2_ADD: u32 = 0xF0;
+    pub const F64X2_SUB: u32 = 0xF1;
+    pub const F64X2_MUL: u32 = 0xF2;
+    pub const F64X2_DIV: u32 = 0xF3;
+    pub const F64X2_MIN: u32 = 0xF4;
+    pub const F64X2_MAX: u32 = 0xF5;
+    pub const F64X2_PMIN: u32 = 0xF6;
+    pub const F64X2_PMAX: u32 = 0xF7;
+    pub const I32X4_TRUNC_SAT_F32X4_S: u32 = 0xF8;
+    pub const I32X4_TRUNC_SAT_F32X4_U: u32 = 0xF9;
+    pub const F32X4_CONVERT_I32X4_S: u32 = 0xFA;
+    pub const F32X4_CONVERT_I32X4_U: u32 = 0xFB;
+    pub const I32X4_TRUNC_SAT_F64X2_S_ZERO: u32 = 0xFC;
+    pub const I32X4_TRUNC_SAT_F64X2_U_ZERO: u32 = 0xFD;
+    pub const F64X2_CONVERT_LOW_I32X4_S: u32 = 0xFE;
+    pub const F64X2_CONVERT_LOW_I32X4_U: u32 = 0xFF;
+
+    const fn fd(
+        sub_opcode: u32,
+        name: &'static str,
+        immediates: Imm,
+        signature: Option<StackSignature>,
+    ) -> OpcodeInfo {
+        OpcodeInfo {
+            opcode: Opcode::Prefixed(FD_EXTENSIONS, sub_opcode),
+            name,
+            immediates,
+            signature,
+            proposal: Some(Proposal::Simd),
+        }
+    }
+
+    /// The registry entries of all opcodes prefixed by [`FD_EXTENSIONS`], sorted by their sub-opcode
+    #[rustfmt::skip]
+    pub static OPCODES: &[OpcodeInfo] = &[
+        fd(V128_LOAD, "v128.load", Imm::MemArg, None),
+        fd(V128_LOAD8X8_S, "v128.load8x8_s", Imm::MemArg, None),
+        fd(V128_LOAD8X8_U, "v128.load8x8_u", Imm::MemArg, None),
+        fd(V128_LOAD16X4_S, "v128.load16x4_s", Imm::MemArg, None),
+        fd(V128_LOAD16X4_U, "v128.load16x4_u", Imm::MemArg, None),
+        fd(V128_LOAD32X2_S, "v128.load32x2_s", Imm::MemArg, None),
+        fd(V128_LOAD32X2_U, "v128.load32x2_u", Imm::MemArg, None),
+        fd(V128_LOAD8_SPLAT, "v128.load8_splat", Imm::MemArg, None),
+        fd(V128_LOAD16_SPLAT, "v128.load16_splat", Imm::MemArg, None),
+        fd(V128_LOAD32_SPLAT, "v128.load32_splat", Imm::MemArg, None),
+        fd(V128_LOAD64_SPLAT, "v128.load64_splat", Imm::MemArg, None),
+        fd(V128_STORE, "v128.store", Imm::MemArg, None),
+        fd(V128_CONST, "v128.const", Imm::V128, sig(&[], &[V128])),
+        fd(I8X16_SHUFFLE, "i8x16.shuffle", Imm::LaneIndices, sig(&[V128, V128], &[V128])),
+        fd(I8X16_SWIZZLE, "i8x16.swizzle", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_SPLAT, "i8x16.splat", Imm::None, sig(&[I32], &[V128])),
+        fd(I16X8_SPLAT, "i16x8.splat", Imm::None, sig(&[I32], &[V128])),
+        fd(I32X4_SPLAT, "i32x4.splat", Imm::None, sig(&[I32], &[V128])),
+        fd(I64X2_SPLAT, "i64x2.splat", Imm::None, sig(&[I64], &[V128])),
+        fd(F32X4_SPLAT, "f32x4.splat", Imm::None, sig(&[F32], &[V128])),
+        fd(F64X2_SPLAT, "f64x2.splat", Imm::None, sig(&[F64], &[V128])),
+        fd(I8X16_EXTRACT_LANE_S, "i8x16.extract_lane_s", Imm::Lane, sig(&[V128], &[I32])),
+        fd(I8X16_EXTRACT_LANE_U, "i8x16.extract_lane_u", Imm::Lane, sig(&[V128], &[I32])),
+        fd(I8X16_REPLACE_LANE, "i8x16.replace_lane", Imm::Lane, sig(&[V128, I32], &[V128])),
+        fd(I16X8_EXTRACT_LANE_S, "i16x8.extract_lane_s", Imm::Lane, sig(&[V128], &[I32])),
+        fd(I16X8_EXTRACT_LANE_U, "i16x8.extract_lane_u", Imm::Lane, sig(&[V128], &[I32])),
+        fd(I16X8_REPLACE_LANE, "i16x8.replace_lane", Imm::Lane, sig(&[V128, I32], &[V128])),
+        fd(I32X4_EXTRACT_LANE, "i32x4.extract_lane", Imm::Lane, sig(&[V128], &[I32])),
+        fd(I32X4_REPLACE_LANE, "i32x4.replace_lane", Imm::Lane, sig(&[V128, I32], &[V128])),
+        fd(I64X2_EXTRACT_LANE, "i64x2.extract_lane", Imm::Lane, sig(&[V128], &[I64])),
+        fd(I64X2_REPLACE_LANE, "i64x2.replace_lane", Imm::Lane, sig(&[V128, I64], &[V128])),
+        fd(F32X4_EXTRACT_LANE, "f32x4.extract_lane", Imm::Lane, sig(&[V128], &[F32])),
+        fd(F32X4_REPLACE_LANE, "f32x4.replace_lane", Imm::Lane, sig(&[V128, F32], &[V128])),
+        fd(F64X2_EXTRACT_LANE, "f64x2.extract_lane", Imm::Lane, sig(&[V128], &[F64])),
+        fd(F64X2_REPLACE_LANE, "f64x2.replace_lane", Imm::Lane, sig(&[V128, F64], &[V128])),
+        fd(I8X16_EQ, "i8x16.eq", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_NE, "i8x16.ne", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_LT_S, "i8x16.lt_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_LT_U, "i8x16.lt_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_GT_S, "i8x16.gt_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_GT_U, "i8x16.gt_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_LE_S, "i8x16.le_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_LE_U, "i8x16.le_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_GE_S, "i8x16.ge_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_GE_U, "i8x16.ge_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_EQ, "i16x8.eq", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_NE, "i16x8.ne", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_LT_S, "i16x8.lt_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_LT_U, "i16x8.lt_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_GT_S, "i16x8.gt_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_GT_U, "i16x8.gt_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_LE_S, "i16x8.le_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_LE_U, "i16x8.le_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_GE_S, "i16x8.ge_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_GE_U, "i16x8.ge_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_EQ, "i32x4.eq", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_NE, "i32x4.ne", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_LT_S, "i32x4.lt_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_LT_U, "i32x4.lt_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_GT_S, "i32x4.gt_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_GT_U, "i32x4.gt_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_LE_S, "i32x4.le_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_LE_U, "i32x4.le_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_GE_S, "i32x4.ge_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_GE_U, "i32x4.ge_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_EQ, "f32x4.eq", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_NE, "f32x4.ne", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_LT, "f32x4.lt", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_GT, "f32x4.gt", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_LE, "f32x4.le", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_GE, "f32x4.ge", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_EQ, "f64x2.eq", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_NE, "f64x2.ne", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_LT, "f64x2.lt", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_GT, "f64x2.gt", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_LE, "f64x2.le", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_GE, "f64x2.ge", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(V128_NOT, "v128.not", Imm::None, sig(&[V128], &[V128])),
+        fd(V128_AND, "v128.and", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(V128_ANDNOT, "v128.andnot", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(V128_OR, "v128.or", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(V128_XOR, "v128.xor", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(V128_BITSELECT, "v128.bitselect", Imm::None, sig(&[V128, V128, V128], &[V128])),
+        fd(V128_ANY_TRUE, "v128.any_true", Imm::None, sig(&[V128], &[I32])),
+        fd(V128_LOAD8_LANE, "v128.load8_lane", Imm::MemArgAndLane, None),
+        fd(V128_LOAD16_LANE, "v128.load16_lane", Imm::MemArgAndLane, None),
+        fd(V128_LOAD32_LANE, "v128.load32_lane", Imm::MemArgAndLane, None),
+        fd(V128_LOAD64_LANE, "v128.load64_lane", Imm::MemArgAndLane, None),
+        fd(V128_STORE8_LANE, "v128.store8_lane", Imm::MemArgAndLane, None),
+        fd(V128_STORE16_LANE, "v128.store16_lane", Imm::MemArgAndLane, None),
+        fd(V128_STORE32_LANE, "v128.store32_lane", Imm::MemArgAndLane, None),
+        fd(V128_STORE64_LANE, "v128.store64_lane", Imm::MemArgAndLane, None),
+        fd(V128_LOAD32_ZERO, "v128.load32_zero", Imm::MemArg, None),
+        fd(V128_LOAD64_ZERO, "v128.load64_zero", Imm::MemArg, None),
+        fd(F32X4_DEMOTE_F64X2_ZERO, "f32x4.demote_f64x2_zero", Imm::None, sig(&[V128], &[V128])),
+        fd(F64X2_PROMOTE_LOW_F32X4, "f64x2.promote_low_f32x4", Imm::None, sig(&[V128], &[V128])),
+        fd(I8X16_ABS, "i8x16.abs", Imm::None, sig(&[V128], &[V128])),
+        fd(I8X16_NEG, "i8x16.neg", Imm::None, sig(&[V128], &[V128])),
+        fd(I8X16_POPCNT, "i8x16.popcnt", Imm::None, sig(&[V128], &[V128])),
+        fd(I8X16_ALL_TRUE, "i8x16.all_true", Imm::None, sig(&[V128], &[I32])),
+        fd(I8X16_BITMASK, "i8x16.bitmask", Imm::None, sig(&[V128], &[I32])),
+        fd(I8X16_NARROW_I16X8_S, "i8x16.narrow_i16x8_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_NARROW_I16X8_U, "i8x16.narrow_i16x8_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_CEIL, "f32x4.ceil", Imm::None, sig(&[V128], &[V128])),
+        fd(F32X4_FLOOR, "f32x4.floor", Imm::None, sig(&[V128], &[V128])),
+        fd(F32X4_TRUNC, "f32x4.trunc", Imm::None, sig(&[V128], &[V128])),
+        fd(F32X4_NEAREST, "f32x4.nearest", Imm::None, sig(&[V128], &[V128])),
+        fd(I8X16_SHL, "i8x16.shl", Imm::None, sig(&[V128, I32], &[V128])),
+        fd(I8X16_SHR_S, "i8x16.shr_s", Imm::None, sig(&[V128, I32], &[V128])),
+        fd(I8X16_SHR_U, "i8x16.shr_u", Imm::None, sig(&[V128, I32], &[V128])),
+        fd(I8X16_ADD, "i8x16.add", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_ADD_SAT_S, "i8x16.add_sat_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_ADD_SAT_U, "i8x16.add_sat_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_SUB, "i8x16.sub", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_SUB_SAT_S, "i8x16.sub_sat_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_SUB_SAT_U, "i8x16.sub_sat_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_CEIL, "f64x2.ceil", Imm::None, sig(&[V128], &[V128])),
+        fd(F64X2_FLOOR, "f64x2.floor", Imm::None, sig(&[V128], &[V128])),
+        fd(I8X16_MIN_S, "i8x16.min_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_MIN_U, "i8x16.min_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_MAX_S, "i8x16.max_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I8X16_MAX_U, "i8x16.max_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_TRUNC, "f64x2.trunc", Imm::None, sig(&[V128], &[V128])),
+        fd(I8X16_AVGR_U, "i8x16.avgr_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_EXTADD_PAIRWISE_I8X16_S, "i16x8.extadd_pairwise_i8x16_s", Imm::None, sig(&[V128], &[V128])),
+        fd(I16X8_EXTADD_PAIRWISE_I8X16_U, "i16x8.extadd_pairwise_i8x16_u", Imm::None, sig(&[V128], &[V128])),
+        fd(I32X4_EXTADD_PAIRWISE_I16X8_S, "i32x4.extadd_pairwise_i16x8_s", Imm::None, sig(&[V128], &[V128])),
+        fd(I32X4_EXTADD_PAIRWISE_I16X8_U, "i32x4.extadd_pairwise_i16x8_u", Imm::None, sig(&[V128], &[V128])),
+        fd(I16X8_ABS, "i16x8.abs", Imm::None, sig(&[V128], &[V128])),
+        fd(I16X8_NEG, "i16x8.neg", Imm::None, sig(&[V128], &[V128])),
+        fd(I16X8_Q15MULR_SAT_S, "i16x8.q15mulr_sat_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_ALL_TRUE, "i16x8.all_true", Imm::None, sig(&[V128], &[I32])),
+        fd(I16X8_BITMASK, "i16x8.bitmask", Imm::None, sig(&[V128], &[I32])),
+        fd(I16X8_NARROW_I32X4_S, "i16x8.narrow_i32x4_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_NARROW_I32X4_U, "i16x8.narrow_i32x4_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_EXTEND_LOW_I8X16_S, "i16x8.extend_low_i8x16_s", Imm::None, sig(&[V128], &[V128])),
+        fd(I16X8_EXTEND_HIGH_I8X16_S, "i16x8.extend_high_i8x16_s", Imm::None, sig(&[V128], &[V128])),
+        fd(I16X8_EXTEND_LOW_I8X16_U, "i16x8.extend_low_i8x16_u", Imm::None, sig(&[V128], &[V128])),
+        fd(I16X8_EXTEND_HIGH_I8X16_U, "i16x8.extend_high_i8x16_u", Imm::None, sig(&[V128], &[V128])),
+        fd(I16X8_SHL, "i16x8.shl", Imm::None, sig(&[V128, I32], &[V128])),
+        fd(I16X8_SHR_S, "i16x8.shr_s", Imm::None, sig(&[V128, I32], &[V128])),
+        fd(I16X8_SHR_U, "i16x8.shr_u", Imm::None, sig(&[V128, I32], &[V128])),
+        fd(I16X8_ADD, "i16x8.add", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_ADD_SAT_S, "i16x8.add_sat_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_ADD_SAT_U, "i16x8.add_sat_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_SUB, "i16x8.sub", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_SUB_SAT_S, "i16x8.sub_sat_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_SUB_SAT_U, "i16x8.sub_sat_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_NEAREST, "f64x2.nearest", Imm::None, sig(&[V128], &[V128])),
+        fd(I16X8_MUL, "i16x8.mul", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_MIN_S, "i16x8.min_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_MIN_U, "i16x8.min_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_MAX_S, "i16x8.max_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_MAX_U, "i16x8.max_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_AVGR_U, "i16x8.avgr_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_EXTMUL_LOW_I8X16_S, "i16x8.extmul_low_i8x16_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_EXTMUL_HIGH_I8X16_S, "i16x8.extmul_high_i8x16_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_EXTMUL_LOW_I8X16_U, "i16x8.extmul_low_i8x16_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I16X8_EXTMUL_HIGH_I8X16_U, "i16x8.extmul_high_i8x16_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_ABS, "i32x4.abs", Imm::None, sig(&[V128], &[V128])),
+        fd(I32X4_NEG, "i32x4.neg", Imm::None, sig(&[V128], &[V128])),
+        fd(I32X4_ALL_TRUE, "i32x4.all_true", Imm::None, sig(&[V128], &[I32])),
+        fd(I32X4_BITMASK, "i32x4.bitmask", Imm::None, sig(&[V128], &[I32])),
+        fd(I32X4_EXTEND_LOW_I16X8_S, "i32x4.extend_low_i16x8_s", Imm::None, sig(&[V128], &[V128])),
+        fd(I32X4_EXTEND_HIGH_I16X8_S, "i32x4.extend_high_i16x8_s", Imm::None, sig(&[V128], &[V128])),
+        fd(I32X4_EXTEND_LOW_I16X8_U, "i32x4.extend_low_i16x8_u", Imm::None, sig(&[V128], &[V128])),
+        fd(I32X4_EXTEND_HIGH_I16X8_U, "i32x4.extend_high_i16x8_u", Imm::None, sig(&[V128], &[V128])),
+        fd(I32X4_SHL, "i32x4.shl", Imm::None, sig(&[V128, I32], &[V128])),
+        fd(I32X4_SHR_S, "i32x4.shr_s", Imm::None, sig(&[V128, I32], &[V128])),
+        fd(I32X4_SHR_U, "i32x4.shr_u", Imm::None, sig(&[V128, I32], &[V128])),
+        fd(I32X4_ADD, "i32x4.add", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_SUB, "i32x4.sub", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_MUL, "i32x4.mul", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_MIN_S, "i32x4.min_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_MIN_U, "i32x4.min_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_MAX_S, "i32x4.max_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_MAX_U, "i32x4.max_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_DOT_I16X8_S, "i32x4.dot_i16x8_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_EXTMUL_LOW_I16X8_S, "i32x4.extmul_low_i16x8_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_EXTMUL_HIGH_I16X8_S, "i32x4.extmul_high_i16x8_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_EXTMUL_LOW_I16X8_U, "i32x4.extmul_low_i16x8_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_EXTMUL_HIGH_I16X8_U, "i32x4.extmul_high_i16x8_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_ABS, "i64x2.abs", Imm::None, sig(&[V128], &[V128])),
+        fd(I64X2_NEG, "i64x2.neg", Imm::None, sig(&[V128], &[V128])),
+        fd(I64X2_ALL_TRUE, "i64x2.all_true", Imm::None, sig(&[V128], &[I32])),
+        fd(I64X2_BITMASK, "i64x2.bitmask", Imm::None, sig(&[V128], &[I32])),
+        fd(I64X2_EXTEND_LOW_I32X4_S, "i64x2.extend_low_i32x4_s", Imm::None, sig(&[V128], &[V128])),
+        fd(I64X2_EXTEND_HIGH_I32X4_S, "i64x2.extend_high_i32x4_s", Imm::None, sig(&[V128], &[V128])),
+        fd(I64X2_EXTEND_LOW_I32X4_U, "i64x2.extend_low_i32x4_u", Imm::None, sig(&[V128], &[V128])),
+        fd(I64X2_EXTEND_HIGH_I32X4_U, "i64x2.extend_high_i32x4_u", Imm::None, sig(&[V128], &[V128])),
+        fd(I64X2_SHL, "i64x2.shl", Imm::None, sig(&[V128, I32], &[V128])),
+        fd(I64X2_SHR_S, "i64x2.shr_s", Imm::None, sig(&[V128, I32], &[V128])),
+        fd(I64X2_SHR_U, "i64x2.shr_u", Imm::None, sig(&[V128, I32], &[V128])),
+        fd(I64X2_ADD, "i64x2.add", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_SUB, "i64x2.sub", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_MUL, "i64x2.mul", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_EQ, "i64x2.eq", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_NE, "i64x2.ne", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_LT_S, "i64x2.lt_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_GT_S, "i64x2.gt_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_LE_S, "i64x2.le_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_GE_S, "i64x2.ge_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_EXTMUL_LOW_I32X4_S, "i64x2.extmul_low_i32x4_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_EXTMUL_HIGH_I32X4_S, "i64x2.extmul_high_i32x4_s", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_EXTMUL_LOW_I32X4_U, "i64x2.extmul_low_i32x4_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I64X2_EXTMUL_HIGH_I32X4_U, "i64x2.extmul_high_i32x4_u", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_ABS, "f32x4.abs", Imm::None, sig(&[V128], &[V128])),
+        fd(F32X4_NEG, "f32x4.neg", Imm::None, sig(&[V128], &[V128])),
+        fd(F32X4_SQRT, "f32x4.sqrt", Imm::None, sig(&[V128], &[V128])),
+        fd(F32X4_ADD, "f32x4.add", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_SUB, "f32x4.sub", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_MUL, "f32x4.mul", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_DIV, "f32x4.div", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_MIN, "f32x4.min", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_MAX, "f32x4.max", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_PMIN, "f32x4.pmin", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F32X4_PMAX, "f32x4.pmax", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_ABS, "f64x2.abs", Imm::None, sig(&[V128], &[V128])),
+        fd(F64X2_NEG, "f64x2.neg", Imm::None, sig(&[V128], &[V128])),
+        fd(F64X2_SQRT, "f64x2.sqrt", Imm::None, sig(&[V128], &[V128])),
+        fd(F64X2_ADD, "f64x2.add", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_SUB, "f64x2.sub", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_MUL, "f64x2.mul", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_DIV, "f64x2.div", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_MIN, "f64x2.min", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_MAX, "f64x2.max", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_PMIN, "f64x2.pmin", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(F64X2_PMAX, "f64x2.pmax", Imm::None, sig(&[V128, V128], &[V128])),
+        fd(I32X4_TRUNC_SAT_F32X4_S, "i32x4.trunc_sat_f32x4_s", Imm::None, sig(&[V128], &[V128])),
+        fd(I32X4_TRUNC_SAT_F32X4_U, "i32x4.trunc_sat_f32x4_u", Imm::None, sig(&[V128], &[V128])),
+        fd(F32X4_CONVERT_I32X4_S, "f32x4.convert_i32x4_s", Imm::None, sig(&[V128], &[V128])),
+        fd(F32X4_CONVERT_I32X4_U, "f32x4.convert_i32x4_u", Imm::None, sig(&[V128], &[V128])),
+        fd(I32X4_TRUNC_SAT_F64X2_S_ZERO, "i32x4.trunc_sat_f64x2_s_zero", Imm::None, sig(&[V128], &[V128])),
+        fd(I32X4_TRUNC_SAT_F64X2_U_ZERO, "i32x4.trunc_sat_f64x2_u_zero", Imm::None, sig(&[V128], &[V128])),
+        fd(F64X2_CONVERT_LOW_I32X4_S, "f64x2.convert_low_i32x4_s", Imm::None, sig(&[V128], &[V128])),
+        fd(F64X2_CONVERT_LOW_I32X4_U, "f64x2.convert_low_i32x4_u", Imm::None, sig(&[V128], &[V128])),
+    ];
+}
+
+/// Opcodes of the threads proposal, which are LEB128-encoded `u32`s following [`FE_EXTENSIONS`]
+///
+/// See <https://webassembly.github.io/threads/core/binary/instructions.html#atomic-memory-instructions>
+pub mod fe_extensions {
+    use super::{sig, Immediates as Imm, Opcode, OpcodeInfo, StackSignature, FE_EXTENSIONS};
+    use crate::core::reader::types::proposal::Proposal;
+
+    pub const MEMORY_ATOMIC_NOTIFY: u32 = 0x00;
+    pub const MEMORY_ATOMIC_WAIT32: u32 = 0x01;
+    pub const MEMORY_ATOMIC_WAIT64: u32 = 0x02;
+    pub const ATOMIC_FENCE: u32 = 0x03;
+    pub const I32_ATOMIC_LOAD: u32 = 0x10;
+    pub const I64_ATOMIC_LOAD: u32 = 0x11;
+    pub const I32_ATOMIC_LOAD8_U: u32 = 0x12;
+    pub const I32_ATOMIC_LOAD16_U: u32 = 0x13;
+    pub const I64_ATOMIC_LOAD8_U: u32 = 0x14;
+    pub const I64_ATOMIC_LOAD16_U: u32 = 0x15;
+    pub const I64_ATOMIC_LOAD32_U: u32 = 0x16;
+    pub const I32_ATOMIC_STORE: u32 = 0x17;
+    pub const I64_ATOMIC_STORE: u32 = 0x18;
+    pub const I32_ATOMIC_STORE8: u32 = 0x19;
+    pub const I32_ATOMIC_STORE16: u32 = 0x1A;
+    pub const I64_ATOMIC_STORE8: u32 = 0x1B;
+    pub const I64_ATOMIC_STORE16: u32 = 0x1C;
+    pub const I64_ATOMIC_STORE32: u32 = 0x1D;
+    pub const I32_ATOMIC_RMW_ADD: u32 = 0x1E;
+    pub const I64_ATOMIC_RMW_ADD: u32 = 0x1F;
+    pub const I32_ATOMIC_RMW8_ADD_U: u32 = 0x20;
+    pub const I32_ATOMIC_RMW16_ADD_U: u32 = 0x21;
+    pub const I64_ATOMIC_RMW8_ADD_U: u32 = 0x22;
+    pub const I64_ATOMIC_RMW16_ADD_U: u32 = 0x23;
+    pub const I64_ATOMIC_RMW32_ADD_U: u32 = 0x24;
+    pub const I32_ATOMIC_RMW_SUB: u32 = 0x25;
+    pub const I64_ATOMIC_RMW_SUB: u32 = 0x26;
+    pub const I32_ATOMIC_RMW8_SUB_U: u32 = 0x27;
+    pub const I32_ATOMIC_RMW16_SUB_U: u32 = 0x28;
+    pub const I64_ATOMIC_RMW8_SUB_U: u32 = 0x29;
+    pub const I64_ATOMIC_RMW16_SUB_U: u32 = 0x2A;
+    pub const I64_ATOMIC_RMW32_SUB_U: u32 = 0x2B;
+    pub const I32_ATOMIC_RMW_AND: u32 = 0x2C;
+    pub const I64_ATOMIC_RMW_AND: u32 = 0x2D;
+    pub const I32_ATOMIC_RMW8_AND_U: u32 = 0x2E;
+    pub const I32_ATOMIC_RMW16_AND_U: u32 = 0x2F;
+    pub const I64_ATOMIC_RMW8_AND_U: u32 = 0x30;
+    pub const I64_ATOMIC_RMW16_AND_U: u32 = 0x31;
+    pub const I64_ATOMIC_RMW32_AND_U: u32 = 0x32;
+    pub const I32_ATOMIC_RMW_OR: u32 = 0x33;
+    pub const I64_ATOMIC_RMW_OR: u32 = 0x34;
+    pub const I32_ATOMIC_RMW8_OR_U: u32 = 0x35;
+    pub const I32_ATOMIC_RMW16_OR_U: u32 = 0x36;
+    pub const I64_ATOMIC_RMW8_OR_U: u32 = 0x37;
+    pub const I64_ATOMIC_RMW16_OR_U: u32 = 0x38;
+    pub const I64_ATOMIC_RMW32_OR_U: u32 = 0x39;
+    pub const I32_ATOMIC_RMW_XOR: u32 = 0x3A;
+    pub const I64_ATOMIC_RMW_XOR: u32 = 0x3B;
+    pub const I32_ATOMIC_RMW8_XOR_U: u32 = 0x3C;
+    pub const I32_ATOMIC_RMW16_XOR_U: u32 = 0x3D;
+    pub const I64_ATOMIC_RMW8_XOR_U: u32 = 0x3E;
+    pub const I64_ATOMIC_RMW16_XOR_U: u32 = 0x3F;
+    pub const I64_ATOMIC_RMW32_XOR_U: u32 = 0x40;
+    pub const I32_ATOMIC_RMW_XCHG: u32 = 0x41;
+    pub const I64_ATOMIC_RMW_XCHG: u32 = 0x42;
+    pub const I32_ATOMIC_RMW8_XCHG_U: u32 = 0x43;
+    pub const I32_ATOMIC_RMW16_XCHG_U: u32 = 0x44;
+    pub const I64_ATOMIC_RMW8_XCHG_U: u32 = 0x45;
+    pub const I64_ATOMIC_RMW16_XCHG_U: u32 = 0x46;
+    pub const I64_ATOMIC_RMW32_XCHG_U: u32 = 0x47;
+    pub const I32_ATOMIC_RMW_CMPXCHG: u32 = 0x48;
+    pub const I64_ATOMIC_RMW_CMPXCHG: u32 = 0x49;
+    pub const I32_ATOMIC_RMW8_CMPXCHG_U: u32 = 0x4A;
+    pub const I32_ATOMIC_RMW16_CMPXCHG_U: u32 = 0x4B;
+    pub const I64_ATOMIC_RMW8_CMPXCHG_U: u32 = 0x4C;
+    pub const I64_ATOMIC_RMW16_CMPXCHG_U: u32 = 0x4D;
+    pub const I64_ATOMIC_RMW32_CMPXCHG_U: u32 = 0x4E;
+
+    const fn fe(
+        sub_opcode: u32,
+        name: &'static str,
+        immediates: Imm,
+        signature: Option<StackSignature>,
+    ) -> OpcodeInfo {
+        OpcodeInfo {
+            opcode: Opcode::Prefixed(FE_EXTENSIONS, sub_opcode),
+            name,
+            immediates,
+            signature,
+            proposal: Some(Proposal::Threads),
+        }
+    }
+
+    /// The registry entries of all opcodes prefixed by [`FE_EXTENSIONS`], sorted by their sub-opcode
+    #[rustfmt::skip]
+    pub static OPCODES: &[OpcodeInfo] = &[
+        fe(MEMORY_ATOMIC_NOTIFY, "memory.atomic.notify", Imm::MemArg, None),
+        fe(MEMORY_ATOMIC_WAIT32, "memory.atomic.wait32", Imm::MemArg, None),
+        fe(MEMORY_ATOMIC_WAIT64, "memory.atomic.wait64", Imm::MemArg, None),
+        fe(ATOMIC_FENCE, "atomic.fence", Imm::Reserved, sig(&[], &[])),
+        fe(I32_ATOMIC_LOAD, "i32.atomic.load", Imm::MemArg, None),
+        fe(I64_ATOMIC_LOAD, "i64.atomic.load", Imm::MemArg, None),
+        fe(I32_ATOMIC_LOAD8_U, "i32.atomic.load8_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_LOAD16_U, "i32.atomic.load16_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_LOAD8_U, "i64.atomic.load8_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_LOAD16_U, "i64.atomic.load16_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_LOAD32_U, "i64.atomic.load32_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_STORE, "i32.atomic.store", Imm::MemArg, None),
+        fe(I64_ATOMIC_STORE, "i64.atomic.store", Imm::MemArg, None),
+        fe(I32_ATOMIC_STORE8, "i32.atomic.store8", Imm::MemArg, None),
+        fe(I32_ATOMIC_STORE16, "i32.atomic.store16", Imm::MemArg, None),
+        fe(I64_ATOMIC_STORE8, "i64.atomic.store8", Imm::MemArg, None),
+        fe(I64_ATOMIC_STORE16, "i64.atomic.store16", Imm::MemArg, None),
+        fe(I64_ATOMIC_STORE32, "i64.atomic.store32", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW_ADD, "i32.atomic.rmw.add", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW_ADD, "i64.atomic.rmw.add", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW8_ADD_U, "i32.atomic.rmw8.add_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW16_ADD_U, "i32.atomic.rmw16.add_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW8_ADD_U, "i64.atomic.rmw8.add_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW16_ADD_U, "i64.atomic.rmw16.add_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW32_ADD_U, "i64.atomic.rmw32.add_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW_SUB, "i32.atomic.rmw.sub", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW_SUB, "i64.atomic.rmw.sub", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW8_SUB_U, "i32.atomic.rmw8.sub_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW16_SUB_U, "i32.atomic.rmw16.sub_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW8_SUB_U, "i64.atomic.rmw8.sub_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW16_SUB_U, "i64.atomic.rmw16.sub_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW32_SUB_U, "i64.atomic.rmw32.sub_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW_AND, "i32.atomic.rmw.and", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW_AND, "i64.atomic.rmw.and", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW8_AND_U, "i32.atomic.rmw8.and_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW16_AND_U, "i32.atomic.rmw16.and_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW8_AND_U, "i64.atomic.rmw8.and_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW16_AND_U, "i64.atomic.rmw16.and_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW32_AND_U, "i64.atomic.rmw32.and_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW_OR, "i32.atomic.rmw.or", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW_OR, "i64.atomic.rmw.or", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW8_OR_U, "i32.atomic.rmw8.or_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW16_OR_U, "i32.atomic.rmw16.or_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW8_OR_U, "i64.atomic.rmw8.or_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW16_OR_U, "i64.atomic.rmw16.or_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW32_OR_U, "i64.atomic.rmw32.or_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW_XOR, "i32.atomic.rmw.xor", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW_XOR, "i64.atomic.rmw.xor", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW8_XOR_U, "i32.atomic.rmw8.xor_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW16_XOR_U, "i32.atomic.rmw16.xor_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW8_XOR_U, "i64.atomic.rmw8.xor_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW16_XOR_U, "i64.atomic.rmw16.xor_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW32_XOR_U, "i64.atomic.rmw32.xor_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW_XCHG, "i32.atomic.rmw.xchg", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW_XCHG, "i64.atomic.rmw.xchg", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW8_XCHG_U, "i32.atomic.rmw8.xchg_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW16_XCHG_U, "i32.atomic.rmw16.xchg_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW8_XCHG_U, "i64.atomic.rmw8.xchg_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW16_XCHG_U, "i64.atomic.rmw16.xchg_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW32_XCHG_U, "i64.atomic.rmw32.xchg_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW_CMPXCHG, "i32.atomic.rmw.cmpxchg", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW_CMPXCHG, "i64.atomic.rmw.cmpxchg", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW8_CMPXCHG_U, "i32.atomic.rmw8.cmpxchg_u", Imm::MemArg, None),
+        fe(I32_ATOMIC_RMW16_CMPXCHG_U, "i32.atomic.rmw16.cmpxchg_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW8_CMPXCHG_U, "i64.atomic.rmw8.cmpxchg_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW16_CMPXCHG_U, "i64.atomic.rmw16.cmpxchg_u", Imm::MemArg, None),
+        fe(I64_ATOMIC_RMW32_CMPXCHG_U, "i64.atomic.rmw32.cmpxchg_u", Imm::MemArg, None),
+    ];
+}
+
+/// An opcode, which is either a single byte or a prefix byte followed by a LEB128-encoded sub-opcode
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Opcode {
+    Single(u8),
+    Prefixed(u8, u32),
+}
+
+impl Opcode {
+    /// Looks up this opcode in the registry, returning `None` for unknown opcodes
+    pub fn info(self) -> Option<&'static OpcodeInfo> {
+        let registry = match self {
+            Opcode::Single(_) => OPCODES,
+            Opcode::Prefixed(FC_EXTENSIONS, _) => fc_extensions::OPCODES,
+            Opcode::Prefixed(FD_EXTENSIONS, _) => fd_extensions::OPCODES,
+            Opcode::Prefixed(FE_EXTENSIONS, _) => fe_extensions::OPCODES,
+            Opcode::Prefixed(..) => return None,
+        };
+        registry
+            .binary_search_by_key(&self, |info| info.opcode)
+            .ok()
+            .map(|idx| &registry[idx])
+    }
+
+    /// Reads the opcode of an already validated instruction, without its immediates
+    pub fn read_unvalidated(wasm: &mut WasmReader) -> Self {
+        match wasm.read_u8().unwrap_validated() {
+            prefix @ (FC_EXTENSIONS | FD_EXTENSIONS | FE_EXTENSIONS) => {
+                Opcode::Prefixed(prefix, wasm.read_var_u32().unwrap_validated())
+            }
+            byte => Opcode::Single(byte),
+        }
+    }
+}
+
+/// The kind of immediate arguments which are encoded after an opcode
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Immediates {
+    None,
+    BlockType,
+    LabelIdx,
+    /// A vector of label indices followed by the default label index
+    LabelTable,
+    FuncIdx,
+    /// A type index followed by a table index, as for `call_indirect`
+    TypeAndTableIdx,
+    /// A vector of value types, as for the typed `select`
+    ValTypes,
+    /// A block type followed by a vector of catch clauses
+    TryTable,
+    TagIdx,
+    LocalIdx,
+    GlobalIdx,
+    TableIdx,
+    MemArg,
+    MemIdx,
+    I32,
+    I64,
+    F32,
+    F64,
+    /// 16 bytes
+    V128,
+    RefType,
+    DataAndMemIdx,
+    DataIdx,
+    /// The destination memory index followed by the source memory index
+    MemIdxPair,
+    ElemAndTableIdx,
+    ElemIdx,
+    /// The destination table index followed by the source table index
+    TableIdxPair,
+    /// A single byte lane index
+    Lane,
+    /// 16 lane indices, as for `i8x16.shuffle`
+    LaneIndices,
+    MemArgAndLane,
+    /// A single reserved byte which must be zero, as for `atomic.fence`
+    Reserved,
+}
+
+impl Immediates {
+    /// Reads over already validated immediates of this kind
+    pub fn read_unvalidated(self, wasm: &mut WasmReader) {
+        match self {
+            Self::None => {}
+            Self::BlockType => {
+                BlockType::read_unvalidated(wasm);
+            }
+            Self::LabelIdx
+            | Self::FuncIdx
+            | Self::TagIdx
+            | Self::LocalIdx
+            | Self::GlobalIdx
+            | Self::TableIdx
+            | Self::MemIdx
+            | Self::DataIdx
+            | Self::ElemIdx => {
+                wasm.read_var_u32().unwrap_validated();
+            }
+            Self::LabelTable => {
+                wasm.read_vec(|wasm| wasm.read_var_u32()).unwrap_validated();
+                wasm.read_var_u32().unwrap_validated();
+            }
+            Self::TypeAndTableIdx
+            | Self::DataAndMemIdx
+            | Self::MemIdxPair
+            | Self::ElemAndTableIdx
+            | Self::TableIdxPair => {
+                wasm.read_var_u32().unwrap_validated();
+                wasm.read_var_u32().unwrap_validated();
+            }
+            Self::ValTypes => {
+                wasm.read_vec(ValType::read).unwrap_validated();
+            }
+            Self::TryTable => {
+                BlockType::read_unvalidated(wasm);
+                wasm.read_vec(CatchClause::read).unwrap_validated();
+            }
+            Self::MemArg => {
+                MemArg::read_unvalidated(wasm);
+            }
+            Self::I32 => {
+                wasm.read_var_i32().unwrap_validated();
+            }
+            Self::I64 => {
+                wasm.read_var_i64().unwrap_validated();
+            }
+            Self::F32 => wasm.skip(4).unwrap_validated(),
+            Self::F64 => wasm.skip(8).unwrap_validated(),
+            Self::V128 | Self::LaneIndices => wasm.skip(16).unwrap_validated(),
+            Self::RefType => {
+                RefType::read_unvalidated(wasm);
+            }
+            Self::Lane | Self::Reserved => wasm.skip(1).unwrap_validated(),
+            Self::MemArgAndLane => {
+                MemArg::read_unvalidated(wasm);
+                wasm.skip(1).unwrap_validated();
+            }
+        }
+    }
+}
+
+/// The types of the operands popped and of the results pushed by an instruction
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct StackSignature {
+    pub params: &'static [ValType],
+    pub results: &'static [ValType],
+}
+
+/// An entry of the opcode registry
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct OpcodeInfo {
+    pub opcode: Opcode,
+    /// The name of the instruction in the text format, e.g. `i32.add`
+    pub name: &'static str,
+    pub immediates: Immediates,
+    /// `None` if the signature depends on the immediates or on the module, e.g. for `call` or `local.get`
+    pub signature: Option<StackSignature>,
+    /// The proposal introducing the instruction, or `None` for instructions of the MVP
+    pub proposal: Option<Proposal>,
+}
+
+/// Iterates over all entries of the registry, sorted by their opcode
+pub fn all_opcodes() -> impl Iterator<Item = &'static OpcodeInfo> {
+    OPCODES
+        .iter()
+        .chain(fc_extensions::OPCODES)
+        .chain(fd_extensions::OPCODES)
+        .chain(fe_extensions::OPCODES)
+}
+
+const I32: ValType = ValType::NumType(NumType::I32);
+const I64: ValType = ValType::NumType(NumType::I64);
+const F32: ValType = ValType::NumType(NumType::F32);
+const F64: ValType = ValType::NumType(NumType::F64);
+const V128: ValType = ValType::VecType;
+
+const fn sig(params: &'static [ValType], results: &'static [ValType]) -> Option<StackSignature> {
+    Some(StackSignature { params, results })
 }
 
+const fn single(
+    byte: u8,
+    name: &'static str,
+    immediates: Immediates,
+    signature: Option<StackSignature>,
+    proposal: Option<Proposal>,
+) -> OpcodeInfo {
+    OpcodeInfo {
+        opcode: Opcode::Single(byte),
+        name,
+        immediates,
+        signature,
+        proposal,
+    }
+}
+
+/// The registry entries of all single-byte opcodes, sorted by their value
+///
+/// The entries of the prefixed opcodes are in the `OPCODES` of [fc_extensions], [fd_extensions] and [fe_extensions].
+#[rustfmt::skip]
+pub static OPCODES: &[OpcodeInfo] = {
+    use Immediates as Imm;
+    use Proposal::*;
+    &[
+        single(UNREACHABLE, "unreachable", Imm::None, None, None),
+        single(NOP, "nop", Imm::None, sig(&[], &[]), None),
+        single(BLOCK, "block", Imm::BlockType, None, None),
+        single(LOOP, "loop", Imm::BlockType, None, None),
+        single(IF, "if", Imm::BlockType, None, None),
+        single(ELSE, "else", Imm::None, None, None),
+        single(THROW, "throw", Imm::TagIdx, None, Some(ExceptionHandling)),
+        single(THROW_REF, "throw_ref", Imm::None, None, Some(ExceptionHandling)),
+        single(END, "end", Imm::None, None, None),
+        single(BR, "br", Imm::LabelIdx, None, None),
+        single(BR_IF, "br_if", Imm::LabelIdx, None, None),
+        single(BR_TABLE, "br_table", Imm::LabelTable, None, None),
+        single(RETURN, "return", Imm::None, None, None),
+        single(CALL, "call", Imm::FuncIdx, None, None),
+        single(CALL_INDIRECT, "call_indirect", Imm::TypeAndTableIdx, None, None),
+        single(RETURN_CALL, "return_call", Imm::FuncIdx, None, Some(TailCall)),
+        single(RETURN_CALL_INDIRECT, "return_call_indirect", Imm::TypeAndTableIdx, None, Some(TailCall)),
+        single(DROP, "drop", Imm::None, None, None),
+        single(SELECT, "select", Imm::None, None, None),
+        single(SELECT_T, "select", Imm::ValTypes, None, Some(ReferenceTypes)),
+        single(TRY_TABLE, "try_table", Imm::TryTable, None, Some(ExceptionHandling)),
+        single(LOCAL_GET, "local.get", Imm::LocalIdx, None, None),
+        single(LOCAL_SET, "local.set", Imm::LocalIdx, None, None),
+        single(LOCAL_TEE, "local.tee", Imm::LocalIdx, None, None),
+        single(GLOBAL_GET, "global.get", Imm::GlobalIdx, None, None),
+        single(GLOBAL_SET, "global.set", Imm::GlobalIdx, None, None),
+        single(TABLE_GET, "table.get", Imm::TableIdx, None, Some(ReferenceTypes)),
+        single(TABLE_SET, "table.set", Imm::TableIdx, None, Some(ReferenceTypes)),
+        single(I32_LOAD, "i32.load", Imm::MemArg, None, None),
+        single(I64_LOAD, "i64.load", Imm::MemArg, None, None),
+        single(F32_LOAD, "f32.load", Imm::MemArg, None, None),
+        single(F64_LOAD, "f64.load", Imm::MemArg, None, None),
+        single(I32_LOAD8_S, "i32.load8_s", Imm::MemArg, None, None),
+        single(I32_LOAD8_U, "i32.load8_u", Imm::MemArg, None, None),
+        single(I32_LOAD16_S, "i32.load16_s", Imm::MemArg, None, None),
+        single(I32_LOAD16_U, "i32.load16_u", Imm::MemArg, None, None),
+        single(I64_LOAD8_S, "i64.load8_s", Imm::MemArg, None, None),
+        single(I64_LOAD8_U, "i64.load8_u", Imm::MemArg, None, None),
+        single(I64_LOAD16_S, "i64.load16_s", Imm::MemArg, None, None),
+        single(I64_LOAD16_U, "i64.load16_u", Imm::MemArg, None, None),
+        single(I64_LOAD32_S, "i64.load32_s", Imm::MemArg, None, None),
+        single(I64_LOAD32_U, "i64.load32_u", Imm::MemArg, None, None),
+        single(I32_STORE, "i32.store", Imm::MemArg, None, None),
+        single(I64_STORE, "i64.store", Imm::MemArg, None, None),
+        single(F32_STORE, "f32.store", Imm::MemArg, None, None),
+        single(F64_STORE, "f64.store", Imm::MemArg, None, None),
+        single(I32_STORE8, "i32.store8", Imm::MemArg, None, None),
+        single(I32_STORE16, "i32.store16", Imm::MemArg, None, None),
+        single(I64_STORE8, "i64.store8", Imm::MemArg, None, None),
+        single(I64_STORE16, "i64.store16", Imm::MemArg, None, None),
+        single(I64_STORE32, "i64.store32", Imm::MemArg, None, None),
+        single(MEMORY_SIZE, "memory.size", Imm::MemIdx, None, None),
+        single(MEMORY_GROW, "memory.grow", Imm::MemIdx, None, None),
+        single(I32_CONST, "i32.const", Imm::I32, sig(&[], &[I32]), None),
+        single(I64_CONST, "i64.const", Imm::I64, sig(&[], &[I64]), None),
+        single(F32_CONST, "f32.const", Imm::F32, sig(&[], &[F32]), None),
+        single(F64_CONST, "f64.const", Imm::F64, sig(&[], &[F64]), None),
+        single(I32_EQZ, "i32.eqz", Imm::None, sig(&[I32], &[I32]), None),
+        single(I32_EQ, "i32.eq", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_NE, "i32.ne", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_LT_S, "i32.lt_s", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_LT_U, "i32.lt_u", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_GT_S, "i32.gt_s", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_GT_U, "i32.gt_u", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_LE_S, "i32.le_s", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_LE_U, "i32.le_u", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_GE_S, "i32.ge_s", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_GE_U, "i32.ge_u", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I64_EQZ, "i64.eqz", Imm::None, sig(&[I64], &[I32]), None),
+        single(I64_EQ, "i64.eq", Imm::None, sig(&[I64, I64], &[I32]), None),
+        single(I64_NE, "i64.ne", Imm::None, sig(&[I64, I64], &[I32]), None),
+        single(I64_LT_S, "i64.lt_s", Imm::None, sig(&[I64, I64], &[I32]), None),
+        single(I64_LT_U, "i64.lt_u", Imm::None, sig(&[I64, I64], &[I32]), None),
+        single(I64_GT_S, "i64.gt_s", Imm::None, sig(&[I64, I64], &[I32]), None),
+        single(I64_GT_U, "i64.gt_u", Imm::None, sig(&[I64, I64], &[I32]), None),
+        single(I64_LE_S, "i64.le_s", Imm::None, sig(&[I64, I64], &[I32]), None),
+        single(I64_LE_U, "i64.le_u", Imm::None, sig(&[I64, I64], &[I32]), None),
+        single(I64_GE_S, "i64.ge_s", Imm::None, sig(&[I64, I64], &[I32]), None),
+        single(I64_GE_U, "i64.ge_u", Imm::None, sig(&[I64, I64], &[I32]), None),
+        single(F32_EQ, "f32.eq", Imm::None, sig(&[F32, F32], &[I32]), None),
+        single(F32_NE, "f32.ne", Imm::None, sig(&[F32, F32], &[I32]), None),
+        single(F32_LT, "f32.lt", Imm::None, sig(&[F32, F32], &[I32]), None),
+        single(F32_GT, "f32.gt", Imm::None, sig(&[F32, F32], &[I32]), None),
+        single(F32_LE, "f32.le", Imm::None, sig(&[F32, F32], &[I32]), None),
+        single(F32_GE, "f32.ge", Imm::None, sig(&[F32, F32], &[I32]), None),
+        single(F64_EQ, "f64.eq", Imm::None, sig(&[F64, F64], &[I32]), None),
+        single(F64_NE, "f64.ne", Imm::None, sig(&[F64, F64], &[I32]), None),
+        single(F64_LT, "f64.lt", Imm::None, sig(&[F64, F64], &[I32]), None),
+        single(F64_GT, "f64.gt", Imm::None, sig(&[F64, F64], &[I32]), None),
+        single(F64_LE, "f64.le", Imm::None, sig(&[F64, F64], &[I32]), None),
+        single(F64_GE, "f64.ge", Imm::None, sig(&[F64, F64], &[I32]), None),
+        single(I32_CLZ, "i32.clz", Imm::None, sig(&[I32], &[I32]), None),
+        single(I32_CTZ, "i32.ctz", Imm::None, sig(&[I32], &[I32]), None),
+        single(I32_POPCNT, "i32.popcnt", Imm::None, sig(&[I32], &[I32]), None),
+        single(I32_ADD, "i32.add", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_SUB, "i32.sub", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_MUL, "i32.mul", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_DIV_S, "i32.div_s", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_DIV_U, "i32.div_u", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_REM_S, "i32.rem_s", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_REM_U, "i32.rem_u", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_AND, "i32.and", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_OR, "i32.or", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_XOR, "i32.xor", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_SHL, "i32.shl", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_SHR_S, "i32.shr_s", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_SHR_U, "i32.shr_u", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_ROTL, "i32.rotl", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I32_ROTR, "i32.rotr", Imm::None, sig(&[I32, I32], &[I32]), None),
+        single(I64_CLZ, "i64.clz", Imm::None, sig(&[I64], &[I64]), None),
+        single(I64_CTZ, "i64.ctz", Imm::None, sig(&[I64], &[I64]), None),
+        single(I64_POPCNT, "i64.popcnt", Imm::None, sig(&[I64], &[I64]), None),
+        single(I64_ADD, "i64.add", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_SUB, "i64.sub", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_MUL, "i64.mul", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_DIV_S, "i64.div_s", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_DIV_U, "i64.div_u", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_REM_S, "i64.rem_s", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_REM_U, "i64.rem_u", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_AND, "i64.and", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_OR, "i64.or", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_XOR, "i64.xor", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_SHL, "i64.shl", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_SHR_S, "i64.shr_s", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_SHR_U, "i64.shr_u", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_ROTL, "i64.rotl", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(I64_ROTR, "i64.rotr", Imm::None, sig(&[I64, I64], &[I64]), None),
+        single(F32_ABS, "f32.abs", Imm::None, sig(&[F32], &[F32]), None),
+        single(F32_NEG, "f32.neg", Imm::None, sig(&[F32], &[F32]), None),
+        single(F32_CEIL, "f32.ceil", Imm::None, sig(&[F32], &[F32]), None),
+        single(F32_FLOOR, "f32.floor", Imm::None, sig(&[F32], &[F32]), None),
+        single(F32_TRUNC, "f32.trunc", Imm::None, sig(&[F32], &[F32]), None),
+        single(F32_NEAREST, "f32.nearest", Imm::None, sig(&[F32], &[F32]), None),
+        single(F32_SQRT, "f32.sqrt", Imm::None, sig(&[F32], &[F32]), None),
+        single(F32_ADD, "f32.add", Imm::None, sig(&[F32, F32], &[F32]), None),
+        single(F32_SUB, "f32.sub", Imm::None, sig(&[F32, F32], &[F32]), None),
+        single(F32_MUL, "f32.mul", Imm::None, sig(&[F32, F32], &[F32]), None),
+        single(F32_DIV, "f32.div", Imm::None, sig(&[F32, F32], &[F32]), None),
+        single(F32_MIN, "f32.min", Imm::None, sig(&[F32, F32], &[F32]), None),
+        single(F32_MAX, "f32.max", Imm::None, sig(&[F32, F32], &[F32]), None),
+        single(F32_COPYSIGN, "f32.copysign", Imm::None, sig(&[F32, F32], &[F32]), None),
+        single(F64_ABS, "f64.abs", Imm::None, sig(&[F64], &[F64]), None),
+        single(F64_NEG, "f64.neg", Imm::None, sig(&[F64], &[F64]), None),
+        single(F64_CEIL, "f64.ceil", Imm::None, sig(&[F64], &[F64]), None),
+        single(F64_FLOOR, "f64.floor", Imm::None, sig(&[F64], &[F64]), None),
+        single(F64_TRUNC, "f64.trunc", Imm::None, sig(&[F64], &[F64]), None),
+        single(F64_NEAREST, "f64.nearest", Imm::None, sig(&[F64], &[F64]), None),
+        single(F64_SQRT, "f64.sqrt", Imm::None, sig(&[F64], &[F64]), None),
+        single(F64_ADD, "f64.add", Imm::None, sig(&[F64, F64], &[F64]), None),
+        single(F64_SUB, "f64.sub", Imm::None, sig(&[F64, F64], &[F64]), None),
+        single(F64_MUL, "f64.mul", Imm::None, sig(&[F64, F64], &[F64]), None),
+        single(F64_DIV, "f64.div", Imm::None, sig(&[F64, F64], &[F64]), None),
+        single(F64_MIN, "f64.min", Imm::None, sig(&[F64, F64], &[F64]), None),
+        single(F64_MAX, "f64.max", Imm::None, sig(&[F64, F64], &[F64]), None),
+        single(F64_COPYSIGN, "f64.copysign", Imm::None, sig(&[F64, F64], &[F64]), None),
+        single(I32_WRAP_I64, "i32.wrap_i64", Imm::None, sig(&[I64], &[I32]), None),
+        single(I32_TRUNC_F32_S, "i32.trunc_f32_s", Imm::None, sig(&[F32], &[I32]), None),
+        single(I32_TRUNC_F32_U, "i32.trunc_f32_u", Imm::None, sig(&[F32], &[I32]), None),
+        single(I32_TRUNC_F64_S, "i32.trunc_f64_s", Imm::None, sig(&[F64], &[I32]), None),
+        single(I32_TRUNC_F64_U, "i32.trunc_f64_u", Imm::None, sig(&[F64], &[I32]), None),
+        single(I64_EXTEND_I32_S, "i64.extend_i32_s", Imm::None, sig(&[I32], &[I64]), None),
+        single(I64_EXTEND_I32_U, "i64.extend_i32_u", Imm::None, sig(&[I32], &[I64]), None),
+        single(I64_TRUNC_F32_S, "i64.trunc_f32_s", Imm::None, sig(&[F32], &[I64]), None),
+        single(I64_TRUNC_F32_U, "i64.trunc_f32_u", Imm::None, sig(&[F32], &[I64]), None),
+        single(I64_TRUNC_F64_S, "i64.trunc_f64_s", Imm::None, sig(&[F64], &[I64]), None),
+        single(I64_TRUNC_F64_U, "i64.trunc_f64_u", Imm::None, sig(&[F64], &[I64]), None),
+        single(F32_CONVERT_I32_S, "f32.convert_i32_s", Imm::None, sig(&[I32], &[F32]), None),
+        single(F32_CONVERT_I32_U, "f32.convert_i32_u", Imm::None, sig(&[I32], &[F32]), None),
+        single(F32_CONVERT_I64_S, "f32.convert_i64_s", Imm::None, sig(&[I64], &[F32]), None),
+        single(F32_CONVERT_I64_U, "f32.convert_i64_u", Imm::None, sig(&[I64], &[F32]), None),
+        single(F32_DEMOTE_F64, "f32.demote_f64", Imm::None, sig(&[F64], &[F32]), None),
+        single(F64_CONVERT_I32_S, "f64.convert_i32_s", Imm::None, sig(&[I32], &[F64]), None),
+        single(F64_CONVERT_I32_U, "f64.convert_i32_u", Imm::None, sig(&[I32], &[F64]), None),
+        single(F64_CONVERT_I64_S, "f64.convert_i64_s", Imm::None, sig(&[I64], &[F64]), None),
+        single(F64_CONVERT_I64_U, "f64.convert_i64_u", Imm::None, sig(&[I64], &[F64]), None),
+        single(F64_PROMOTE_F32, "f64.promote_f32", Imm::None, sig(&[F32], &[F64]), None),
+        single(I32_REINTERPRET_F32, "i32.reinterpret_f32", Imm::None, sig(&[F32], &[I32]), None),
+        single(I64_REINTERPRET_F64, "i64.reinterpret_f64", Imm::None, sig(&[F64], &[I64]), None),
+        single(F32_REINTERPRET_I32, "f32.reinterpret_i32", Imm::None, sig(&[I32], &[F32]), None),
+        single(F64_REINTERPRET_I64, "f64.reinterpret_i64", Imm::None, sig(&[I64], &[F64]), None),
+        single(I32_EXTEND8_S, "i32.extend8_s", Imm::None, sig(&[I32], &[I32]), Some(SignExtension)),
+        single(I32_EXTEND16_S, "i32.extend16_s", Imm::None, sig(&[I32], &[I32]), Some(SignExtension)),
+        single(I64_EXTEND8_S, "i64.extend8_s", Imm::None, sig(&[I64], &[I64]), Some(SignExtension)),
+        single(I64_EXTEND16_S, "i64.extend16_s", Imm::None, sig(&[I64], &[I64]), Some(SignExtension)),
+        single(I64_EXTEND32_S, "i64.extend32_s", Imm::None, sig(&[I64], &[I64]), Some(SignExtension)),
+        single(REF_NULL, "ref.null", Imm::RefType, None, Some(ReferenceTypes)),
+        single(REF_IS_NULL, "ref.is_null", Imm::None, None, Some(ReferenceTypes)),
+        single(REF_FUNC, "ref.func", Imm::FuncIdx, None, Some(ReferenceTypes)),
+    ]
+};
+
+/// The name of a single-byte opcode for debug output, e.g. `i32.add`
 #[cfg(debug_assertions)]
 pub fn opcode_byte_to_str(byte: u8) -> alloc::string::String {
     use alloc::borrow::ToOwned;
+
     match byte {
-        UNREACHABLE => "UNREACHABLE",
-        NOP => "NOP",
-        // BLOCK => "BLOCK",
-        // LOOP => "LOOP",
-        // IF => "IF",
-        // ELSE => "ELSE",
-        END => "END",
-        // BR => "BR",
-        // BR_IF => "BR_IF",
-        // BR_TABLE => "BR_TABLE",
-        RETURN => "RETURN",
-        CALL => "CALL",
-        // CALL_INDIRECT => "CALL_INDIRECT",
-        DROP => "DROP",
-        // SELECT => "SELECT",
-        LOCAL_GET => "LOCAL_GET",
-        LOCAL_SET => "LOCAL_SET",
-        LOCAL_TEE => "LOCAL_TEE",
-        GLOBAL_GET => "GLOBAL_GET",
-        GLOBAL_SET => "GLOBAL_SET",
-        I32_LOAD => "I32_LOAD",
-        I64_LOAD => "I64_LOAD",
-        F32_LOAD => "F32_LOAD",
-        F64_LOAD => "F64_LOAD",
-        I32_LOAD8_S => "I32_LOAD8_S",
-        I32_LOAD8_U => "I32_LOAD8_U",
-        I32_LOAD16_S => "I32_LOAD16_S",
-        I32_LOAD16_U => "I32_LOAD16_U",
-        I64_LOAD8_S => "I64_LOAD8_S",
-        I64_LOAD8_U => "I64_LOAD8_U",
-        I64_LOAD16_S => "I64_LOAD16_S",
-        I64_LOAD16_U => "I64_LOAD16_U",
-        I64_LOAD32_S => "I64_LOAD32_S",
-        I64_LOAD32_U => "I64_LOAD32_U",
-        I32_STORE => "I32_STORE",
-        I64_STORE => "I64_STORE",
-        F32_STORE => "F32_STORE",
-        F64_STORE => "F64_STORE",
-        I32_STORE8 => "I32_STORE8",
-        I32_STORE16 => "I32_STORE16",
-        I64_STORE8 => "I64_STORE8",
-        I64_STORE16 => "I64_STORE16",
-        I64_STORE32 => "I64_STORE32",
-        MEMORY_SIZE => "MEMORY_SIZE",
-        MEMORY_GROW => "MEMORY_GROW",
-        I32_CONST => "I32_CONST",
-        I64_CONST => "I64_CONST",
-        F32_CONST => "F32_CONST",
-        F64_CONST => "F64_CONST",
-        I32_EQZ => "I32_EQZ",
-        I32_EQ => "I32_EQ",
-        I32_NE => "I32_NE",
-        I32_LT_S => "I32_LT_S",
-        I32_LT_U => "I32_LT_U",
-        I32_GT_S => "I32_GT_S",
-        I32_GT_U => "I32_GT_U",
-        I32_LE_S => "I32_LE_S",
-        I32_LE_U => "I32_LE_U",
-        I32_GE_S => "I32_GE_S",
-        I32_GE_U => "I32_GE_U",
-        I64_EQZ => "I64_EQZ",
-        I64_EQ => "I64_EQ",
-        I64_NE => "I64_NE",
-        I64_LT_S => "I64_LT_S",
-        I64_LT_U => "I64_LT_U",
-        I64_GT_S => "I64_GT_S",
-        I64_GT_U => "I64_GT_U",
-        I64_LE_S => "I64_LE_S",
-        I64_LE_U => "I64_LE_U",
-        I64_GE_S => "I64_GE_S",
-        I64_GE_U => "I64_GE_U",
-        F32_EQ => "F32_EQ",
-        F32_NE => "F32_NE",
-        F32_LT => "F32_LT",
-        F32_GT => "F32_GT",
-        F32_LE => "F32_LE",
-        F32_GE => "F32_GE",
-        F64_EQ => "F64_EQ",
-        F64_NE => "F64_NE",
-        F64_LT => "F64_LT",
-        F64_GT => "F64_GT",
-        F64_LE => "F64_LE",
-        F64_GE => "F64_GE",
-        I32_CLZ => "I32_CLZ",
-        I32_CTZ => "I32_CTZ",
-        I32_POPCNT => "I32_POPCNT",
-        I32_ADD => "I32_ADD",
-        I32_SUB => "I32_SUB",
-        I32_MUL => "I32_MUL",
-        I32_DIV_S => "I32_DIV_S",
-        I32_DIV_U => "I32_DIV_U",
-        I32_REM_S => "I32_REM_S",
-        I32_REM_U => "I32_REM_U",
-        I32_AND => "I32_AND",
-        I32_OR => "I32_OR",
-        I32_XOR => "I32_XOR",
-        I32_SHL => "I32_SHL",
-        I32_SHR_S => "I32_SHR_S",
-        I32_SHR_U => "I32_SHR_U",
-        I32_ROTL => "I32_ROTL",
-        I32_ROTR => "I32_ROTR",
-        I64_CLZ => "I64_CLZ",
-        I64_CTZ => "I64_CTZ",
-        I64_POPCNT => "I64_POPCNT",
-        I64_ADD => "I64_ADD",
-        I64_SUB => "I64_SUB",
-        I64_MUL => "I64_MUL",
-        I64_DIV_S => "I64_DIV_S",
-        I64_DIV_U => "I64_DIV_U",
-        I64_REM_S => "I64_REM_S",
-        I64_REM_U => "I64_REM_U",
-        I64_AND => "I64_AND",
-        I64_OR => "I64_OR",
-        I64_XOR => "I64_XOR",
-        I64_SHL => "I64_SHL",
-        I64_SHR_S => "I64_SHR_S",
-        I64_SHR_U => "I64_SHR_U",
-        I64_ROTL => "I64_ROTL",
-        I64_ROTR => "I64_ROTR",
-        F32_ABS => "F32_ABS",
-        F32_NEG => "F32_NEG",
-        F32_CEIL => "F32_CEIL",
-        F32_FLOOR => "F32_FLOOR",
-        F32_TRUNC => "F32_TRUNC",
-        F32_NEAREST => "F32_NEAREST",
-        F32_SQRT => "F32_SQRT",
-        F32_ADD => "F32_ADD",
-        F32_SUB => "F32_SUB",
-        F32_MUL => "F32_MUL",
-        F32_DIV => "F32_DIV",
-        F32_MIN => "F32_MIN",
-        F32_MAX => "F32_MAX",
-        F32_COPYSIGN => "F32_COPYSIGN",
-        F64_ABS => "F64_ABS",
-        F64_NEG => "F64_NEG",
-        F64_CEIL => "F64_CEIL",
-        F64_FLOOR => "F64_FLOOR",
-        F64_TRUNC => "F64_TRUNC",
-        F64_NEAREST => "F64_NEAREST",
-        F64_SQRT => "F64_SQRT",
-        F64_ADD => "F64_ADD",
-        F64_SUB => "F64_SUB",
-        F64_MUL => "F64_MUL",
-        F64_DIV => "F64_DIV",
-        F64_MIN => "F64_MIN",
-        F64_MAX => "F64_MAX",
-        F64_COPYSIGN => "F64_COPYSIGN",
-        I32_WRAP_I64 => "I32_WRAP_I64",
-        I32_TRUNC_F32_S => "I32_TRUNC_F32_S",
-        I32_TRUNC_F32_U => "I32_TRUNC_F32_U",
-        I32_TRUNC_F64_S => "I32_TRUNC_F64_S",
-        I32_TRUNC_F64_U => "I32_TRUNC_F64_U",
-        I64_EXTEND_I32_S => "I64_EXTEND_I32_S",
-        I64_EXTEND_I32_U => "I64_EXTEND_I32_U",
-        I64_TRUNC_F32_S => "I64_TRUNC_F32_S",
-        I64_TRUNC_F32_U => "I64_TRUNC_F32_U",
-        I64_TRUNC_F64_S => "I64_TRUNC_F64_S",
-        I64_TRUNC_F64_U => "I64_TRUNC_F64_U",
-        F32_CONVERT_I32_S => "F32_CONVERT_I32_S",
-        F32_CONVERT_I32_U => "F32_CONVERT_I32_U",
-        F32_CONVERT_I64_S => "F32_CONVERT_I64_S",
-        F32_CONVERT_I64_U => "F32_CONVERT_I64_U",
-        F32_DEMOTE_F64 => "F32_DEMOTE_F64",
-        F64_CONVERT_I32_S => "F64_CONVERT_I32_S",
-        F64_CONVERT_I32_U => "F64_CONVERT_I32_U",
-        F64_CONVERT_I64_S => "F64_CONVERT_I64_S",
-        F64_CONVERT_I64_U => "F64_CONVERT_I64_U",
-        F64_PROMOTE_F32 => "F64_PROMOTE_F32",
-        I32_REINTERPRET_F32 => "I32_REINTERPRET_F32",
-        I64_REINTERPRET_F64 => "I64_REINTERPRET_F64",
-        F32_REINTERPRET_I32 => "F32_REINTERPRET_I32",
-        F64_REINTERPRET_I64 => "F64_REINTERPRET_I64",
-        REF_NULL => "REF_NULL",
-        REF_FUNC => "REF_FUNC",
-        FC_EXTENSIONS => "FC_EXTENSIONS",
-        I32_EXTEND8_S => "I32_EXTEND8_S",
-        I32_EXTEND16_S => "I32_EXTEND16_S",
-        I64_EXTEND8_S => "I64_EXTEND8_S",
-        I64_EXTEND16_S => "I64_EXTEND16_S",
-        I64_EXTEND32_S => "I64_EXTEND32_S",
-        _ => "UNKNOWN",
+        FC_EXTENSIONS => "0xFC prefix".to_owned(),
+        FD_EXTENSIONS => "0xFD prefix".to_owned(),
+        FE_EXTENSIONS => "0xFE prefix".to_owned(),
+        _ => Opcode::Single(byte)
+            .info()
+            .map_or("UNKNOWN", |info| info.name)
+            .to_owned(),
     }
-    .to_owned()
 }
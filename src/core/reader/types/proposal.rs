@@ -0,0 +1,131 @@
+//! The WebAssembly proposals this interpreter implements on top of the MVP
+//!
+//! See <https://github.com/WebAssembly/proposals/blob/main/finished-proposals.md>
+
+use core::fmt::{Display, Formatter};
+
+/// A proposal extending the WebAssembly MVP
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Proposal {
+    /// <https://github.com/WebAssembly/sign-extension-ops>
+    SignExtension,
+    /// <https://github.com/WebAssembly/nontrapping-float-to-int-conversions>
+    NonTrappingFloatToInt,
+    /// <https://github.com/WebAssembly/bulk-memory-operations>
+    BulkMemory,
+    /// <https://github.com/WebAssembly/reference-types>
+    ReferenceTypes,
+    /// <https://github.com/WebAssembly/simd>
+    Simd,
+    /// <https://github.com/WebAssembly/tail-call>
+    TailCall,
+    /// <https://github.com/WebAssembly/exception-handling>
+    ExceptionHandling,
+    /// <https://github.com/WebAssembly/threads>
+    Threads,
+    /// <https://github.com/WebAssembly/memory64>
+    Memory64,
+    /// <https://github.com/WebAssembly/multi-memory>
+    MultiMemory,
+    /// <https://github.com/WebAssembly/extended-const>
+    ExtendedConst,
+    /// <https://github.com/WebAssembly/multi-value>
+    MultiValue,
+}
+
+impl Proposal {
+    /// All proposals, in the order of their bits in a [ProposalSet]
+    pub const ALL: [Proposal; 12] = [
+        Proposal::SignExtension,
+        Proposal::NonTrappingFloatToInt,
+        Proposal::BulkMemory,
+        Proposal::ReferenceTypes,
+        Proposal::Simd,
+        Proposal::TailCall,
+        Proposal::ExceptionHandling,
+        Proposal::Threads,
+        Proposal::Memory64,
+        Proposal::MultiMemory,
+        Proposal::ExtendedConst,
+        Proposal::MultiValue,
+    ];
+
+    fn bit(self) -> u16 {
+        1 << self as u16
+    }
+}
+
+impl Display for Proposal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let name = match self {
+            Proposal::SignExtension => "sign-extension operators",
+            Proposal::NonTrappingFloatToInt => "non-trapping float-to-int conversions",
+            Proposal::BulkMemory => "bulk memory operations",
+            Proposal::ReferenceTypes => "reference types",
+            Proposal::Simd => "fixed-width SIMD",
+            Proposal::TailCall => "tail calls",
+            Proposal::ExceptionHandling => "exception handling",
+            Proposal::Threads => "threads",
+            Proposal::Memory64 => "64-bit memories",
+            Proposal::MultiMemory => "multiple memories",
+            Proposal::ExtendedConst => "extended constant expressions",
+            Proposal::MultiValue => "multiple values",
+        };
+        f.write_str(name)
+    }
+}
+
+/// A set of [Proposal]s, e.g. the proposals used by a module
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ProposalSet(u16);
+
+impl ProposalSet {
+    /// The set containing no proposal, i.e. only the MVP
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// The set containing every proposal
+    pub fn all() -> Self {
+        Proposal::ALL.into_iter().collect()
+    }
+
+    pub fn contains(&self, proposal: Proposal) -> bool {
+        self.0 & proposal.bit() != 0
+    }
+
+    pub fn insert(&mut self, proposal: Proposal) {
+        self.0 |= proposal.bit();
+    }
+
+    pub fn remove(&mut self, proposal: Proposal) {
+        self.0 &= !proposal.bit();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0
+    }
+
+    /// Iterates over the contained proposals, in the order of [Proposal::ALL]
+    pub fn iter(&self) -> impl Iterator<Item = Proposal> + '_ {
+        Proposal::ALL
+            .into_iter()
+            .filter(|proposal| self.contains(*proposal))
+    }
+}
+
+impl FromIterator<Proposal> for ProposalSet {
+    fn from_iter<I: IntoIterator<Item = Proposal>>(iter: I) -> Self {
+        let mut set = Self::empty();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<Proposal> for ProposalSet {
+    fn extend<I: IntoIterator<Item = Proposal>>(&mut self, iter: I) {
+        for proposal in iter {
+            self.insert(proposal);
+        }
+    }
+}
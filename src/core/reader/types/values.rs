@@ -27,7 +27,7 @@ use core::mem;
 use crate::core::reader::WasmReader;
 use crate::{Error, Result};
 
-impl WasmReader<'_> {
+impl<'a> WasmReader<'a> {
     /// Note: If `Err`, the [WasmReader] object is no longer guaranteed to be in a valid state
     pub fn read_u8(&mut self) -> Result<u8> {
         match self.peek_u8() {
@@ -161,7 +161,7 @@ impl WasmReader<'_> {
     }
 
     /// Note: If `Err`, the [WasmReader] object is no longer guaranteed to be in a valid state
-    pub fn read_name(&mut self) -> Result<&str> {
+    pub fn read_name(&mut self) -> Result<&'a str> {
         let len = self.read_var_u32()? as usize;
 
         if len > self.full_wasm_binary.len() - self.pc {
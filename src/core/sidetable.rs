@@ -29,6 +29,8 @@
 
 use alloc::vec::Vec;
 
+use crate::core::reader::types::CatchClause;
+
 // A sidetable
 
 pub type Sidetable = Vec<SidetableEntry>;
@@ -41,6 +43,9 @@ pub type Sidetable = Vec<SidetableEntry>;
 /// - br_if
 /// - br_table
 /// - else
+/// - try_table, which requires one entry for its end followed by one entry per catch clause. The former only records
+///   the end of the instructions covered by the catch clauses, the latter are taken when an exception is caught and
+///   hold their [`CatchClause`], so that throwing an exception does not need to decode the catch clauses again.
 // TODO hide implementation
 // TODO Remove Clone trait from sidetables
 #[derive(Debug, Clone)]
@@ -62,4 +67,7 @@ pub struct SidetableEntry {
     /// Taking a branch unwinds the operand stack down to the height where the targeted structured
     /// control instruction was entered.
     pub popcnt: usize,
+
+    /// The catch clause of a `try_table` this entry is taken for, or `None` for all other entries
+    pub catch: Option<CatchClause>,
 }
@@ -1,13 +1,15 @@
 use crate::{
     assert_validated::UnwrapValidatedExt,
+    core::indices::GlobalIdx,
     core::reader::{span::Span, WasmReadable, WasmReader},
+    store::GlobalInst,
     value::{self, FuncAddr, Ref},
     value_stack::Stack,
-    NumType, RefType, ValType, Value,
+    NumType, RefType, RuntimeError, ValType, Value,
 };
 
 /// Execute a previosly-validated constant expression. These type of expressions are used for initializing global
-/// variables.
+/// variables, as well as for the offsets and initialization expressions of data and element segments.
 ///
 /// # Arguments
 /// - `wasm` - a [WasmReader] whose [program counter](WasmReader::pc) is set at the beginning of the constant
@@ -15,45 +17,44 @@ use crate::{
 /// - `stack` - a [Stack]. It is preferrable for it to be clean, but that is not required. As long as the executed code
 ///   is validated, the values on this stack will remain the same except for the addition of the return value of this
 ///   code sequence. A global's final value can be popped off the top of the stack.
-/// - `imported_globals` (TODO) - instances of all imported globals. They are required as local globals can reference
-///   imported globals in their initialization.
+/// - `imported_globals` - instances of all imported globals. They are required as constant expressions can reference
+///   imported globals via `global.get`.
+/// - `module_idx` - the index of the module the expression belongs to, function references created via `ref.func`
+///   refer to functions of this module.
+///
+/// Fails only if a value can not be pushed to `stack` because it is full.
 ///
 /// # Safety
 /// This function assumes that the expression has been validated. Passing unvalidated code will likely result in a
 /// panic, or undefined behaviour.
-///
-/// # Note
-/// The following instructions are not yet supported:
-/// - `ref.null`
-/// - `ref.func`
-/// - `global.get`
 pub(crate) fn run_const(
     mut wasm: WasmReader,
     stack: &mut Stack,
-    _imported_globals: (), /*todo!*/
-) {
+    imported_globals: &[GlobalInst],
+    module_idx: usize,
+) -> Result<(), RuntimeError> {
     use crate::core::reader::types::opcode::*;
     loop {
         let first_instr_byte = wasm.read_u8().unwrap_validated();
 
         match first_instr_byte {
             END => {
-                break;
+                return Ok(());
             }
             I32_CONST => {
                 let constant = wasm.read_var_i32().unwrap_validated();
                 trace!("Constant instruction: i32.const [] -> [{constant}]");
-                stack.push_value(constant.into());
+                stack.push_value(constant.into())?;
             }
             F32_CONST => {
                 let constant = value::F32::from_bits(wasm.read_var_f32().unwrap_validated());
                 trace!("Constanting instruction: f32.const [] -> [{constant}]");
-                stack.push_value(constant.into());
+                stack.push_value(constant.into())?;
             }
             F64_CONST => {
                 let constant = value::F64::from_bits(wasm.read_var_f64().unwrap_validated());
                 trace!("Constanting instruction: f64.const [] -> [{constant}]");
-                stack.push_value(constant.into());
+                stack.push_value(constant.into())?;
             }
             I32_ADD => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -61,7 +62,7 @@ pub(crate) fn run_const(
                 let res = v1.wrapping_add(v2);
 
                 trace!("Constant instruction: i32.add [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_SUB => {
                 let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -69,7 +70,7 @@ pub(crate) fn run_const(
                 let res = v1.wrapping_sub(v2);
 
                 trace!("Constant instruction: i32.sub [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_MUL => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -77,12 +78,12 @@ pub(crate) fn run_const(
                 let res = v1.wrapping_mul(v2);
 
                 trace!("Constant instruction: i32.mul [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_CONST => {
                 let constant = wasm.read_var_i64().unwrap_validated();
                 trace!("Constant instruction: i64.const [] -> [{constant}]");
-                stack.push_value(constant.into());
+                stack.push_value(constant.into())?;
             }
             I64_ADD => {
                 let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -90,7 +91,7 @@ pub(crate) fn run_const(
                 let res = v1.wrapping_add(v2);
 
                 trace!("Constant instruction: i64.add [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_SUB => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -98,7 +99,7 @@ pub(crate) fn run_const(
                 let res = v1.wrapping_sub(v2);
 
                 trace!("Constant instruction: i64.sub [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_MUL => {
                 let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -106,18 +107,40 @@ pub(crate) fn run_const(
                 let res = v1.wrapping_mul(v2);
 
                 trace!("Constant instruction: i64.mul [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
+            }
+            GLOBAL_GET => {
+                // we already checked that only imported globals are referenced during validation
+                let global_idx = wasm.read_var_u32().unwrap_validated() as GlobalIdx;
+                let value = imported_globals
+                    .get(global_idx)
+                    .unwrap_validated()
+                    .value
+                    .get();
+
+                stack.push_value(value)?;
+                trace!("Constant instruction: global.get {global_idx} [] -> [{value:?}]");
+            }
+            FD_EXTENSIONS => {
+                // the validator only allows `v128.const` here
+                let _second_instr = wasm.read_var_u32().unwrap_validated();
+                let constant = wasm.strip_bytes::<16>().unwrap_validated();
+                trace!("Constant instruction: v128.const [] -> [{constant:?}]");
+                stack.push_value(constant.into())?;
             }
             REF_NULL => {
                 let reftype = RefType::read_unvalidated(&mut wasm);
 
-                stack.push_value(Value::Ref(reftype.to_null_ref()));
+                stack.push_value(Value::Ref(reftype.to_null_ref()))?;
                 trace!("Instruction: ref.null '{:?}' -> [{:?}]", reftype, reftype);
             }
             REF_FUNC => {
                 // we already checked for the func_idx to be in bounds during validation
                 let func_idx = wasm.read_var_u32().unwrap_validated() as usize;
-                stack.push_value(Value::Ref(Ref::Func(FuncAddr::new(Some(func_idx)))));
+                stack.push_value(Value::Ref(Ref::Func(FuncAddr::new_in_module(
+                    module_idx,
+                    Some(func_idx),
+                ))))?;
             }
             other => {
                 panic!("Unknown constant instruction {other:#x}, validation allowed an unimplemented instruction.");
@@ -129,15 +152,16 @@ pub(crate) fn run_const(
 pub(crate) fn run_const_span(
     wasm: &[u8],
     span: &Span,
-    imported_globals: (),
+    imported_globals: &[GlobalInst],
+    module_idx: usize,
     // funcs: &[FuncInst],
-) -> Option<Value> {
+) -> Result<Option<Value>, RuntimeError> {
     let mut wasm = WasmReader::new(wasm);
 
     wasm.move_start_to(*span).unwrap_validated();
 
     let mut stack = Stack::new();
-    run_const(wasm, &mut stack, imported_globals);
+    run_const(wasm, &mut stack, imported_globals, module_idx)?;
 
-    stack.peek_unknown_value()
+    Ok(stack.peek_unknown_value())
 }
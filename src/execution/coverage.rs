@@ -0,0 +1,334 @@
+//! Instruction-level code coverage of the executed WASM code
+//!
+//! [Coverage] is a [HookSet] which counts how often each instruction was executed and which branches were taken.
+//! Together with the code and the sidetable of every function, this yields which instructions and which directions of
+//! the conditional branches were covered, see [Coverage::functions]. [Coverage::lcov] exports the result as an lcov
+//! tracefile.
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::core::indices::FuncIdx;
+use crate::core::reader::types::opcode::{
+    Opcode, BR, BR_IF, BR_TABLE, ELSE, END, IF, RETURN, TRY_TABLE,
+};
+use crate::core::reader::types::{BlockType, CatchClause};
+use crate::core::reader::{WasmReadable, WasmReader};
+use crate::execution::assert_validated::UnwrapValidatedExt;
+use crate::execution::execution_info::ExecutionInfo;
+use crate::execution::hooks::{HookAction, HookSet};
+use crate::execution::store::LocalFuncInst;
+use crate::execution::RuntimeInstance;
+
+/// A [HookSet] recording the instructions and branches executed by a [RuntimeInstance]
+///
+/// Positions are offsets in the binary of a module, modules are identified by their index in
+/// [RuntimeInstance::modules].
+#[derive(Debug, Clone, Default)]
+pub struct Coverage {
+    /// The execution count of every instruction, indexed by module and by the position of the instruction
+    instructions: Vec<Vec<u64>>,
+    /// How often the branch instruction at a position in a module continued execution at a target position
+    branches: BTreeMap<(usize, usize, usize), u64>,
+}
+
+/// The coverage of a function defined in a module
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FunctionCoverage {
+    pub module_idx: usize,
+    pub function_idx: FuncIdx,
+    /// The name of the function in the name section of its module, if there is one
+    pub function_name: Option<String>,
+    /// The position and execution count of every instruction of the function, in the order of their positions
+    ///
+    /// The `end` of blocks is left out, as branches to a block skip it. The `end` of the function is kept.
+    pub instructions: Vec<(usize, u64)>,
+    /// The conditional branch instructions of the function, in the order of their positions
+    pub branches: Vec<BranchCoverage>,
+}
+
+/// A conditional branch instruction together with how often each of its directions was taken
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchCoverage {
+    /// The position of the instruction
+    pub pc: usize,
+    /// The number of times each direction was taken
+    ///
+    /// These are, for `if`, the then arm followed by the else arm or the end, for `br_if`, falling through followed
+    /// by the branch, and for `br_table`, its distinct targets in the order of its labels, the default label last.
+    pub directions: Vec<u64>,
+}
+
+impl FunctionCoverage {
+    /// The number of times the function was entered, i. e. its first instruction was executed
+    pub fn calls(&self) -> u64 {
+        self.instructions.first().map_or(0, |&(_, count)| count)
+    }
+}
+
+impl HookSet for Coverage {
+    fn instruction_hook(&mut self, module_idx: usize, bytecode: &[u8], pc: usize) -> HookAction {
+        if self.instructions.len() <= module_idx {
+            self.instructions.resize_with(module_idx + 1, Vec::new);
+        }
+        let counts = &mut self.instructions[module_idx];
+        if counts.is_empty() {
+            counts.resize(bytecode.len(), 0);
+        }
+        counts[pc] += 1;
+        HookAction::Continue
+    }
+
+    fn branch_hook(&mut self, module_idx: usize, pc: usize, target_pc: usize) -> HookAction {
+        *self
+            .branches
+            .entry((module_idx, pc, target_pc))
+            .or_default() += 1;
+        HookAction::Continue
+    }
+}
+
+impl Coverage {
+    /// How often the instruction at position `pc` in module `module_idx` was executed
+    pub fn instruction_count(&self, module_idx: usize, pc: usize) -> u64 {
+        self.instructions
+            .get(module_idx)
+            .and_then(|counts| counts.get(pc))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// How often the branch instruction at position `pc` in module `module_idx` continued execution at `target_pc`
+    pub fn branch_count(&self, module_idx: usize, pc: usize, target_pc: usize) -> u64 {
+        self.branches
+            .get(&(module_idx, pc, target_pc))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// The coverage of all functions defined in the modules of `instance`, which must be the instance this
+    /// [Coverage] was recorded on
+    pub fn functions<H: HookSet>(&self, instance: &RuntimeInstance<H>) -> Vec<FunctionCoverage> {
+        instance
+            .modules
+            .iter()
+            .enumerate()
+            .flat_map(|(module_idx, module)| {
+                module
+                    .store
+                    .funcs
+                    .iter()
+                    .enumerate()
+                    .filter_map(move |(function_idx, func)| {
+                        let func = func.try_into_local()?;
+                        Some(self.function(module_idx, module, function_idx, func))
+                    })
+            })
+            .collect()
+    }
+
+    /// Export the coverage of all functions defined in the modules of `instance` as lcov tracefile
+    ///
+    /// Instructions of modules with DWARF debug information are attributed to their source lines, while instructions
+    /// without a source location are left out. For other modules, the "source file" is the name of the module and its
+    /// "lines" are the positions of its instructions. Functions are named as in the name section, or `func<index>`.
+    pub fn lcov<H: HookSet>(&self, instance: &RuntimeInstance<H>) -> String {
+        let mut files: BTreeMap<String, LcovFile> = BTreeMap::new();
+        for function in self.functions(instance) {
+            let module = &instance.modules[function.module_idx];
+            let location = |pc: usize| -> Option<(String, u32)> {
+                if module.dwarf.is_empty() {
+                    Some((module.name.clone(), pc as u32))
+                } else {
+                    let location = module.dwarf.lookup(pc)?;
+                    Some((location.file, location.line))
+                }
+            };
+
+            let name = function
+                .function_name
+                .clone()
+                .unwrap_or_else(|| format!("func{}", function.function_idx));
+            let calls = function.calls();
+            let Some((file, line)) = function
+                .instructions
+                .iter()
+                .find_map(|&(pc, _)| location(pc))
+            else {
+                continue;
+            };
+            files
+                .entry(file)
+                .or_default()
+                .functions
+                .push((line, name, calls));
+
+            for &(pc, count) in &function.instructions {
+                if let Some((file, line)) = location(pc) {
+                    let lines = &mut files.entry(file).or_default().lines;
+                    let line_count = lines.entry(line).or_default();
+                    *line_count = (*line_count).max(count);
+                }
+            }
+
+            for branch in &function.branches {
+                let Some((file, line)) = location(branch.pc) else {
+                    continue;
+                };
+                let executed = self.instruction_count(function.module_idx, branch.pc) > 0;
+                let blocks = &mut files.entry(file).or_default().branches;
+                let block = blocks.iter().filter(|(l, ..)| *l == line).count();
+                blocks.push((line, block, executed, branch.directions.clone()));
+            }
+        }
+
+        let mut lcov = String::new();
+        for (file, record) in files {
+            record.write(&mut lcov, &file).unwrap();
+        }
+        lcov
+    }
+
+    /// Walk over the instructions of a function, collecting their execution counts and those of its branches
+    fn function(
+        &self,
+        module_idx: usize,
+        module: &ExecutionInfo,
+        function_idx: FuncIdx,
+        func: &LocalFuncInst,
+    ) -> FunctionCoverage {
+        let mut instructions = Vec::new();
+        let mut branches = Vec::new();
+
+        let mut wasm = WasmReader::new(module.wasm_bytecode);
+        wasm.move_start_to(func.code_expr).unwrap_validated();
+        let end = func.code_expr.from() + func.code_expr.len();
+        // the sidetable holds the entries of the branch instructions in the order of their positions
+        let mut stp = 0;
+        while wasm.pc < end {
+            let pc = wasm.pc;
+            let count = self.instruction_count(module_idx, pc);
+            let opcode = Opcode::read_unvalidated(&mut wasm);
+            // branches to a block continue after its `end`, which is thus only executed if its block falls through
+            if opcode != Opcode::Single(END) || pc + 1 == end {
+                instructions.push((pc, count));
+            }
+
+            let entries = match opcode {
+                Opcode::Single(BR_TABLE) => {
+                    let labels = wasm.read_vec(|wasm| wasm.read_var_u32()).unwrap_validated();
+                    wasm.read_var_u32().unwrap_validated();
+                    labels.len() + 1
+                }
+                Opcode::Single(TRY_TABLE) => {
+                    BlockType::read_unvalidated(&mut wasm);
+                    wasm.read_vec(CatchClause::read).unwrap_validated().len() + 1
+                }
+                _ => {
+                    opcode
+                        .info()
+                        .unwrap_validated()
+                        .immediates
+                        .read_unvalidated(&mut wasm);
+                    match opcode {
+                        Opcode::Single(IF | ELSE | BR | BR_IF | RETURN) => 1,
+                        _ => 0,
+                    }
+                }
+            };
+
+            // branches are taken relative to the end of the instruction
+            let target = |stp: usize| (wasm.pc as isize + func.sidetable[stp].delta_pc) as usize;
+            match opcode {
+                Opcode::Single(IF | BR_IF) => {
+                    let taken = self.branch_count(module_idx, pc, target(stp));
+                    branches.push(BranchCoverage {
+                        pc,
+                        directions: vec![count.saturating_sub(taken), taken],
+                    });
+                }
+                Opcode::Single(BR_TABLE) => {
+                    let mut targets = Vec::new();
+                    for target in (stp..stp + entries).map(target) {
+                        if !targets.contains(&target) {
+                            targets.push(target);
+                        }
+                    }
+                    branches.push(BranchCoverage {
+                        pc,
+                        directions: targets
+                            .into_iter()
+                            .map(|target| self.branch_count(module_idx, pc, target))
+                            .collect(),
+                    });
+                }
+                _ => {}
+            }
+            stp += entries;
+        }
+
+        FunctionCoverage {
+            module_idx,
+            function_idx,
+            function_name: module.names.function_name(function_idx).map(String::from),
+            instructions,
+            branches,
+        }
+    }
+}
+
+/// The records of a source file in an lcov tracefile
+#[derive(Default)]
+struct LcovFile {
+    /// The line, name and number of calls of every function
+    functions: Vec<(u32, String, u64)>,
+    /// The execution count of every line
+    lines: BTreeMap<u32, u64>,
+    /// The line, the index of the branch instruction within its line, whether it was executed and the counts of its
+    /// directions
+    branches: Vec<(u32, usize, bool, Vec<u64>)>,
+}
+
+impl LcovFile {
+    /// See <https://github.com/linux-test-project/lcov/blob/master/man/geninfo.1> for the format
+    fn write(&self, lcov: &mut String, file: &str) -> core::fmt::Result {
+        writeln!(lcov, "SF:{file}")?;
+        for (line, name, _) in &self.functions {
+            writeln!(lcov, "FN:{line},{name}")?;
+        }
+        for (_, name, calls) in &self.functions {
+            writeln!(lcov, "FNDA:{calls},{name}")?;
+        }
+        writeln!(lcov, "FNF:{}", self.functions.len())?;
+        let hit_functions = self.functions.iter().filter(|(.., calls)| *calls > 0);
+        writeln!(lcov, "FNH:{}", hit_functions.count())?;
+
+        let (mut found_branches, mut hit_branches) = (0, 0);
+        for (line, block, executed, directions) in &self.branches {
+            for (branch, taken) in directions.iter().enumerate() {
+                // lcov marks the directions of branches which were never evaluated with `-`
+                if *executed {
+                    writeln!(lcov, "BRDA:{line},{block},{branch},{taken}")?;
+                } else {
+                    writeln!(lcov, "BRDA:{line},{block},{branch},-")?;
+                }
+                found_branches += 1;
+                hit_branches += usize::from(*taken > 0);
+            }
+        }
+        writeln!(lcov, "BRF:{found_branches}")?;
+        writeln!(lcov, "BRH:{hit_branches}")?;
+
+        for (line, count) in &self.lines {
+            writeln!(lcov, "DA:{line},{count}")?;
+        }
+        writeln!(lcov, "LF:{}", self.lines.len())?;
+        let hit_lines = self.lines.values().filter(|count| **count > 0);
+        writeln!(lcov, "LH:{}", hit_lines.count())?;
+        writeln!(lcov, "end_of_record")
+    }
+}
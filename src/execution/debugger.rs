@@ -0,0 +1,147 @@
+//! Debugging of WASM code, by stopping its execution at breakpoints or after single steps
+//!
+//! The debugger only stops invocations which can be suspended, i.e. those started via
+//! [RuntimeInstance::invoke_resumable](crate::RuntimeInstance::invoke_resumable). When it stops, the invocation is
+//! suspended with [SuspendReason::Breakpoint](crate::resumable::SuspendReason::Breakpoint) or
+//! [SuspendReason::Step](crate::resumable::SuspendReason::Step), and its current function can be inspected through a
+//! [DebugFrame] until execution is continued via [RuntimeInstance::resume](crate::RuntimeInstance::resume) or
+//! [RuntimeInstance::step](crate::RuntimeInstance::step).
+
+use alloc::collections::btree_set::BTreeSet;
+use alloc::vec::Vec;
+
+use crate::core::indices::{FuncIdx, GlobalIdx, MemIdx};
+use crate::execution::execution_info::ExecutionInfo;
+use crate::execution::value_stack::CallFrame;
+use crate::{SourceLocation, Value};
+
+/// The breakpoints of a [RuntimeInstance](crate::RuntimeInstance), each being the index of a module and the position
+/// of an instruction in its binary
+pub(crate) type Breakpoints = BTreeSet<(usize, usize)>;
+
+/// How far execution continues before it stops again, see [RuntimeInstance::step](crate::RuntimeInstance::step)
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Step {
+    /// Execute a single instruction, stopping at the first instruction of a called function
+    Into,
+    /// Execute a single instruction, including all functions it calls
+    Over,
+    /// Execute until the current function returned to its caller
+    Out,
+}
+
+impl Step {
+    /// Whether the step is complete, if it was requested with `start_depth` stackframes and the next instruction is
+    /// executed with `depth` stackframes
+    pub(crate) fn is_complete(self, start_depth: usize, depth: usize) -> bool {
+        match self {
+            Step::Into => true,
+            Step::Over => depth <= start_depth,
+            Step::Out => depth < start_depth,
+        }
+    }
+}
+
+/// A function that is active in a suspended invocation
+///
+/// See [RuntimeInstance::debug_frame](crate::RuntimeInstance::debug_frame)
+pub struct DebugFrame<'a, 'b> {
+    pub(crate) module: &'a ExecutionInfo<'b>,
+    pub(crate) frame: &'a CallFrame,
+    /// The position of the next instruction, or for callers the instruction following their active call
+    pub(crate) pc: usize,
+    pub(crate) values: &'a [Value],
+}
+
+impl<'a> DebugFrame<'a, '_> {
+    /// The name under which the function's module was added to the [RuntimeInstance](crate::RuntimeInstance)
+    pub fn module_name(&self) -> &'a str {
+        &self.module.name
+    }
+
+    /// The index of the function in its module, including the imported functions
+    pub fn function_idx(&self) -> FuncIdx {
+        self.frame.func_idx
+    }
+
+    /// The name of the function in the name section of its module, if there is one
+    pub fn function_name(&self) -> Option<&'a str> {
+        self.module.names.function_name(self.frame.func_idx)
+    }
+
+    /// The position of the next instruction in the binary of the module
+    ///
+    /// For the callers of the current function, this is the instruction following their active call.
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// The location of the next instruction in the source code, if the module contains DWARF debug information
+    pub fn source_location(&self) -> Option<SourceLocation> {
+        self.module.dwarf.lookup(self.pc)
+    }
+
+    /// The parameters followed by the declared locals of the function
+    pub fn locals(&self) -> &'a [Value] {
+        self.frame.locals.values()
+    }
+
+    /// The values the function pushed onto the value stack, the topmost one last
+    pub fn value_stack(&self) -> &'a [Value] {
+        self.values
+    }
+
+    /// The current value of a global of the function's module, including the imported globals
+    pub fn global(&self, global_idx: GlobalIdx) -> Option<Value> {
+        let global = self.module.store.globals.get(global_idx)?;
+        Some(global.value.get())
+    }
+
+    /// Read `len` bytes starting at `address` from a memory of the function's module
+    ///
+    /// Returns `None` if there is no such memory or the bytes are out of its bounds.
+    pub fn read_memory(&self, mem_idx: MemIdx, address: usize, len: usize) -> Option<Vec<u8>> {
+        read_memory(self.module, mem_idx, address, len)
+    }
+
+    /// Write `bytes` starting at `address` to a memory of the function's module
+    ///
+    /// Returns `None` without writing anything if there is no such memory or the bytes are out of its bounds.
+    pub fn write_memory(&self, mem_idx: MemIdx, address: usize, bytes: &[u8]) -> Option<()> {
+        write_memory(self.module, mem_idx, address, bytes)
+    }
+}
+
+/// See [DebugFrame::read_memory]
+pub(crate) fn read_memory(
+    module: &ExecutionInfo,
+    mem_idx: MemIdx,
+    address: usize,
+    len: usize,
+) -> Option<Vec<u8>> {
+    let memory = &module.store.mems.get(mem_idx)?.mem;
+    let end = address.checked_add(len)?;
+    if end > memory.len() {
+        return None;
+    }
+    (address..end)
+        .map(|address| memory.load::<1, u8>(address).ok())
+        .collect()
+}
+
+/// See [DebugFrame::write_memory]
+pub(crate) fn write_memory(
+    module: &ExecutionInfo,
+    mem_idx: MemIdx,
+    address: usize,
+    bytes: &[u8],
+) -> Option<()> {
+    let memory = &module.store.mems.get(mem_idx)?.mem;
+    if address.checked_add(bytes.len())? > memory.len() {
+        return None;
+    }
+    for (address, byte) in (address..).zip(bytes) {
+        memory.store::<1, u8>(address, *byte).ok()?;
+    }
+    Some(())
+}
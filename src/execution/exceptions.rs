@@ -0,0 +1,135 @@
+//! Support for the exception handling proposal
+//!
+//! Exceptions are thrown by `throw` and `throw_ref`, and caught by the catch clauses of `try_table` blocks, possibly in
+//! a function further up the call stack. Exceptions which are not caught at all are returned to the embedder as
+//! [RuntimeError::UncaughtException](crate::RuntimeError::UncaughtException), which can be decoded into an
+//! [Exception] via [RuntimeInstance::exception](crate::RuntimeInstance::exception).
+//!
+//! See <https://webassembly.github.io/exception-handling/core/>
+
+use alloc::rc::Rc;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::execution::execution_info::ExecutionInfo;
+use crate::execution::store::{ExnInst, ExnSlot, TagInst};
+use crate::execution::value::{ExnAddr, Ref};
+use crate::execution::value_stack::Stack;
+use crate::Value;
+
+/// The number of slots a store holds at least before its exceptions are collected
+pub(crate) const MIN_EXCEPTION_COLLECTION_THRESHOLD: usize = 16;
+
+/// Identifies a tag, see [RuntimeInstance::get_tag_by_name](crate::RuntimeInstance::get_tag_by_name)
+///
+/// An imported tag is the same tag as the one exported, so both are identified by equal [TagRef]s.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct TagRef(usize);
+
+impl TagRef {
+    pub(crate) fn new(tag_inst: &TagInst) -> Self {
+        Self(Rc::as_ptr(&tag_inst.ty) as usize)
+    }
+}
+
+/// Store the new exception `exn` thrown by the module `module_idx`, whose execution uses `stack`
+///
+/// If the store has no free slot left and already reached its threshold, all exceptions which are no longer referenced
+/// are collected first. The threshold is then set to twice the number of exceptions still stored, so collecting is
+/// amortized over the exceptions stored since.
+pub(crate) fn store_exception(
+    modules: &mut [ExecutionInfo],
+    module_idx: usize,
+    exn: ExnInst,
+    stack: &Stack,
+) -> ExnAddr {
+    let store = &modules[module_idx].store;
+    if store.free_exceptions.is_empty()
+        && store.exceptions.len() >= store.exception_collection_threshold
+    {
+        collect_exceptions(modules, stack.all_values().chain(&exn.payload));
+    }
+
+    let store = &mut modules[module_idx].store;
+    let addr = match store.free_exceptions.pop() {
+        Some(addr) => {
+            store.exceptions[addr].exn = Some(exn);
+            addr
+        }
+        None => {
+            store.exceptions.push(ExnSlot {
+                generation: 0,
+                exn: Some(exn),
+            });
+            store.exceptions.len() - 1
+        }
+    };
+    ExnAddr::new_in_module(module_idx, addr, store.exceptions[addr].generation)
+}
+
+/// Free the slots of all exceptions which are neither referenced by `roots`, nor by the globals, tables and reachable
+/// exceptions of any module
+fn collect_exceptions<'a>(modules: &mut [ExecutionInfo], roots: impl Iterator<Item = &'a Value>) {
+    let mut pending: Vec<ExnAddr> = roots.filter_map(exn_addr).collect();
+    for module in modules.iter() {
+        pending.extend(
+            module
+                .store
+                .globals
+                .iter()
+                .filter_map(|global| exn_addr(&global.value.get())),
+        );
+        for table in &module.store.tables {
+            pending.extend(table.elem.borrow().iter().filter_map(|elem| match elem {
+                Ref::Exn(addr) => Some(*addr),
+                _ => None,
+            }));
+        }
+    }
+
+    // mark all exceptions reachable from the roots, including those in the payloads of reachable exceptions
+    let mut reachable: Vec<Vec<bool>> = modules
+        .iter()
+        .map(|module| vec![false; module.store.exceptions.len()])
+        .collect();
+    while let Some(addr) = pending.pop() {
+        let Some(exn) = modules[addr.module_idx].store.exception(addr) else {
+            continue;
+        };
+        let marked = &mut reachable[addr.module_idx][addr.addr.unwrap()];
+        if !*marked {
+            *marked = true;
+            pending.extend(exn.payload.iter().filter_map(exn_addr));
+        }
+    }
+
+    for (module, reachable) in modules.iter_mut().zip(reachable) {
+        let store = &mut module.store;
+        for (addr, slot) in store.exceptions.iter_mut().enumerate() {
+            if slot.exn.is_some() && !reachable[addr] {
+                slot.exn = None;
+                slot.generation = slot.generation.wrapping_add(1);
+                store.free_exceptions.push(addr);
+            }
+        }
+        let stored = store.exceptions.len() - store.free_exceptions.len();
+        store.exception_collection_threshold = MIN_EXCEPTION_COLLECTION_THRESHOLD.max(2 * stored);
+    }
+}
+
+/// The address of the exception referenced by `value`, if it is an exnref
+fn exn_addr(value: &Value) -> Option<ExnAddr> {
+    match value {
+        Value::Ref(Ref::Exn(addr)) => Some(*addr),
+        _ => None,
+    }
+}
+
+/// The tag and payload of an exception which was thrown, but not caught by any `try_table`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Exception {
+    /// The tag the exception was thrown with
+    pub tag: TagRef,
+    /// The values carried by the exception, of the types of the tag's parameters
+    pub payload: Vec<Value>,
+}
@@ -1,6 +1,8 @@
 use alloc::string::{String, ToString};
 use alloc::vec::Vec;
 
+use crate::core::reader::types::custom_section::NameSection;
+use crate::core::reader::types::dwarf::DwarfInfo;
 use crate::core::reader::types::FuncType;
 use crate::core::reader::WasmReader;
 use crate::execution::Store;
@@ -14,16 +16,29 @@ pub struct ExecutionInfo<'r> {
     pub wasm_reader: WasmReader<'r>,
     pub fn_types: Vec<FuncType>,
     pub store: Store,
+    /// The debug names of the module's name section
+    pub names: NameSection<'r>,
+    /// The DWARF debug information of the module's custom sections
+    pub dwarf: DwarfInfo,
 }
 
 impl<'r> ExecutionInfo<'r> {
-    pub fn new(name: &str, wasm_bytecode: &'r [u8], fn_types: Vec<FuncType>, store: Store) -> Self {
+    pub fn new(
+        name: &str,
+        wasm_bytecode: &'r [u8],
+        fn_types: Vec<FuncType>,
+        store: Store,
+        names: NameSection<'r>,
+        dwarf: DwarfInfo,
+    ) -> Self {
         ExecutionInfo {
             name: name.to_string(),
             wasm_bytecode,
             wasm_reader: WasmReader::new(wasm_bytecode),
             fn_types,
             store,
+            names,
+            dwarf,
         }
     }
 }
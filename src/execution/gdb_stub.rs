@@ -0,0 +1,455 @@
+//! A stub for the GDB remote serial protocol, through which debuggers like LLDB control suspended invocations
+//!
+//! The stub speaks the WebAssembly dialect of the protocol that LLDB understands. Its addresses are 64 bits wide: The
+//! two most significant bits select an address space, the next 30 bits the index of a module in the
+//! [RuntimeInstance] and the lowest 32 bits an offset in the address space of that module:
+//! - `0x4000_0000_0000_0000`: code addresses are positions in the binary of the module, which is read by the debugger
+//! - `0x0000_0000_0000_0000`: memory addresses are positions in the first memory of the module
+//!
+//! Besides the standard packets to read and write memory (`m`, `M`), to set and remove breakpoints (`Z0`, `z0`) and to
+//! continue (`c`) or step (`s`) execution, the stub answers the WebAssembly specific queries `qWasmCallStack`,
+//! `qWasmLocal`, `qWasmGlobal`, `qWasmStackValue` and `qWasmMem`. All invocations appear to the debugger as a single
+//! thread of a process.
+
+use alloc::borrow::ToOwned;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::execution::debugger::{self, Step};
+use crate::execution::hooks::HookSet;
+use crate::execution::resumable::{InvocationState, Resumable, SuspendReason};
+use crate::execution::trap::Trap;
+use crate::execution::RuntimeInstance;
+use crate::Value;
+
+/// The connection to a debugger, e.g. a socket or a UART
+pub trait Transport {
+    type Error;
+
+    /// Wait for the next byte sent by the debugger
+    fn read_byte(&mut self) -> Result<u8, Self::Error>;
+
+    /// Send all `bytes` to the debugger
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), Self::Error>;
+}
+
+/// Why [GdbStub::serve] returned
+pub enum DebugOutcome {
+    /// The invocation returned these values, which ended the debugged process
+    Finished(Vec<Value>),
+    /// The invocation trapped, which terminated the debugged process
+    Trapped(Trap),
+    /// The invocation was suspended for another reason than the debugger, e.g. because it ran out of fuel
+    ///
+    /// The debugger still waits for the invocation to stop, so it should be passed to [GdbStub::serve] again.
+    Suspended(Resumable),
+    /// The debugger detached, such that the invocation can be continued without it
+    Detached(Resumable),
+    /// The debugger killed the invocation
+    Killed,
+}
+
+/// A GDB remote serial protocol server, which lets a debugger drive the execution of a [RuntimeInstance]
+pub struct GdbStub<T: Transport> {
+    transport: T,
+    /// Whether the debugger asked to stop acknowledging packets
+    no_ack_mode: bool,
+    /// How execution continues on behalf of the debugger, while it waits for the invocation to stop
+    running: Option<Resume>,
+}
+
+#[derive(Debug, Copy, Clone)]
+enum Resume {
+    Continue,
+    Step,
+}
+
+/// What the stub does in response to a packet
+enum Command {
+    Reply(String),
+    Resume(Resume),
+    Detach,
+    Kill,
+}
+
+/// The address space of code addresses, see the [module](self) documentation
+const CODE_ADDRESS_SPACE: u64 = 1;
+/// The address space of memory addresses, see the [module](self) documentation
+const MEMORY_ADDRESS_SPACE: u64 = 0;
+
+const TRIPLE: &str = "wasm32-unknown-unknown-wasm";
+
+/// The only register, the program counter as a code address
+const REGISTER_INFO: &str =
+    "name:pc;alt-name:pc;bitsize:64;offset:0;encoding:uint;format:hex;set:General Purpose Registers;generic:pc;";
+
+/// The signal reported when an invocation stops at a breakpoint or after a step
+const SIGTRAP: u8 = 5;
+/// The signal reported when an invocation traps, as traps are raised by illegal operations
+const SIGILL: u8 = 4;
+
+impl<T: Transport> GdbStub<T> {
+    pub fn new(transport: T) -> Self {
+        GdbStub {
+            transport,
+            no_ack_mode: false,
+            running: None,
+        }
+    }
+
+    pub fn into_transport(self) -> T {
+        self.transport
+    }
+
+    /// Serve the debugger, which controls the execution of a suspended invocation
+    ///
+    /// The invocation is reported to the debugger as stopped and only continues when the debugger requests it, until
+    /// it hits a breakpoint or the step is complete. Breakpoints are set via
+    /// [RuntimeInstance::set_breakpoint], so they also stop other resumable invocations of the `instance`. As the
+    /// invocation runs on the caller's thread, the debugger can not interrupt it.
+    ///
+    /// # Returns
+    /// - `Ok(outcome)`, once the invocation finished or was suspended for another reason, or the debugger detached or
+    ///   killed it.
+    /// - `Err(error)`, if the transport failed.
+    pub fn serve<H: HookSet>(
+        &mut self,
+        instance: &mut RuntimeInstance<'_, H>,
+        mut resumable: Resumable,
+    ) -> Result<DebugOutcome, T::Error> {
+        loop {
+            if let Some(resume) = self.running {
+                let state = match resume {
+                    Resume::Continue => instance.resume(resumable),
+                    Resume::Step => instance.step(resumable, Step::Into),
+                };
+                resumable = match state {
+                    Ok(InvocationState::Suspended(resumable)) => resumable,
+                    Ok(InvocationState::Finished(values)) => {
+                        self.running = None;
+                        self.write_packet(b"W00")?;
+                        return Ok(DebugOutcome::Finished(values));
+                    }
+                    Err(error) => {
+                        self.running = None;
+                        self.write_packet(format!("X{SIGILL:02x}").as_bytes())?;
+                        return Ok(DebugOutcome::Trapped(error));
+                    }
+                };
+                match resumable.suspend_reason() {
+                    Some(SuspendReason::Breakpoint | SuspendReason::Step) => {
+                        self.running = None;
+                        self.write_packet(stop_reply(&resumable).as_bytes())?;
+                    }
+                    _ => return Ok(DebugOutcome::Suspended(resumable)),
+                }
+            }
+
+            let packet = self.read_packet()?;
+            match self.handle(instance, &resumable, &packet) {
+                Command::Reply(reply) => self.write_packet(reply.as_bytes())?,
+                Command::Resume(resume) => self.running = Some(resume),
+                Command::Detach => {
+                    self.write_packet(b"OK")?;
+                    return Ok(DebugOutcome::Detached(resumable));
+                }
+                Command::Kill => return Ok(DebugOutcome::Killed),
+            }
+        }
+    }
+
+    /// Wait for the next packet with a valid checksum, returning its unescaped contents
+    ///
+    /// Acknowledgements of the debugger are skipped, as the stub never retransmits its packets.
+    fn read_packet(&mut self) -> Result<Vec<u8>, T::Error> {
+        loop {
+            while self.transport.read_byte()? != b'$' {}
+
+            let mut packet = Vec::new();
+            let mut checksum = 0_u8;
+            let mut escaped = false;
+            loop {
+                let byte = self.transport.read_byte()?;
+                if byte == b'#' {
+                    break;
+                }
+                checksum = checksum.wrapping_add(byte);
+                match (escaped, byte) {
+                    (false, b'}') => escaped = true,
+                    (true, _) => {
+                        packet.push(byte ^ 0x20);
+                        escaped = false;
+                    }
+                    (false, _) => packet.push(byte),
+                }
+            }
+            let expected_checksum = [self.transport.read_byte()?, self.transport.read_byte()?];
+
+            if self.no_ack_mode {
+                return Ok(packet);
+            }
+            if parse_bytes(core::str::from_utf8(&expected_checksum).unwrap_or_default())
+                == Some(vec![checksum])
+            {
+                self.transport.write_all(b"+")?;
+                return Ok(packet);
+            }
+            warn!("Received a GDB packet with an invalid checksum");
+            self.transport.write_all(b"-")?;
+        }
+    }
+
+    fn write_packet(&mut self, data: &[u8]) -> Result<(), T::Error> {
+        let mut packet = vec![b'$'];
+        for &byte in data {
+            if matches!(byte, b'$' | b'#' | b'}' | b'*') {
+                packet.extend([b'}', byte ^ 0x20]);
+            } else {
+                packet.push(byte);
+            }
+        }
+        let checksum = packet[1..]
+            .iter()
+            .fold(0_u8, |checksum, byte| checksum.wrapping_add(*byte));
+        packet.push(b'#');
+        packet.extend(hex(&[checksum]).bytes());
+        self.transport.write_all(&packet)
+    }
+
+    fn handle<H: HookSet>(
+        &mut self,
+        instance: &mut RuntimeInstance<'_, H>,
+        resumable: &Resumable,
+        packet: &[u8],
+    ) -> Command {
+        let Ok(packet) = core::str::from_utf8(packet) else {
+            return Command::Reply(String::new());
+        };
+        trace!("Received GDB packet {packet}");
+
+        // queries are named by everything up to their arguments, all other packets by their first character
+        let (name, args) = match packet.as_bytes().first() {
+            None => return Command::Reply(String::new()),
+            Some(b'q' | b'Q' | b'v') => packet.split_once(':').unwrap_or((packet, "")),
+            Some(_) => packet.split_at(1),
+        };
+
+        let reply = match name {
+            "?" => Some(stop_reply(resumable)),
+            "c" | "s" if !args.is_empty() => None,
+            "c" => return Command::Resume(Resume::Continue),
+            "s" => return Command::Resume(Resume::Step),
+            "D" => return Command::Detach,
+            "k" => return Command::Kill,
+            "H" | "T" => Some("OK".to_owned()),
+            "g" => Some(hex(&current_pc(resumable).to_le_bytes())),
+            "p" => (parse_hex(args) == Some(0)).then(|| hex(&current_pc(resumable).to_le_bytes())),
+            "m" => read_memory(instance, args),
+            "M" => write_memory(instance, args),
+            "Z" | "z" => set_breakpoint(instance, name == "Z", args),
+            "qSupported" => {
+                Some("PacketSize=1000;QStartNoAckMode+;qXfer:libraries:read+".to_owned())
+            }
+            "QStartNoAckMode" => {
+                self.no_ack_mode = true;
+                Some("OK".to_owned())
+            }
+            "qHostInfo" => Some(format!(
+                "triple:{};ptrsize:4;endian:little;",
+                hex(TRIPLE.as_bytes())
+            )),
+            "qProcessInfo" => Some(format!(
+                "pid:1;triple:{};ptrsize:4;endian:little;",
+                hex(TRIPLE.as_bytes())
+            )),
+            "qC" => Some("QC1".to_owned()),
+            "qfThreadInfo" => Some("m1".to_owned()),
+            "qsThreadInfo" => Some("l".to_owned()),
+            "qAttached" => Some("1".to_owned()),
+            "qRegisterInfo0" => Some(REGISTER_INFO.to_owned()),
+            _ if name.starts_with("qRegisterInfo") => Some("E45".to_owned()),
+            "qXfer" => libraries(instance, args),
+            "qWasmCallStack" => Some(call_stack(instance, resumable)),
+            "qWasmLocal" | "qWasmGlobal" | "qWasmStackValue" | "qWasmMem" => {
+                frame_query(instance, resumable, name, args)
+            }
+            _ => Some(String::new()),
+        };
+        Command::Reply(reply.unwrap_or_else(|| "E01".to_owned()))
+    }
+}
+
+fn code_address(module_idx: usize, offset: usize) -> u64 {
+    (CODE_ADDRESS_SPACE << 62) | ((module_idx as u64) << 32) | offset as u64
+}
+
+/// Split an address into its address space, module index and offset
+fn decode_address(address: u64) -> (u64, usize, usize) {
+    (
+        address >> 62,
+        ((address >> 32) & 0x3fff_ffff) as usize,
+        (address & 0xffff_ffff) as usize,
+    )
+}
+
+fn current_pc(resumable: &Resumable) -> u64 {
+    code_address(resumable.current_module_idx, resumable.pc)
+}
+
+fn stop_reply(resumable: &Resumable) -> String {
+    format!(
+        "T{SIGTRAP:02x}thread:1;00:{};",
+        hex(&current_pc(resumable).to_le_bytes())
+    )
+}
+
+/// `m<address>,<length>`, of which partial reads of a module's binary are answered
+fn read_memory<H: HookSet>(instance: &RuntimeInstance<'_, H>, args: &str) -> Option<String> {
+    let (address, len) = args.split_once(',')?;
+    let (address_space, module_idx, offset) = decode_address(parse_hex(address)?);
+    let len = parse_hex(len)? as usize;
+    let module = instance.modules.get(module_idx)?;
+    let bytes = match address_space {
+        CODE_ADDRESS_SPACE => {
+            let end = offset.saturating_add(len).min(module.wasm_bytecode.len());
+            module.wasm_bytecode.get(offset..end)?.to_vec()
+        }
+        MEMORY_ADDRESS_SPACE => debugger::read_memory(module, 0, offset, len)?,
+        _ => return None,
+    };
+    Some(hex(&bytes))
+}
+
+/// `M<address>,<length>:<bytes>`, only for memory addresses
+fn write_memory<H: HookSet>(instance: &RuntimeInstance<'_, H>, args: &str) -> Option<String> {
+    let (address, args) = args.split_once(',')?;
+    let (len, bytes) = args.split_once(':')?;
+    let (address_space, module_idx, offset) = decode_address(parse_hex(address)?);
+    let bytes = parse_bytes(bytes)?;
+    if address_space != MEMORY_ADDRESS_SPACE || parse_hex(len)? != bytes.len() as u64 {
+        return None;
+    }
+    debugger::write_memory(instance.modules.get(module_idx)?, 0, offset, &bytes)?;
+    Some("OK".to_owned())
+}
+
+/// `Z0,<address>,<kind>` and `z0,<address>,<kind>` for software breakpoints at code addresses
+fn set_breakpoint<H: HookSet>(
+    instance: &mut RuntimeInstance<'_, H>,
+    enable: bool,
+    args: &str,
+) -> Option<String> {
+    let mut args = args.split(',');
+    if args.next()? != "0" {
+        return Some(String::new());
+    }
+    let (address_space, module_idx, offset) = decode_address(parse_hex(args.next()?)?);
+    if address_space != CODE_ADDRESS_SPACE {
+        return None;
+    }
+    let module_name = instance.modules.get(module_idx)?.name.clone();
+    if enable {
+        instance.set_breakpoint(&module_name, offset).ok()?;
+    } else {
+        instance.remove_breakpoint(&module_name, offset);
+    }
+    Some("OK".to_owned())
+}
+
+/// `qXfer:libraries:read::<offset>,<length>`, listing the modules with the code address of their binary
+fn libraries<H: HookSet>(instance: &RuntimeInstance<'_, H>, args: &str) -> Option<String> {
+    let (offset, len) = args.strip_prefix("libraries:read::")?.split_once(',')?;
+    let mut xml = String::from("<library-list>");
+    for (module_idx, module) in instance.modules.iter().enumerate() {
+        xml.push_str("<library name=\"");
+        // the list stays ASCII, such that it can be split at any offset
+        for c in module.name.chars() {
+            if c.is_ascii() && !matches!(c, '"' | '&' | '<' | '>') {
+                xml.push(c);
+            } else {
+                write!(xml, "&#{};", c as u32).unwrap();
+            }
+        }
+        write!(
+            xml,
+            "\"><section address=\"0x{:x}\"/></library>",
+            code_address(module_idx, 0)
+        )
+        .unwrap();
+    }
+    xml.push_str("</library-list>");
+
+    let start = (parse_hex(offset)? as usize).min(xml.len());
+    let end = start
+        .saturating_add(parse_hex(len)? as usize)
+        .min(xml.len());
+    let more = if end < xml.len() { 'm' } else { 'l' };
+    Some(format!("{more}{}", &xml[start..end]))
+}
+
+/// `qWasmCallStack`, the current code address of all active functions, the current one first
+fn call_stack<H: HookSet>(instance: &RuntimeInstance<'_, H>, resumable: &Resumable) -> String {
+    let mut reply = String::new();
+    let mut depth = 0;
+    while let Some(frame) = instance.caller_debug_frame(resumable, depth) {
+        reply.push_str(&hex(
+            &code_address(frame.frame.module_idx, frame.pc()).to_le_bytes()
+        ));
+        depth += 1;
+    }
+    reply
+}
+
+/// `qWasmLocal:<depth>;<index>`, `qWasmGlobal:<depth>;<index>`, `qWasmStackValue:<depth>;<index>` and
+/// `qWasmMem:<depth>;<address>;<length>`, where `depth` selects an active function like
+/// [RuntimeInstance::caller_debug_frame]
+fn frame_query<H: HookSet>(
+    instance: &RuntimeInstance<'_, H>,
+    resumable: &Resumable,
+    name: &str,
+    args: &str,
+) -> Option<String> {
+    let mut args = args.split(';').map(parse_hex);
+    let frame = instance.caller_debug_frame(resumable, args.next()?? as usize)?;
+    let index = args.next()?? as usize;
+    let value = match name {
+        "qWasmLocal" => frame.locals().get(index).copied()?,
+        "qWasmGlobal" => frame.global(index)?,
+        "qWasmStackValue" => frame.value_stack().get(index).copied()?,
+        _ => return Some(hex(&frame.read_memory(0, index, args.next()?? as usize)?)),
+    };
+    let bytes = match value {
+        Value::I32(value) => value.to_le_bytes().to_vec(),
+        Value::I64(value) => value.to_le_bytes().to_vec(),
+        Value::F32(value) => value.to_bits().to_le_bytes().to_vec(),
+        Value::F64(value) => value.to_bits().to_le_bytes().to_vec(),
+        Value::V128(bytes) => bytes.to_vec(),
+        Value::Ref(_) => return None,
+    };
+    Some(hex(&bytes))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        write!(hex, "{byte:02x}").unwrap();
+    }
+    hex
+}
+
+fn parse_hex(hex: &str) -> Option<u64> {
+    u64::from_str_radix(hex, 16).ok()
+}
+
+fn parse_bytes(hex: &str) -> Option<Vec<u8>> {
+    if hex.len() % 2 != 0 {
+        return None;
+    }
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok())
+        .collect()
+}
@@ -1,14 +1,141 @@
+use alloc::string::String;
+use core::ops::Range;
+
+use crate::core::indices::{FuncIdx, MemIdx, TableIdx};
+use crate::{RuntimeError, Value};
+
+/// What the interpreter does after a hook returned
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum HookAction {
+    /// Execution continues as if the hook was not there
+    #[default]
+    Continue,
+    /// Execution traps with [RuntimeError::HookTrap], carrying the given reason
+    ///
+    /// Hooks which are called before an effect, like [HookSet::store_hook], prevent it this way.
+    Trap(String),
+    /// Execution is suspended with [SuspendReason::HookYield](crate::resumable::SuspendReason::HookYield) before the
+    /// next instruction
+    ///
+    /// Invocations which can not be suspended, e. g. via [RuntimeInstance::invoke](crate::RuntimeInstance::invoke),
+    /// continue right away.
+    Yield,
+}
+
 /// Trait that allows user specified hooks for various events during interpretation
 ///
-/// The default implementation of all trait methods are empty, i. e. can be optimized out fully.
+/// The default implementation of all trait methods are empty, i. e. can be optimized out fully. Except for
+/// [HookSet::trap_hook], all hooks decide via their [HookAction] whether execution continues.
+///
+/// Modules are identified by their index in [RuntimeInstance::modules](crate::RuntimeInstance::modules), all other
+/// indices are relative to the module. Positions are offsets in the binary of the module.
 // It mus always be checked that there is no performance penalty for an empty hook!
+#[allow(unused_variables)]
 pub trait HookSet: Default {
     /// A hook which is called before every wasm instruction
     ///
     /// This allows the most intricate insight into the interpreters behavior, at the cost of a
     /// hefty performance penalty
-    #[allow(unused_variables)]
-    fn instruction_hook(&mut self, bytecode: &[u8], pc: usize) {}
+    fn instruction_hook(&mut self, module_idx: usize, bytecode: &[u8], pc: usize) -> HookAction {
+        HookAction::Continue
+    }
+
+    /// A hook which is called when a function is entered, before its first instruction
+    ///
+    /// Host functions are reported as the imported function of the module calling them, right before they are called.
+    fn call_hook(&mut self, module_idx: usize, func_idx: FuncIdx, args: &[Value]) -> HookAction {
+        HookAction::Continue
+    }
+
+    /// A hook which is called when a function returns, after its last instruction
+    fn return_hook(
+        &mut self,
+        module_idx: usize,
+        func_idx: FuncIdx,
+        results: &[Value],
+    ) -> HookAction {
+        HookAction::Continue
+    }
+
+    /// A hook which is called when a function is left without returning, because it was replaced by the callee of a
+    /// tail call or an exception was thrown through it
+    ///
+    /// Functions which are active when execution traps are reported to neither this hook nor
+    /// [HookSet::return_hook].
+    fn unwind_hook(&mut self, module_idx: usize, func_idx: FuncIdx) -> HookAction {
+        HookAction::Continue
+    }
+
+    /// A hook which is called when the branch instruction at `pc` continues execution at `target_pc`
+    ///
+    /// Besides taken branches of `br`, `br_if` and `br_table`, this includes `if` jumping to its else arm or end,
+    /// `else` jumping from the end of the then arm to the end of the `if`, and `return`.
+    fn branch_hook(&mut self, module_idx: usize, pc: usize, target_pc: usize) -> HookAction {
+        HookAction::Continue
+    }
+
+    /// A hook which is called after `width` bytes were loaded from `address`, producing `value`
+    fn load_hook(
+        &mut self,
+        module_idx: usize,
+        mem_idx: MemIdx,
+        address: usize,
+        width: usize,
+        value: Value,
+    ) -> HookAction {
+        HookAction::Continue
+    }
+
+    /// A hook which is called before `value` is stored at `address`, writing `width` bytes
+    ///
+    /// The store may still trap if it is out of bounds. For atomic read-modify-write instructions, `value` is the
+    /// operand the datum is modified with, and for compare-exchange the replacement.
+    fn store_hook(
+        &mut self,
+        module_idx: usize,
+        mem_idx: MemIdx,
+        address: usize,
+        width: usize,
+        value: Value,
+    ) -> HookAction {
+        HookAction::Continue
+    }
+
+    /// A hook which is called before `memory.init`, `memory.copy` or `memory.fill` write `len` bytes at `address`
+    fn bulk_store_hook(
+        &mut self,
+        module_idx: usize,
+        mem_idx: MemIdx,
+        address: usize,
+        len: usize,
+    ) -> HookAction {
+        HookAction::Continue
+    }
+
+    /// A hook which is called after `memory.grow`, with the sizes in pages, which are equal if growing failed
+    fn memory_grow_hook(
+        &mut self,
+        module_idx: usize,
+        mem_idx: MemIdx,
+        previous_pages: usize,
+        pages: usize,
+    ) -> HookAction {
+        HookAction::Continue
+    }
+
+    /// A hook which is called after the `elements` of a table were written by `table.set`, `table.init`,
+    /// `table.copy`, `table.fill` or a successful `table.grow`
+    fn table_hook(
+        &mut self,
+        module_idx: usize,
+        table_idx: TableIdx,
+        elements: Range<usize>,
+    ) -> HookAction {
+        HookAction::Continue
+    }
+
+    /// A hook which is called when execution traps at the instruction at `pc`
+    fn trap_hook(&mut self, error: &RuntimeError, module_idx: usize, pc: usize) {}
 }
 
 /// Default implementation of a hookset, with all hooks empty
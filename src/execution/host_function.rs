@@ -0,0 +1,44 @@
+use alloc::vec::Vec;
+
+use crate::Value;
+
+/// A function implemented by the embedder, which can be imported by WASM modules
+///
+/// The interpreter makes sure that `params` matches the parameter types the function was registered with (see
+/// [RuntimeInstance::add_host_function](crate::RuntimeInstance::add_host_function)). In turn, the returned values
+/// have to match the registered return types, otherwise the call traps with
+/// [RuntimeError::HostFunctionSignatureMismatch](crate::RuntimeError::HostFunctionSignatureMismatch).
+///
+/// This trait is implemented for all closures of the form `FnMut(&[Value]) -> R`, where `R` is either a `Vec<Value>`
+/// or a [HostCallOutcome].
+pub trait HostFunction {
+    fn call(&mut self, params: &[Value]) -> HostCallOutcome;
+}
+
+impl<F, R> HostFunction for F
+where
+    F: FnMut(&[Value]) -> R,
+    R: Into<HostCallOutcome>,
+{
+    fn call(&mut self, params: &[Value]) -> HostCallOutcome {
+        self(params).into()
+    }
+}
+
+/// The outcome of calling a [HostFunction]
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostCallOutcome {
+    /// Return the values to the calling WASM function and continue execution
+    Return(Vec<Value>),
+    /// Return the values to the calling WASM function, but suspend execution right after the call
+    ///
+    /// Only invocations started with [RuntimeInstance::invoke_resumable](crate::RuntimeInstance::invoke_resumable)
+    /// are actually suspended, any other invocation just continues execution.
+    Yield(Vec<Value>),
+}
+
+impl From<Vec<Value>> for HostCallOutcome {
+    fn from(values: Vec<Value>) -> Self {
+        HostCallOutcome::Return(values)
+    }
+}
@@ -10,39 +10,86 @@
 //!      [`Error::RuntimeError`](crate::Error::RuntimeError) variant, which as per 2., we don not
 //!      want
 
+use alloc::rc::Rc;
 use alloc::vec;
 use alloc::vec::Vec;
+use core::sync::atomic::{self, Ordering};
+use core::time::Duration;
 
 use crate::{
     assert_validated::UnwrapValidatedExt,
     core::{
-        indices::{DataIdx, FuncIdx, GlobalIdx, LabelIdx, LocalIdx, MemIdx, TableIdx, TypeIdx},
+        indices::{
+            DataIdx, FuncIdx, GlobalIdx, LabelIdx, LocalIdx, MemIdx, TableIdx, TagIdx, TypeIdx,
+        },
         reader::{
-            types::{memarg::MemArg, BlockType},
+            types::{memarg::MemArg, AddrType, BlockType, CatchClause},
             WasmReadable, WasmReader,
         },
         sidetable::Sidetable,
     },
+    linear_memory::LinearMemory,
     locals::Locals,
-    store::{DataInst, FuncInst},
-    value::{self, FuncAddr, Ref},
+    simd,
+    store::{DataInst, ExnInst, FuncInst, HostFuncInst},
+    threads::{WaitKey, WaitOutcome, Waiter},
+    unreachable_validated,
+    value::{self, ExnAddr, FuncAddr, Ref},
     value_stack::Stack,
-    Limits, NumType, RefType, RuntimeError, ValType, Value,
+    FuncType, NumType, RefType, RuntimeError, ValType, Value,
 };
 
 #[cfg(feature = "hooks")]
+use crate::execution::hooks::HookAction;
 use crate::execution::hooks::HookSet;
 
-use super::{execution_info::ExecutionInfo, lut::Lut};
+use super::{
+    debugger::Breakpoints,
+    exceptions::store_exception,
+    execution_info::ExecutionInfo,
+    host_function::HostCallOutcome,
+    lut::{FunctionTarget, Lut},
+    resumable::{Resumable, SuspendReason},
+};
 
 /// Interprets a functions. Parameters and return values are passed on the stack.
+///
+/// Execution starts at the `pc` and `stp` stored in the [`Resumable`], within the function of its current stackframe.
+///
+/// If the [`Resumable`] has fuel, every executed instruction consumes one unit of fuel, and bulk instructions
+/// additionally consume one unit per byte or element they operate on.
+///
+/// Before each instruction, execution stops if the instruction has one of the `breakpoints` or if the pending step of
+/// the [`Resumable`] is complete, unless execution was already stopped there.
+///
+/// # Returns
+/// - `Ok(None)`, if the outermost function returned.
+/// - `Ok(Some(reason))`, if execution was suspended. The [`Resumable`] then holds everything needed to continue
+///   execution by calling this function again.
+/// - `Err(err)`, if execution trapped. The `pc` of the [`Resumable`] then points to the trapping instruction, and
+///   its stack still holds the stackframes of all active functions.
 pub(super) fn run<H: HookSet>(
     modules: &mut [ExecutionInfo],
-    current_module_idx: &mut usize,
     lut: &Lut,
-    stack: &mut Stack,
-    mut hooks: H,
-) -> Result<(), RuntimeError> {
+    host_functions: &mut [HostFuncInst],
+    resumable: &mut Resumable,
+    waiter: Option<&dyn Waiter>,
+    breakpoints: &Breakpoints,
+    hooks: &mut H,
+) -> Result<Option<SuspendReason>, RuntimeError> {
+    let Resumable {
+        stack,
+        current_module_idx,
+        pc: suspended_pc,
+        stp: suspended_stp,
+        maybe_fuel,
+        required_fuel,
+        step,
+        stopped_by_debugger,
+        hook_yield,
+        ..
+    } = resumable;
+
     let func_inst = modules[*current_module_idx]
         .store
         .funcs
@@ -51,22 +98,71 @@ pub(super) fn run<H: HookSet>(
         .try_into_local()
         .unwrap_validated();
 
-    // Start reading the function's instructions
+    // Continue reading the function's instructions
     let mut wasm = &mut modules[*current_module_idx].wasm_reader;
+    wasm.pc = *suspended_pc;
 
     // the sidetable and stp for this function, stp will reset to 0 every call
     // since function instances have their own sidetable.
     let mut current_sidetable: &Sidetable = &func_inst.sidetable;
-    let mut stp = 0;
-
-    // unwrap is sound, because the validation assures that the function points to valid subslice of the WASM binary
-    wasm.move_start_to(func_inst.code_expr).unwrap();
+    let mut stp = *suspended_stp;
 
     use crate::core::reader::types::opcode::*;
     loop {
+        // suspend if a hook of the previous instruction asked for it
+        #[cfg(feature = "hooks")]
+        if *hook_yield {
+            *suspended_pc = wasm.pc;
+            *suspended_stp = stp;
+            *hook_yield = false;
+            return Ok(Some(SuspendReason::HookYield));
+        }
+
+        // stop for the debugger before executing the next instruction
+        if !*stopped_by_debugger {
+            let debugger_stop = if breakpoints.contains(&(*current_module_idx, wasm.pc)) {
+                Some(SuspendReason::Breakpoint)
+            } else {
+                step.filter(|(step, call_depth)| {
+                    step.is_complete(*call_depth, stack.callframe_count())
+                })
+                .map(|_| SuspendReason::Step)
+            };
+            if debugger_stop.is_some() {
+                *suspended_pc = wasm.pc;
+                *suspended_stp = stp;
+                *step = None;
+                *stopped_by_debugger = true;
+                return Ok(debugger_stop);
+            }
+        }
+
+        // consume the fuel for the next instruction, or suspend before executing it
+        if let Some(fuel) = maybe_fuel {
+            let cost = fuel_cost(wasm, stack);
+            if *fuel < cost {
+                *suspended_pc = wasm.pc;
+                *suspended_stp = stp;
+                *required_fuel = cost;
+                return Ok(Some(SuspendReason::OutOfFuel));
+            }
+            *fuel -= cost;
+        }
+        *stopped_by_debugger = false;
+
+        // remember where the instruction starts, such that a trap can be located within the function
+        *suspended_pc = wasm.pc;
+
         // call the instruction hook
         #[cfg(feature = "hooks")]
-        hooks.instruction_hook(modules[*current_module_idx].wasm_bytecode, wasm.pc);
+        hook_action(
+            hooks.instruction_hook(
+                *current_module_idx,
+                modules[*current_module_idx].wasm_bytecode,
+                wasm.pc,
+            ),
+            hook_yield,
+        )?;
 
         let first_instr_byte = wasm.read_u8().unwrap_validated();
 
@@ -100,8 +196,9 @@ pub(super) fn run<H: HookSet>(
                     continue;
                 }
 
-                let (return_module, maybe_return_address, maybe_return_stp) =
-                    stack.pop_stackframe();
+                #[cfg(feature = "hooks")]
+                hook_action(return_hook(hooks, stack), hook_yield)?;
+                let (maybe_return_address, maybe_return_stp) = stack.pop_stackframe();
 
                 // We finished this entire invocation if there is no stackframe left. If there are
                 // one or more stack frames, we need to continue from where the callee was called
@@ -111,6 +208,7 @@ pub(super) fn run<H: HookSet>(
                 }
 
                 trace!("end of function reached, returning to previous stack frame");
+                let return_module = stack.current_stackframe().module_idx;
                 wasm = &mut modules[return_module].wasm_reader;
                 wasm.pc = maybe_return_address;
                 stp = maybe_return_stp;
@@ -135,10 +233,20 @@ pub(super) fn run<H: HookSet>(
                     stp += 1;
                 } else {
                     do_sidetable_control_transfer(wasm, stack, &mut stp, current_sidetable);
+                    #[cfg(feature = "hooks")]
+                    hook_action(
+                        hooks.branch_hook(*current_module_idx, *suspended_pc, wasm.pc),
+                        hook_yield,
+                    )?;
                 }
             }
             ELSE => {
                 do_sidetable_control_transfer(wasm, stack, &mut stp, current_sidetable);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.branch_hook(*current_module_idx, *suspended_pc, wasm.pc),
+                    hook_yield,
+                )?;
             }
             BR_IF => {
                 wasm.read_var_u32().unwrap_validated();
@@ -147,6 +255,11 @@ pub(super) fn run<H: HookSet>(
 
                 if test_val != 0 {
                     do_sidetable_control_transfer(wasm, stack, &mut stp, current_sidetable);
+                    #[cfg(feature = "hooks")]
+                    hook_action(
+                        hooks.branch_hook(*current_module_idx, *suspended_pc, wasm.pc),
+                        hook_yield,
+                    )?;
                 } else {
                     stp += 1;
                 }
@@ -168,11 +281,21 @@ pub(super) fn run<H: HookSet>(
                 }
 
                 do_sidetable_control_transfer(wasm, stack, &mut stp, current_sidetable);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.branch_hook(*current_module_idx, *suspended_pc, wasm.pc),
+                    hook_yield,
+                )?;
             }
             BR => {
                 //skip n of BR n
                 wasm.read_var_u32().unwrap_validated();
                 do_sidetable_control_transfer(wasm, stack, &mut stp, current_sidetable);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.branch_hook(*current_module_idx, *suspended_pc, wasm.pc),
+                    hook_yield,
+                )?;
             }
             BLOCK | LOOP => {
                 BlockType::read_unvalidated(wasm);
@@ -180,6 +303,11 @@ pub(super) fn run<H: HookSet>(
             RETURN => {
                 //same as BR, except no need to skip n of BR n
                 do_sidetable_control_transfer(wasm, stack, &mut stp, current_sidetable);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.branch_hook(*current_module_idx, *suspended_pc, wasm.pc),
+                    hook_yield,
+                )?;
             }
             CALL => {
                 let func_to_call_idx = wasm.read_var_u32().unwrap_validated() as FuncIdx;
@@ -210,18 +338,53 @@ pub(super) fn run<H: HookSet>(
                             locals,
                             wasm.pc,
                             stp,
-                        );
+                        )?;
 
                         wasm.move_start_to(local_func_inst.code_expr)
                             .unwrap_validated();
 
                         stp = 0;
                         current_sidetable = &local_func_inst.sidetable;
+                        #[cfg(feature = "hooks")]
+                        {
+                            // a trap of the hook is located at the first instruction of the callee
+                            *suspended_pc = wasm.pc;
+                            hook_action(
+                                call_hook(hooks, stack, func_to_call_ty.params.valtypes.len()),
+                                hook_yield,
+                            )?;
+                        }
                     }
                     FuncInst::Imported(_imported_func_inst) => {
-                        let (next_module, next_func_idx) = lut
+                        let (next_module, next_func_idx) = match lut
                             .lookup(*current_module_idx, func_to_call_idx)
-                            .expect("invalid state for lookup");
+                            .expect("invalid state for lookup")
+                        {
+                            FunctionTarget::Module(next_module, next_func_idx) => {
+                                (next_module, next_func_idx)
+                            }
+                            FunctionTarget::Host(host_idx) => {
+                                let params: Vec<Value> = params.collect();
+                                let yielded = call_host_function(
+                                    host_functions,
+                                    host_idx,
+                                    *current_module_idx,
+                                    func_to_call_idx,
+                                    func_to_call_ty,
+                                    params,
+                                    stack,
+                                    hooks,
+                                    hook_yield,
+                                )?;
+
+                                if yielded {
+                                    *suspended_pc = wasm.pc;
+                                    *suspended_stp = stp;
+                                    return Ok(Some(SuspendReason::HostYield));
+                                }
+                                continue;
+                            }
+                        };
 
                         let local_func_inst = modules[next_module].store.funcs[next_func_idx]
                             .try_into_local()
@@ -231,13 +394,13 @@ pub(super) fn run<H: HookSet>(
                         let locals = Locals::new(params, remaining_locals);
 
                         stack.push_stackframe(
-                            *current_module_idx,
-                            func_to_call_idx,
+                            next_module,
+                            next_func_idx,
                             func_to_call_ty,
                             locals,
                             wasm.pc,
                             stp,
-                        );
+                        )?;
 
                         wasm = &mut modules[next_module].wasm_reader;
                         *current_module_idx = next_module;
@@ -247,6 +410,15 @@ pub(super) fn run<H: HookSet>(
 
                         stp = 0;
                         current_sidetable = &local_func_inst.sidetable;
+                        #[cfg(feature = "hooks")]
+                        {
+                            // a trap of the hook is located at the first instruction of the callee
+                            *suspended_pc = wasm.pc;
+                            hook_action(
+                                call_hook(hooks, stack, func_to_call_ty.params.valtypes.len()),
+                                hook_yield,
+                            )?;
+                        }
                     }
                 }
             }
@@ -268,7 +440,9 @@ pub(super) fn run<H: HookSet>(
 
                 let r = tab
                     .elem
+                    .borrow()
                     .get(i as usize)
+                    .copied()
                     .ok_or(RuntimeError::UndefinedTableIndex)
                     .and_then(|r| {
                         if r.is_null() {
@@ -279,19 +453,23 @@ pub(super) fn run<H: HookSet>(
                         }
                     })?;
 
-                let func_addr = match *r {
-                    Ref::Func(func_addr) => func_addr.addr.unwrap_validated(),
-                    Ref::Extern(_) => unreachable!(),
+                // the table may have been populated by another module, so the function is looked up in the module
+                // owning it
+                let (func_module_idx, func_addr) = match r {
+                    Ref::Func(func_addr) => {
+                        (func_addr.module_idx, func_addr.addr.unwrap_validated())
+                    }
+                    Ref::Extern(_) | Ref::Exn(_) => unreachable!(),
                 };
 
-                let func_to_call_inst = modules[*current_module_idx]
+                let func_to_call_inst = modules[func_module_idx]
                     .store
                     .funcs
                     .get(func_addr)
                     .unwrap_validated();
 
                 let actual_type_idx = func_to_call_inst.ty();
-                let actual_ty = modules[*current_module_idx]
+                let actual_ty = modules[func_module_idx]
                     .fn_types
                     .get(actual_type_idx)
                     .unwrap_validated();
@@ -308,24 +486,65 @@ pub(super) fn run<H: HookSet>(
                         trace!("Instruction: call_indirect [{func_addr:?}]");
                         let locals = Locals::new(params, remaining_locals);
                         stack.push_stackframe(
-                            *current_module_idx,
+                            func_module_idx,
                             func_addr,
                             func_ty,
                             locals,
                             wasm.pc,
                             stp,
-                        );
+                        )?;
+
+                        wasm = &mut modules[func_module_idx].wasm_reader;
+                        *current_module_idx = func_module_idx;
 
                         wasm.move_start_to(local_func_inst.code_expr)
                             .unwrap_validated();
 
                         stp = 0;
                         current_sidetable = &local_func_inst.sidetable;
+                        #[cfg(feature = "hooks")]
+                        {
+                            // a trap of the hook is located at the first instruction of the callee
+                            *suspended_pc = wasm.pc;
+                            hook_action(
+                                call_hook(hooks, stack, func_ty.params.valtypes.len()),
+                                hook_yield,
+                            )?;
+                        }
                     }
                     FuncInst::Imported(_imported_func_inst) => {
-                        let (next_module, next_func_idx) = lut
-                            .lookup(*current_module_idx, func_addr)
-                            .expect("invalid state for lookup");
+                        let (next_module, next_func_idx) = match lut
+                            .lookup(func_module_idx, func_addr)
+                            .expect("invalid state for lookup")
+                        {
+                            FunctionTarget::Module(next_module, next_func_idx) => {
+                                (next_module, next_func_idx)
+                            }
+                            FunctionTarget::Host(host_idx) => {
+                                let params: Vec<Value> =
+                                    stack.pop_tail_iter(func_ty.params.valtypes.len()).collect();
+
+                                trace!("Instruction: call_indirect [{func_addr:?}]");
+                                let yielded = call_host_function(
+                                    host_functions,
+                                    host_idx,
+                                    func_module_idx,
+                                    func_addr,
+                                    func_ty,
+                                    params,
+                                    stack,
+                                    hooks,
+                                    hook_yield,
+                                )?;
+
+                                if yielded {
+                                    *suspended_pc = wasm.pc;
+                                    *suspended_stp = stp;
+                                    return Ok(Some(SuspendReason::HostYield));
+                                }
+                                continue;
+                            }
+                        };
 
                         let local_func_inst = modules[next_module].store.funcs[next_func_idx]
                             .try_into_local()
@@ -337,13 +556,13 @@ pub(super) fn run<H: HookSet>(
                         trace!("Instruction: call_indirect [{func_addr:?}]");
                         let locals = Locals::new(params, remaining_locals);
                         stack.push_stackframe(
-                            *current_module_idx,
-                            func_addr,
+                            next_module,
+                            next_func_idx,
                             func_ty,
                             locals,
                             wasm.pc,
                             stp,
-                        );
+                        )?;
 
                         wasm = &mut modules[next_module].wasm_reader;
                         *current_module_idx = next_module;
@@ -353,9 +572,253 @@ pub(super) fn run<H: HookSet>(
 
                         stp = 0;
                         current_sidetable = &local_func_inst.sidetable;
+                        #[cfg(feature = "hooks")]
+                        {
+                            // a trap of the hook is located at the first instruction of the callee
+                            *suspended_pc = wasm.pc;
+                            hook_action(
+                                call_hook(hooks, stack, func_ty.params.valtypes.len()),
+                                hook_yield,
+                            )?;
+                        }
                     }
                 }
             }
+            RETURN_CALL | RETURN_CALL_INDIRECT => {
+                // the callee is determined just like for `call` and `call_indirect`
+                let (func_module_idx, func_addr) = if first_instr_byte == RETURN_CALL {
+                    let func_to_call_idx = wasm.read_var_u32().unwrap_validated() as FuncIdx;
+                    (*current_module_idx, func_to_call_idx)
+                } else {
+                    let given_type_idx = wasm.read_var_u32().unwrap_validated() as TypeIdx;
+                    let table_idx = wasm.read_var_u32().unwrap_validated() as TableIdx;
+
+                    let tab = modules[*current_module_idx]
+                        .store
+                        .tables
+                        .get(table_idx)
+                        .unwrap_validated();
+
+                    let i: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                    let r = tab
+                        .elem
+                        .borrow()
+                        .get(i as usize)
+                        .copied()
+                        .ok_or(RuntimeError::UndefinedTableIndex)
+                        .and_then(|r| {
+                            if r.is_null() {
+                                trace!("table_idx ({table_idx}) --- element index in table ({i})");
+                                Err(RuntimeError::UninitializedElement)
+                            } else {
+                                Ok(r)
+                            }
+                        })?;
+
+                    let (func_module_idx, func_addr) = match r {
+                        Ref::Func(func_addr) => {
+                            (func_addr.module_idx, func_addr.addr.unwrap_validated())
+                        }
+                        Ref::Extern(_) | Ref::Exn(_) => unreachable!(),
+                    };
+
+                    let given_ty = modules[*current_module_idx]
+                        .fn_types
+                        .get(given_type_idx)
+                        .unwrap_validated();
+                    let actual_type_idx = modules[func_module_idx]
+                        .store
+                        .funcs
+                        .get(func_addr)
+                        .unwrap_validated()
+                        .ty();
+                    let actual_ty = modules[func_module_idx]
+                        .fn_types
+                        .get(actual_type_idx)
+                        .unwrap_validated();
+
+                    if given_ty != actual_ty {
+                        return Err(RuntimeError::SignatureMismatch);
+                    }
+
+                    (func_module_idx, func_addr)
+                };
+
+                trace!("Instruction: return_call [{func_addr:?}]");
+
+                let func_to_call_inst = modules[func_module_idx]
+                    .store
+                    .funcs
+                    .get(func_addr)
+                    .unwrap_validated();
+                let func_to_call_ty = modules[func_module_idx]
+                    .fn_types
+                    .get(func_to_call_inst.ty())
+                    .unwrap_validated();
+
+                let (next_module, next_func_idx) = match func_to_call_inst {
+                    FuncInst::Local(_) => (func_module_idx, func_addr),
+                    FuncInst::Imported(_imported_func_inst) => match lut
+                        .lookup(func_module_idx, func_addr)
+                        .expect("invalid state for lookup")
+                    {
+                        FunctionTarget::Module(next_module, next_func_idx) => {
+                            (next_module, next_func_idx)
+                        }
+                        FunctionTarget::Host(host_idx) => {
+                            let params: Vec<Value> = stack
+                                .pop_tail_iter(func_to_call_ty.params.valtypes.len())
+                                .collect();
+                            let yielded = call_host_function(
+                                host_functions,
+                                host_idx,
+                                func_module_idx,
+                                func_addr,
+                                func_to_call_ty,
+                                params,
+                                stack,
+                                hooks,
+                                hook_yield,
+                            )?;
+
+                            // Host functions have no stackframe which could replace the current one. Instead, the
+                            // current function returns the values of the host function by executing its final END.
+                            let current_func_span = modules[*current_module_idx]
+                                .store
+                                .funcs
+                                .get(stack.current_stackframe().func_idx)
+                                .unwrap_validated()
+                                .try_into_local()
+                                .unwrap_validated()
+                                .code_expr;
+                            wasm.pc = current_func_span.from() + current_func_span.len() - 1;
+
+                            if yielded {
+                                *suspended_pc = wasm.pc;
+                                *suspended_stp = stp;
+                                return Ok(Some(SuspendReason::HostYield));
+                            }
+                            continue;
+                        }
+                    },
+                };
+
+                let local_func_inst = modules[next_module].store.funcs[next_func_idx]
+                    .try_into_local()
+                    .unwrap_validated();
+
+                let params = stack.pop_tail_iter(func_to_call_ty.params.valtypes.len());
+                let remaining_locals = local_func_inst.locals.iter().cloned();
+                let locals = Locals::new(params, remaining_locals);
+
+                // the callee reuses the stackframe of the current function, so tail calls do not grow the call stack
+                #[cfg(feature = "hooks")]
+                hook_action(unwind_hook(hooks, stack), hook_yield)?;
+                stack.replace_stackframe(next_module, next_func_idx, locals)?;
+
+                wasm = &mut modules[next_module].wasm_reader;
+                *current_module_idx = next_module;
+
+                wasm.move_start_to(local_func_inst.code_expr)
+                    .unwrap_validated();
+
+                stp = 0;
+                current_sidetable = &local_func_inst.sidetable;
+                #[cfg(feature = "hooks")]
+                {
+                    // a trap of the hook is located at the first instruction of the callee
+                    *suspended_pc = wasm.pc;
+                    hook_action(
+                        call_hook(hooks, stack, func_to_call_ty.params.valtypes.len()),
+                        hook_yield,
+                    )?;
+                }
+            }
+            TRY_TABLE => {
+                // the position of the try_table itself is outside of the instructions covered by its catch clauses
+                let try_table_pc = wasm.pc - 1;
+                let block_ty = BlockType::read_unvalidated(wasm);
+                let param_count = match block_ty {
+                    BlockType::Empty | BlockType::Returns(_) => 0,
+                    BlockType::Type(type_idx) => modules[*current_module_idx].fn_types
+                        [type_idx as TypeIdx]
+                        .params
+                        .valtypes
+                        .len(),
+                };
+
+                // the catch clauses are only skipped, throwing an exception reads them from the sidetable entries
+                let catch_count = wasm.read_var_u32().unwrap_validated() as usize;
+                for _ in 0..catch_count {
+                    CatchClause::read_unvalidated(wasm);
+                }
+
+                // the first sidetable entry of the try_table points behind its END, see the validation of try_table
+                let body_start = wasm.pc;
+                let body_end = (body_start as isize + current_sidetable[stp].delta_pc) as usize;
+                trace!("Instruction: try_table with {catch_count} catch clauses");
+
+                stack.push_handler(
+                    body_start..body_end,
+                    catch_count,
+                    stp + 1,
+                    param_count,
+                    try_table_pc,
+                );
+                stp += catch_count + 1;
+            }
+            THROW | THROW_REF => {
+                // a new exception is only stored once an exnref to it is created
+                let (exn, exn_addr) = if first_instr_byte == THROW {
+                    let tag_idx = wasm.read_var_u32().unwrap_validated() as TagIdx;
+                    let store = &modules[*current_module_idx].store;
+                    let tag = store.tags.get(tag_idx).unwrap_validated().clone();
+                    let payload = stack.pop_tail_iter(tag.ty.params.valtypes.len()).collect();
+                    trace!("Instruction: throw [{tag_idx}]");
+
+                    (ExnInst { tag, payload }, None)
+                } else {
+                    let Ref::Exn(exn_addr) = stack.pop_unknown_ref() else {
+                        unreachable_validated!()
+                    };
+                    trace!("Instruction: throw_ref [{exn_addr:?}]");
+                    if exn_addr.addr.is_none() {
+                        return Err(RuntimeError::NullExceptionReference);
+                    }
+                    let exn = modules[exn_addr.module_idx]
+                        .store
+                        .exception(exn_addr)
+                        .ok_or(RuntimeError::ReclaimedExceptionReference)?
+                        .clone();
+                    (exn, Some(exn_addr))
+                };
+
+                let pc = wasm.pc;
+                let (handler_module, handler_pc, handler_stp) = throw(
+                    modules,
+                    stack,
+                    exn,
+                    exn_addr,
+                    *current_module_idx,
+                    pc,
+                    hooks,
+                    hook_yield,
+                )?;
+
+                wasm = &mut modules[handler_module].wasm_reader;
+                wasm.pc = handler_pc;
+                stp = handler_stp;
+                current_sidetable = &modules[handler_module]
+                    .store
+                    .funcs
+                    .get(stack.current_stackframe().func_idx)
+                    .unwrap_validated()
+                    .try_into_local()
+                    .unwrap_validated()
+                    .sidetable;
+                *current_module_idx = handler_module;
+            }
             DROP => {
                 stack.drop_value();
             }
@@ -364,9 +827,9 @@ pub(super) fn run<H: HookSet>(
                 let val2 = stack.pop_value_with_unknown_type();
                 let val1 = stack.pop_value_with_unknown_type();
                 if test_val != 0 {
-                    stack.push_value(val1);
+                    stack.push_value(val1)?;
                 } else {
-                    stack.push_value(val2);
+                    stack.push_value(val2)?;
                 }
             }
             SELECT_T => {
@@ -375,14 +838,14 @@ pub(super) fn run<H: HookSet>(
                 let val2 = stack.pop_value(type_vec[0]);
                 let val1 = stack.pop_value(type_vec[0]);
                 if test_val != 0 {
-                    stack.push_value(val1);
+                    stack.push_value(val1)?;
                 } else {
-                    stack.push_value(val2);
+                    stack.push_value(val2)?;
                 }
             }
             LOCAL_GET => {
                 let local_idx = wasm.read_var_u32().unwrap_validated() as LocalIdx;
-                stack.get_local(local_idx);
+                stack.get_local(local_idx)?;
                 trace!("Instruction: local.get {} [] -> [t]", local_idx);
             }
             LOCAL_SET => stack.set_local(wasm.read_var_u32().unwrap_validated() as LocalIdx),
@@ -395,17 +858,17 @@ pub(super) fn run<H: HookSet>(
                     .get(global_idx)
                     .unwrap_validated();
 
-                stack.push_value(global.value);
+                stack.push_value(global.value.get())?;
             }
             GLOBAL_SET => {
                 let global_idx = wasm.read_var_u32().unwrap_validated() as GlobalIdx;
                 let global = modules[*current_module_idx]
                     .store
                     .globals
-                    .get_mut(global_idx)
+                    .get(global_idx)
                     .unwrap_validated();
 
-                global.value = stack.pop_value(global.global.ty.ty)
+                global.value.set(stack.pop_value(global.ty.ty))
             }
             TABLE_GET => {
                 let table_idx = wasm.read_var_u32().unwrap_validated() as TableIdx;
@@ -420,10 +883,12 @@ pub(super) fn run<H: HookSet>(
 
                 let val = tab
                     .elem
+                    .borrow()
                     .get(i as usize)
+                    .copied()
                     .ok_or(RuntimeError::TableAccessOutOfBounds)?;
 
-                stack.push_value((*val).into());
+                stack.push_value(val.into())?;
                 trace!(
                     "Instruction: table.get '{}' [{}] -> [{}]",
                     table_idx,
@@ -434,15 +899,21 @@ pub(super) fn run<H: HookSet>(
             TABLE_SET => {
                 let table_idx = wasm.read_var_u32().unwrap_validated() as TableIdx;
 
-                let tab = &mut modules[*current_module_idx].store.tables[table_idx];
+                let tab = &modules[*current_module_idx].store.tables[table_idx];
 
                 let val: Ref = stack.pop_value(ValType::RefType(tab.ty.et)).into();
                 let i: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 tab.elem
+                    .borrow_mut()
                     .get_mut(i as usize)
                     .ok_or(RuntimeError::TableAccessOutOfBounds)
                     .map(|r| *r = val)?;
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.table_hook(*current_module_idx, table_idx, i as usize..i as usize + 1),
+                    hook_yield,
+                )?;
                 trace!(
                     "Instruction: table.set '{}' [{} {}] -> []",
                     table_idx,
@@ -452,241 +923,420 @@ pub(super) fn run<H: HookSet>(
             }
             I32_LOAD => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem_inst = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem_inst.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data = mem_inst.mem.load(idx)?;
 
-                stack.push_value(Value::I32(data));
+                let value = Value::I32(data);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: i32.load [{relative_address}] -> [{data}]");
             }
             I64_LOAD => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
+                    .get(memarg.memory)
                     .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data = mem.mem.load(idx)?;
 
-                stack.push_value(Value::I64(data));
+                let value = Value::I64(data);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: i64.load [{relative_address}] -> [{data}]");
             }
             F32_LOAD => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data = mem.mem.load(idx)?;
 
-                stack.push_value(Value::F32(value::F32(data)));
+                let value = Value::F32(value::F32(data));
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: f32.load [{relative_address}] -> [{data}]");
             }
             F64_LOAD => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
+                    .get(memarg.memory)
                     .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data = mem.mem.load(idx)?;
 
-                stack.push_value(Value::F64(value::F64(data)));
+                let value = Value::F64(value::F64(data));
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: f64.load [{relative_address}] -> [{data}]");
             }
             I32_LOAD8_S => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data: i8 = mem.mem.load(idx)?;
 
-                stack.push_value(Value::I32(data as u32));
+                let value = Value::I32(data as u32);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: i32.load8_s [{relative_address}] -> [{data}]");
             }
             I32_LOAD8_U => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data: u8 = mem.mem.load(idx)?;
 
-                stack.push_value(Value::I32(data as u32));
+                let value = Value::I32(data as u32);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: i32.load8_u [{relative_address}] -> [{data}]");
             }
             I32_LOAD16_S => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data: i16 = mem.mem.load(idx)?;
 
-                stack.push_value(Value::I32(data as u32));
+                let value = Value::I32(data as u32);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: i32.load16_s [{relative_address}] -> [{data}]");
             }
             I32_LOAD16_U => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data: u16 = mem.mem.load(idx)?;
 
-                stack.push_value(Value::I32(data as u32));
+                let value = Value::I32(data as u32);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: i32.load16_u [{relative_address}] -> [{data}]");
             }
             I64_LOAD8_S => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data: i8 = mem.mem.load(idx)?;
 
-                stack.push_value(Value::I64(data as u64));
+                let value = Value::I64(data as u64);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: i64.load8_s [{relative_address}] -> [{data}]");
             }
             I64_LOAD8_U => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data: u8 = mem.mem.load(idx)?;
 
-                stack.push_value(Value::I64(data as u64));
+                let value = Value::I64(data as u64);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: i64.load8_u [{relative_address}] -> [{data}]");
             }
             I64_LOAD16_S => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data: i16 = mem.mem.load(idx)?;
 
-                stack.push_value(Value::I64(data as u64));
+                let value = Value::I64(data as u64);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: i64.load16_s [{relative_address}] -> [{data}]");
             }
             I64_LOAD16_U => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data: u16 = mem.mem.load(idx)?;
 
-                stack.push_value(Value::I64(data as u64));
+                let value = Value::I64(data as u64);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: i64.load16_u [{relative_address}] -> [{data}]");
             }
             I64_LOAD32_S => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data: i32 = mem.mem.load(idx)?;
 
-                stack.push_value(Value::I64(data as u64));
+                let value = Value::I64(data as u64);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: i64.load32_s [{relative_address}] -> [{data}]");
             }
             I64_LOAD32_U => {
                 let memarg = MemArg::read_unvalidated(wasm);
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .first()
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
                 let data: u32 = mem.mem.load(idx)?;
 
-                stack.push_value(Value::I64(data as u64));
+                let value = Value::I64(data as u64);
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.load_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        core::mem::size_of_val(&data),
+                        value,
+                    ),
+                    hook_yield,
+                )?;
+                stack.push_value(value)?;
                 trace!("Instruction: i64.load32_u [{relative_address}] -> [{data}]");
             }
             I32_STORE => {
                 let memarg = MemArg::read_unvalidated(wasm);
 
                 let data_to_store: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .get_mut(0)
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.store_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        4,
+                        data_to_store.into(),
+                    ),
+                    hook_yield,
+                )?;
                 mem.mem.store(idx, data_to_store)?;
 
                 trace!("Instruction: i32.store [{relative_address} {data_to_store}] -> []");
@@ -695,15 +1345,26 @@ pub(super) fn run<H: HookSet>(
                 let memarg = MemArg::read_unvalidated(wasm);
 
                 let data_to_store: u64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .get_mut(0)
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.store_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        8,
+                        data_to_store.into(),
+                    ),
+                    hook_yield,
+                )?;
                 mem.mem.store(idx, data_to_store)?;
 
                 trace!("Instruction: i64.store [{relative_address} {data_to_store}] -> []");
@@ -712,15 +1373,26 @@ pub(super) fn run<H: HookSet>(
                 let memarg = MemArg::read_unvalidated(wasm);
 
                 let data_to_store: f32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .get_mut(0)
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.store_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        4,
+                        data_to_store.into(),
+                    ),
+                    hook_yield,
+                )?;
                 mem.mem.store(idx, data_to_store)?;
 
                 trace!("Instruction: f32.store [{relative_address} {data_to_store}] -> []");
@@ -729,15 +1401,26 @@ pub(super) fn run<H: HookSet>(
                 let memarg = MemArg::read_unvalidated(wasm);
 
                 let data_to_store: f64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .get_mut(0)
-                    .unwrap_validated(); // there is only one memory allowed as of now
+                    .get(memarg.memory)
+                    .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.store_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        8,
+                        data_to_store.into(),
+                    ),
+                    hook_yield,
+                )?;
                 mem.mem.store(idx, data_to_store)?;
 
                 trace!("Instruction: f64.store [{relative_address} {data_to_store}] -> []");
@@ -746,15 +1429,26 @@ pub(super) fn run<H: HookSet>(
                 let memarg = MemArg::read_unvalidated(wasm);
 
                 let data_to_store: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .get_mut(0)
+                    .get(memarg.memory)
                     .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.store_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        1,
+                        data_to_store.into(),
+                    ),
+                    hook_yield,
+                )?;
                 mem.mem.store(idx, data_to_store)?;
 
                 trace!("Instruction: i32.store8 [{relative_address} {data_to_store}] -> []");
@@ -763,15 +1457,26 @@ pub(super) fn run<H: HookSet>(
                 let memarg = MemArg::read_unvalidated(wasm);
 
                 let data_to_store: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .get_mut(0)
+                    .get(memarg.memory)
                     .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.store_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        2,
+                        data_to_store.into(),
+                    ),
+                    hook_yield,
+                )?;
                 mem.mem.store(idx, data_to_store)?;
 
                 trace!("Instruction: i32.store16 [{relative_address} {data_to_store}] -> []");
@@ -780,15 +1485,26 @@ pub(super) fn run<H: HookSet>(
                 let memarg = MemArg::read_unvalidated(wasm);
 
                 let data_to_store: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .get_mut(0)
+                    .get(memarg.memory)
                     .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.store_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        1,
+                        data_to_store.into(),
+                    ),
+                    hook_yield,
+                )?;
                 mem.mem.store(idx, data_to_store)?;
 
                 trace!("Instruction: i64.store8 [{relative_address} {data_to_store}] -> []");
@@ -797,15 +1513,26 @@ pub(super) fn run<H: HookSet>(
                 let memarg = MemArg::read_unvalidated(wasm);
 
                 let data_to_store: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .get_mut(0)
+                    .get(memarg.memory)
                     .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.store_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        2,
+                        data_to_store.into(),
+                    ),
+                    hook_yield,
+                )?;
                 mem.mem.store(idx, data_to_store)?;
 
                 trace!("Instruction: i64.store16 [{relative_address} {data_to_store}] -> []");
@@ -814,60 +1541,85 @@ pub(super) fn run<H: HookSet>(
                 let memarg = MemArg::read_unvalidated(wasm);
 
                 let data_to_store: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
-                let relative_address: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .get_mut(0)
+                    .get(memarg.memory)
                     .unwrap_validated();
+                let relative_address = pop_address(stack, mem.ty.addr_type);
 
                 let idx = get_store_index(&memarg, relative_address)?;
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.store_hook(
+                        *current_module_idx,
+                        memarg.memory,
+                        idx,
+                        4,
+                        data_to_store.into(),
+                    ),
+                    hook_yield,
+                )?;
                 mem.mem.store(idx, data_to_store)?;
 
                 trace!("Instruction: i64.store32 [{relative_address} {data_to_store}] -> []");
             }
             MEMORY_SIZE => {
-                let mem_idx = wasm.read_u8().unwrap_validated() as usize;
+                let mem_idx = wasm.read_var_u32().unwrap_validated() as usize;
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
                     .get(mem_idx)
                     .unwrap_validated();
-                let size = mem.size() as u32;
-                stack.push_value(Value::I32(size));
+                let size = mem.size() as u64;
+                stack.push_value(address_value(size, mem.ty.addr_type))?;
                 trace!("Instruction: memory.size [] -> [{}]", size);
             }
             MEMORY_GROW => {
-                let mem_idx = wasm.read_u8().unwrap_validated() as usize;
+                let mem_idx = wasm.read_var_u32().unwrap_validated() as usize;
                 let mem = modules[*current_module_idx]
                     .store
                     .mems
-                    .get_mut(mem_idx)
+                    .get(mem_idx)
                     .unwrap_validated();
-                let delta: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-
-                let upper_limit = mem.ty.limits.max.unwrap_or(Limits::MAX_MEM_BYTES);
-                let pushed_value = if delta < 0 || delta as u32 + mem.size() as u32 > upper_limit {
-                    stack.push_value((-1).into());
-                    -1
-                } else {
-                    let previous_size: i32 = mem.size() as i32;
-                    mem.grow(delta as usize);
-                    stack.push_value(previous_size.into());
-                    previous_size
-                };
-                trace!("Instruction: memory.grow [{}] -> [{}]", delta, pushed_value);
+                let delta = pop_address(stack, mem.ty.addr_type);
+
+                let upper_limit = mem.ty.limits.max.unwrap_or(mem.ty.addr_type.max_pages());
+                let previous_size = mem.size() as u64;
+                // Growing also fails if the host can not provide the memory
+                let grown = delta <= upper_limit.saturating_sub(previous_size)
+                    && usize::try_from(delta).is_ok_and(|delta| mem.grow(delta).is_ok());
+
+                #[cfg(feature = "hooks")]
+                hook_action(
+                    hooks.memory_grow_hook(
+                        *current_module_idx,
+                        mem_idx,
+                        previous_size as usize,
+                        mem.size(),
+                    ),
+                    hook_yield,
+                )?;
+
+                // -1 signals failure, regardless of the address type
+                let pushed_value = if grown { previous_size } else { u64::MAX };
+                stack.push_value(address_value(pushed_value, mem.ty.addr_type))?;
+                trace!(
+                    "Instruction: memory.grow [{}] -> [{}]",
+                    delta,
+                    pushed_value as i64
+                );
             }
             I32_CONST => {
                 let constant = wasm.read_var_i32().unwrap_validated();
                 trace!("Instruction: i32.const [] -> [{constant}]");
-                stack.push_value(constant.into());
+                stack.push_value(constant.into())?;
             }
             F32_CONST => {
                 let constant = f32::from_bits(wasm.read_var_f32().unwrap_validated());
                 trace!("Instruction: f32.const [] -> [{constant:.7}]");
-                stack.push_value(constant.into());
+                stack.push_value(constant.into())?;
             }
             I32_EQZ => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -875,7 +1627,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 == 0 { 1 } else { 0 };
 
                 trace!("Instruction: i32.eqz [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_EQ => {
                 let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -884,7 +1636,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 == v2 { 1 } else { 0 };
 
                 trace!("Instruction: i32.eq [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_NE => {
                 let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -893,7 +1645,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 != v2 { 1 } else { 0 };
 
                 trace!("Instruction: i32.ne [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_LT_S => {
                 let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -902,7 +1654,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 < v2 { 1 } else { 0 };
 
                 trace!("Instruction: i32.lt_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
 
             I32_LT_U => {
@@ -912,7 +1664,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if (v1 as u32) < (v2 as u32) { 1 } else { 0 };
 
                 trace!("Instruction: i32.lt_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_GT_S => {
                 let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -921,7 +1673,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 > v2 { 1 } else { 0 };
 
                 trace!("Instruction: i32.gt_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_GT_U => {
                 let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -930,7 +1682,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if (v1 as u32) > (v2 as u32) { 1 } else { 0 };
 
                 trace!("Instruction: i32.gt_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_LE_S => {
                 let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -939,7 +1691,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 <= v2 { 1 } else { 0 };
 
                 trace!("Instruction: i32.le_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_LE_U => {
                 let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -948,7 +1700,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if (v1 as u32) <= (v2 as u32) { 1 } else { 0 };
 
                 trace!("Instruction: i32.le_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_GE_S => {
                 let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -957,7 +1709,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 >= v2 { 1 } else { 0 };
 
                 trace!("Instruction: i32.ge_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_GE_U => {
                 let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -966,7 +1718,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if (v1 as u32) >= (v2 as u32) { 1 } else { 0 };
 
                 trace!("Instruction: i32.ge_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_EQZ => {
                 let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -974,7 +1726,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 == 0 { 1 } else { 0 };
 
                 trace!("Instruction: i64.eqz [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_EQ => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -983,7 +1735,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 == v2 { 1 } else { 0 };
 
                 trace!("Instruction: i64.eq [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_NE => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -992,7 +1744,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 != v2 { 1 } else { 0 };
 
                 trace!("Instruction: i64.ne [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_LT_S => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1001,7 +1753,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 < v2 { 1 } else { 0 };
 
                 trace!("Instruction: i64.lt_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
 
             I64_LT_U => {
@@ -1011,7 +1763,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if (v1 as u64) < (v2 as u64) { 1 } else { 0 };
 
                 trace!("Instruction: i64.lt_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_GT_S => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1020,7 +1772,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 > v2 { 1 } else { 0 };
 
                 trace!("Instruction: i64.gt_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_GT_U => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1029,7 +1781,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if (v1 as u64) > (v2 as u64) { 1 } else { 0 };
 
                 trace!("Instruction: i64.gt_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_LE_S => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1038,7 +1790,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 <= v2 { 1 } else { 0 };
 
                 trace!("Instruction: i64.le_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_LE_U => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1047,7 +1799,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if (v1 as u64) <= (v2 as u64) { 1 } else { 0 };
 
                 trace!("Instruction: i64.le_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_GE_S => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1056,7 +1808,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 >= v2 { 1 } else { 0 };
 
                 trace!("Instruction: i64.ge_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_GE_U => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1065,7 +1817,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if (v1 as u64) >= (v2 as u64) { 1 } else { 0 };
 
                 trace!("Instruction: i64.ge_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_EQ => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1074,7 +1826,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 == v2 { 1 } else { 0 };
 
                 trace!("Instruction: f32.eq [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_NE => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1083,7 +1835,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 != v2 { 1 } else { 0 };
 
                 trace!("Instruction: f32.ne [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_LT => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1092,7 +1844,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 < v2 { 1 } else { 0 };
 
                 trace!("Instruction: f32.lt [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_GT => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1101,7 +1853,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 > v2 { 1 } else { 0 };
 
                 trace!("Instruction: f32.gt [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_LE => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1110,7 +1862,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 <= v2 { 1 } else { 0 };
 
                 trace!("Instruction: f32.le [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_GE => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1119,7 +1871,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 >= v2 { 1 } else { 0 };
 
                 trace!("Instruction: f32.ge [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
 
             F64_EQ => {
@@ -1129,7 +1881,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 == v2 { 1 } else { 0 };
 
                 trace!("Instruction: f64.eq [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_NE => {
                 let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1138,7 +1890,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 != v2 { 1 } else { 0 };
 
                 trace!("Instruction: f64.ne [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_LT => {
                 let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1147,7 +1899,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 < v2 { 1 } else { 0 };
 
                 trace!("Instruction: f64.lt [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_GT => {
                 let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1156,7 +1908,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 > v2 { 1 } else { 0 };
 
                 trace!("Instruction: f64.gt [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_LE => {
                 let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1165,7 +1917,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 <= v2 { 1 } else { 0 };
 
                 trace!("Instruction: f64.le [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_GE => {
                 let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1174,7 +1926,7 @@ pub(super) fn run<H: HookSet>(
                 let res = if v1 >= v2 { 1 } else { 0 };
 
                 trace!("Instruction: f64.ge [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
 
             I32_CLZ => {
@@ -1182,31 +1934,31 @@ pub(super) fn run<H: HookSet>(
                 let res = v1.leading_zeros() as i32;
 
                 trace!("Instruction: i32.clz [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_CTZ => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
                 let res = v1.trailing_zeros() as i32;
 
                 trace!("Instruction: i32.ctz [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_POPCNT => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
                 let res = v1.count_ones() as i32;
 
                 trace!("Instruction: i32.popcnt [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_CONST => {
                 let constant = wasm.read_var_i64().unwrap_validated();
                 trace!("Instruction: i64.const [] -> [{constant}]");
-                stack.push_value(constant.into());
+                stack.push_value(constant.into())?;
             }
             F64_CONST => {
                 let constant = f64::from_bits(wasm.read_var_f64().unwrap_validated());
                 trace!("Instruction: f64.const [] -> [{constant}]");
-                stack.push_value(constant.into());
+                stack.push_value(constant.into())?;
             }
             I32_ADD => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1214,7 +1966,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1.wrapping_add(v2);
 
                 trace!("Instruction: i32.add [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_SUB => {
                 let v2: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1222,7 +1974,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1.wrapping_sub(v2);
 
                 trace!("Instruction: i32.sub [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_MUL => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1230,7 +1982,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1.wrapping_mul(v2);
 
                 trace!("Instruction: i32.mul [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_DIV_S => {
                 let dividend: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1246,7 +1998,7 @@ pub(super) fn run<H: HookSet>(
                 let res = divisor / dividend;
 
                 trace!("Instruction: i32.div_s [{divisor} {dividend}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_DIV_U => {
                 let dividend: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1262,7 +2014,7 @@ pub(super) fn run<H: HookSet>(
                 let res = (divisor / dividend) as i32;
 
                 trace!("Instruction: i32.div_u [{divisor} {dividend}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_REM_S => {
                 let dividend: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1276,28 +2028,28 @@ pub(super) fn run<H: HookSet>(
                 let res = res.unwrap_or_default();
 
                 trace!("Instruction: i32.rem_s [{divisor} {dividend}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_CLZ => {
                 let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
                 let res = v1.leading_zeros() as i64;
 
                 trace!("Instruction: i64.clz [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_CTZ => {
                 let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
                 let res = v1.trailing_zeros() as i64;
 
                 trace!("Instruction: i64.ctz [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_POPCNT => {
                 let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
                 let res = v1.count_ones() as i64;
 
                 trace!("Instruction: i64.popcnt [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_ADD => {
                 let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1305,7 +2057,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1.wrapping_add(v2);
 
                 trace!("Instruction: i64.add [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_SUB => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1313,7 +2065,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1.wrapping_sub(v2);
 
                 trace!("Instruction: i64.sub [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_MUL => {
                 let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1321,7 +2073,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1.wrapping_mul(v2);
 
                 trace!("Instruction: i64.mul [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_DIV_S => {
                 let dividend: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1337,7 +2089,7 @@ pub(super) fn run<H: HookSet>(
                 let res = divisor / dividend;
 
                 trace!("Instruction: i64.div_s [{divisor} {dividend}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_DIV_U => {
                 let dividend: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1353,7 +2105,7 @@ pub(super) fn run<H: HookSet>(
                 let res = (divisor / dividend) as i64;
 
                 trace!("Instruction: i64.div_u [{divisor} {dividend}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_REM_S => {
                 let dividend: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1367,7 +2119,7 @@ pub(super) fn run<H: HookSet>(
                 let res = res.unwrap_or_default();
 
                 trace!("Instruction: i64.rem_s [{divisor} {dividend}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_REM_U => {
                 let dividend: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1383,7 +2135,7 @@ pub(super) fn run<H: HookSet>(
                 let res = (divisor % dividend) as i64;
 
                 trace!("Instruction: i64.rem_u [{divisor} {dividend}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_AND => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1392,7 +2144,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1 & v2;
 
                 trace!("Instruction: i64.and [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_OR => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1401,7 +2153,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1 | v2;
 
                 trace!("Instruction: i64.or [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_XOR => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1410,7 +2162,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1 ^ v2;
 
                 trace!("Instruction: i64.xor [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_SHL => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1419,7 +2171,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1.wrapping_shl((v2 & 63) as u32);
 
                 trace!("Instruction: i64.shl [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_SHR_S => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1428,7 +2180,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1.wrapping_shr((v2 & 63) as u32);
 
                 trace!("Instruction: i64.shr_s [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_SHR_U => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1437,7 +2189,7 @@ pub(super) fn run<H: HookSet>(
                 let res = (v1 as u64).wrapping_shr((v2 & 63) as u32);
 
                 trace!("Instruction: i64.shr_u [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_ROTL => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1446,7 +2198,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1.rotate_left((v2 & 63) as u32);
 
                 trace!("Instruction: i64.rotl [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_ROTR => {
                 let v2: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
@@ -1455,7 +2207,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1.rotate_right((v2 & 63) as u32);
 
                 trace!("Instruction: i64.rotr [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_REM_U => {
                 let dividend: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1472,7 +2224,7 @@ pub(super) fn run<H: HookSet>(
                 let res = res.unwrap_or_default() as i32;
 
                 trace!("Instruction: i32.rem_u [{divisor} {dividend}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_AND => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1480,7 +2232,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1 & v2;
 
                 trace!("Instruction: i32.and [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_OR => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1488,7 +2240,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1 | v2;
 
                 trace!("Instruction: i32.or [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_XOR => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1496,7 +2248,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v1 ^ v2;
 
                 trace!("Instruction: i32.xor [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_SHL => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1504,7 +2256,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v2.wrapping_shl(v1 as u32);
 
                 trace!("Instruction: i32.shl [{v2} {v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_SHR_S => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1513,7 +2265,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v2.wrapping_shr(v1 as u32);
 
                 trace!("Instruction: i32.shr_s [{v2} {v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_SHR_U => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1522,7 +2274,7 @@ pub(super) fn run<H: HookSet>(
                 let res = (v2 as u32).wrapping_shr(v1 as u32) as i32;
 
                 trace!("Instruction: i32.shr_u [{v2} {v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_ROTL => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1531,7 +2283,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v2.rotate_left(v1 as u32);
 
                 trace!("Instruction: i32.rotl [{v2} {v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_ROTR => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
@@ -1540,7 +2292,7 @@ pub(super) fn run<H: HookSet>(
                 let res = v2.rotate_right(v1 as u32);
 
                 trace!("Instruction: i32.rotr [{v2} {v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
 
             F32_ABS => {
@@ -1548,49 +2300,49 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F32 = v1.abs();
 
                 trace!("Instruction: f32.abs [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_NEG => {
                 let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
                 let res: value::F32 = v1.neg();
 
                 trace!("Instruction: f32.neg [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_CEIL => {
                 let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
                 let res: value::F32 = v1.ceil();
 
                 trace!("Instruction: f32.ceil [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_FLOOR => {
                 let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
                 let res: value::F32 = v1.floor();
 
                 trace!("Instruction: f32.floor [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_TRUNC => {
                 let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
                 let res: value::F32 = v1.trunc();
 
                 trace!("Instruction: f32.trunc [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_NEAREST => {
                 let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
                 let res: value::F32 = v1.nearest();
 
                 trace!("Instruction: f32.nearest [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_SQRT => {
                 let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
                 let res: value::F32 = v1.sqrt();
 
                 trace!("Instruction: f32.sqrt [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_ADD => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1598,7 +2350,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F32 = v1 + v2;
 
                 trace!("Instruction: f32.add [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_SUB => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1606,7 +2358,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F32 = v1 - v2;
 
                 trace!("Instruction: f32.sub [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_MUL => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1614,7 +2366,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F32 = v1 * v2;
 
                 trace!("Instruction: f32.mul [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_DIV => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1622,7 +2374,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F32 = v1 / v2;
 
                 trace!("Instruction: f32.div [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_MIN => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1630,7 +2382,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F32 = v1.min(v2);
 
                 trace!("Instruction: f32.min [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_MAX => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1638,7 +2390,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F32 = v1.max(v2);
 
                 trace!("Instruction: f32.max [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_COPYSIGN => {
                 let v2: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1646,7 +2398,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F32 = v1.copysign(v2);
 
                 trace!("Instruction: f32.copysign [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
 
             F64_ABS => {
@@ -1654,49 +2406,49 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F64 = v1.abs();
 
                 trace!("Instruction: f64.abs [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_NEG => {
                 let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
                 let res: value::F64 = v1.neg();
 
                 trace!("Instruction: f64.neg [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_CEIL => {
                 let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
                 let res: value::F64 = v1.ceil();
 
                 trace!("Instruction: f64.ceil [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_FLOOR => {
                 let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
                 let res: value::F64 = v1.floor();
 
                 trace!("Instruction: f64.floor [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_TRUNC => {
                 let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
                 let res: value::F64 = v1.trunc();
 
                 trace!("Instruction: f64.trunc [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_NEAREST => {
                 let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
                 let res: value::F64 = v1.nearest();
 
                 trace!("Instruction: f64.nearest [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_SQRT => {
                 let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
                 let res: value::F64 = v1.sqrt();
 
                 trace!("Instruction: f64.sqrt [{v1}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_ADD => {
                 let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1704,7 +2456,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F64 = v1 + v2;
 
                 trace!("Instruction: f64.add [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_SUB => {
                 let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1712,7 +2464,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F64 = v1 - v2;
 
                 trace!("Instruction: f64.sub [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_MUL => {
                 let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1720,7 +2472,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F64 = v1 * v2;
 
                 trace!("Instruction: f64.mul [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_DIV => {
                 let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1728,7 +2480,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F64 = v1 / v2;
 
                 trace!("Instruction: f64.div [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_MIN => {
                 let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1736,7 +2488,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F64 = v1.min(v2);
 
                 trace!("Instruction: f64.min [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_MAX => {
                 let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1744,7 +2496,7 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F64 = v1.max(v2);
 
                 trace!("Instruction: f64.max [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_COPYSIGN => {
                 let v2: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1752,14 +2504,14 @@ pub(super) fn run<H: HookSet>(
                 let res: value::F64 = v1.copysign(v2);
 
                 trace!("Instruction: f64.copysign [{v1} {v2}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_WRAP_I64 => {
                 let v: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
                 let res: i32 = v as i32;
 
                 trace!("Instruction: i32.wrap_i64 [{v}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_TRUNC_F32_S => {
                 let v: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1776,7 +2528,7 @@ pub(super) fn run<H: HookSet>(
                 let res: i32 = v.as_i32();
 
                 trace!("Instruction: i32.trunc_f32_s [{v:.7}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_TRUNC_F32_U => {
                 let v: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1793,7 +2545,7 @@ pub(super) fn run<H: HookSet>(
                 let res: i32 = v.as_u32() as i32;
 
                 trace!("Instruction: i32.trunc_f32_u [{v:.7}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
 
             I32_TRUNC_F64_S => {
@@ -1811,7 +2563,7 @@ pub(super) fn run<H: HookSet>(
                 let res: i32 = v.as_i32();
 
                 trace!("Instruction: i32.trunc_f64_s [{v:.7}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_TRUNC_F64_U => {
                 let v: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1828,7 +2580,7 @@ pub(super) fn run<H: HookSet>(
                 let res: i32 = v.as_u32() as i32;
 
                 trace!("Instruction: i32.trunc_f32_u [{v:.7}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
 
             I64_EXTEND_I32_S => {
@@ -1837,7 +2589,7 @@ pub(super) fn run<H: HookSet>(
                 let res: i64 = v as i64;
 
                 trace!("Instruction: i64.extend_i32_s [{v}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
 
             I64_EXTEND_I32_U => {
@@ -1846,7 +2598,7 @@ pub(super) fn run<H: HookSet>(
                 let res: i64 = v as u32 as i64;
 
                 trace!("Instruction: i64.extend_i32_u [{v}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
 
             I64_TRUNC_F32_S => {
@@ -1865,7 +2617,7 @@ pub(super) fn run<H: HookSet>(
                 let res: i64 = v.as_i64();
 
                 trace!("Instruction: i64.trunc_f32_s [{v:.7}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_TRUNC_F32_U => {
                 let v: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -1882,7 +2634,7 @@ pub(super) fn run<H: HookSet>(
                 let res: i64 = v.as_u64() as i64;
 
                 trace!("Instruction: i64.trunc_f32_u [{v:.7}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
 
             I64_TRUNC_F64_S => {
@@ -1901,7 +2653,7 @@ pub(super) fn run<H: HookSet>(
                 let res: i64 = v.as_i64();
 
                 trace!("Instruction: i64.trunc_f64_s [{v:.17}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_TRUNC_F64_U => {
                 let v: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -1918,110 +2670,110 @@ pub(super) fn run<H: HookSet>(
                 let res: i64 = v.as_u64() as i64;
 
                 trace!("Instruction: i64.trunc_f64_u [{v:.17}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_CONVERT_I32_S => {
                 let v: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
                 let res: value::F32 = value::F32(v as f32);
 
                 trace!("Instruction: f32.convert_i32_s [{v}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_CONVERT_I32_U => {
                 let v: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
                 let res: value::F32 = value::F32(v as u32 as f32);
 
                 trace!("Instruction: f32.convert_i32_u [{v}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_CONVERT_I64_S => {
                 let v: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
                 let res: value::F32 = value::F32(v as f32);
 
                 trace!("Instruction: f32.convert_i64_s [{v}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_CONVERT_I64_U => {
                 let v: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
                 let res: value::F32 = value::F32(v as u64 as f32);
 
                 trace!("Instruction: f32.convert_i64_u [{v}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_DEMOTE_F64 => {
                 let v: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
                 let res: value::F32 = v.as_f32();
 
                 trace!("Instruction: f32.demote_f64 [{v:.17}] -> [{res:.7}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_CONVERT_I32_S => {
                 let v: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
                 let res: value::F64 = value::F64(v as f64);
 
                 trace!("Instruction: f64.convert_i32_s [{v}] -> [{res:.17}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_CONVERT_I32_U => {
                 let v: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
                 let res: value::F64 = value::F64(v as u32 as f64);
 
                 trace!("Instruction: f64.convert_i32_u [{v}] -> [{res:.17}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_CONVERT_I64_S => {
                 let v: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
                 let res: value::F64 = value::F64(v as f64);
 
                 trace!("Instruction: f64.convert_i64_s [{v}] -> [{res:.17}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_CONVERT_I64_U => {
                 let v: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
                 let res: value::F64 = value::F64(v as u64 as f64);
 
                 trace!("Instruction: f64.convert_i64_u [{v}] -> [{res:.17}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_PROMOTE_F32 => {
                 let v: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
                 let res: value::F64 = v.as_f32();
 
                 trace!("Instruction: f64.promote_f32 [{v:.7}] -> [{res:.17}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I32_REINTERPRET_F32 => {
                 let v: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
                 let res: i32 = v.reinterpret_as_i32();
 
                 trace!("Instruction: i32.reinterpret_f32 [{v:.7}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             I64_REINTERPRET_F64 => {
                 let v: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
                 let res: i64 = v.reinterpret_as_i64();
 
                 trace!("Instruction: i64.reinterpret_f64 [{v:.17}] -> [{res}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F32_REINTERPRET_I32 => {
                 let v1: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
                 let res: value::F32 = value::F32::from_bits(v1 as u32);
 
                 trace!("Instruction: f32.reinterpret_i32 [{v1}] -> [{res:.7}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             F64_REINTERPRET_I64 => {
                 let v1: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
                 let res: value::F64 = value::F64::from_bits(v1 as u64);
 
                 trace!("Instruction: f64.reinterpret_i64 [{v1}] -> [{res:.17}]");
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
             }
             REF_NULL => {
                 let reftype = RefType::read_unvalidated(wasm);
 
-                stack.push_value(Value::Ref(reftype.to_null_ref()));
+                stack.push_value(Value::Ref(reftype.to_null_ref()))?;
                 trace!("Instruction: ref.null '{:?}' -> [{:?}]", reftype, reftype);
             }
             REF_IS_NULL => {
@@ -2029,20 +2781,25 @@ pub(super) fn run<H: HookSet>(
                 let is_null = match rref {
                     Ref::Extern(rref) => rref.addr.is_none(),
                     Ref::Func(rref) => rref.addr.is_none(),
+                    Ref::Exn(rref) => rref.addr.is_none(),
                 };
 
                 let res = if is_null { 1 } else { 0 };
                 trace!("Instruction: ref.is_null [{}] -> [{}]", rref, res);
-                stack.push_value(Value::I32(res));
+                stack.push_value(Value::I32(res))?;
             }
             // https://webassembly.github.io/spec/core/exec/instructions.html#xref-syntax-instructions-syntax-instr-ref-mathsf-ref-func-x
             REF_FUNC => {
                 let func_idx = wasm.read_var_u32().unwrap_validated() as FuncIdx;
-                stack.push_value(Value::Ref(Ref::Func(FuncAddr::new(Some(func_idx)))));
+                stack.push_value(Value::Ref(Ref::Func(FuncAddr::new_in_module(
+                    *current_module_idx,
+                    Some(func_idx),
+                ))))?;
             }
             FC_EXTENSIONS => {
                 // Should we call instruction hook here as well? Multibyte instruction
-                let second_instr_byte = wasm.read_u8().unwrap_validated();
+                // the validator only accepts sub-opcodes which fit into a single byte
+                let second_instr_byte = wasm.read_var_u32().unwrap_validated() as u8;
 
                 use crate::core::reader::types::opcode::fc_extensions::*;
                 match second_instr_byte {
@@ -2061,7 +2818,7 @@ pub(super) fn run<H: HookSet>(
                         };
 
                         trace!("Instruction: i32.trunc_sat_f32_s [{v1}] -> [{res}]");
-                        stack.push_value(res.into());
+                        stack.push_value(res.into())?;
                     }
                     I32_TRUNC_SAT_F32_U => {
                         let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -2076,7 +2833,7 @@ pub(super) fn run<H: HookSet>(
                         };
 
                         trace!("Instruction: i32.trunc_sat_f32_u [{v1}] -> [{res}]");
-                        stack.push_value(res.into());
+                        stack.push_value(res.into())?;
                     }
                     I32_TRUNC_SAT_F64_S => {
                         let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -2093,7 +2850,7 @@ pub(super) fn run<H: HookSet>(
                         };
 
                         trace!("Instruction: i32.trunc_sat_f64_s [{v1}] -> [{res}]");
-                        stack.push_value(res.into());
+                        stack.push_value(res.into())?;
                     }
                     I32_TRUNC_SAT_F64_U => {
                         let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -2108,7 +2865,7 @@ pub(super) fn run<H: HookSet>(
                         };
 
                         trace!("Instruction: i32.trunc_sat_f64_u [{v1}] -> [{res}]");
-                        stack.push_value(res.into());
+                        stack.push_value(res.into())?;
                     }
                     I64_TRUNC_SAT_F32_S => {
                         let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -2125,7 +2882,7 @@ pub(super) fn run<H: HookSet>(
                         };
 
                         trace!("Instruction: i64.trunc_sat_f32_s [{v1}] -> [{res}]");
-                        stack.push_value(res.into());
+                        stack.push_value(res.into())?;
                     }
                     I64_TRUNC_SAT_F32_U => {
                         let v1: value::F32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
@@ -2140,7 +2897,7 @@ pub(super) fn run<H: HookSet>(
                         };
 
                         trace!("Instruction: i64.trunc_sat_f32_u [{v1}] -> [{res}]");
-                        stack.push_value(res.into());
+                        stack.push_value(res.into())?;
                     }
                     I64_TRUNC_SAT_F64_S => {
                         let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -2157,7 +2914,7 @@ pub(super) fn run<H: HookSet>(
                         };
 
                         trace!("Instruction: i64.trunc_sat_f64_s [{v1}] -> [{res}]");
-                        stack.push_value(res.into());
+                        stack.push_value(res.into())?;
                     }
                     I64_TRUNC_SAT_F64_U => {
                         let v1: value::F64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
@@ -2172,7 +2929,7 @@ pub(super) fn run<H: HookSet>(
                         };
 
                         trace!("Instruction: i64.trunc_sat_f64_u [{v1}] -> [{res}]");
-                        stack.push_value(res.into());
+                        stack.push_value(res.into())?;
                     }
                     // See https://webassembly.github.io/bulk-memory-operations/core/exec/instructions.html#xref-syntax-instructions-syntax-instr-memory-mathsf-memory-init-x
                     // Copy a region from a data segment into memory
@@ -2187,18 +2944,24 @@ pub(super) fn run<H: HookSet>(
                             .data
                             .get(data_idx)
                             .unwrap();
-                        let mem_idx = wasm.read_u8().unwrap_validated() as usize;
+                        let mem_idx = wasm.read_var_u32().unwrap_validated() as usize;
                         let mem = modules[*current_module_idx]
                             .store
                             .mems
                             .get(mem_idx)
                             .unwrap_validated();
-                        let n: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                        let s: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                        let d: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
-                        mem.mem
-                            .init(d as MemIdx, &data.data, s as MemIdx, n as MemIdx)?;
+                        let n: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let s: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let d = pop_address(stack, mem.ty.addr_type);
+
+                        let d = address_to_mem_idx(d)?;
+                        #[cfg(feature = "hooks")]
+                        hook_action(
+                            hooks.bulk_store_hook(*current_module_idx, mem_idx, d, n as usize),
+                            hook_yield,
+                        )?;
+                        mem.mem.init(d, &data.data, s as MemIdx, n as MemIdx)?;
 
                         trace!("Instruction: memory.init");
                     }
@@ -2221,12 +2984,9 @@ pub(super) fn run<H: HookSet>(
                         //      s => source address to copy from
                         //      d => destination address to copy to
                         let (dst_idx, src_idx) = (
-                            wasm.read_u8().unwrap_validated() as usize,
-                            wasm.read_u8().unwrap_validated() as usize,
+                            wasm.read_var_u32().unwrap_validated() as usize,
+                            wasm.read_var_u32().unwrap_validated() as usize,
                         );
-                        let n: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                        let s: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
-                        let d: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                         let src_mem = modules[*current_module_idx]
                             .store
@@ -2240,9 +3000,26 @@ pub(super) fn run<H: HookSet>(
                             .get(dst_idx)
                             .unwrap_validated();
 
-                        dest_mem
-                            .mem
-                            .copy(d as MemIdx, &src_mem.mem, s as MemIdx, n as MemIdx)?;
+                        // The count is only 64-bit if both memories are
+                        let n_addr_type = match (dest_mem.ty.addr_type, src_mem.ty.addr_type) {
+                            (AddrType::I64, AddrType::I64) => AddrType::I64,
+                            _ => AddrType::I32,
+                        };
+                        let n = pop_address(stack, n_addr_type);
+                        let s = pop_address(stack, src_mem.ty.addr_type);
+                        let d = pop_address(stack, dest_mem.ty.addr_type);
+
+                        let (d, s, n) = (
+                            address_to_mem_idx(d)?,
+                            address_to_mem_idx(s)?,
+                            address_to_mem_idx(n)?,
+                        );
+                        #[cfg(feature = "hooks")]
+                        hook_action(
+                            hooks.bulk_store_hook(*current_module_idx, dst_idx, d, n),
+                            hook_yield,
+                        )?;
+                        dest_mem.mem.copy(d, &src_mem.mem, s, n)?;
 
                         trace!("Instruction: memory.copy");
                     }
@@ -2252,22 +3029,29 @@ pub(super) fn run<H: HookSet>(
                         //      n => number of bytes to update
                         //      val => the value to set each byte to (must be < 256)
                         //      d => the pointer to the region to update
-                        let mem_idx = wasm.read_u8().unwrap_validated() as usize;
+                        let mem_idx = wasm.read_var_u32().unwrap_validated() as usize;
                         let mem = modules[*current_module_idx]
                             .store
                             .mems
                             .get(mem_idx)
                             .unwrap_validated();
-                        let n: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+
+                        let n = pop_address(stack, mem.ty.addr_type);
                         let val: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
                         if !(0..=255).contains(&val) {
                             warn!("Value for memory.fill does not fit in a byte ({val})");
                         }
 
-                        let d: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let d = pop_address(stack, mem.ty.addr_type);
 
-                        mem.mem.fill(d as usize, val as u8, n as usize)?;
+                        let (d, n) = (address_to_mem_idx(d)?, address_to_mem_idx(n)?);
+                        #[cfg(feature = "hooks")]
+                        hook_action(
+                            hooks.bulk_store_hook(*current_module_idx, mem_idx, d, n),
+                            hook_yield,
+                        )?;
+                        mem.mem.fill(d, val as u8, n)?;
 
                         trace!("Instruction: memory.fill");
                     }
@@ -2283,17 +3067,12 @@ pub(super) fn run<H: HookSet>(
                         let s: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into(); // offset
                         let d: i32 = stack.pop_value(ValType::NumType(NumType::I32)).into(); // dst
 
-                        let tab_len = modules[*current_module_idx]
-                            .store
-                            .tables
-                            .get(table_idx)
-                            .unwrap_validated()
-                            .len();
                         let tab = modules[*current_module_idx]
                             .store
                             .tables
-                            .get_mut(table_idx)
+                            .get(table_idx)
                             .unwrap_validated();
+                        let tab_len = tab.len();
 
                         let elem_len = if modules[*current_module_idx]
                             .store
@@ -2335,9 +3114,18 @@ pub(super) fn run<H: HookSet>(
                             .get(elem_idx)
                             .unwrap_validated();
 
-                        let dest = &mut tab.elem[d as usize..];
+                        let dest = &mut tab.elem.borrow_mut()[d as usize..];
                         let src = &elem.references[s as usize..final_src_offset];
                         dest[..src.len()].copy_from_slice(src);
+                        #[cfg(feature = "hooks")]
+                        hook_action(
+                            hooks.table_hook(
+                                *current_module_idx,
+                                table_idx,
+                                d as usize..d as usize + n as usize,
+                            ),
+                            hook_yield,
+                        )?;
                     }
                     ELEM_DROP => {
                         let elem_idx = wasm.read_var_u32().unwrap_validated() as usize;
@@ -2355,12 +3143,11 @@ pub(super) fn run<H: HookSet>(
                         let table_x_idx = wasm.read_var_u32().unwrap_validated() as usize;
                         let table_y_idx = wasm.read_var_u32().unwrap_validated() as usize;
 
-                        let tab_x_elem_len = modules[*current_module_idx].store.tables[table_x_idx]
-                            .elem
-                            .len();
-                        let tab_y_elem_len = modules[*current_module_idx].store.tables[table_y_idx]
-                            .elem
-                            .len();
+                        let tab_x = &modules[*current_module_idx].store.tables[table_x_idx];
+                        let tab_y = &modules[*current_module_idx].store.tables[table_y_idx];
+
+                        let tab_x_elem_len = tab_x.len();
+                        let tab_y_elem_len = tab_y.len();
 
                         let n: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into(); // size
                         let s: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into(); // source
@@ -2388,31 +3175,21 @@ pub(super) fn run<H: HookSet>(
                             _ => return Err(RuntimeError::TableAccessOutOfBounds),
                         };
 
-                        let dst = table_x_idx;
-                        let src = table_y_idx;
-
-                        if table_x_idx == table_y_idx {
-                            modules[*current_module_idx].store.tables[table_x_idx]
+                        // different table indices may still refer to the same (imported) table
+                        if Rc::ptr_eq(&tab_x.elem, &tab_y.elem) {
+                            tab_x
                                 .elem
-                                .copy_within(s as usize..src_res, d as usize); // }
+                                .borrow_mut()
+                                .copy_within(s as usize..src_res, d as usize);
                         } else {
-                            use core::cmp::Ordering::*;
-                            let (src_table, dst_table) = match dst.cmp(&src) {
-                                Greater => {
-                                    let (left, right) =
-                                        modules[*current_module_idx].store.tables.split_at_mut(dst);
-                                    (&left[src], &mut right[0])
-                                }
-                                Less => {
-                                    let (left, right) =
-                                        modules[*current_module_idx].store.tables.split_at_mut(src);
-                                    (&right[0], &mut left[dst])
-                                }
-                                Equal => unreachable!(),
-                            };
-                            dst_table.elem[d as usize..dst_res]
-                                .copy_from_slice(&src_table.elem[s as usize..src_res]);
+                            tab_x.elem.borrow_mut()[d as usize..dst_res]
+                                .copy_from_slice(&tab_y.elem.borrow()[s as usize..src_res]);
                         }
+                        #[cfg(feature = "hooks")]
+                        hook_action(
+                            hooks.table_hook(*current_module_idx, table_x_idx, d as usize..dst_res),
+                            hook_yield,
+                        )?;
 
                         trace!(
                             "Instruction: table.copy '{}' '{}' [{} {} {}] -> []",
@@ -2429,10 +3206,10 @@ pub(super) fn run<H: HookSet>(
                         let tab = modules[*current_module_idx]
                             .store
                             .tables
-                            .get_mut(table_idx)
+                            .get(table_idx)
                             .unwrap_validated();
 
-                        let sz = tab.elem.len() as u32;
+                        let sz = tab.len() as u32;
 
                         let n: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
                         let val = stack.pop_unknown_ref();
@@ -2443,15 +3220,24 @@ pub(super) fn run<H: HookSet>(
 
                         match final_size {
                             Some(final_size) => {
-                                if final_size > max {
-                                    stack.push_value(Value::I32(u32::MAX))
+                                if u64::from(final_size) > max {
+                                    stack.push_value(Value::I32(u32::MAX))?;
                                 } else {
-                                    tab.elem.extend(vec![val; n as usize]);
-
-                                    stack.push_value(Value::I32(sz));
+                                    tab.elem.borrow_mut().extend(vec![val; n as usize]);
+                                    #[cfg(feature = "hooks")]
+                                    hook_action(
+                                        hooks.table_hook(
+                                            *current_module_idx,
+                                            table_idx,
+                                            sz as usize..final_size as usize,
+                                        ),
+                                        hook_yield,
+                                    )?;
+
+                                    stack.push_value(Value::I32(sz))?;
                                 }
                             }
-                            _ => stack.push_value(Value::I32(u32::MAX)),
+                            _ => stack.push_value(Value::I32(u32::MAX))?,
                         }
                     }
                     TABLE_SIZE => {
@@ -2463,9 +3249,9 @@ pub(super) fn run<H: HookSet>(
                             .get(table_idx)
                             .unwrap_validated();
 
-                        let sz = tab.elem.len() as u32;
+                        let sz = tab.len() as u32;
 
-                        stack.push_value(Value::I32(sz));
+                        stack.push_value(Value::I32(sz))?;
 
                         trace!("Instruction: table.size '{}' [] -> [{}]", table_idx, sz);
                     }
@@ -2475,7 +3261,7 @@ pub(super) fn run<H: HookSet>(
                         let tab = modules[*current_module_idx]
                             .store
                             .tables
-                            .get_mut(table_idx)
+                            .get(table_idx)
                             .unwrap_validated();
                         let ty = tab.ty.et;
 
@@ -2488,9 +3274,15 @@ pub(super) fn run<H: HookSet>(
                             .ok_or(RuntimeError::TableAccessOutOfBounds)?;
 
                         tab.elem
+                            .borrow_mut()
                             .get_mut(i as usize..end)
                             .ok_or(RuntimeError::TableAccessOutOfBounds)?
                             .fill(val);
+                        #[cfg(feature = "hooks")]
+                        hook_action(
+                            hooks.table_hook(*current_module_idx, table_idx, i as usize..end),
+                            hook_yield,
+                        )?;
 
                         trace!(
                             "Instruction table.fill '{}' [{} {} {}] -> []",
@@ -2504,6 +3296,1009 @@ pub(super) fn run<H: HookSet>(
                 }
             }
 
+            FD_EXTENSIONS => {
+                let second_instr = wasm.read_var_u32().unwrap_validated();
+                trace!("Executing vector instruction {second_instr:#04X?}");
+
+                use crate::core::reader::types::opcode::fd_extensions::*;
+                match second_instr {
+                    V128_LOAD | V128_LOAD8X8_S | V128_LOAD8X8_U | V128_LOAD16X4_S
+                    | V128_LOAD16X4_U | V128_LOAD32X2_S | V128_LOAD32X2_U | V128_LOAD8_SPLAT
+                    | V128_LOAD16_SPLAT | V128_LOAD32_SPLAT | V128_LOAD64_SPLAT
+                    | V128_LOAD32_ZERO | V128_LOAD64_ZERO => {
+                        let memarg = MemArg::read_unvalidated(wasm);
+
+                        let mem = modules[*current_module_idx]
+                            .store
+                            .mems
+                            .get(memarg.memory)
+                            .unwrap_validated();
+                        let relative_address = pop_address(stack, mem.ty.addr_type);
+
+                        let idx = get_store_index(&memarg, relative_address)?;
+                        let data: [u8; 16] = match second_instr {
+                            V128_LOAD => mem.mem.load::<16, u128>(idx)?.to_le_bytes(),
+                            V128_LOAD8_SPLAT => simd::splat(mem.mem.load::<1, u8>(idx)?),
+                            V128_LOAD16_SPLAT => simd::splat(mem.mem.load::<2, u16>(idx)?),
+                            V128_LOAD32_SPLAT => simd::splat(mem.mem.load::<4, u32>(idx)?),
+                            V128_LOAD64_SPLAT => simd::splat(mem.mem.load::<8, u64>(idx)?),
+                            V128_LOAD32_ZERO => {
+                                u128::from(mem.mem.load::<4, u32>(idx)?).to_le_bytes()
+                            }
+                            V128_LOAD64_ZERO => {
+                                u128::from(mem.mem.load::<8, u64>(idx)?).to_le_bytes()
+                            }
+                            // the remaining loads read 64 bits and extend their lanes to twice the width
+                            _ => {
+                                let v = u128::from(mem.mem.load::<8, u64>(idx)?).to_le_bytes();
+                                match second_instr {
+                                    V128_LOAD8X8_S => simd::extend(v, false, |x: i8| i16::from(x)),
+                                    V128_LOAD8X8_U => simd::extend(v, false, |x: u8| u16::from(x)),
+                                    V128_LOAD16X4_S => {
+                                        simd::extend(v, false, |x: i16| i32::from(x))
+                                    }
+                                    V128_LOAD16X4_U => {
+                                        simd::extend(v, false, |x: u16| u32::from(x))
+                                    }
+                                    V128_LOAD32X2_S => {
+                                        simd::extend(v, false, |x: i32| i64::from(x))
+                                    }
+                                    V128_LOAD32X2_U => {
+                                        simd::extend(v, false, |x: u32| u64::from(x))
+                                    }
+                                    _ => unreachable_validated!(),
+                                }
+                            }
+                        };
+
+                        #[cfg(feature = "hooks")]
+                        {
+                            let width = match second_instr {
+                                V128_LOAD => 16,
+                                V128_LOAD8_SPLAT => 1,
+                                V128_LOAD16_SPLAT => 2,
+                                V128_LOAD32_SPLAT | V128_LOAD32_ZERO => 4,
+                                _ => 8,
+                            };
+                            hook_action(
+                                hooks.load_hook(
+                                    *current_module_idx,
+                                    memarg.memory,
+                                    idx,
+                                    width,
+                                    data.into(),
+                                ),
+                                hook_yield,
+                            )?;
+                        }
+                        stack.push_value(data.into())?;
+                        trace!("Instruction: v128.load [{relative_address}] -> [{data:?}]");
+                    }
+                    V128_STORE => {
+                        let memarg = MemArg::read_unvalidated(wasm);
+
+                        let data_to_store: [u8; 16] = stack.pop_value(ValType::VecType).into();
+
+                        let mem = modules[*current_module_idx]
+                            .store
+                            .mems
+                            .get(memarg.memory)
+                            .unwrap_validated();
+                        let relative_address = pop_address(stack, mem.ty.addr_type);
+
+                        let idx = get_store_index(&memarg, relative_address)?;
+                        #[cfg(feature = "hooks")]
+                        hook_action(
+                            hooks.store_hook(
+                                *current_module_idx,
+                                memarg.memory,
+                                idx,
+                                16,
+                                data_to_store.into(),
+                            ),
+                            hook_yield,
+                        )?;
+                        mem.mem.store(idx, u128::from_le_bytes(data_to_store))?;
+
+                        trace!(
+                            "Instruction: v128.store [{relative_address} {data_to_store:?}] -> []"
+                        );
+                    }
+                    V128_LOAD8_LANE | V128_LOAD16_LANE | V128_LOAD32_LANE | V128_LOAD64_LANE => {
+                        let memarg = MemArg::read_unvalidated(wasm);
+                        let lane_idx = wasm.read_u8().unwrap_validated();
+
+                        let v: [u8; 16] = stack.pop_value(ValType::VecType).into();
+
+                        let mem = modules[*current_module_idx]
+                            .store
+                            .mems
+                            .get(memarg.memory)
+                            .unwrap_validated();
+                        let relative_address = pop_address(stack, mem.ty.addr_type);
+
+                        let idx = get_store_index(&memarg, relative_address)?;
+                        let res = match second_instr {
+                            V128_LOAD8_LANE => {
+                                simd::replace_lane(v, lane_idx, mem.mem.load::<1, u8>(idx)?)
+                            }
+                            V128_LOAD16_LANE => {
+                                simd::replace_lane(v, lane_idx, mem.mem.load::<2, u16>(idx)?)
+                            }
+                            V128_LOAD32_LANE => {
+                                simd::replace_lane(v, lane_idx, mem.mem.load::<4, u32>(idx)?)
+                            }
+                            _ => simd::replace_lane(v, lane_idx, mem.mem.load::<8, u64>(idx)?),
+                        };
+
+                        #[cfg(feature = "hooks")]
+                        hook_action(
+                            hooks.load_hook(
+                                *current_module_idx,
+                                memarg.memory,
+                                idx,
+                                match second_instr {
+                                    V128_LOAD8_LANE => 1,
+                                    V128_LOAD16_LANE => 2,
+                                    V128_LOAD32_LANE => 4,
+                                    _ => 8,
+                                },
+                                res.into(),
+                            ),
+                            hook_yield,
+                        )?;
+                        stack.push_value(res.into())?;
+                        trace!("Instruction: v128.load_lane {lane_idx} [{relative_address} {v:?}] -> [{res:?}]");
+                    }
+                    V128_STORE8_LANE | V128_STORE16_LANE | V128_STORE32_LANE
+                    | V128_STORE64_LANE => {
+                        let memarg = MemArg::read_unvalidated(wasm);
+                        let lane_idx = wasm.read_u8().unwrap_validated();
+
+                        let v: [u8; 16] = stack.pop_value(ValType::VecType).into();
+
+                        let mem = modules[*current_module_idx]
+                            .store
+                            .mems
+                            .get(memarg.memory)
+                            .unwrap_validated();
+                        let relative_address = pop_address(stack, mem.ty.addr_type);
+
+                        let idx = get_store_index(&memarg, relative_address)?;
+                        #[cfg(feature = "hooks")]
+                        hook_action(
+                            hooks.store_hook(
+                                *current_module_idx,
+                                memarg.memory,
+                                idx,
+                                match second_instr {
+                                    V128_STORE8_LANE => 1,
+                                    V128_STORE16_LANE => 2,
+                                    V128_STORE32_LANE => 4,
+                                    _ => 8,
+                                },
+                                v.into(),
+                            ),
+                            hook_yield,
+                        )?;
+                        match second_instr {
+                            V128_STORE8_LANE => mem
+                                .mem
+                                .store(idx, simd::extract_lane::<1, u8>(v, lane_idx))?,
+                            V128_STORE16_LANE => mem
+                                .mem
+                                .store(idx, simd::extract_lane::<2, u16>(v, lane_idx))?,
+                            V128_STORE32_LANE => mem
+                                .mem
+                                .store(idx, simd::extract_lane::<4, u32>(v, lane_idx))?,
+                            _ => mem
+                                .mem
+                                .store(idx, simd::extract_lane::<8, u64>(v, lane_idx))?,
+                        }
+
+                        trace!("Instruction: v128.store_lane {lane_idx} [{relative_address} {v:?}] -> []");
+                    }
+                    V128_CONST => {
+                        let constant = wasm.strip_bytes::<16>().unwrap_validated();
+
+                        stack.push_value(constant.into())?;
+                        trace!("Instruction: v128.const [] -> [{constant:?}]");
+                    }
+                    I8X16_SHUFFLE => {
+                        let lane_idxs = wasm.strip_bytes::<16>().unwrap_validated();
+                        binary_v128(stack, |v1, v2| simd::shuffle(v1, v2, lane_idxs))?;
+                    }
+                    I8X16_SWIZZLE => binary_v128(stack, simd::swizzle)?,
+
+                    I8X16_SPLAT => {
+                        let x: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        stack.push_value(simd::splat(x as u8).into())?;
+                    }
+                    I16X8_SPLAT => {
+                        let x: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        stack.push_value(simd::splat(x as u16).into())?;
+                    }
+                    I32X4_SPLAT => {
+                        let x: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        stack.push_value(simd::splat(x).into())?;
+                    }
+                    I64X2_SPLAT => {
+                        let x: u64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                        stack.push_value(simd::splat(x).into())?;
+                    }
+                    F32X4_SPLAT => {
+                        let x: f32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                        stack.push_value(simd::splat(x).into())?;
+                    }
+                    F64X2_SPLAT => {
+                        let x: f64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                        stack.push_value(simd::splat(x).into())?;
+                    }
+
+                    I8X16_EXTRACT_LANE_S | I8X16_EXTRACT_LANE_U | I16X8_EXTRACT_LANE_S
+                    | I16X8_EXTRACT_LANE_U | I32X4_EXTRACT_LANE | I64X2_EXTRACT_LANE
+                    | F32X4_EXTRACT_LANE | F64X2_EXTRACT_LANE => {
+                        let lane_idx = wasm.read_u8().unwrap_validated();
+                        let v: [u8; 16] = stack.pop_value(ValType::VecType).into();
+
+                        let res: Value = match second_instr {
+                            I8X16_EXTRACT_LANE_S => {
+                                i32::from(simd::extract_lane::<1, i8>(v, lane_idx)).into()
+                            }
+                            I8X16_EXTRACT_LANE_U => {
+                                u32::from(simd::extract_lane::<1, u8>(v, lane_idx)).into()
+                            }
+                            I16X8_EXTRACT_LANE_S => {
+                                i32::from(simd::extract_lane::<2, i16>(v, lane_idx)).into()
+                            }
+                            I16X8_EXTRACT_LANE_U => {
+                                u32::from(simd::extract_lane::<2, u16>(v, lane_idx)).into()
+                            }
+                            I32X4_EXTRACT_LANE => simd::extract_lane::<4, u32>(v, lane_idx).into(),
+                            I64X2_EXTRACT_LANE => simd::extract_lane::<8, u64>(v, lane_idx).into(),
+                            F32X4_EXTRACT_LANE => simd::extract_lane::<4, f32>(v, lane_idx).into(),
+                            _ => simd::extract_lane::<8, f64>(v, lane_idx).into(),
+                        };
+
+                        stack.push_value(res)?;
+                        trace!("Instruction: extract_lane {lane_idx} [{v:?}] -> [{res:?}]");
+                    }
+                    I8X16_REPLACE_LANE | I16X8_REPLACE_LANE | I32X4_REPLACE_LANE => {
+                        let lane_idx = wasm.read_u8().unwrap_validated();
+                        let x: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let v: [u8; 16] = stack.pop_value(ValType::VecType).into();
+
+                        let res = match second_instr {
+                            I8X16_REPLACE_LANE => simd::replace_lane(v, lane_idx, x as u8),
+                            I16X8_REPLACE_LANE => simd::replace_lane(v, lane_idx, x as u16),
+                            _ => simd::replace_lane(v, lane_idx, x),
+                        };
+
+                        stack.push_value(res.into())?;
+                        trace!("Instruction: replace_lane {lane_idx} [{v:?} {x}] -> [{res:?}]");
+                    }
+                    I64X2_REPLACE_LANE => {
+                        let lane_idx = wasm.read_u8().unwrap_validated();
+                        let x: u64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                        let v: [u8; 16] = stack.pop_value(ValType::VecType).into();
+
+                        let res = simd::replace_lane(v, lane_idx, x);
+
+                        stack.push_value(res.into())?;
+                        trace!(
+                            "Instruction: i64x2.replace_lane {lane_idx} [{v:?} {x}] -> [{res:?}]"
+                        );
+                    }
+                    F32X4_REPLACE_LANE => {
+                        let lane_idx = wasm.read_u8().unwrap_validated();
+                        let x: f32 = stack.pop_value(ValType::NumType(NumType::F32)).into();
+                        let v: [u8; 16] = stack.pop_value(ValType::VecType).into();
+
+                        let res = simd::replace_lane(v, lane_idx, x);
+
+                        stack.push_value(res.into())?;
+                        trace!(
+                            "Instruction: f32x4.replace_lane {lane_idx} [{v:?} {x}] -> [{res:?}]"
+                        );
+                    }
+                    F64X2_REPLACE_LANE => {
+                        let lane_idx = wasm.read_u8().unwrap_validated();
+                        let x: f64 = stack.pop_value(ValType::NumType(NumType::F64)).into();
+                        let v: [u8; 16] = stack.pop_value(ValType::VecType).into();
+
+                        let res = simd::replace_lane(v, lane_idx, x);
+
+                        stack.push_value(res.into())?;
+                        trace!(
+                            "Instruction: f64x2.replace_lane {lane_idx} [{v:?} {x}] -> [{res:?}]"
+                        );
+                    }
+
+                    I8X16_EQ => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u8, y| x == y))?
+                    }
+                    I8X16_NE => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u8, y| x != y))?
+                    }
+                    I8X16_LT_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i8, y| x < y))?
+                    }
+                    I8X16_LT_U => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u8, y| x < y))?
+                    }
+                    I8X16_GT_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i8, y| x > y))?
+                    }
+                    I8X16_GT_U => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u8, y| x > y))?
+                    }
+                    I8X16_LE_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i8, y| x <= y))?
+                    }
+                    I8X16_LE_U => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u8, y| x <= y))?
+                    }
+                    I8X16_GE_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i8, y| x >= y))?
+                    }
+                    I8X16_GE_U => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u8, y| x >= y))?
+                    }
+                    I16X8_EQ => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u16, y| x == y))?
+                    }
+                    I16X8_NE => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u16, y| x != y))?
+                    }
+                    I16X8_LT_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i16, y| x < y))?
+                    }
+                    I16X8_LT_U => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u16, y| x < y))?
+                    }
+                    I16X8_GT_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i16, y| x > y))?
+                    }
+                    I16X8_GT_U => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u16, y| x > y))?
+                    }
+                    I16X8_LE_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i16, y| x <= y))?
+                    }
+                    I16X8_LE_U => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u16, y| x <= y))?
+                    }
+                    I16X8_GE_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i16, y| x >= y))?
+                    }
+                    I16X8_GE_U => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u16, y| x >= y))?
+                    }
+                    I32X4_EQ => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u32, y| x == y))?
+                    }
+                    I32X4_NE => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u32, y| x != y))?
+                    }
+                    I32X4_LT_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i32, y| x < y))?
+                    }
+                    I32X4_LT_U => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u32, y| x < y))?
+                    }
+                    I32X4_GT_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i32, y| x > y))?
+                    }
+                    I32X4_GT_U => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u32, y| x > y))?
+                    }
+                    I32X4_LE_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i32, y| x <= y))?
+                    }
+                    I32X4_LE_U => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u32, y| x <= y))?
+                    }
+                    I32X4_GE_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i32, y| x >= y))?
+                    }
+                    I32X4_GE_U => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u32, y| x >= y))?
+                    }
+                    I64X2_EQ => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u64, y| x == y))?
+                    }
+                    I64X2_NE => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: u64, y| x != y))?
+                    }
+                    I64X2_LT_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i64, y| x < y))?
+                    }
+                    I64X2_GT_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i64, y| x > y))?
+                    }
+                    I64X2_LE_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i64, y| x <= y))?
+                    }
+                    I64X2_GE_S => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: i64, y| x >= y))?
+                    }
+                    F32X4_EQ => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: f32, y| x == y))?
+                    }
+                    F32X4_NE => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: f32, y| x != y))?
+                    }
+                    F32X4_LT => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: f32, y| x < y))?
+                    }
+                    F32X4_GT => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: f32, y| x > y))?
+                    }
+                    F32X4_LE => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: f32, y| x <= y))?
+                    }
+                    F32X4_GE => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: f32, y| x >= y))?
+                    }
+                    F64X2_EQ => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: f64, y| x == y))?
+                    }
+                    F64X2_NE => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: f64, y| x != y))?
+                    }
+                    F64X2_LT => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: f64, y| x < y))?
+                    }
+                    F64X2_GT => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: f64, y| x > y))?
+                    }
+                    F64X2_LE => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: f64, y| x <= y))?
+                    }
+                    F64X2_GE => {
+                        binary_v128(stack, |v1, v2| simd::compare(v1, v2, |x: f64, y| x >= y))?
+                    }
+
+                    V128_NOT => unary_v128(stack, |v| v.map(|byte| !byte))?,
+                    V128_AND => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, |x: u8, y| x & y))?
+                    }
+                    V128_ANDNOT => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, |x: u8, y| x & !y))?
+                    }
+                    V128_OR => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, |x: u8, y| x | y))?
+                    }
+                    V128_XOR => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, |x: u8, y| x ^ y))?
+                    }
+                    V128_BITSELECT => {
+                        let c: [u8; 16] = stack.pop_value(ValType::VecType).into();
+                        binary_v128(stack, |v1, v2| {
+                            let v1 = simd::zip_map(v1, c, |x: u8, c| x & c);
+                            let v2 = simd::zip_map(v2, c, |x: u8, c| x & !c);
+                            simd::zip_map(v1, v2, |x: u8, y| x | y)
+                        })?;
+                    }
+                    V128_ANY_TRUE => test_v128(stack, |v| v != [0; 16])?,
+
+                    F32X4_DEMOTE_F64X2_ZERO => {
+                        unary_v128(stack, |v| simd::narrow(v, [0; 16], |x: f64| x as f32))?
+                    }
+                    F64X2_PROMOTE_LOW_F32X4 => {
+                        unary_v128(stack, |v| simd::extend(v, false, |x: f32| f64::from(x)))?
+                    }
+
+                    I8X16_ABS => unary_v128(stack, |v| simd::map(v, i8::wrapping_abs))?,
+                    I8X16_NEG => unary_v128(stack, |v| simd::map(v, i8::wrapping_neg))?,
+                    I8X16_POPCNT => {
+                        unary_v128(stack, |v| simd::map(v, |x: u8| x.count_ones() as u8))?
+                    }
+                    I8X16_ALL_TRUE => test_v128(stack, simd::all_true::<1>)?,
+                    I8X16_BITMASK => bitmask_v128(stack, simd::bitmask::<1>)?,
+                    I8X16_NARROW_I16X8_S => binary_v128(stack, |v1, v2| {
+                        simd::narrow(v1, v2, |x: i16| {
+                            x.clamp(i8::MIN.into(), i8::MAX.into()) as i8
+                        })
+                    })?,
+                    I8X16_NARROW_I16X8_U => binary_v128(stack, |v1, v2| {
+                        simd::narrow(v1, v2, |x: i16| x.clamp(0, u8::MAX.into()) as u8)
+                    })?,
+                    I8X16_SHL => shift_v128(stack, |v, s| simd::map(v, |x: u8| x.wrapping_shl(s)))?,
+                    I8X16_SHR_S => {
+                        shift_v128(stack, |v, s| simd::map(v, |x: i8| x.wrapping_shr(s)))?
+                    }
+                    I8X16_SHR_U => {
+                        shift_v128(stack, |v, s| simd::map(v, |x: u8| x.wrapping_shr(s)))?
+                    }
+                    I8X16_ADD => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u8::wrapping_add))?
+                    }
+                    I8X16_ADD_SAT_S => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, i8::saturating_add))?
+                    }
+                    I8X16_ADD_SAT_U => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u8::saturating_add))?
+                    }
+                    I8X16_SUB => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u8::wrapping_sub))?
+                    }
+                    I8X16_SUB_SAT_S => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, i8::saturating_sub))?
+                    }
+                    I8X16_SUB_SAT_U => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u8::saturating_sub))?
+                    }
+                    I8X16_MIN_S => binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, i8::min))?,
+                    I8X16_MIN_U => binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u8::min))?,
+                    I8X16_MAX_S => binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, i8::max))?,
+                    I8X16_MAX_U => binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u8::max))?,
+                    I8X16_AVGR_U => binary_v128(stack, |v1, v2| {
+                        simd::zip_map(v1, v2, |x: u8, y| {
+                            (u16::from(x) + u16::from(y)).div_ceil(2) as u8
+                        })
+                    })?,
+
+                    I16X8_EXTADD_PAIRWISE_I8X16_S => unary_v128(stack, |v| {
+                        simd::pairwise(v, v, |x: i8, y, _, _| i16::from(x) + i16::from(y))
+                    })?,
+                    I16X8_EXTADD_PAIRWISE_I8X16_U => unary_v128(stack, |v| {
+                        simd::pairwise(v, v, |x: u8, y, _, _| u16::from(x) + u16::from(y))
+                    })?,
+                    I32X4_EXTADD_PAIRWISE_I16X8_S => unary_v128(stack, |v| {
+                        simd::pairwise(v, v, |x: i16, y, _, _| i32::from(x) + i32::from(y))
+                    })?,
+                    I32X4_EXTADD_PAIRWISE_I16X8_U => unary_v128(stack, |v| {
+                        simd::pairwise(v, v, |x: u16, y, _, _| u32::from(x) + u32::from(y))
+                    })?,
+
+                    I16X8_ABS => unary_v128(stack, |v| simd::map(v, i16::wrapping_abs))?,
+                    I16X8_NEG => unary_v128(stack, |v| simd::map(v, i16::wrapping_neg))?,
+                    I16X8_Q15MULR_SAT_S => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, simd::q15mulr_sat))?
+                    }
+                    I16X8_ALL_TRUE => test_v128(stack, simd::all_true::<2>)?,
+                    I16X8_BITMASK => bitmask_v128(stack, simd::bitmask::<2>)?,
+                    I16X8_NARROW_I32X4_S => binary_v128(stack, |v1, v2| {
+                        simd::narrow(v1, v2, |x: i32| {
+                            x.clamp(i16::MIN.into(), i16::MAX.into()) as i16
+                        })
+                    })?,
+                    I16X8_NARROW_I32X4_U => binary_v128(stack, |v1, v2| {
+                        simd::narrow(v1, v2, |x: i32| x.clamp(0, u16::MAX.into()) as u16)
+                    })?,
+                    I16X8_EXTEND_LOW_I8X16_S => {
+                        unary_v128(stack, |v| simd::extend(v, false, |x: i8| i16::from(x)))?
+                    }
+                    I16X8_EXTEND_HIGH_I8X16_S => {
+                        unary_v128(stack, |v| simd::extend(v, true, |x: i8| i16::from(x)))?
+                    }
+                    I16X8_EXTEND_LOW_I8X16_U => {
+                        unary_v128(stack, |v| simd::extend(v, false, |x: u8| u16::from(x)))?
+                    }
+                    I16X8_EXTEND_HIGH_I8X16_U => {
+                        unary_v128(stack, |v| simd::extend(v, true, |x: u8| u16::from(x)))?
+                    }
+                    I16X8_SHL => {
+                        shift_v128(stack, |v, s| simd::map(v, |x: u16| x.wrapping_shl(s)))?
+                    }
+                    I16X8_SHR_S => {
+                        shift_v128(stack, |v, s| simd::map(v, |x: i16| x.wrapping_shr(s)))?
+                    }
+                    I16X8_SHR_U => {
+                        shift_v128(stack, |v, s| simd::map(v, |x: u16| x.wrapping_shr(s)))?
+                    }
+                    I16X8_ADD => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u16::wrapping_add))?
+                    }
+                    I16X8_ADD_SAT_S => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, i16::saturating_add))?
+                    }
+                    I16X8_ADD_SAT_U => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u16::saturating_add))?
+                    }
+                    I16X8_SUB => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u16::wrapping_sub))?
+                    }
+                    I16X8_SUB_SAT_S => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, i16::saturating_sub))?
+                    }
+                    I16X8_SUB_SAT_U => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u16::saturating_sub))?
+                    }
+                    I16X8_MUL => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u16::wrapping_mul))?
+                    }
+                    I16X8_MIN_S => binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, i16::min))?,
+                    I16X8_MIN_U => binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u16::min))?,
+                    I16X8_MAX_S => binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, i16::max))?,
+                    I16X8_MAX_U => binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u16::max))?,
+                    I16X8_AVGR_U => binary_v128(stack, |v1, v2| {
+                        simd::zip_map(v1, v2, |x: u16, y| {
+                            (u32::from(x) + u32::from(y)).div_ceil(2) as u16
+                        })
+                    })?,
+                    I16X8_EXTMUL_LOW_I8X16_S => binary_v128(stack, |v1, v2| {
+                        simd::extend_zip(v1, v2, false, |x: i8, y| i16::from(x) * i16::from(y))
+                    })?,
+                    I16X8_EXTMUL_HIGH_I8X16_S => binary_v128(stack, |v1, v2| {
+                        simd::extend_zip(v1, v2, true, |x: i8, y| i16::from(x) * i16::from(y))
+                    })?,
+                    I16X8_EXTMUL_LOW_I8X16_U => binary_v128(stack, |v1, v2| {
+                        simd::extend_zip(v1, v2, false, |x: u8, y| u16::from(x) * u16::from(y))
+                    })?,
+                    I16X8_EXTMUL_HIGH_I8X16_U => binary_v128(stack, |v1, v2| {
+                        simd::extend_zip(v1, v2, true, |x: u8, y| u16::from(x) * u16::from(y))
+                    })?,
+
+                    I32X4_ABS => unary_v128(stack, |v| simd::map(v, i32::wrapping_abs))?,
+                    I32X4_NEG => unary_v128(stack, |v| simd::map(v, i32::wrapping_neg))?,
+                    I32X4_ALL_TRUE => test_v128(stack, simd::all_true::<4>)?,
+                    I32X4_BITMASK => bitmask_v128(stack, simd::bitmask::<4>)?,
+                    I32X4_EXTEND_LOW_I16X8_S => {
+                        unary_v128(stack, |v| simd::extend(v, false, |x: i16| i32::from(x)))?
+                    }
+                    I32X4_EXTEND_HIGH_I16X8_S => {
+                        unary_v128(stack, |v| simd::extend(v, true, |x: i16| i32::from(x)))?
+                    }
+                    I32X4_EXTEND_LOW_I16X8_U => {
+                        unary_v128(stack, |v| simd::extend(v, false, |x: u16| u32::from(x)))?
+                    }
+                    I32X4_EXTEND_HIGH_I16X8_U => {
+                        unary_v128(stack, |v| simd::extend(v, true, |x: u16| u32::from(x)))?
+                    }
+                    I32X4_SHL => {
+                        shift_v128(stack, |v, s| simd::map(v, |x: u32| x.wrapping_shl(s)))?
+                    }
+                    I32X4_SHR_S => {
+                        shift_v128(stack, |v, s| simd::map(v, |x: i32| x.wrapping_shr(s)))?
+                    }
+                    I32X4_SHR_U => {
+                        shift_v128(stack, |v, s| simd::map(v, |x: u32| x.wrapping_shr(s)))?
+                    }
+                    I32X4_ADD => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u32::wrapping_add))?
+                    }
+                    I32X4_SUB => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u32::wrapping_sub))?
+                    }
+                    I32X4_MUL => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u32::wrapping_mul))?
+                    }
+                    I32X4_MIN_S => binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, i32::min))?,
+                    I32X4_MIN_U => binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u32::min))?,
+                    I32X4_MAX_S => binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, i32::max))?,
+                    I32X4_MAX_U => binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u32::max))?,
+                    I32X4_DOT_I16X8_S => binary_v128(stack, |v1, v2| {
+                        simd::pairwise(v1, v2, |x1: i16, x2, y1, y2| {
+                            (i32::from(x1) * i32::from(y1))
+                                .wrapping_add(i32::from(x2) * i32::from(y2))
+                        })
+                    })?,
+                    I32X4_EXTMUL_LOW_I16X8_S => binary_v128(stack, |v1, v2| {
+                        simd::extend_zip(v1, v2, false, |x: i16, y| i32::from(x) * i32::from(y))
+                    })?,
+                    I32X4_EXTMUL_HIGH_I16X8_S => binary_v128(stack, |v1, v2| {
+                        simd::extend_zip(v1, v2, true, |x: i16, y| i32::from(x) * i32::from(y))
+                    })?,
+                    I32X4_EXTMUL_LOW_I16X8_U => binary_v128(stack, |v1, v2| {
+                        simd::extend_zip(v1, v2, false, |x: u16, y| u32::from(x) * u32::from(y))
+                    })?,
+                    I32X4_EXTMUL_HIGH_I16X8_U => binary_v128(stack, |v1, v2| {
+                        simd::extend_zip(v1, v2, true, |x: u16, y| u32::from(x) * u32::from(y))
+                    })?,
+
+                    I64X2_ABS => unary_v128(stack, |v| simd::map(v, i64::wrapping_abs))?,
+                    I64X2_NEG => unary_v128(stack, |v| simd::map(v, i64::wrapping_neg))?,
+                    I64X2_ALL_TRUE => test_v128(stack, simd::all_true::<8>)?,
+                    I64X2_BITMASK => bitmask_v128(stack, simd::bitmask::<8>)?,
+                    I64X2_EXTEND_LOW_I32X4_S => {
+                        unary_v128(stack, |v| simd::extend(v, false, |x: i32| i64::from(x)))?
+                    }
+                    I64X2_EXTEND_HIGH_I32X4_S => {
+                        unary_v128(stack, |v| simd::extend(v, true, |x: i32| i64::from(x)))?
+                    }
+                    I64X2_EXTEND_LOW_I32X4_U => {
+                        unary_v128(stack, |v| simd::extend(v, false, |x: u32| u64::from(x)))?
+                    }
+                    I64X2_EXTEND_HIGH_I32X4_U => {
+                        unary_v128(stack, |v| simd::extend(v, true, |x: u32| u64::from(x)))?
+                    }
+                    I64X2_SHL => {
+                        shift_v128(stack, |v, s| simd::map(v, |x: u64| x.wrapping_shl(s)))?
+                    }
+                    I64X2_SHR_S => {
+                        shift_v128(stack, |v, s| simd::map(v, |x: i64| x.wrapping_shr(s)))?
+                    }
+                    I64X2_SHR_U => {
+                        shift_v128(stack, |v, s| simd::map(v, |x: u64| x.wrapping_shr(s)))?
+                    }
+                    I64X2_ADD => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u64::wrapping_add))?
+                    }
+                    I64X2_SUB => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u64::wrapping_sub))?
+                    }
+                    I64X2_MUL => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, u64::wrapping_mul))?
+                    }
+                    I64X2_EXTMUL_LOW_I32X4_S => binary_v128(stack, |v1, v2| {
+                        simd::extend_zip(v1, v2, false, |x: i32, y| i64::from(x) * i64::from(y))
+                    })?,
+                    I64X2_EXTMUL_HIGH_I32X4_S => binary_v128(stack, |v1, v2| {
+                        simd::extend_zip(v1, v2, true, |x: i32, y| i64::from(x) * i64::from(y))
+                    })?,
+                    I64X2_EXTMUL_LOW_I32X4_U => binary_v128(stack, |v1, v2| {
+                        simd::extend_zip(v1, v2, false, |x: u32, y| u64::from(x) * u64::from(y))
+                    })?,
+                    I64X2_EXTMUL_HIGH_I32X4_U => binary_v128(stack, |v1, v2| {
+                        simd::extend_zip(v1, v2, true, |x: u32, y| u64::from(x) * u64::from(y))
+                    })?,
+
+                    F32X4_CEIL => {
+                        unary_v128(stack, |v| simd::map(v, |x: f32| value::F32(x).ceil().0))?
+                    }
+                    F32X4_FLOOR => {
+                        unary_v128(stack, |v| simd::map(v, |x: f32| value::F32(x).floor().0))?
+                    }
+                    F32X4_TRUNC => {
+                        unary_v128(stack, |v| simd::map(v, |x: f32| value::F32(x).trunc().0))?
+                    }
+                    F32X4_NEAREST => {
+                        unary_v128(stack, |v| simd::map(v, |x: f32| value::F32(x).nearest().0))?
+                    }
+                    F32X4_ABS => {
+                        unary_v128(stack, |v| simd::map(v, |x: f32| value::F32(x).abs().0))?
+                    }
+                    F32X4_NEG => unary_v128(stack, |v| simd::map(v, |x: f32| -x))?,
+                    F32X4_SQRT => {
+                        unary_v128(stack, |v| simd::map(v, |x: f32| value::F32(x).sqrt().0))?
+                    }
+                    F32X4_ADD => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, |x: f32, y| x + y))?
+                    }
+                    F32X4_SUB => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, |x: f32, y| x - y))?
+                    }
+                    F32X4_MUL => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, |x: f32, y| x * y))?
+                    }
+                    F32X4_DIV => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, |x: f32, y| x / y))?
+                    }
+                    F32X4_MIN => binary_v128(stack, |v1, v2| {
+                        simd::zip_map(v1, v2, |x: f32, y| value::F32(x).min(value::F32(y)).0)
+                    })?,
+                    F32X4_MAX => binary_v128(stack, |v1, v2| {
+                        simd::zip_map(v1, v2, |x: f32, y| value::F32(x).max(value::F32(y)).0)
+                    })?,
+                    F32X4_PMIN => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, simd::pmin::<f32>))?
+                    }
+                    F32X4_PMAX => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, simd::pmax::<f32>))?
+                    }
+
+                    F64X2_CEIL => {
+                        unary_v128(stack, |v| simd::map(v, |x: f64| value::F64(x).ceil().0))?
+                    }
+                    F64X2_FLOOR => {
+                        unary_v128(stack, |v| simd::map(v, |x: f64| value::F64(x).floor().0))?
+                    }
+                    F64X2_TRUNC => {
+                        unary_v128(stack, |v| simd::map(v, |x: f64| value::F64(x).trunc().0))?
+                    }
+                    F64X2_NEAREST => {
+                        unary_v128(stack, |v| simd::map(v, |x: f64| value::F64(x).nearest().0))?
+                    }
+                    F64X2_ABS => {
+                        unary_v128(stack, |v| simd::map(v, |x: f64| value::F64(x).abs().0))?
+                    }
+                    F64X2_NEG => unary_v128(stack, |v| simd::map(v, |x: f64| -x))?,
+                    F64X2_SQRT => {
+                        unary_v128(stack, |v| simd::map(v, |x: f64| value::F64(x).sqrt().0))?
+                    }
+                    F64X2_ADD => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, |x: f64, y| x + y))?
+                    }
+                    F64X2_SUB => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, |x: f64, y| x - y))?
+                    }
+                    F64X2_MUL => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, |x: f64, y| x * y))?
+                    }
+                    F64X2_DIV => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, |x: f64, y| x / y))?
+                    }
+                    F64X2_MIN => binary_v128(stack, |v1, v2| {
+                        simd::zip_map(v1, v2, |x: f64, y| value::F64(x).min(value::F64(y)).0)
+                    })?,
+                    F64X2_MAX => binary_v128(stack, |v1, v2| {
+                        simd::zip_map(v1, v2, |x: f64, y| value::F64(x).max(value::F64(y)).0)
+                    })?,
+                    F64X2_PMIN => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, simd::pmin::<f64>))?
+                    }
+                    F64X2_PMAX => {
+                        binary_v128(stack, |v1, v2| simd::zip_map(v1, v2, simd::pmax::<f64>))?
+                    }
+
+                    // `as` saturates and converts NaN to zero, just like the `trunc_sat` instructions
+                    I32X4_TRUNC_SAT_F32X4_S => {
+                        unary_v128(stack, |v| simd::map(v, |x: f32| x as i32))?
+                    }
+                    I32X4_TRUNC_SAT_F32X4_U => {
+                        unary_v128(stack, |v| simd::map(v, |x: f32| x as u32))?
+                    }
+                    F32X4_CONVERT_I32X4_S => {
+                        unary_v128(stack, |v| simd::map(v, |x: i32| x as f32))?
+                    }
+                    F32X4_CONVERT_I32X4_U => {
+                        unary_v128(stack, |v| simd::map(v, |x: u32| x as f32))?
+                    }
+                    I32X4_TRUNC_SAT_F64X2_S_ZERO => {
+                        unary_v128(stack, |v| simd::narrow(v, [0; 16], |x: f64| x as i32))?
+                    }
+                    I32X4_TRUNC_SAT_F64X2_U_ZERO => {
+                        unary_v128(stack, |v| simd::narrow(v, [0; 16], |x: f64| x as u32))?
+                    }
+                    F64X2_CONVERT_LOW_I32X4_S => {
+                        unary_v128(stack, |v| simd::extend(v, false, |x: i32| f64::from(x)))?
+                    }
+                    F64X2_CONVERT_LOW_I32X4_U => {
+                        unary_v128(stack, |v| simd::extend(v, false, |x: u32| f64::from(x)))?
+                    }
+
+                    _ => unreachable_validated!(),
+                }
+            }
+            FE_EXTENSIONS => {
+                let second_instr = wasm.read_var_u32().unwrap_validated();
+                trace!("Executing atomic instruction {second_instr:#04X?}");
+
+                use crate::core::reader::types::opcode::fe_extensions::*;
+                if second_instr == ATOMIC_FENCE {
+                    let _reserved = wasm.read_u8().unwrap_validated();
+                    // Atomic accesses are exclusive anyway, this only orders the accesses of the host
+                    atomic::fence(Ordering::SeqCst);
+                    trace!("Instruction: atomic.fence [] -> []");
+                    continue;
+                }
+
+                let memarg = MemArg::read_unvalidated(wasm);
+                let mem = modules[*current_module_idx]
+                    .store
+                    .mems
+                    .get(memarg.memory)
+                    .unwrap_validated();
+
+                match second_instr {
+                    MEMORY_ATOMIC_NOTIFY => {
+                        let count: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+                        let relative_address = pop_address(stack, mem.ty.addr_type);
+                        let idx = get_store_index(&memarg, relative_address)?;
+
+                        // bounds and alignment are checked, even if there can not be any waiting threads
+                        mem.mem.atomic_load::<4, u32>(idx)?;
+                        let woken = match waiter {
+                            Some(waiter) if mem.ty.shared => {
+                                waiter.notify(WaitKey::new(mem, idx), count)
+                            }
+                            _ => 0,
+                        };
+
+                        stack.push_value(Value::I32(woken))?;
+                        trace!("Instruction: memory.atomic.notify [{relative_address} {count}] -> [{woken}]");
+                    }
+                    MEMORY_ATOMIC_WAIT32 | MEMORY_ATOMIC_WAIT64 => {
+                        let (num_type, width) = if second_instr == MEMORY_ATOMIC_WAIT32 {
+                            (NumType::I32, 4)
+                        } else {
+                            (NumType::I64, 8)
+                        };
+                        let timeout: i64 = stack.pop_value(ValType::NumType(NumType::I64)).into();
+                        let expected = pop_atomic_operand(stack, num_type);
+                        let relative_address = pop_address(stack, mem.ty.addr_type);
+                        let idx = get_store_index(&memarg, relative_address)?;
+
+                        atomic_rmw(&mem.mem, idx, width, |old| old)?;
+                        if !mem.ty.shared {
+                            return Err(RuntimeError::ExpectedSharedMemory);
+                        }
+
+                        // a negative timeout means waiting forever
+                        let timeout = u64::try_from(timeout).ok().map(Duration::from_nanos);
+                        let mut still_expected =
+                            || atomic_rmw(&mem.mem, idx, width, |old| old) == Ok(expected);
+                        let outcome = match waiter {
+                            Some(waiter) => {
+                                waiter.wait(WaitKey::new(mem, idx), timeout, &mut still_expected)
+                            }
+                            None if !still_expected() => WaitOutcome::NotEqual,
+                            None => return Err(RuntimeError::WaiterNotConfigured),
+                        };
+
+                        stack.push_value(Value::I32(i32::from(outcome) as u32))?;
+                        trace!("Instruction: memory.atomic.wait [{relative_address} {expected} {timeout:?}] -> [{outcome:?}]");
+                    }
+                    I32_ATOMIC_LOAD..=I64_ATOMIC_RMW32_CMPXCHG_U => {
+                        let (num_type, width) = atomic_access_shape(second_instr);
+                        let replacement = if second_instr >= I32_ATOMIC_RMW_CMPXCHG {
+                            pop_atomic_operand(stack, num_type)
+                        } else {
+                            0
+                        };
+                        let operand = if second_instr >= I32_ATOMIC_STORE {
+                            pop_atomic_operand(stack, num_type)
+                        } else {
+                            0
+                        };
+                        let relative_address = pop_address(stack, mem.ty.addr_type);
+                        let idx = get_store_index(&memarg, relative_address)?;
+
+                        let to_value = |datum| match num_type {
+                            NumType::I32 => Value::I32(datum as u32),
+                            _ => Value::I64(datum),
+                        };
+                        #[cfg(feature = "hooks")]
+                        if second_instr >= I32_ATOMIC_STORE {
+                            let stored = if second_instr >= I32_ATOMIC_RMW_CMPXCHG {
+                                replacement
+                            } else {
+                                operand
+                            };
+                            hook_action(
+                                hooks.store_hook(
+                                    *current_module_idx,
+                                    memarg.memory,
+                                    idx,
+                                    width,
+                                    to_value(stored),
+                                ),
+                                hook_yield,
+                            )?;
+                        }
+
+                        // the result of the closure is truncated to the width of the accessed datum
+                        let mask = u64::MAX >> (64 - 8 * width);
+                        let old = atomic_rmw(&mem.mem, idx, width, |old| match second_instr {
+                            I32_ATOMIC_LOAD..=I64_ATOMIC_LOAD32_U => old,
+                            I32_ATOMIC_STORE..=I64_ATOMIC_STORE32
+                            | I32_ATOMIC_RMW_XCHG..=I64_ATOMIC_RMW32_XCHG_U => operand,
+                            I32_ATOMIC_RMW_ADD..=I64_ATOMIC_RMW32_ADD_U => {
+                                old.wrapping_add(operand)
+                            }
+                            I32_ATOMIC_RMW_SUB..=I64_ATOMIC_RMW32_SUB_U => {
+                                old.wrapping_sub(operand)
+                            }
+                            I32_ATOMIC_RMW_AND..=I64_ATOMIC_RMW32_AND_U => old & operand,
+                            I32_ATOMIC_RMW_OR..=I64_ATOMIC_RMW32_OR_U => old | operand,
+                            I32_ATOMIC_RMW_XOR..=I64_ATOMIC_RMW32_XOR_U => old ^ operand,
+                            // the expected value is wrapped to the width of the datum before comparing
+                            _ if old == operand & mask => replacement,
+                            _ => old,
+                        })?;
+
+                        if !(I32_ATOMIC_STORE..=I64_ATOMIC_STORE32).contains(&second_instr) {
+                            #[cfg(feature = "hooks")]
+                            hook_action(
+                                hooks.load_hook(
+                                    *current_module_idx,
+                                    memarg.memory,
+                                    idx,
+                                    width,
+                                    to_value(old),
+                                ),
+                                hook_yield,
+                            )?;
+                            stack.push_value(to_value(old))?;
+                        }
+                        trace!("Instruction: atomic access {second_instr:#04X?} [{relative_address} {operand} {replacement}] -> [{old}]");
+                    }
+
+                    _ => unreachable_validated!(),
+                }
+            }
             I32_EXTEND8_S => {
                 let mut v: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
 
@@ -2514,7 +4309,7 @@ pub(super) fn run<H: HookSet>(
 
                 let res = if v | 0x7F != 0x7F { v | 0xFFFFFF00 } else { v };
 
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
 
                 trace!("Instruction i32.extend8_s [{}] -> [{}]", v, res);
             }
@@ -2532,7 +4327,7 @@ pub(super) fn run<H: HookSet>(
                     v
                 };
 
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
 
                 trace!("Instruction i32.extend16_s [{}] -> [{}]", v, res);
             }
@@ -2550,7 +4345,7 @@ pub(super) fn run<H: HookSet>(
                     v
                 };
 
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
 
                 trace!("Instruction i64.extend8_s [{}] -> [{}]", v, res);
             }
@@ -2568,7 +4363,7 @@ pub(super) fn run<H: HookSet>(
                     v
                 };
 
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
 
                 trace!("Instruction i64.extend16_s [{}] -> [{}]", v, res);
             }
@@ -2586,7 +4381,7 @@ pub(super) fn run<H: HookSet>(
                     v
                 };
 
-                stack.push_value(res.into());
+                stack.push_value(res.into())?;
 
                 trace!("Instruction i64.extend32_s [{}] -> [{}]", v, res);
             }
@@ -2596,7 +4391,7 @@ pub(super) fn run<H: HookSet>(
             }
         }
     }
-    Ok(())
+    Ok(None)
 }
 
 //helper function for avoiding code duplication at intraprocedural jumps
@@ -2608,30 +4403,349 @@ fn do_sidetable_control_transfer(
 ) {
     let sidetable_entry = &current_sidetable[*current_stp];
 
-    // TODO fix this corner cutting implementation
-    let jump_vals = stack
-        .pop_tail_iter(sidetable_entry.valcnt)
-        .collect::<Vec<_>>();
-    stack.pop_n_values(sidetable_entry.popcnt);
-
-    for val in jump_vals {
-        stack.push_value(val);
-    }
+    stack.remove_below_top(sidetable_entry.valcnt, sidetable_entry.popcnt);
 
     *current_stp = (*current_stp as isize + sidetable_entry.delta_stp) as usize;
     wasm.pc = (wasm.pc as isize + sidetable_entry.delta_pc) as usize;
 }
 
+/// Call the host function `host_idx` with `params` and push its results onto the value stack, where the host function
+/// is the function `func_idx` of type `func_ty` in the module `module_idx`. Returns whether the host function yielded.
+///
+/// Like functions defined in a WASM module, the host function is reported to [`HookSet::call_hook`] and
+/// [`HookSet::return_hook`].
+#[cfg_attr(not(feature = "hooks"), allow(unused_variables))]
+#[allow(clippy::too_many_arguments)]
+fn call_host_function<H: HookSet>(
+    host_functions: &mut [HostFuncInst],
+    host_idx: usize,
+    module_idx: usize,
+    func_idx: FuncIdx,
+    func_ty: &FuncType,
+    params: Vec<Value>,
+    stack: &mut Stack,
+    hooks: &mut H,
+    hook_yield: &mut bool,
+) -> Result<bool, RuntimeError> {
+    #[cfg(feature = "hooks")]
+    hook_action(hooks.call_hook(module_idx, func_idx, &params), hook_yield)?;
+    let (returns, yielded) = match host_functions[host_idx].func.call(&params) {
+        HostCallOutcome::Return(returns) => (returns, false),
+        HostCallOutcome::Yield(returns) => (returns, true),
+    };
+
+    let return_types = returns.iter().map(Value::to_ty);
+    if !return_types.eq(func_ty.returns.valtypes.iter().copied()) {
+        return Err(RuntimeError::HostFunctionSignatureMismatch);
+    }
+
+    #[cfg(feature = "hooks")]
+    hook_action(
+        hooks.return_hook(module_idx, func_idx, &returns),
+        hook_yield,
+    )?;
+    for value in returns {
+        stack.push_value(value)?;
+    }
+    Ok(yielded)
+}
+
+/// Call [`HookSet::call_hook`] for the function of the current [`CallFrame`](crate::value_stack::CallFrame), which
+/// was just entered with the first `param_count` of its locals as arguments
+#[cfg(feature = "hooks")]
+fn call_hook<H: HookSet>(hooks: &mut H, stack: &Stack, param_count: usize) -> HookAction {
+    let frame = stack.current_stackframe();
+    hooks.call_hook(
+        frame.module_idx,
+        frame.func_idx,
+        &frame.locals.values()[..param_count],
+    )
+}
+
+/// Call [`HookSet::return_hook`] for the function of the current [`CallFrame`](crate::value_stack::CallFrame), whose
+/// results are on top of the value stack
+#[cfg(feature = "hooks")]
+fn return_hook<H: HookSet>(hooks: &mut H, stack: &Stack) -> HookAction {
+    let frame = stack.current_stackframe();
+    let values = stack.frame_values(stack.callframe_count() - 1);
+    hooks.return_hook(
+        frame.module_idx,
+        frame.func_idx,
+        &values[values.len() - frame.return_value_count..],
+    )
+}
+
+/// Call [`HookSet::unwind_hook`] for the function of the current [`CallFrame`](crate::value_stack::CallFrame)
+#[cfg(feature = "hooks")]
+fn unwind_hook<H: HookSet>(hooks: &mut H, stack: &Stack) -> HookAction {
+    let frame = stack.current_stackframe();
+    hooks.unwind_hook(frame.module_idx, frame.func_idx)
+}
+
+/// Apply the [`HookAction`] returned by a hook, i. e. trap or remember to suspend execution before the next
+/// instruction
+#[cfg(feature = "hooks")]
+fn hook_action(action: HookAction, hook_yield: &mut bool) -> Result<(), RuntimeError> {
+    match action {
+        HookAction::Continue => {}
+        HookAction::Trap(reason) => return Err(RuntimeError::HookTrap(reason)),
+        HookAction::Yield => *hook_yield = true,
+    }
+    Ok(())
+}
+
+/// Throw the exception `exn`, unwinding the stack to the innermost `try_table` with a matching catch clause
+///
+/// `exn_addr` is the address of `exn` if it was thrown by `throw_ref`, or `None` for a new exception thrown by the
+/// module `module_idx`. New exceptions are only stored if they are passed as exnref to a catch clause or not caught at
+/// all, otherwise no reference to them can exist. `pc` is the program counter within the function of the current
+/// stackframe. Returns the module, program counter and stp at which execution continues, after the caught exception
+/// was passed to the catch clause's label.
+#[cfg_attr(not(feature = "hooks"), allow(unused_variables))]
+#[allow(clippy::too_many_arguments)]
+fn throw<H: HookSet>(
+    modules: &mut [ExecutionInfo],
+    stack: &mut Stack,
+    exn: ExnInst,
+    exn_addr: Option<ExnAddr>,
+    module_idx: usize,
+    pc: usize,
+    hooks: &mut H,
+    hook_yield: &mut bool,
+) -> Result<(usize, usize, usize), RuntimeError> {
+    while let Some(handler) = stack.pop_handler(pc) {
+        let module = &modules[handler.module_idx];
+        let sidetable = &module
+            .store
+            .funcs
+            .get(handler.func_idx)
+            .unwrap_validated()
+            .try_into_local()
+            .unwrap_validated()
+            .sidetable;
+
+        let catch_entries = &sidetable[handler.stp..handler.stp + handler.catch_count];
+        for (i, sidetable_entry) in catch_entries.iter().enumerate() {
+            let catch_stp = handler.stp + i;
+            // the entry is copied, as storing the exception below requires the modules to be mutable
+            let sidetable_entry = sidetable_entry.clone();
+            let catch = sidetable_entry.catch.unwrap_validated();
+            // tags are compared by identity, as the same tag may have different indices in different modules
+            let catches_payload = match catch.tag_idx() {
+                Some(tag_idx) => {
+                    let tag = module.store.tags.get(tag_idx).unwrap_validated();
+                    if !tag.is_same(&exn.tag) {
+                        continue;
+                    }
+                    true
+                }
+                None => false,
+            };
+
+            #[cfg(feature = "hooks")]
+            for frame in stack.call_frames()[handler.frame_count..].iter().rev() {
+                hook_action(
+                    hooks.unwind_hook(frame.module_idx, frame.func_idx),
+                    hook_yield,
+                )?;
+            }
+            stack.unwind_to_handler(&handler);
+            if catches_payload {
+                for value in &exn.payload {
+                    stack.push_value(*value)?;
+                }
+            }
+            if catch.passes_exnref() {
+                let exn_addr =
+                    exn_addr.unwrap_or_else(|| store_exception(modules, module_idx, exn, stack));
+                stack.push_value(Value::Ref(Ref::Exn(exn_addr)))?;
+            }
+
+            // the catch clause branches to its label as if `br` was executed at the start of the try_table's body
+            stack.remove_below_top(sidetable_entry.valcnt, sidetable_entry.popcnt);
+
+            return Ok((
+                handler.module_idx,
+                (handler.body.start as isize + sidetable_entry.delta_pc) as usize,
+                (catch_stp as isize + sidetable_entry.delta_stp) as usize,
+            ));
+        }
+    }
+
+    Err(RuntimeError::UncaughtException(exn_addr.unwrap_or_else(
+        || store_exception(modules, module_idx, exn, stack),
+    )))
+}
+
 #[inline(always)]
-fn get_store_index(memarg: &MemArg, relative_address: u32) -> Result<MemIdx, RuntimeError> {
-    memarg
+fn get_store_index(memarg: &MemArg, relative_address: u64) -> Result<MemIdx, RuntimeError> {
+    let address = memarg
         .offset
-        // The spec states that this should be a 33 bit integer, e.g. it is not legal to wrap if the
-        // sum of offset and relative_address exceeds u32::MAX. To emulate this behavior, we use a
-        // checked addition.
+        // The spec states that this should be a 33 bit integer (65 bit for 64-bit memories), e.g. it is not legal to
+        // wrap if the sum of offset and relative_address exceeds the maximum address. For 32-bit memories both fit
+        // into 32 bits, so only 64-bit memories can overflow here. To emulate this behavior, we use a checked
+        // addition.
         // See: https://webassembly.github.io/spec/core/syntax/instructions.html#memory-instructions
         .checked_add(relative_address)
-        .ok_or(RuntimeError::MemoryAccessOutOfBounds)?
+        .ok_or(RuntimeError::MemoryAccessOutOfBounds)?;
+    address_to_mem_idx(address)
+}
+
+/// Convert an address of a 32-bit or 64-bit memory to an index into the
+/// [LinearMemory]
+///
+/// Addresses which do not fit into the host's address space are out of bounds in any case.
+#[inline(always)]
+fn address_to_mem_idx(address: u64) -> Result<MemIdx, RuntimeError> {
+    address
         .try_into()
         .map_err(|_| RuntimeError::MemoryAccessOutOfBounds)
 }
+
+/// Pop a `v128` operand off the stack and push the result of `f` applied to it
+fn unary_v128(stack: &mut Stack, f: impl FnOnce([u8; 16]) -> [u8; 16]) -> Result<(), RuntimeError> {
+    let v: [u8; 16] = stack.pop_value(ValType::VecType).into();
+    let res = f(v);
+    trace!("Instruction: v128 unop [{v:?}] -> [{res:?}]");
+    stack.push_value(res.into())
+}
+
+/// Pop two `v128` operands off the stack and push the result of `f` applied to them
+fn binary_v128(
+    stack: &mut Stack,
+    f: impl FnOnce([u8; 16], [u8; 16]) -> [u8; 16],
+) -> Result<(), RuntimeError> {
+    let v2: [u8; 16] = stack.pop_value(ValType::VecType).into();
+    let v1: [u8; 16] = stack.pop_value(ValType::VecType).into();
+    let res = f(v1, v2);
+    trace!("Instruction: v128 binop [{v1:?} {v2:?}] -> [{res:?}]");
+    stack.push_value(res.into())
+}
+
+/// Pop a `v128` and an `i32` shift amount off the stack and push the result of `f` applied to them
+fn shift_v128(
+    stack: &mut Stack,
+    f: impl FnOnce([u8; 16], u32) -> [u8; 16],
+) -> Result<(), RuntimeError> {
+    let s: u32 = stack.pop_value(ValType::NumType(NumType::I32)).into();
+    let v: [u8; 16] = stack.pop_value(ValType::VecType).into();
+    let res = f(v, s);
+    trace!("Instruction: v128 shift [{v:?} {s}] -> [{res:?}]");
+    stack.push_value(res.into())
+}
+
+/// Pop a `v128` operand off the stack and push whether `f` holds for it as an `i32`
+fn test_v128(stack: &mut Stack, f: impl FnOnce([u8; 16]) -> bool) -> Result<(), RuntimeError> {
+    bitmask_v128(stack, |v| f(v).into())
+}
+
+/// Pop a `v128` operand off the stack and push the `i32` computed by `f` from it
+fn bitmask_v128(stack: &mut Stack, f: impl FnOnce([u8; 16]) -> u32) -> Result<(), RuntimeError> {
+    let v: [u8; 16] = stack.pop_value(ValType::VecType).into();
+    let res = f(v);
+    trace!("Instruction: v128 test [{v:?}] -> [{res}]");
+    stack.push_value(Value::I32(res))
+}
+
+/// The type of the operands and the width in bytes of the datum accessed by an atomic load, store or
+/// read-modify-write instruction
+///
+/// The opcodes of each of these instruction families consist of seven variants in the same order: the full-width
+/// `i32` and `i64` variants, followed by the narrow `i32` variants and the narrow `i64` variants.
+fn atomic_access_shape(second_instr: u32) -> (NumType, usize) {
+    use crate::core::reader::types::opcode::fe_extensions::I32_ATOMIC_LOAD;
+
+    match (second_instr - I32_ATOMIC_LOAD) % 7 {
+        0 => (NumType::I32, 4),
+        1 => (NumType::I64, 8),
+        2 => (NumType::I32, 1),
+        3 => (NumType::I32, 2),
+        4 => (NumType::I64, 1),
+        5 => (NumType::I64, 2),
+        _ => (NumType::I64, 4),
+    }
+}
+
+/// Pop an `i32` or `i64` operand of an atomic instruction off the stack, zero-extended to `u64`
+fn pop_atomic_operand(stack: &mut Stack, num_type: NumType) -> u64 {
+    match stack.pop_value(ValType::NumType(num_type)) {
+        Value::I32(x) => x.into(),
+        Value::I64(x) => x,
+        _ => unreachable_validated!(),
+    }
+}
+
+/// Atomically replace the datum of `width` bytes at `idx` by the result of `f`, see
+/// [LinearMemory::atomic_rmw]. Both the datum passed to `f` and the returned previous datum are zero-extended to
+/// `u64`, and the result of `f` is truncated to `width` bytes.
+fn atomic_rmw(
+    mem: &LinearMemory,
+    idx: MemIdx,
+    width: usize,
+    f: impl FnOnce(u64) -> u64,
+) -> Result<u64, RuntimeError> {
+    match width {
+        1 => mem
+            .atomic_rmw(idx, |old: u8| f(old.into()) as u8)
+            .map(u64::from),
+        2 => mem
+            .atomic_rmw(idx, |old: u16| f(old.into()) as u16)
+            .map(u64::from),
+        4 => mem
+            .atomic_rmw(idx, |old: u32| f(old.into()) as u32)
+            .map(u64::from),
+        _ => mem.atomic_rmw(idx, f),
+    }
+}
+
+/// Pop an address operand of a memory with the given [AddrType] off the stack
+#[inline(always)]
+fn pop_address(stack: &mut Stack, addr_type: AddrType) -> u64 {
+    match stack.pop_value(addr_type.into()) {
+        Value::I32(address) => address.into(),
+        Value::I64(address) => address,
+        _ => unreachable_validated!(),
+    }
+}
+
+/// Create an address operand of a memory with the given [AddrType]. Addresses of 32-bit memories are truncated, so
+/// that `u64::MAX` turns into `-1` for both address types.
+#[inline(always)]
+fn address_value(address: u64, addr_type: AddrType) -> Value {
+    match addr_type {
+        AddrType::I32 => Value::I32(address as u32),
+        AddrType::I64 => Value::I64(address),
+    }
+}
+
+/// The fuel required to execute the instruction at `wasm.pc`, without advancing `wasm.pc`.
+///
+/// Every instruction costs one unit of fuel. Bulk instructions additionally cost one unit per byte or element they
+/// operate on, which is always given by the top value on the stack.
+#[inline(always)]
+fn fuel_cost(wasm: &mut WasmReader, stack: &Stack) -> u64 {
+    use crate::core::reader::types::opcode::{fc_extensions::*, FC_EXTENSIONS};
+
+    if wasm.peek_u8().unwrap_validated() != FC_EXTENSIONS {
+        return 1;
+    }
+
+    // the sub-opcode is decoded like the dispatch of the interpreter loop does, as it may be a non-minimal LEB128
+    let pc = wasm.pc;
+    let _ = wasm.read_u8();
+    let second_instr_byte = wasm.read_var_u32().unwrap_validated() as u8;
+    wasm.pc = pc;
+
+    match second_instr_byte {
+        MEMORY_INIT | MEMORY_COPY | MEMORY_FILL | TABLE_INIT | TABLE_COPY | TABLE_FILL => {
+            // the count is an `i64` for bulk instructions on 64-bit memories
+            let n: u64 = match stack.peek_unknown_value().unwrap_validated() {
+                Value::I32(n) => n.into(),
+                Value::I64(n) => n,
+                _ => unreachable_validated!(),
+            };
+            n.saturating_add(1)
+        }
+        _ => 1,
+    }
+}
@@ -1,5 +1,6 @@
 use core::{cell::UnsafeCell, mem};
 
+use alloc::collections::TryReserveError;
 use alloc::vec::Vec;
 
 use crate::{
@@ -69,8 +70,15 @@ pub struct LinearMemory<const PAGE_SIZE: usize = { crate::Limits::MEM_PAGE_SIZE
     inner_data: RwSpinLock<Vec<UnsafeCell<u8>>>,
 }
 
+// Safety: All access to the inner data happens through raw pointers while holding the lock, and growing the linear
+// memory requires the write lock. The race conditions of unsynchronized, concurrent accesses are tolerated as explained
+// in the "Unsafe Note" above, which is what enables sharing memories between threads.
+unsafe impl<const PAGE_SIZE: usize> Sync for LinearMemory<PAGE_SIZE> {}
+
 /// Type to express the page count
-pub type PageCountTy = u16;
+///
+/// 64-bit memories may have far more than `u16::MAX` pages, so this is as large as the host's address space allows.
+pub type PageCountTy = usize;
 
 impl<const PAGE_SIZE: usize> LinearMemory<PAGE_SIZE> {
     /// Size of a page in the linear memory, measured in bytes
@@ -86,28 +94,37 @@ impl<const PAGE_SIZE: usize> LinearMemory<PAGE_SIZE> {
         }
     }
 
-    /// Create a new, empty [`LinearMemory`]
-    pub fn new_with_initial_pages(pages: PageCountTy) -> Self {
-        let size_bytes = Self::PAGE_SIZE * pages as usize;
-        let mut data = Vec::with_capacity(size_bytes);
+    /// Create a new [`LinearMemory`] of `pages` zeroed pages
+    ///
+    /// Fails if the host can not provide the memory.
+    pub fn new_with_initial_pages(pages: PageCountTy) -> Result<Self, TryReserveError> {
+        // Saturating, as a size exceeding the address space is rejected by the reservation anyway
+        let size_bytes = Self::PAGE_SIZE.saturating_mul(pages);
+        let mut data = Vec::new();
+        data.try_reserve_exact(size_bytes)?;
         data.resize_with(size_bytes, || UnsafeCell::new(0));
 
-        Self {
+        Ok(Self {
             inner_data: RwSpinLock::new(data),
-        }
+        })
     }
 
     /// Grow the [`LinearMemory`] by a number of pages
-    pub fn grow(&self, pages_to_add: PageCountTy) {
+    ///
+    /// Fails without changing the [`LinearMemory`] if the host can not provide the additional memory.
+    pub fn grow(&self, pages_to_add: PageCountTy) -> Result<(), TryReserveError> {
         let mut lock_guard = self.inner_data.write();
-        let prior_length_bytes = lock_guard.len();
-        let new_length_bytes = prior_length_bytes + Self::PAGE_SIZE * pages_to_add as usize;
+        // Saturating, as a size exceeding the address space is rejected by the reservation anyway
+        let additional_bytes = Self::PAGE_SIZE.saturating_mul(pages_to_add);
+        lock_guard.try_reserve_exact(additional_bytes)?;
+        let new_length_bytes = lock_guard.len() + additional_bytes;
         lock_guard.resize_with(new_length_bytes, || UnsafeCell::new(0));
+        Ok(())
     }
 
     /// Get the number of pages currently allocated to this [`LinearMemory`]
     pub fn pages(&self) -> PageCountTy {
-        PageCountTy::try_from(self.inner_data.read().len() / PAGE_SIZE).unwrap()
+        self.inner_data.read().len() / PAGE_SIZE
     }
 
     /// Get the length in bytes currently allocated to this [`LinearMemory`]
@@ -211,6 +228,65 @@ impl<const PAGE_SIZE: usize> LinearMemory<PAGE_SIZE> {
         Ok(T::from_le_bytes(bytes))
     }
 
+    /// At a given index, atomically replace a datum in the [`LinearMemory`] by the result of `f`, returning the
+    /// previous datum
+    ///
+    /// Unlike [`LinearMemory::load`] and [`LinearMemory::store`], the access acquires the write lock. Thus it can not
+    /// interleave with any other access to the linear memory, including the ones of other threads. This is how the
+    /// atomic instructions of the threads proposal are implemented, without requiring the host to support atomics of
+    /// every width. Atomic accesses must be naturally aligned, otherwise
+    /// [`RuntimeError::UnalignedAtomic`] is returned.
+    pub fn atomic_rmw<const N: usize, T: LittleEndianBytes<N> + Copy>(
+        &self,
+        index: MemIdx,
+        f: impl FnOnce(T) -> T,
+    ) -> Result<T, RuntimeError> {
+        let lock_guard = self.inner_data.write();
+
+        // The same overflow avoiding bounds check as in `load` and `store`
+        if N > lock_guard.len() || index > lock_guard.len() - N {
+            error!("atomic access would extend beyond the end of the linear memory");
+            return Err(RuntimeError::MemoryAccessOutOfBounds);
+        }
+
+        if index % N != 0 {
+            error!("atomic access is not naturally aligned");
+            return Err(RuntimeError::UnalignedAtomic);
+        }
+
+        let ptr = lock_guard[index].get();
+        let mut bytes = [0; N];
+
+        // Safety argument:
+        //
+        // - the bounds check above guarantees that all `N` bytes starting at `index` are part of the linear memory
+        // - the write lock guarantees that no other access to the linear memory happens concurrently
+        // - as in `load` and `store`, no `&` or `&mut` is ever created on the `u8`s inside the `UnsafeCell`s
+        unsafe { ptr.copy_to_nonoverlapping(bytes.as_mut_ptr(), N) };
+        let old = T::from_le_bytes(bytes);
+        let new = f(old);
+        unsafe { ptr.copy_from_nonoverlapping(new.to_le_bytes().as_ref().as_ptr(), N) };
+
+        Ok(old)
+    }
+
+    /// From a given index, atomically load a datum in the [`LinearMemory`], see [`LinearMemory::atomic_rmw`]
+    pub fn atomic_load<const N: usize, T: LittleEndianBytes<N> + Copy>(
+        &self,
+        index: MemIdx,
+    ) -> Result<T, RuntimeError> {
+        self.atomic_rmw(index, |old: T| old)
+    }
+
+    /// At a given index, atomically store a datum in the [`LinearMemory`], see [`LinearMemory::atomic_rmw`]
+    pub fn atomic_store<const N: usize, T: LittleEndianBytes<N> + Copy>(
+        &self,
+        index: MemIdx,
+        value: T,
+    ) -> Result<(), RuntimeError> {
+        self.atomic_rmw(index, |_: T| value).map(|_| ())
+    }
+
     /// Implementation of the behavior described in
     /// <https://webassembly.github.io/spec/core/exec/instructions.html#xref-syntax-instructions-syntax-instr-memory-mathsf-memory-fill>.
     /// Note, that the WASM spec defines the behavior by recursion, while our implementation uses
@@ -396,13 +472,13 @@ mod test {
     #[test]
     fn new_grow() {
         let lin_mem = LinearMemory::<PAGE_SIZE>::new();
-        lin_mem.grow(1);
+        lin_mem.grow(1).unwrap();
         assert_eq!(lin_mem.pages(), 1);
     }
 
     #[test]
     fn debug_print() {
-        let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(1);
+        let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(1).unwrap();
         assert_eq!(lin_mem.pages(), 1);
 
         let expected_length = "LinearMemory { inner_data: [  ] }".len() + PAGE_SIZE * "0, ".len();
@@ -419,7 +495,7 @@ mod test {
         let x: i8 = -127;
         let highest_legal_offset = PAGE_SIZE - mem::size_of::<i8>();
         for offset in 0..MemIdx::try_from(highest_legal_offset).unwrap() {
-            let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(PAGES);
+            let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(PAGES).unwrap();
 
             lin_mem.store(offset, x).unwrap();
 
@@ -438,7 +514,7 @@ mod test {
         let x: f32 = 13.0;
         let highest_legal_offset = PAGE_SIZE - mem::size_of::<f32>();
         for offset in 0..MemIdx::try_from(highest_legal_offset).unwrap() {
-            let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(PAGES);
+            let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(PAGES).unwrap();
 
             lin_mem.store(offset, x).unwrap();
 
@@ -457,7 +533,7 @@ mod test {
         let x: f64 = f64::MIN;
         let highest_legal_offset = PAGE_SIZE - mem::size_of::<f64>();
         for offset in 0..MemIdx::try_from(highest_legal_offset).unwrap() {
-            let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(PAGES);
+            let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(PAGES).unwrap();
 
             lin_mem.store(offset, x).unwrap();
 
@@ -476,7 +552,7 @@ mod test {
         let x: f64 = f64::NAN;
         let highest_legal_offset = PAGE_SIZE - mem::size_of::<f64>();
         for offset in 0..MemIdx::try_from(highest_legal_offset).unwrap() {
-            let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(PAGES);
+            let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(PAGES).unwrap();
 
             lin_mem.store(offset, x).unwrap();
 
@@ -499,7 +575,7 @@ mod test {
         let pages = 1;
         let lowest_illegal_offset = PAGE_SIZE - mem::size_of::<u128>() + 1;
         let lowest_illegal_offset = MemIdx::try_from(lowest_illegal_offset).unwrap();
-        let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(pages);
+        let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(pages).unwrap();
 
         lin_mem.store(lowest_illegal_offset, x).unwrap();
     }
@@ -513,7 +589,7 @@ mod test {
         let pages = 0;
         let lowest_illegal_offset = PAGE_SIZE - mem::size_of::<u8>() + 1;
         let lowest_illegal_offset = MemIdx::try_from(lowest_illegal_offset).unwrap();
-        let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(pages);
+        let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(pages).unwrap();
 
         lin_mem.store(lowest_illegal_offset, x).unwrap();
     }
@@ -526,7 +602,7 @@ mod test {
         let pages = 1;
         let lowest_illegal_offset = PAGE_SIZE - mem::size_of::<u128>() + 1;
         let lowest_illegal_offset = MemIdx::try_from(lowest_illegal_offset).unwrap();
-        let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(pages);
+        let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(pages).unwrap();
 
         let _x: u128 = lin_mem.load(lowest_illegal_offset).unwrap();
     }
@@ -539,7 +615,7 @@ mod test {
         let pages = 0;
         let lowest_illegal_offset = PAGE_SIZE - mem::size_of::<u8>() + 1;
         let lowest_illegal_offset = MemIdx::try_from(lowest_illegal_offset).unwrap();
-        let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(pages);
+        let lin_mem = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(pages).unwrap();
 
         let _x: u8 = lin_mem.load(lowest_illegal_offset).unwrap();
     }
@@ -547,8 +623,8 @@ mod test {
     #[test]
     #[should_panic]
     fn copy_out_of_bounds() {
-        let lin_mem_0 = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(2);
-        let lin_mem_1 = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(1);
+        let lin_mem_0 = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(2).unwrap();
+        let lin_mem_1 = LinearMemory::<PAGE_SIZE>::new_with_initial_pages(1).unwrap();
         lin_mem_0.copy(0, &lin_mem_1, 0, PAGE_SIZE + 1).unwrap();
     }
 }
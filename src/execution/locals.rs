@@ -37,4 +37,9 @@ impl Locals {
     pub fn get_mut(&mut self, idx: usize) -> &mut Value {
         self.data.get_mut(idx).unwrap_validated()
     }
+
+    /// All values, i.e. the parameters followed by the declared locals
+    pub fn values(&self) -> &[Value] {
+        &self.data
+    }
 }
@@ -1,14 +1,29 @@
-use crate::{core::reader::types::export::ExportDesc, execution::execution_info::ExecutionInfo};
+use crate::{
+    core::reader::types::{export::ExportDesc, FuncType},
+    execution::{execution_info::ExecutionInfo, store::HostFuncInst},
+};
 use alloc::{collections::btree_map::BTreeMap, string::String, vec::Vec};
 
+/// The implementation an imported function was resolved to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FunctionTarget {
+    /// `(foreign_module_idx, function_foreign_idx)`, i.e. a function exported by another WASM module.
+    Module(usize, usize),
+    /// An index into the host functions registered with the [RuntimeInstance](crate::RuntimeInstance).
+    Host(usize),
+}
+
 pub struct Lut {
-    /// function_lut\[local_module_idx\]\[function_local_idx\] = (foreign_module_idx, function_foreign_idx)
+    /// function_lut\[local_module_idx\]\[function_local_idx\] = target
     ///
     /// - Module A imports a function "foo". Inside module A, the function has the index "function_local_idx". Module A
     ///   is assigned the index "local_module_idx".
     /// - Module B exports a function "foo". Inside module B, the function has the index "function_foreign_idx". Module
-    ///   B is assigned the index "foreign_module_idx".
-    function_lut: Vec<Vec<(usize, usize)>>,
+    ///   B is assigned the index "foreign_module_idx". The target is `FunctionTarget::Module(foreign_module_idx,
+    ///   function_foreign_idx)`.
+    /// - If no module exports "foo", but a host function "foo" with the same signature is registered at index
+    ///   "host_idx", the target is `FunctionTarget::Host(host_idx)`.
+    function_lut: Vec<Vec<FunctionTarget>>,
 }
 
 impl Lut {
@@ -17,10 +32,15 @@ impl Lut {
     /// # Arguments
     /// - `modules`: The modules to link together.
     /// - `module_map`: A map from module name to module index within the `modules` array.
+    /// - `host_functions`: The host functions which may be used to resolve imports not provided by any module.
     ///
     /// # Returns
     /// A new linker lookup-table. Can return `None` if there are import directives that cannot be resolved.
-    pub fn new(modules: &[ExecutionInfo], module_map: &BTreeMap<String, usize>) -> Option<Self> {
+    pub fn new(
+        modules: &[ExecutionInfo],
+        module_map: &BTreeMap<String, usize>,
+        host_functions: &[HostFuncInst],
+    ) -> Option<Self> {
         let mut function_lut = Vec::new();
         for module in modules {
             let module_lut = module
@@ -29,12 +49,24 @@ impl Lut {
                 .iter()
                 .filter_map(|f| f.try_into_imported())
                 .map(|import| {
+                    let func_ty = module.fn_types.get(import.ty)?;
                     Self::manual_lookup(
                         modules,
                         module_map,
                         &import.module_name,
                         &import.function_name,
+                        func_ty,
                     )
+                    .map(|(module_idx, func_idx)| FunctionTarget::Module(module_idx, func_idx))
+                    .or_else(|| {
+                        Self::manual_lookup_host(
+                            host_functions,
+                            &import.module_name,
+                            &import.function_name,
+                            func_ty,
+                        )
+                        .map(FunctionTarget::Host)
+                    })
                 })
                 .collect::<Option<Vec<_>>>()?;
 
@@ -56,9 +88,11 @@ impl Lut {
     ///
     /// # Returns
     /// - `None`, if the indicies are out of bound
-    /// - `Some(export_module_idx, export_function_idx)`, where the new indicies are the indicies of the module which
-    ///   contains the implementation of the imported function, and the implementation has the returned index within.
-    pub fn lookup(&self, module_idx: usize, function_idx: usize) -> Option<(usize, usize)> {
+    /// - `Some(FunctionTarget::Module(export_module_idx, export_function_idx))`, where the new indicies are the
+    ///   indicies of the module which contains the implementation of the imported function, and the implementation has
+    ///   the returned index within.
+    /// - `Some(FunctionTarget::Host(host_idx))`, if the imported function is implemented by a host function.
+    pub fn lookup(&self, module_idx: usize, function_idx: usize) -> Option<FunctionTarget> {
         self.function_lut
             .get(module_idx)?
             .get(function_idx)
@@ -75,18 +109,20 @@ impl Lut {
     /// - `module_map`: A map from module name to module index within the `modules` array.
     /// - `module_name`: The name of the module which imports the function.
     /// - `function_name`: The name of the function to import.
+    /// - `func_ty`: The type the importing module expects the function to have.
     ///
     /// # Returns
-    /// - `None`, if the module or function is not found.
+    /// - `None`, if the module or function is not found, or if the function does not have the type `func_ty`.
     /// - `Some(export_module_idx, export_function_idx)`, where the new indicies are the indicies of the module which
-    ///    contains the implementation of the imported function, and the implementation has the returned index within.
-    ///    Note that this function returns the first matching function, if there are multiple functions with the same
-    ///    name.
+    ///   contains the implementation of the imported function, and the implementation has the returned index within.
+    ///   Note that this function returns the first matching function, if there are multiple functions with the same
+    ///   name.
     pub fn manual_lookup(
         modules: &[ExecutionInfo],
         module_map: &BTreeMap<String, usize>,
         module_name: &str,
         function_name: &str,
+        func_ty: &FuncType,
     ) -> Option<(usize, usize)> {
         let module_idx = module_map.get(module_name)?;
         let module = &modules[*module_idx];
@@ -109,5 +145,30 @@ impl Lut {
                     None
                 }
             })
+            .filter(|(_, func_idx)| module.fn_types[module.store.funcs[*func_idx].ty()] == *func_ty)
+    }
+
+    /// Manually lookup a host function by its module and function name.
+    ///
+    /// # Arguments
+    /// - `host_functions`: The registered host functions.
+    /// - `module_name`: The module name the host function was registered under.
+    /// - `function_name`: The name of the function to import.
+    /// - `func_ty`: The type the importing module expects the function to have.
+    ///
+    /// # Returns
+    /// - `None`, if no host function with this name and type exists.
+    /// - `Some(host_idx)`, the index of the first matching host function.
+    pub fn manual_lookup_host(
+        host_functions: &[HostFuncInst],
+        module_name: &str,
+        function_name: &str,
+        func_ty: &FuncType,
+    ) -> Option<usize> {
+        host_functions.iter().position(|host_func| {
+            host_func.module_name == module_name
+                && host_func.function_name == function_name
+                && host_func.ty == *func_ty
+        })
     }
 }
@@ -1,16 +1,26 @@
+use alloc::boxed::Box;
 use alloc::collections::btree_map::BTreeMap;
 use alloc::string::{String, ToString};
+use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
 
 use const_interpreter_loop::{run_const, run_const_span};
+use debugger::{Breakpoints, DebugFrame, Step};
+use exceptions::{Exception, TagRef};
 use execution_info::ExecutionInfo;
 use function_ref::FunctionRef;
+use host_function::HostFunction;
 use interpreter_loop::run;
 use locals::Locals;
 use lut::Lut;
-use store::{DataInst, ElemInst, ImportedFuncInst, LocalFuncInst, TableInst};
-use value::{ExternAddr, FuncAddr, Ref};
+use resumable::{InvocationState, Resumable, SuspendReason};
+use store::{
+    DataInst, ElemInst, HostFuncInst, HostMemInst, ImportedFuncInst, LocalFuncInst, TableInst,
+};
+use threads::{SharedMemory, Waiter};
+use trap::{Trap, TrapFrame};
+use value::{ExnAddr, FuncAddr, Ref};
 use value_stack::Stack;
 
 use crate::core::error::StoreInstantiationError;
@@ -18,39 +28,69 @@ use crate::core::indices::MemIdx;
 use crate::core::reader::types::element::{ElemItems, ElemMode};
 use crate::core::reader::types::export::ExportDesc;
 use crate::core::reader::types::import::ImportDesc;
+use crate::core::reader::types::FuncType;
 use crate::core::reader::WasmReader;
 use crate::execution::assert_validated::UnwrapValidatedExt;
+#[cfg(feature = "hooks")]
+use crate::execution::hooks::HookAction;
 use crate::execution::hooks::{EmptyHookSet, HookSet};
-use crate::execution::store::{FuncInst, GlobalInst, MemInst, Store};
+use crate::execution::store::{FuncInst, GlobalInst, MemInst, Store, TagInst};
 use crate::execution::value::Value;
 use crate::validation::code::read_declared_locals;
 use crate::value::InteropValueList;
-use crate::{RefType, Result as CustomResult, RuntimeError, ValType, ValidationInfo};
+use crate::{
+    Limits, Result as CustomResult, RuntimeError, SourceLocation, ValType, ValidationInfo,
+};
 
 // TODO
 pub(crate) mod assert_validated;
 pub mod const_interpreter_loop;
+#[cfg(feature = "hooks")]
+pub mod coverage;
+pub mod debugger;
+pub mod exceptions;
 pub(crate) mod execution_info;
 pub mod function_ref;
+pub mod gdb_stub;
 pub mod hooks;
+pub mod host_function;
 mod interpreter_loop;
 pub(crate) mod linear_memory;
 pub(crate) mod locals;
 pub(crate) mod lut;
+pub mod profiler;
+pub mod resumable;
+pub(crate) mod simd;
 pub(crate) mod store;
+pub mod threads;
+pub mod trap;
 pub mod value;
 pub mod value_stack;
 
 /// The default module name if a [RuntimeInstance] was created using [RuntimeInstance::new].
 pub const DEFAULT_MODULE: &str = "__interpreter_default__";
 
+/// The default maximum number of nested function calls of a single invocation, see
+/// [RuntimeInstance::set_max_call_depth].
+pub const DEFAULT_MAX_CALL_DEPTH: usize = 16384;
+
+/// The default maximum number of values on the value stack of a single invocation, see
+/// [RuntimeInstance::set_max_value_stack_size].
+pub const DEFAULT_MAX_VALUE_STACK_SIZE: usize = 1 << 20;
+
 pub struct RuntimeInstance<'b, H = EmptyHookSet>
 where
     H: HookSet,
 {
     pub modules: Vec<ExecutionInfo<'b>>,
     module_map: BTreeMap<String, usize>,
+    host_functions: Vec<HostFuncInst>,
+    host_memories: Vec<HostMemInst>,
+    waiter: Option<Arc<dyn Waiter>>,
     lut: Option<Lut>,
+    max_call_depth: usize,
+    max_value_stack_size: usize,
+    breakpoints: Breakpoints,
     pub hook_set: H,
 }
 
@@ -65,6 +105,14 @@ impl<'b> RuntimeInstance<'b, EmptyHookSet> {
     ) -> CustomResult<Self> {
         Self::new_with_hooks(module_name, validation_info, EmptyHookSet)
     }
+
+    /// Create a [RuntimeInstance] without any modules
+    ///
+    /// This allows registering host functions and shared memories before adding the first module via
+    /// [RuntimeInstance::add_module]. Unlike the other constructors, the start function of that module is not executed.
+    pub fn new_empty() -> Self {
+        Self::new_empty_with_hooks(EmptyHookSet)
+    }
 }
 
 impl<'b, H> RuntimeInstance<'b, H>
@@ -78,12 +126,7 @@ where
     ) -> CustomResult<Self> {
         trace!("Starting instantiation of bytecode");
 
-        let mut instance = RuntimeInstance {
-            modules: Vec::new(),
-            module_map: BTreeMap::new(),
-            lut: None,
-            hook_set,
-        };
+        let mut instance = Self::new_empty_with_hooks(hook_set);
         instance.add_module(module_name, validation_info)?;
 
         // TODO: how do we handle the start function, if we don't have a LUT yet?
@@ -103,6 +146,22 @@ where
         Ok(instance)
     }
 
+    /// Create a [RuntimeInstance] without any modules, like [RuntimeInstance::new_empty]
+    pub fn new_empty_with_hooks(hook_set: H) -> Self {
+        RuntimeInstance {
+            modules: Vec::new(),
+            module_map: BTreeMap::new(),
+            host_functions: Vec::new(),
+            host_memories: Vec::new(),
+            waiter: None,
+            lut: None,
+            max_call_depth: DEFAULT_MAX_CALL_DEPTH,
+            max_value_stack_size: DEFAULT_MAX_VALUE_STACK_SIZE,
+            breakpoints: Breakpoints::new(),
+            hook_set,
+        }
+    }
+
     pub fn get_function_by_name(
         &self,
         module_name: &str,
@@ -149,50 +208,231 @@ where
         })
     }
 
+    /// The name of a function in the name section of its module, if there is one
+    ///
+    /// Unlike [FunctionRef]s obtained by [RuntimeInstance::get_function_by_name], this name is meant for diagnostics
+    /// and also available for functions which are not exported.
+    pub fn function_debug_name(&self, module_name: &str, function_idx: usize) -> Option<&str> {
+        let module_idx = *self.module_map.get(module_name)?;
+        self.modules[module_idx].names.function_name(function_idx)
+    }
+
+    /// The source location of the instruction at position `pc` in the module's binary, found in the module's DWARF
+    /// debug information
+    ///
+    /// Positions are the same as [TrapFrame::code_offset](trap::TrapFrame::code_offset).
+    pub fn source_location(&self, module_name: &str, pc: usize) -> Option<SourceLocation> {
+        let module_idx = *self.module_map.get(module_name)?;
+        self.modules[module_idx].dwarf.lookup(pc)
+    }
+
     pub fn add_module(
         &mut self,
         module_name: &str,
         validation_info: &'_ ValidationInfo<'b>,
     ) -> CustomResult<()> {
-        let store = Self::init_store(validation_info)?;
+        self.check_imported_functions(validation_info)?;
+        let imported_tables = self.resolve_imported_tables(validation_info)?;
+        let imported_memories = self.resolve_imported_memories(validation_info)?;
+        let imported_globals = self.resolve_imported_globals(validation_info)?;
+        let imported_tags = self.resolve_imported_tags(validation_info)?;
+        let store = Self::init_store(
+            validation_info,
+            self.modules.len(),
+            imported_tables,
+            imported_memories,
+            imported_globals,
+            imported_tags,
+        )?;
         let exec_info = ExecutionInfo::new(
             module_name,
             validation_info.wasm,
             validation_info.types.clone(),
             store,
+            validation_info.names.clone(),
+            validation_info.dwarf.clone(),
         );
 
         self.module_map
             .insert(module_name.to_string(), self.modules.len());
         self.modules.push(exec_info);
 
-        self.lut = Lut::new(&self.modules, &self.module_map);
+        self.lut = Lut::new(&self.modules, &self.module_map, &self.host_functions);
 
         Ok(())
     }
 
+    /// Register a function implemented by the embedder, which WASM modules can import via `module_name` and
+    /// `function_name`.
+    ///
+    /// Imports are resolved to exports of WASM modules first. Only if no module named `module_name` exports
+    /// `function_name`, a host function with the same names and a type equal to `func_ty` is used.
+    ///
+    /// Note that the start function of a module is executed when the [RuntimeInstance] is created, so it cannot call
+    /// host functions registered afterwards.
+    pub fn add_host_function(
+        &mut self,
+        module_name: &str,
+        function_name: &str,
+        func_ty: FuncType,
+        func: impl HostFunction + 'static,
+    ) {
+        self.host_functions.push(HostFuncInst {
+            ty: func_ty,
+            module_name: module_name.to_string(),
+            function_name: function_name.to_string(),
+            func: Box::new(func),
+        });
+
+        self.lut = Lut::new(&self.modules, &self.module_map, &self.host_functions);
+    }
+
+    /// Get a handle to the shared memory exported as `name` by the module `module_name`, which can be sent to other
+    /// threads and registered with their [RuntimeInstance]s via [RuntimeInstance::add_shared_memory].
+    ///
+    /// # Returns
+    /// - `Err(RuntimeError::ModuleNotFound)`, if the module is not found.
+    /// - `Err(RuntimeError::MemoryNotFound)`, if the module does not export a memory named `name`, or if that memory
+    ///   is not shared.
+    pub fn get_shared_memory(
+        &self,
+        module_name: &str,
+        name: &str,
+    ) -> Result<SharedMemory, RuntimeError> {
+        let module_idx = *self
+            .module_map
+            .get(module_name)
+            .ok_or(RuntimeError::ModuleNotFound)?;
+        let store = &self.modules[module_idx].store;
+
+        store
+            .exports
+            .iter()
+            .find_map(|export| match export.desc {
+                ExportDesc::MemIdx(mem_idx) if export.name == name => Some(&store.mems[mem_idx]),
+                _ => None,
+            })
+            .filter(|mem_inst| mem_inst.ty.shared)
+            .map(|mem_inst| SharedMemory {
+                mem: mem_inst.clone(),
+            })
+            .ok_or(RuntimeError::MemoryNotFound)
+    }
+
+    /// Register a shared memory, usually created by a [RuntimeInstance] on another thread, which WASM modules can
+    /// import via `module_name` and `name`.
+    ///
+    /// Like host functions, imports are resolved to exports of WASM modules first. Memories are linked when a module
+    /// is added, so the shared memory must be registered before adding the modules importing it.
+    pub fn add_shared_memory(&mut self, module_name: &str, name: &str, memory: SharedMemory) {
+        self.host_memories.push(HostMemInst {
+            module_name: module_name.to_string(),
+            name: name.to_string(),
+            mem: memory.mem,
+        });
+    }
+
+    /// Get the tag exported as `name` by the module `module_name`, to identify the tag of an
+    /// [Exception] which was not caught.
+    ///
+    /// # Returns
+    /// - `Err(RuntimeError::ModuleNotFound)`, if the module is not found.
+    /// - `Err(RuntimeError::TagNotFound)`, if the module does not export a tag named `name`.
+    pub fn get_tag_by_name(&self, module_name: &str, name: &str) -> Result<TagRef, RuntimeError> {
+        let module_idx = *self
+            .module_map
+            .get(module_name)
+            .ok_or(RuntimeError::ModuleNotFound)?;
+        let store = &self.modules[module_idx].store;
+
+        store
+            .exports
+            .iter()
+            .find_map(|export| match export.desc {
+                ExportDesc::TagIdx(tag_idx) if export.name == name => {
+                    Some(TagRef::new(&store.tags[tag_idx]))
+                }
+                _ => None,
+            })
+            .ok_or(RuntimeError::TagNotFound)
+    }
+
+    /// Decode the exception referenced by `exn_addr`, e.g. the one of a
+    /// [RuntimeError::UncaughtException], into its tag and payload
+    ///
+    /// Exceptions are reclaimed once they are no longer referenced by the running invocation, or the globals, tables and
+    /// stored exceptions of any module, so an uncaught exception must be decoded before any other function is invoked.
+    /// Returns `None` for null references, reclaimed exceptions and addresses which do not refer to an exception of
+    /// this instance.
+    pub fn exception(&self, exn_addr: ExnAddr) -> Option<Exception> {
+        let exn = self
+            .modules
+            .get(exn_addr.module_idx)?
+            .store
+            .exception(exn_addr)?;
+
+        Some(Exception {
+            tag: TagRef::new(&exn.tag),
+            payload: exn.payload.clone(),
+        })
+    }
+
+    /// Use `waiter` to suspend and wake up threads executing `memory.atomic.wait32`, `memory.atomic.wait64` and
+    /// `memory.atomic.notify`. Without a waiter, waiting traps with [RuntimeError::WaiterNotConfigured] and
+    /// notifying wakes up no threads.
+    pub fn set_waiter(&mut self, waiter: Arc<dyn Waiter>) {
+        self.waiter = Some(waiter);
+    }
+
+    /// The maximum number of nested function calls, including the invoked function itself
+    pub fn max_call_depth(&self) -> usize {
+        self.max_call_depth
+    }
+
+    /// Limit the number of nested function calls of every following invocation. Calling a function beyond this
+    /// depth traps with [RuntimeError::StackSmash].
+    pub fn set_max_call_depth(&mut self, max_call_depth: usize) {
+        self.max_call_depth = max_call_depth;
+    }
+
+    /// The maximum number of values on the value stack
+    pub fn max_value_stack_size(&self) -> usize {
+        self.max_value_stack_size
+    }
+
+    /// Limit the number of values on the value stack of every following invocation. The parameters and locals of all
+    /// active functions count towards this limit. Pushing a value or calling a function beyond it traps with
+    /// [RuntimeError::StackSmash].
+    pub fn set_max_value_stack_size(&mut self, max_value_stack_size: usize) {
+        self.max_value_stack_size = max_value_stack_size;
+    }
+
     pub fn invoke<Param: InteropValueList, Returns: InteropValueList>(
         &mut self,
         function_ref: &FunctionRef,
         params: Param,
-    ) -> Result<Returns, RuntimeError> {
+    ) -> Result<Returns, Trap> {
+        self.invoke_with_fuel(function_ref, params, None)
+    }
+
+    /// Invokes a function like [RuntimeInstance::invoke], but bounds its execution by `maybe_fuel`, if it is `Some`.
+    ///
+    /// Every executed instruction consumes one unit of fuel. Bulk instructions such as `memory.fill`, `memory.copy` or
+    /// `table.init` additionally consume one unit per byte or element they operate on. If the fuel is exhausted,
+    /// execution traps with [RuntimeError::OutOfFuel].
+    pub fn invoke_with_fuel<Param: InteropValueList, Returns: InteropValueList>(
+        &mut self,
+        function_ref: &FunctionRef,
+        params: Param,
+        maybe_fuel: Option<u64>,
+    ) -> Result<Returns, Trap> {
         // First, verify that the function reference is valid
         let (module_idx, func_idx) = self.verify_function_ref(function_ref)?;
 
         // -=-= Verification =-=-
         trace!("{:?}", self.modules[module_idx].store.funcs);
 
-        let func_inst = self.modules[module_idx]
-            .store
-            .funcs
-            .get(func_idx)
-            .ok_or(RuntimeError::FunctionNotFound)?
-            .try_into_local()
-            .ok_or(RuntimeError::FunctionNotFound)?;
-        let func_ty = self.modules[module_idx]
-            .fn_types
-            .get(func_inst.ty)
-            .unwrap_validated();
+        let func_ty = self.get_local_function_type(module_idx, func_idx)?;
 
         // Check correct function parameters and return types
         if func_ty.params.valtypes != Param::TYS {
@@ -202,44 +442,11 @@ where
             panic!("Invalid `Returns` generics");
         }
 
-        // Prepare a new stack with the locals for the entry function
-        let mut stack = Stack::new();
-        let locals = Locals::new(
-            params.into_values().into_iter(),
-            func_inst.locals.iter().cloned(),
-        );
+        let mut resumable =
+            self.create_resumable(module_idx, func_idx, params.into_values(), maybe_fuel)?;
+        self.run_to_completion(&mut resumable)?;
 
-        // setting `usize::MAX` as return address for the outermost function ensures that we
-        // observably fail upon errornoeusly continuing execution after that function returns.
-        stack.push_stackframe(
-            module_idx,
-            func_idx,
-            func_ty,
-            locals,
-            usize::MAX,
-            usize::MAX,
-        );
-
-        let mut current_module_idx = module_idx;
-        // Run the interpreter
-        run(
-            &mut self.modules,
-            &mut current_module_idx,
-            self.lut.as_ref().ok_or(RuntimeError::UnmetImport)?,
-            &mut stack,
-            EmptyHookSet,
-        )?;
-
-        // Pop return values from stack
-        let return_values = Returns::TYS
-            .iter()
-            .rev()
-            .map(|ty| stack.pop_value(*ty))
-            .collect::<Vec<Value>>();
-
-        // Values are reversed because they were popped from stack one-by-one. Now reverse them back
-        let reversed_values = return_values.into_iter().rev();
-        let ret: Returns = Returns::from_values(reversed_values);
+        let ret: Returns = Returns::from_values(self.pop_return_values(&mut resumable).into_iter());
         debug!("Successfully invoked function");
         Ok(ret)
     }
@@ -250,22 +457,24 @@ where
         function_ref: &FunctionRef,
         params: Vec<Value>,
         ret_types: &[ValType],
-    ) -> Result<Vec<Value>, RuntimeError> {
+    ) -> Result<Vec<Value>, Trap> {
+        self.invoke_dynamic_with_fuel(function_ref, params, ret_types, None)
+    }
+
+    /// Invokes a function like [RuntimeInstance::invoke_dynamic], but bounds its execution by `maybe_fuel`, if it is
+    /// `Some`. See [RuntimeInstance::invoke_with_fuel] for how fuel is consumed.
+    pub fn invoke_dynamic_with_fuel(
+        &mut self,
+        function_ref: &FunctionRef,
+        params: Vec<Value>,
+        ret_types: &[ValType],
+        maybe_fuel: Option<u64>,
+    ) -> Result<Vec<Value>, Trap> {
         // First, verify that the function reference is valid
         let (module_idx, func_idx) = self.verify_function_ref(function_ref)?;
 
         // -=-= Verification =-=-
-        let func_inst = self.modules[module_idx]
-            .store
-            .funcs
-            .get(func_idx)
-            .ok_or(RuntimeError::FunctionNotFound)?
-            .try_into_local()
-            .ok_or(RuntimeError::FunctionNotFound)?;
-        let func_ty = self.modules[module_idx]
-            .fn_types
-            .get(func_inst.ty)
-            .unwrap_validated();
+        let func_ty = self.get_local_function_type(module_idx, func_idx)?;
 
         // Verify that the given parameters match the function parameters
         let param_types = params.iter().map(|v| v.to_ty()).collect::<Vec<_>>();
@@ -279,45 +488,10 @@ where
             panic!("Invalid return types for function");
         }
 
-        // Prepare a new stack with the locals for the entry function
-        let mut stack = Stack::new();
-        let locals = Locals::new(params.into_iter(), func_inst.locals.iter().cloned());
-        stack.push_stackframe(module_idx, func_idx, func_ty, locals, 0, 0);
-
-        let mut currrent_module_idx = module_idx;
-        // Run the interpreter
-        run(
-            &mut self.modules,
-            &mut currrent_module_idx,
-            self.lut.as_ref().ok_or(RuntimeError::UnmetImport)?,
-            &mut stack,
-            EmptyHookSet,
-        )?;
-
-        let func_inst = self.modules[module_idx]
-            .store
-            .funcs
-            .get(func_idx)
-            .ok_or(RuntimeError::FunctionNotFound)?
-            .try_into_local()
-            .ok_or(RuntimeError::FunctionNotFound)?;
-        let func_ty = self.modules[module_idx]
-            .fn_types
-            .get(func_inst.ty)
-            .unwrap_validated();
+        let mut resumable = self.create_resumable(module_idx, func_idx, params, maybe_fuel)?;
+        self.run_to_completion(&mut resumable)?;
 
-        // Pop return values from stack
-        let return_values = func_ty
-            .returns
-            .valtypes
-            .iter()
-            .rev()
-            .map(|ty| stack.pop_value(*ty))
-            .collect::<Vec<Value>>();
-
-        // Values are reversed because they were popped from stack one-by-one. Now reverse them back
-        let reversed_values = return_values.into_iter().rev();
-        let ret = reversed_values.collect();
+        let ret = self.pop_return_values(&mut resumable);
         debug!("Successfully invoked function");
         Ok(ret)
     }
@@ -338,22 +512,12 @@ where
         &mut self,
         function_ref: &FunctionRef,
         params: Vec<Value>,
-    ) -> Result<Vec<Value>, RuntimeError> {
+    ) -> Result<Vec<Value>, Trap> {
         // First, verify that the function reference is valid
         let (module_idx, func_idx) = self.verify_function_ref(function_ref)?;
 
         // -=-= Verification =-=-
-        let func_inst = self.modules[module_idx]
-            .store
-            .funcs
-            .get(func_idx)
-            .ok_or(RuntimeError::FunctionNotFound)?
-            .try_into_local()
-            .ok_or(RuntimeError::FunctionNotFound)?;
-        let func_ty = self.modules[module_idx]
-            .fn_types
-            .get(func_inst.ty)
-            .unwrap_validated();
+        let func_ty = self.get_local_function_type(module_idx, func_idx)?;
 
         // Verify that the given parameters match the function parameters
         let param_types = params.iter().map(|v| v.to_ty()).collect::<Vec<_>>();
@@ -362,21 +526,221 @@ where
             panic!("Invalid parameters for function");
         }
 
-        // Prepare a new stack with the locals for the entry function
-        let mut stack = Stack::new();
-        let locals = Locals::new(params.into_iter(), func_inst.locals.iter().cloned());
-        stack.push_stackframe(module_idx, func_idx, func_ty, locals, 0, 0);
+        let mut resumable = self.create_resumable(module_idx, func_idx, params, None)?;
+        self.run_to_completion(&mut resumable)?;
+
+        let ret = self.pop_return_values(&mut resumable);
+        debug!("Successfully invoked function");
+        Ok(ret)
+    }
+
+    /// Invokes a function with the given parameters, such that its execution can be suspended and resumed later on.
+    ///
+    /// Execution is suspended when `maybe_fuel` is exhausted (see [RuntimeInstance::invoke_with_fuel] for how fuel is
+    /// consumed), or when a host function yields (see [HostCallOutcome::Yield](host_function::HostCallOutcome::Yield)).
+    ///
+    /// # Returns
+    /// - `Ok(InvocationState::Finished(values))`, if the function returned `values` without being suspended.
+    /// - `Ok(InvocationState::Suspended(resumable))`, if execution was suspended. It can be continued by passing
+    ///   `resumable` to [RuntimeInstance::resume], after refueling it via [Resumable::set_fuel] if required.
+    /// - `Err(_)`, if the function reference is invalid or execution trapped.
+    pub fn invoke_resumable(
+        &mut self,
+        function_ref: &FunctionRef,
+        params: Vec<Value>,
+        maybe_fuel: Option<u64>,
+    ) -> Result<InvocationState, Trap> {
+        // First, verify that the function reference is valid
+        let (module_idx, func_idx) = self.verify_function_ref(function_ref)?;
+
+        // -=-= Verification =-=-
+        let func_ty = self.get_local_function_type(module_idx, func_idx)?;
+
+        // Verify that the given parameters match the function parameters
+        let param_types = params.iter().map(|v| v.to_ty()).collect::<Vec<_>>();
+
+        if func_ty.params.valtypes != param_types {
+            panic!("Invalid parameters for function");
+        }
+
+        let resumable = self.create_resumable(module_idx, func_idx, params, maybe_fuel)?;
+        self.resume(resumable)
+    }
+
+    /// Continues the execution of a suspended invocation, exactly where it was suspended.
+    ///
+    /// # Returns
+    /// The same as [RuntimeInstance::invoke_resumable].
+    pub fn resume(&mut self, mut resumable: Resumable) -> Result<InvocationState, Trap> {
+        resumable.step = None;
+        self.continue_execution(resumable)
+    }
+
+    /// Continues the execution of a suspended invocation like [RuntimeInstance::resume], but stops again as soon as
+    /// the `step` is complete
+    ///
+    /// The next instruction is executed even if it has a breakpoint. Breakpoints of any following instructions stop
+    /// execution before the step is complete, which cancels the step.
+    ///
+    /// # Returns
+    /// The same as [RuntimeInstance::invoke_resumable], where a complete step suspends execution with
+    /// [SuspendReason::Step].
+    pub fn step(&mut self, mut resumable: Resumable, step: Step) -> Result<InvocationState, Trap> {
+        resumable.step = Some((step, resumable.stack.callframe_count()));
+        resumable.stopped_by_debugger = true;
+        self.continue_execution(resumable)
+    }
+
+    /// Stop the execution of resumable invocations before executing the instruction at position `pc` in the binary of
+    /// the module, see [RuntimeInstance::invoke_resumable]
+    ///
+    /// A breakpoint at a position without an instruction is never hit.
+    pub fn set_breakpoint(&mut self, module_name: &str, pc: usize) -> Result<(), RuntimeError> {
+        let module_idx = *self
+            .module_map
+            .get(module_name)
+            .ok_or(RuntimeError::ModuleNotFound)?;
+        self.breakpoints.insert((module_idx, pc));
+        Ok(())
+    }
+
+    /// Set a breakpoint at the first instruction of a local function, like [RuntimeInstance::set_breakpoint]
+    ///
+    /// # Returns
+    /// - `Ok(pc)`, the position of the function's first instruction
+    /// - `Err(RuntimeError::ModuleNotFound)`, if the module is not found.
+    /// - `Err(RuntimeError::FunctionNotFound)`, if there is no such function or it is imported.
+    pub fn set_function_breakpoint(
+        &mut self,
+        module_name: &str,
+        function_idx: usize,
+    ) -> Result<usize, RuntimeError> {
+        let module_idx = *self
+            .module_map
+            .get(module_name)
+            .ok_or(RuntimeError::ModuleNotFound)?;
+        let pc = self.modules[module_idx]
+            .store
+            .funcs
+            .get(function_idx)
+            .and_then(FuncInst::try_into_local)
+            .ok_or(RuntimeError::FunctionNotFound)?
+            .code_expr
+            .from();
+        self.breakpoints.insert((module_idx, pc));
+        Ok(pc)
+    }
+
+    /// Remove a breakpoint, returning whether it was set
+    pub fn remove_breakpoint(&mut self, module_name: &str, pc: usize) -> bool {
+        self.module_map
+            .get(module_name)
+            .is_some_and(|module_idx| self.breakpoints.remove(&(*module_idx, pc)))
+    }
+
+    pub fn clear_breakpoints(&mut self) {
+        self.breakpoints.clear();
+    }
+
+    /// Inspect the function a [Resumable] was suspended in
+    pub fn debug_frame<'a>(&'a self, resumable: &'a Resumable) -> DebugFrame<'a, 'b> {
+        self.caller_debug_frame(resumable, 0).unwrap_validated()
+    }
 
-        let mut currrent_module_idx = module_idx;
-        // Run the interpreter
-        run(
+    /// Inspect a function that is active in a [Resumable], where `depth` 0 is the function it was suspended in, 1
+    /// its caller and so on
+    ///
+    /// Returns `None` if fewer than `depth + 1` functions are active.
+    pub fn caller_debug_frame<'a>(
+        &'a self,
+        resumable: &'a Resumable,
+        depth: usize,
+    ) -> Option<DebugFrame<'a, 'b>> {
+        let call_frames = resumable.stack.call_frames();
+        let frame_idx = call_frames.len().checked_sub(depth + 1)?;
+        let frame = &call_frames[frame_idx];
+        let pc = call_frames
+            .get(frame_idx + 1)
+            .map_or(resumable.pc, |callee| callee.return_addr);
+        Some(DebugFrame {
+            module: &self.modules[frame.module_idx],
+            frame,
+            pc,
+            values: resumable.stack.frame_values(frame_idx),
+        })
+    }
+
+    /// The functions which are active in a [Resumable], the current one first and the invoked one last
+    pub fn backtrace(&self, resumable: &Resumable) -> Vec<TrapFrame> {
+        trap::frames(&self.modules, &resumable.stack, resumable.pc)
+    }
+
+    /// Run a [Resumable] until it returns, is suspended or traps
+    fn continue_execution(&mut self, mut resumable: Resumable) -> Result<InvocationState, Trap> {
+        if resumable.current_module_idx >= self.modules.len() {
+            return Err(RuntimeError::ModuleNotFound.into());
+        }
+
+        let result = run(
             &mut self.modules,
-            &mut currrent_module_idx,
             self.lut.as_ref().ok_or(RuntimeError::UnmetImport)?,
-            &mut stack,
-            EmptyHookSet,
-        )?;
+            &mut self.host_functions,
+            &mut resumable,
+            self.waiter.as_deref(),
+            &self.breakpoints,
+            &mut self.hook_set,
+        );
+
+        match self.attach_call_stack(result, &resumable)? {
+            None => {
+                let ret = self.pop_return_values(&mut resumable);
+                debug!("Successfully invoked function");
+                Ok(InvocationState::Finished(ret))
+            }
+            Some(reason) => {
+                debug!("Suspended function invocation: {reason:?}");
+                resumable.suspend_reason = Some(reason);
+                Ok(InvocationState::Suspended(resumable))
+            }
+        }
+    }
+
+    /// Get the type of a local function.
+    ///
+    /// # Returns
+    /// - `Ok(func_ty)`
+    /// - `Err(RuntimeError::FunctionNotFound)`, if there is no such function or it is imported.
+    fn get_local_function_type(
+        &self,
+        module_idx: usize,
+        func_idx: usize,
+    ) -> Result<&FuncType, RuntimeError> {
+        let func_inst = self.modules[module_idx]
+            .store
+            .funcs
+            .get(func_idx)
+            .ok_or(RuntimeError::FunctionNotFound)?
+            .try_into_local()
+            .ok_or(RuntimeError::FunctionNotFound)?;
 
+        Ok(self.modules[module_idx]
+            .fn_types
+            .get(func_inst.ty)
+            .unwrap_validated())
+    }
+
+    /// Prepare a new [Resumable], which starts executing the local function `func_idx` of module `module_idx` with the
+    /// given parameters. The parameters must already be verified to match the function's type.
+    ///
+    /// The entry of the function is reported to [HookSet::call_hook], as it is entered before execution starts. If the
+    /// hook yields, the [Resumable] is suspended before its first instruction.
+    fn create_resumable(
+        &mut self,
+        module_idx: usize,
+        func_idx: usize,
+        params: Vec<Value>,
+        maybe_fuel: Option<u64>,
+    ) -> Result<Resumable, RuntimeError> {
         let func_inst = self.modules[module_idx]
             .store
             .funcs
@@ -389,20 +753,110 @@ where
             .get(func_inst.ty)
             .unwrap_validated();
 
+        // Prepare a new stack with the locals for the entry function
+        let mut stack = Stack::new_with_limits(self.max_value_stack_size, self.max_call_depth);
+        let locals = Locals::new(params.into_iter(), func_inst.locals.iter().cloned());
+
+        // setting `usize::MAX` as return address for the outermost function ensures that we
+        // observably fail upon errornoeusly continuing execution after that function returns.
+        stack.push_stackframe(
+            module_idx,
+            func_idx,
+            func_ty,
+            locals,
+            usize::MAX,
+            usize::MAX,
+        )?;
+        #[cfg_attr(not(feature = "hooks"), allow(unused_mut))]
+        let mut hook_yield = false;
+        #[cfg(feature = "hooks")]
+        match self.hook_set.call_hook(
+            module_idx,
+            func_idx,
+            &stack.current_stackframe().locals.values()[..func_ty.params.valtypes.len()],
+        ) {
+            HookAction::Continue => {}
+            HookAction::Trap(reason) => return Err(RuntimeError::HookTrap(reason)),
+            HookAction::Yield => hook_yield = true,
+        }
+
+        Ok(Resumable {
+            stack,
+            current_module_idx: module_idx,
+            pc: func_inst.code_expr.from(),
+            stp: 0,
+            maybe_fuel,
+            required_fuel: 0,
+            root_function: (module_idx, func_idx),
+            suspend_reason: None,
+            step: None,
+            stopped_by_debugger: false,
+            hook_yield,
+        })
+    }
+
+    /// Run the interpreter until the invoked function returns. Yielding host functions and hooks do not suspend
+    /// execution, but running out of fuel traps with [RuntimeError::OutOfFuel].
+    fn run_to_completion(&mut self, resumable: &mut Resumable) -> Result<(), Trap> {
+        loop {
+            let result = run(
+                &mut self.modules,
+                self.lut.as_ref().ok_or(RuntimeError::UnmetImport)?,
+                &mut self.host_functions,
+                resumable,
+                self.waiter.as_deref(),
+                &Breakpoints::new(),
+                &mut self.hook_set,
+            );
+
+            match self.attach_call_stack(result, resumable)? {
+                None => return Ok(()),
+                // the debugger does not stop invocations which run to completion
+                Some(
+                    SuspendReason::HostYield
+                    | SuspendReason::HookYield
+                    | SuspendReason::Breakpoint
+                    | SuspendReason::Step,
+                ) => continue,
+                Some(SuspendReason::OutOfFuel) => return Err(RuntimeError::OutOfFuel.into()),
+            }
+        }
+    }
+
+    /// Attach the call stack of `resumable` to `result`, if it is a trap
+    fn attach_call_stack<T>(
+        &mut self,
+        result: Result<T, RuntimeError>,
+        resumable: &Resumable,
+    ) -> Result<T, Trap> {
+        result.map_err(|err| {
+            #[cfg(feature = "hooks")]
+            self.hook_set
+                .trap_hook(&err, resumable.current_module_idx, resumable.pc);
+            let trap = Trap::new(err, &self.modules, &resumable.stack, resumable.pc);
+            debug!("Execution trapped: {trap}");
+            trap
+        })
+    }
+
+    /// Pop the return values of the invoked function from the stack of a finished [Resumable].
+    fn pop_return_values(&self, resumable: &mut Resumable) -> Vec<Value> {
+        let (module_idx, func_idx) = resumable.root_function;
+        let func_ty = self
+            .get_local_function_type(module_idx, func_idx)
+            .unwrap_validated();
+
         // Pop return values from stack
         let return_values = func_ty
             .returns
             .valtypes
             .iter()
             .rev()
-            .map(|ty| stack.pop_value(*ty))
+            .map(|ty| resumable.stack.pop_value(*ty))
             .collect::<Vec<Value>>();
 
         // Values are reversed because they were popped from stack one-by-one. Now reverse them back
-        let reversed_values = return_values.into_iter().rev();
-        let ret = reversed_values.collect();
-        debug!("Successfully invoked function");
-        Ok(ret)
+        return_values.into_iter().rev().collect()
     }
 
     fn get_indicies(
@@ -491,7 +945,240 @@ where
         }
     }
 
-    fn init_store(validation_info: &ValidationInfo) -> CustomResult<Store> {
+    /// Find the entity exported as `name` by the module `module_name`
+    fn lookup_export(&self, module_name: &str, name: &str) -> CustomResult<(usize, &ExportDesc)> {
+        self.module_map
+            .get(module_name)
+            .and_then(|&module_idx| {
+                self.modules[module_idx]
+                    .store
+                    .exports
+                    .iter()
+                    .find(|export| export.name == name)
+                    .map(|export| (module_idx, &export.desc))
+            })
+            .ok_or_else(|| {
+                StoreInstantiationError::UnknownImport(module_name.to_string(), name.to_string())
+                    .into()
+            })
+    }
+
+    /// Check that all functions imported by a module match the type of the functions exported under their names by
+    /// the already added modules
+    ///
+    /// Imports which no module exports are left to be resolved by modules or host functions added later, see
+    /// [Lut::new].
+    ///
+    /// See <https://webassembly.github.io/spec/core/exec/modules.html#import-matching>
+    fn check_imported_functions(&self, validation_info: &ValidationInfo) -> CustomResult<()> {
+        validation_info
+            .imports
+            .iter()
+            .filter_map(|import| match &import.desc {
+                ImportDesc::Func(type_idx) => Some((import, type_idx)),
+                _ => None,
+            })
+            .try_for_each(|(import, type_idx)| {
+                let Ok((module_idx, desc)) = self.lookup_export(&import.module_name, &import.name)
+                else {
+                    return Ok(());
+                };
+                let module = &self.modules[module_idx];
+                let matches = match desc {
+                    ExportDesc::FuncIdx(func_idx) => {
+                        module.fn_types[module.store.funcs[*func_idx].ty()]
+                            == validation_info.types[*type_idx]
+                    }
+                    _ => false,
+                };
+
+                if !matches {
+                    return Err(StoreInstantiationError::IncompatibleImportType(
+                        import.module_name.clone(),
+                        import.name.clone(),
+                    )
+                    .into());
+                }
+                Ok(())
+            })
+    }
+
+    /// Link all tables imported by a module to the tables exported by the already added modules
+    ///
+    /// See <https://webassembly.github.io/spec/core/exec/modules.html#import-matching>
+    fn resolve_imported_tables(
+        &self,
+        validation_info: &ValidationInfo,
+    ) -> CustomResult<Vec<TableInst>> {
+        validation_info
+            .imports
+            .iter()
+            .filter_map(|import| match &import.desc {
+                ImportDesc::Table(table_type) => Some((import, table_type)),
+                _ => None,
+            })
+            .map(|(import, table_type)| {
+                let incompatible = || {
+                    StoreInstantiationError::IncompatibleImportType(
+                        import.module_name.clone(),
+                        import.name.clone(),
+                    )
+                };
+
+                let (module_idx, desc) = self.lookup_export(&import.module_name, &import.name)?;
+                let ExportDesc::TableIdx(table_idx) = desc else {
+                    return Err(incompatible().into());
+                };
+                let table_inst = &self.modules[module_idx].store.tables[*table_idx];
+
+                if table_inst.ty.et != table_type.et
+                    || !limits_match(table_inst.len(), table_inst.ty.lim, table_type.lim)
+                {
+                    return Err(incompatible().into());
+                }
+
+                Ok(table_inst.clone())
+            })
+            .collect()
+    }
+
+    /// Link all memories imported by a module to the memories exported by the already added modules, or to the shared
+    /// memories registered via [RuntimeInstance::add_shared_memory]
+    ///
+    /// See <https://webassembly.github.io/spec/core/exec/modules.html#import-matching>
+    fn resolve_imported_memories(
+        &self,
+        validation_info: &ValidationInfo,
+    ) -> CustomResult<Vec<MemInst>> {
+        validation_info
+            .imports
+            .iter()
+            .filter_map(|import| match &import.desc {
+                ImportDesc::Mem(mem_type) => Some((import, mem_type)),
+                _ => None,
+            })
+            .map(|(import, mem_type)| {
+                let incompatible = || {
+                    StoreInstantiationError::IncompatibleImportType(
+                        import.module_name.clone(),
+                        import.name.clone(),
+                    )
+                };
+
+                let mem_inst = match self.lookup_export(&import.module_name, &import.name) {
+                    Ok((module_idx, desc)) => {
+                        let ExportDesc::MemIdx(mem_idx) = desc else {
+                            return Err(incompatible().into());
+                        };
+                        &self.modules[module_idx].store.mems[*mem_idx]
+                    }
+                    Err(err) => self
+                        .host_memories
+                        .iter()
+                        .find(|host_mem| {
+                            host_mem.module_name == import.module_name
+                                && host_mem.name == import.name
+                        })
+                        .map(|host_mem| &host_mem.mem)
+                        .ok_or(err)?,
+                };
+
+                if mem_inst.ty.addr_type != mem_type.addr_type
+                    || mem_inst.ty.shared != mem_type.shared
+                    || !limits_match(mem_inst.size(), mem_inst.ty.limits, mem_type.limits)
+                {
+                    return Err(incompatible().into());
+                }
+
+                Ok(mem_inst.clone())
+            })
+            .collect()
+    }
+
+    /// Link all globals imported by a module to the globals exported by the already added modules
+    ///
+    /// See <https://webassembly.github.io/spec/core/exec/modules.html#import-matching>
+    fn resolve_imported_globals(
+        &self,
+        validation_info: &ValidationInfo,
+    ) -> CustomResult<Vec<GlobalInst>> {
+        validation_info
+            .imports
+            .iter()
+            .filter_map(|import| match &import.desc {
+                ImportDesc::Global(global_type) => Some((import, global_type)),
+                _ => None,
+            })
+            .map(|(import, global_type)| {
+                let incompatible = || {
+                    StoreInstantiationError::IncompatibleImportType(
+                        import.module_name.clone(),
+                        import.name.clone(),
+                    )
+                };
+
+                let (module_idx, desc) = self.lookup_export(&import.module_name, &import.name)?;
+                let ExportDesc::GlobalIdx(global_idx) = desc else {
+                    return Err(incompatible().into());
+                };
+                let global_inst = &self.modules[module_idx].store.globals[*global_idx];
+
+                // Both the value type and the mutability have to match exactly
+                if global_inst.ty != *global_type {
+                    return Err(incompatible().into());
+                }
+
+                Ok(global_inst.clone())
+            })
+            .collect()
+    }
+
+    /// Link all tags imported by a module to the tags exported by the already added modules
+    ///
+    /// See <https://webassembly.github.io/exception-handling/core/exec/modules.html#import-matching>
+    fn resolve_imported_tags(
+        &self,
+        validation_info: &ValidationInfo,
+    ) -> CustomResult<Vec<TagInst>> {
+        validation_info
+            .imports
+            .iter()
+            .filter_map(|import| match &import.desc {
+                ImportDesc::Tag(tag_type) => Some((import, tag_type)),
+                _ => None,
+            })
+            .map(|(import, tag_type)| {
+                let incompatible = || {
+                    StoreInstantiationError::IncompatibleImportType(
+                        import.module_name.clone(),
+                        import.name.clone(),
+                    )
+                };
+
+                let (module_idx, desc) = self.lookup_export(&import.module_name, &import.name)?;
+                let ExportDesc::TagIdx(tag_idx) = desc else {
+                    return Err(incompatible().into());
+                };
+                let tag_inst = &self.modules[module_idx].store.tags[*tag_idx];
+
+                // The parameters of both tags have to match exactly
+                if *tag_inst.ty != validation_info.types[tag_type.type_idx] {
+                    return Err(incompatible().into());
+                }
+
+                Ok(tag_inst.clone())
+            })
+            .collect()
+    }
+
+    fn init_store(
+        validation_info: &ValidationInfo,
+        module_idx: usize,
+        imported_tables: Vec<TableInst>,
+        imported_memories: Vec<MemInst>,
+        imported_globals: Vec<GlobalInst>,
+        imported_tags: Vec<TagInst>,
+    ) -> CustomResult<Store> {
         use crate::core::error::*;
         use StoreInstantiationError::*;
         let function_instances: Vec<FuncInst> = {
@@ -539,10 +1226,10 @@ where
         };
 
         // https://webassembly.github.io/spec/core/exec/modules.html#tables
-        let mut tables: Vec<TableInst> = validation_info
-            .tables
-            .iter()
-            .map(|ty| TableInst::new(*ty))
+        // Imported tables precede the local ones in the table index space
+        let tables: Vec<TableInst> = imported_tables
+            .into_iter()
+            .chain(validation_info.tables.iter().map(|ty| TableInst::new(*ty)))
             .collect();
 
         let mut passive_elem_indexes: Vec<usize> = vec![];
@@ -551,38 +1238,38 @@ where
             .elements
             .iter()
             .enumerate()
-            .filter_map(|(i, elem)| {
+            .map(|(i, elem)| {
                 trace!("Instantiating element {:#?}", elem);
 
-                let offsets = match &elem.init {
+                let references: Vec<Ref> = match &elem.init {
                     ElemItems::Exprs(_ref_type, init_exprs) => init_exprs
                         .iter()
                         .map(|expr| {
-                            get_address_offset(
-                                run_const_span(validation_info.wasm, expr, ()).unwrap_validated(),
-                            )
+                            match run_const_span(
+                                validation_info.wasm,
+                                expr,
+                                &imported_globals,
+                                module_idx,
+                            )?
+                            .unwrap_validated()
+                            {
+                                Value::Ref(rref) => Ok(rref),
+                                // We are already asserting that the expression results in a reference at validation time
+                                _ => unreachable!(),
+                            }
                         })
-                        .collect::<Vec<Option<u32>>>(),
-                    ElemItems::RefFuncs(indicies) => {
-                        // This branch gets taken when the elements are direct function references (i32 values), so we just return the indices
-                        indicies
-                            .iter()
-                            .map(|el| Some(*el))
-                            .collect::<Vec<Option<u32>>>()
-                    }
+                        .collect::<core::result::Result<_, RuntimeError>>()?,
+                    ElemItems::RefFuncs(indicies) => indicies
+                        .iter()
+                        .map(|func_idx| {
+                            Ref::Func(FuncAddr::new_in_module(
+                                module_idx,
+                                Some(*func_idx as usize),
+                            ))
+                        })
+                        .collect(),
                 };
 
-                let references: Vec<Ref> = offsets
-                    .iter()
-                    .map(|offset| {
-                        let offset = offset.as_ref().map(|offset| *offset as usize);
-                        match elem.ty() {
-                            RefType::FuncRef => Ref::Func(FuncAddr::new(offset)),
-                            RefType::ExternRef => Ref::Extern(ExternAddr::new(offset)),
-                        }
-                    })
-                    .collect();
-
                 let instance = ElemInst {
                     ty: elem.ty(),
                     references,
@@ -596,112 +1283,81 @@ where
                     // Also, the answer given by Andreas Rossberg (the editor of the WASM Spec - Release 2.0)
                     // Per https://stackoverflow.com/questions/78672934/what-is-the-purpose-of-a-wasm-declarative-element-segment
                     // "[...] The reason Wasm requires this (admittedly ugly) forward declaration is to support streaming compilation [...]"
-                    ElemMode::Declarative => None,
+                    ElemMode::Declarative => Ok(None),
                     ElemMode::Passive => {
                         passive_elem_indexes.push(i);
-                        Some(instance)
+                        Ok(Some(instance))
                     }
                     ElemMode::Active(active_elem) => {
                         let table_idx = active_elem.table_idx as usize;
 
-                        let offset =
-                            match run_const_span(validation_info.wasm, &active_elem.init_expr, ())
-                                .unwrap_validated()
-                            {
-                                Value::I32(offset) => offset as usize,
-                                // We are already asserting that on top of the stack there is an I32 at validation time
-                                _ => unreachable!(),
-                            };
-
-                        let table = &mut tables[table_idx];
+                        let offset = match run_const_span(
+                            validation_info.wasm,
+                            &active_elem.init_expr,
+                            &imported_globals,
+                            module_idx,
+                        )?
+                        .unwrap_validated()
+                        {
+                            Value::I32(offset) => offset as usize,
+                            // We are already asserting that on top of the stack there is an I32 at validation time
+                            _ => unreachable!(),
+                        };
+
+                        let table = &tables[table_idx];
                         // This can't be verified at validation-time because we don't keep track of actual values when validating expressions
                         //  we only keep track of the type of the values. As such we can't pop the exact value of an i32 from the validation stack
                         assert!(table.len() >= (offset + instance.len()));
 
-                        table.elem[offset..offset + instance.references.len()]
+                        table.elem.borrow_mut()[offset..offset + instance.references.len()]
                             .copy_from_slice(&instance.references);
 
-                        Some(instance)
+                        Ok(Some(instance))
                     }
                 }
             })
+            .collect::<CustomResult<Vec<_>>>()?
+            .into_iter()
+            .flatten()
             .collect();
 
-        let mut memory_instances: Vec<MemInst> = validation_info
-            .memories
-            .iter()
-            .map(|ty| MemInst::new(*ty))
-            .collect();
-
-        let import_memory_instances_len = {
-            let mut len: usize = 0;
-            for import in &validation_info.imports {
-                if let crate::core::reader::types::import::ImportDesc::Mem(_) = import.desc {
-                    len += 1;
-                }
-            }
-            len
-        };
-        match memory_instances
-            .len()
-            .checked_add(import_memory_instances_len)
-        {
-            None => {
-                return Err(Error::StoreInstantiationError(
-                    StoreInstantiationError::TooManyMemories(usize::MAX),
-                ))
-            }
-            Some(mem_instances) => {
-                if mem_instances > 1 {
-                    return Err(Error::UnsupportedProposal(Proposal::MultipleMemories));
-                }
-            }
-        };
+        // Imported memories precede the local ones in the memory index space
+        let memory_instances: Vec<MemInst> = imported_memories
+            .into_iter()
+            .map(Ok)
+            .chain(validation_info.memories.iter().map(|ty| MemInst::new(*ty)))
+            .collect::<core::result::Result<_, _>>()?;
 
         let data_sections: Vec<DataInst> = validation_info
             .data
             .iter()
             .map(|d| {
                 use crate::core::reader::types::data::DataMode;
-                use crate::NumType;
                 if let DataMode::Active(active_data) = d.mode.clone() {
                     let mem_idx = active_data.memory_idx;
-                    if mem_idx != 0 {
-                        todo!("Active data has memory_idx different than 0");
-                    }
-                    assert!(
-                        memory_instances.len() > mem_idx,
-                        "Multiple memories not yet supported"
-                    );
+
+                    // the memory index was checked during validation
+                    let mem_inst = memory_instances.get(mem_idx).unwrap_validated();
 
                     let boxed_value = {
                         let mut wasm = WasmReader::new(validation_info.wasm);
                         wasm.move_start_to(active_data.offset).unwrap_validated();
                         let mut stack = Stack::new();
-                        run_const(wasm, &mut stack, ());
-                        stack.pop_value(ValType::NumType(NumType::I32))
-                        // stack.peek_unknown_value().ok_or(MissingValueOnTheStack)?
+                        run_const(wasm, &mut stack, &imported_globals, module_idx)?;
+                        // the offset is an i64 for 64-bit memories
+                        stack.pop_value(mem_inst.ty.addr_type.into())
                     };
 
-                    // TODO: this shouldn't be a simple value, should it? I mean it can't be, but it can also be any type of ValType
-                    // TODO: also, do we need to forcefully make it i32?
-                    let offset: u32 = match boxed_value {
-                        Value::I32(val) => val,
-                        // Value::I64(val) => {
-                        //     if val > u32::MAX as u64 {
-                        //         return Err(I64ValueOutOfReach("data segment".to_owned()));
-                        //     }
-                        //     val as u32
-                        // }
-                        // TODO: implement all value types
-                        _ => todo!(),
+                    let offset: u64 = match boxed_value {
+                        Value::I32(val) => val.into(),
+                        Value::I64(val) => val,
+                        // We are already asserting the type of the offset at validation time
+                        _ => unreachable!(),
                     };
 
-                    let mem_inst = memory_instances.get_mut(mem_idx).unwrap();
-
-                    mem_inst
-                        .mem
-                        .init(offset as MemIdx, &d.init, 0, d.init.len())
+                    MemIdx::try_from(offset)
+                        .map_err(|_| RuntimeError::MemoryAccessOutOfBounds)
+                        .and_then(|offset| mem_inst.mem.init(offset, &d.init, 0, d.init.len()))
                         .map_err(|_| Error::StoreInstantiationError(ActiveDataWriteOutOfBounds))?;
                 }
                 Ok(DataInst {
@@ -710,26 +1366,39 @@ where
             })
             .collect::<Result<Vec<DataInst>>>()?;
 
-        let global_instances: Vec<GlobalInst> = validation_info
+        let mut stack = Stack::new();
+        let local_global_instances: Vec<GlobalInst> = validation_info
             .globals
             .iter()
-            .map({
-                let mut stack = Stack::new();
-                move |global| {
-                    let mut wasm = WasmReader::new(validation_info.wasm);
-                    // The place we are moving the start to should, by all means, be inside the wasm bytecode.
-                    wasm.move_start_to(global.init_expr).unwrap_validated();
-                    // We shouldn't need to clear the stack. If validation is correct, it will remain empty after execution.
-
-                    run_const(wasm, &mut stack, ());
-                    let value = stack.pop_value(global.ty.ty);
-
-                    GlobalInst {
-                        global: *global,
-                        value,
-                    }
-                }
+            .map(|global| {
+                let mut wasm = WasmReader::new(validation_info.wasm);
+                // The place we are moving the start to should, by all means, be inside the wasm bytecode.
+                wasm.move_start_to(global.init_expr).unwrap_validated();
+                // We shouldn't need to clear the stack. If validation is correct, it will remain empty after execution.
+
+                run_const(wasm, &mut stack, &imported_globals, module_idx)?;
+                let value = stack.pop_value(global.ty.ty);
+
+                Ok(GlobalInst::new(global.ty, value))
             })
+            .collect::<CustomResult<_>>()?;
+
+        // Imported globals precede the local ones in the global index space
+        let global_instances: Vec<GlobalInst> = imported_globals
+            .into_iter()
+            .chain(local_global_instances)
+            .collect();
+
+        // Imported tags precede the local ones in the tag index space. Every local tag is a new tag, even if another
+        // tag has the same type.
+        let tags: Vec<TagInst> = imported_tags
+            .into_iter()
+            .chain(
+                validation_info
+                    .tags
+                    .iter()
+                    .map(|tag_type| TagInst::new(validation_info.types[tag_type.type_idx].clone())),
+            )
             .collect();
 
         let exports = validation_info.exports.clone();
@@ -737,6 +1406,10 @@ where
             funcs: function_instances,
             mems: memory_instances,
             globals: global_instances,
+            tags,
+            exceptions: Vec::new(),
+            free_exceptions: Vec::new(),
+            exception_collection_threshold: exceptions::MIN_EXCEPTION_COLLECTION_THRESHOLD,
             data: data_sections,
             tables,
             elements,
@@ -746,25 +1419,16 @@ where
     }
 }
 
-/// Used for getting the offset of an address.
+/// Check whether an exported table or memory of the current size `exported_size` and type limits `exported` can be
+/// imported with the limits `imported`
 ///
-/// Related to the Active Elements
-///
-/// <https://webassembly.github.io/spec/core/syntax/modules.html#element-segments>
-///
-/// Since active elements need an offset given by a constant expression, in this case
-/// they can only be an i32 (which can be understood from either a [`Value::I32`] - but
-/// since we don't unbox the address of the reference, for us also a [`Value::Ref`] -
-/// or from a Global)
-fn get_address_offset(value: Value) -> Option<u32> {
-    match value {
-        Value::I32(val) => Some(val),
-        Value::Ref(rref) => match rref {
-            Ref::Extern(_) => todo!("Not yet implemented"),
-            // TODO: fix
-            Ref::Func(func_addr) => func_addr.addr.map(|addr| addr as u32),
-        },
-        // INFO: from wasmtime - implement only global
-        _ => unreachable!(),
-    }
+/// See <https://webassembly.github.io/spec/core/valid/types.html#match-limits>
+fn limits_match(exported_size: usize, exported: Limits, imported: Limits) -> bool {
+    let min_matches = exported_size >= imported.min as usize;
+    let max_matches = match (exported.max, imported.max) {
+        (_, None) => true,
+        (Some(exported_max), Some(imported_max)) => exported_max <= imported_max,
+        (None, Some(_)) => false,
+    };
+    min_matches && max_matches
 }
@@ -0,0 +1,251 @@
+//! Profiling of the executed WASM code, attributing the work done to the WASM call stack
+//!
+//! The `Profiler` is a [HookSet] which counts every executed instruction and requires the `hooks` feature, while the
+//! [SamplingProfiler] runs an invocation in slices of fuel and samples the call stack of the [Resumable] after each
+//! slice. Both result in a [Profile], which can be written as folded stacks for flamegraph tools via
+//! [Profile::folded].
+
+use alloc::collections::btree_map::BTreeMap;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::core::indices::FuncIdx;
+#[cfg(feature = "hooks")]
+use crate::execution::hooks::HookAction;
+use crate::execution::hooks::HookSet;
+use crate::execution::resumable::{InvocationState, Resumable, SuspendReason};
+use crate::execution::{RuntimeInstance, DEFAULT_MODULE};
+use crate::RuntimeError;
+#[cfg(feature = "hooks")]
+use crate::Value;
+
+/// Instruction counts or fuel, attributed to the call stacks they were spent in
+///
+/// A call stack consists of the module and function index of every active function, the outermost function first.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    stacks: BTreeMap<Vec<(usize, FuncIdx)>, u64>,
+}
+
+impl Profile {
+    /// All call stacks together with their weight, i. e. the instructions executed or the fuel consumed while the
+    /// innermost of their functions was running
+    pub fn stacks(&self) -> impl Iterator<Item = (&[(usize, FuncIdx)], u64)> {
+        self.stacks
+            .iter()
+            .map(|(stack, weight)| (stack.as_slice(), *weight))
+    }
+
+    /// The weight of all call stacks together
+    pub fn total(&self) -> u64 {
+        self.stacks.values().sum()
+    }
+
+    /// Write the profile in the folded stack format, with one line like `main;parse;read_byte 1234` per call stack
+    ///
+    /// Functions are named as in the name section of their module, or `func<index>`. The names of functions of modules
+    /// other than the [DEFAULT_MODULE] are prefixed with their module name, like `env::malloc`.
+    pub fn folded<H: HookSet>(&self, instance: &RuntimeInstance<H>) -> String {
+        let mut folded = String::new();
+        for (stack, weight) in &self.stacks {
+            for (i, &(module_idx, func_idx)) in stack.iter().enumerate() {
+                if i > 0 {
+                    folded.push(';');
+                }
+                let module = &instance.modules[module_idx];
+                if module.name != DEFAULT_MODULE {
+                    folded.push_str(&module.name);
+                    folded.push_str("::");
+                }
+                // semicolons separate the frames
+                match module.names.function_name(func_idx) {
+                    Some(name) => folded.push_str(&name.replace(';', ",")),
+                    None => write!(folded, "func{func_idx}").unwrap(),
+                }
+            }
+            writeln!(folded, " {weight}").unwrap();
+        }
+        folded
+    }
+
+    fn add(&mut self, stack: Vec<(usize, FuncIdx)>, weight: u64) {
+        *self.stacks.entry(stack).or_default() += weight;
+    }
+}
+
+/// A [HookSet] which attributes every executed instruction to the call stack it was executed in
+///
+/// The call stack is followed via the hooks for entering and leaving functions, so the profiler must be in place when
+/// an invocation starts. Suspended invocations must be finished before others are started, as their instructions
+/// would be attributed to the wrong call stacks otherwise.
+#[cfg(feature = "hooks")]
+#[derive(Debug, Clone, Default)]
+pub struct Profiler {
+    /// The call tree of all invocations, in which every node is a function called from the node's caller
+    nodes: Vec<CallNode>,
+    /// The node of every function called from a node, or from outside of any function
+    callees: BTreeMap<(Option<usize>, usize, FuncIdx), usize>,
+    /// The node of the running function, or `None` if no invocation is running
+    current: Option<usize>,
+}
+
+#[cfg(feature = "hooks")]
+#[derive(Debug, Clone)]
+struct CallNode {
+    caller: Option<usize>,
+    module_idx: usize,
+    func_idx: FuncIdx,
+    instructions: u64,
+}
+
+#[cfg(feature = "hooks")]
+impl Profiler {
+    /// The instructions executed so far, attributed to their call stacks
+    pub fn profile(&self) -> Profile {
+        let mut profile = Profile::default();
+        for (node_idx, node) in self.nodes.iter().enumerate() {
+            if node.instructions == 0 {
+                continue;
+            }
+            let mut stack = Vec::new();
+            let mut frame = Some(node_idx);
+            while let Some(node_idx) = frame {
+                let node = &self.nodes[node_idx];
+                stack.push((node.module_idx, node.func_idx));
+                frame = node.caller;
+            }
+            stack.reverse();
+            profile.add(stack, node.instructions);
+        }
+        profile
+    }
+
+    /// Leave the running function, returning to its caller
+    fn leave(&mut self) {
+        self.current = self
+            .current
+            .and_then(|node_idx| self.nodes[node_idx].caller);
+    }
+}
+
+#[cfg(feature = "hooks")]
+impl HookSet for Profiler {
+    fn instruction_hook(&mut self, _module_idx: usize, _bytecode: &[u8], _pc: usize) -> HookAction {
+        if let Some(node_idx) = self.current {
+            self.nodes[node_idx].instructions += 1;
+        }
+        HookAction::Continue
+    }
+
+    fn call_hook(&mut self, module_idx: usize, func_idx: FuncIdx, _args: &[Value]) -> HookAction {
+        let caller = self.current;
+        let nodes = &mut self.nodes;
+        let node_idx = *self
+            .callees
+            .entry((caller, module_idx, func_idx))
+            .or_insert_with(|| {
+                nodes.push(CallNode {
+                    caller,
+                    module_idx,
+                    func_idx,
+                    instructions: 0,
+                });
+                nodes.len() - 1
+            });
+        self.current = Some(node_idx);
+        HookAction::Continue
+    }
+
+    fn return_hook(
+        &mut self,
+        _module_idx: usize,
+        _func_idx: FuncIdx,
+        _results: &[Value],
+    ) -> HookAction {
+        self.leave();
+        HookAction::Continue
+    }
+
+    fn unwind_hook(&mut self, _module_idx: usize, _func_idx: FuncIdx) -> HookAction {
+        self.leave();
+        HookAction::Continue
+    }
+
+    fn trap_hook(&mut self, _error: &RuntimeError, _module_idx: usize, _pc: usize) {
+        self.current = None;
+    }
+}
+
+/// A profiler which samples the call stack of an invocation each time it consumed a given amount of fuel
+///
+/// The fuel consumed since the previous sample is attributed to the call stack of the sample. This requires no
+/// [HookSet], but suspends execution for every sample.
+#[derive(Debug, Clone)]
+pub struct SamplingProfiler {
+    interval: u64,
+    profile: Profile,
+}
+
+impl SamplingProfiler {
+    /// Create a profiler taking a sample every `interval` units of fuel, or every unit if `interval` is zero
+    pub fn new(interval: u64) -> Self {
+        SamplingProfiler {
+            interval: interval.max(1),
+            profile: Profile::default(),
+        }
+    }
+
+    /// The fuel consumed by all sampled invocations, attributed to the sampled call stacks
+    pub fn profile(&self) -> &Profile {
+        &self.profile
+    }
+
+    /// Consume the profiler, returning the fuel consumed by all sampled invocations like [SamplingProfiler::profile]
+    pub fn into_profile(self) -> Profile {
+        self.profile
+    }
+
+    /// Continue execution like [RuntimeInstance::resume], while taking samples
+    ///
+    /// The fuel of the [Resumable] still bounds execution, which is suspended with [SuspendReason::OutOfFuel] once it
+    /// is exhausted. All other suspensions are passed on as well. The fuel consumed after the last sample of a
+    /// finished invocation is not attributed to any call stack.
+    pub fn resume<H: HookSet>(
+        &mut self,
+        instance: &mut RuntimeInstance<H>,
+        mut resumable: Resumable,
+    ) -> Result<InvocationState, RuntimeError> {
+        let mut budget = resumable.fuel();
+        let mut slice = self.interval;
+        loop {
+            let (fuel, exhausts_budget) = match budget {
+                Some(budget) if budget <= slice => (budget, true),
+                _ => (slice, false),
+            };
+            resumable.set_fuel(Some(fuel));
+            let mut suspended = match instance.resume(resumable)? {
+                InvocationState::Finished(values) => return Ok(InvocationState::Finished(values)),
+                InvocationState::Suspended(suspended) => suspended,
+            };
+
+            let consumed = fuel - suspended.fuel().unwrap_or(fuel);
+            budget = budget.map(|budget| budget - consumed);
+            if consumed > 0 {
+                let stack = suspended.stack.call_frames().iter();
+                let stack = stack.map(|frame| (frame.module_idx, frame.func_idx));
+                self.profile.add(stack.collect(), consumed);
+            }
+
+            if exhausts_budget || suspended.suspend_reason() != Some(SuspendReason::OutOfFuel) {
+                suspended.set_fuel(budget);
+                return Ok(InvocationState::Suspended(suspended));
+            }
+            // an instruction costing more fuel than a slice is given a slice large enough to execute it
+            slice = self
+                .interval
+                .max(suspended.required_fuel().unwrap_or_default());
+            resumable = suspended;
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use alloc::vec::Vec;
+
+use crate::execution::{
+    debugger::Step, hooks::HookSet, trap::Trap, value_stack::Stack, RuntimeInstance,
+};
+use crate::Value;
+
+/// The reason why the execution of a [Resumable] was suspended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SuspendReason {
+    /// The fuel budget is too small to execute the next instruction
+    OutOfFuel,
+    /// A host function returned [HostCallOutcome::Yield](crate::execution::host_function::HostCallOutcome::Yield)
+    HostYield,
+    /// A hook returned [HookAction::Yield](crate::hooks::HookAction::Yield)
+    HookYield,
+    /// The next instruction has a breakpoint, see [RuntimeInstance::set_breakpoint]
+    Breakpoint,
+    /// The step requested via [RuntimeInstance::step] is complete
+    Step,
+}
+
+/// The result of an invocation which may be suspended
+pub enum InvocationState {
+    /// The invoked function returned these values
+    Finished(Vec<Value>),
+    /// Execution was suspended and can be continued via [Resumable::resume]
+    Suspended(Resumable),
+}
+
+/// A suspended invocation, holding all interpreter state needed to continue execution as if it was never suspended
+///
+/// A [Resumable] must only be resumed on the [RuntimeInstance] that created it. Adding further modules to that
+/// [RuntimeInstance] is fine.
+pub struct Resumable {
+    /// The value stack and call frames of the invocation
+    pub(crate) stack: Stack,
+    /// The module the next instruction belongs to
+    pub(crate) current_module_idx: usize,
+    /// The position of the next instruction in the module's WASM binary
+    pub(crate) pc: usize,
+    /// The sidetable pointer belonging to `pc`
+    pub(crate) stp: usize,
+    pub(crate) maybe_fuel: Option<u64>,
+    /// The fuel the next instruction costs, if execution was suspended with [SuspendReason::OutOfFuel]
+    pub(crate) required_fuel: u64,
+    /// The module and function index of the invoked function, required to retrieve its return values
+    pub(crate) root_function: (usize, usize),
+    pub(crate) suspend_reason: Option<SuspendReason>,
+    /// The pending step and the number of stackframes when it was requested
+    pub(crate) step: Option<(Step, usize)>,
+    /// Whether execution was suspended by the debugger at `pc`, such that it continues with this instruction instead
+    /// of stopping there again
+    pub(crate) stopped_by_debugger: bool,
+    /// Whether a hook requested to suspend execution before the next instruction
+    pub(crate) hook_yield: bool,
+}
+
+impl Resumable {
+    /// Continue execution, see [RuntimeInstance::resume]
+    pub fn resume<H: HookSet>(
+        self,
+        runtime: &mut RuntimeInstance<H>,
+    ) -> Result<InvocationState, Trap> {
+        runtime.resume(self)
+    }
+
+    /// Why execution was suspended, or `None` if this [Resumable] was not yet run
+    pub fn suspend_reason(&self) -> Option<SuspendReason> {
+        self.suspend_reason
+    }
+
+    /// The position of the next instruction in the binary of its module
+    pub fn pc(&self) -> usize {
+        self.pc
+    }
+
+    /// The remaining fuel, or `None` if execution is not fuel bounded
+    pub fn fuel(&self) -> Option<u64> {
+        self.maybe_fuel
+    }
+
+    /// The fuel the next instruction costs, if execution was suspended with [SuspendReason::OutOfFuel]
+    ///
+    /// Bulk instructions cost more than one unit of fuel, see
+    /// [RuntimeInstance::invoke_with_fuel](crate::RuntimeInstance::invoke_with_fuel). Execution can only continue once
+    /// the fuel is refilled to at least this amount.
+    pub fn required_fuel(&self) -> Option<u64> {
+        (self.suspend_reason == Some(SuspendReason::OutOfFuel)).then_some(self.required_fuel)
+    }
+
+    /// Replace the remaining fuel. `None` lifts the bound on execution entirely.
+    pub fn set_fuel(&mut self, maybe_fuel: Option<u64>) {
+        self.maybe_fuel = maybe_fuel;
+    }
+}
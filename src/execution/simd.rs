@@ -0,0 +1,241 @@
+//! Portable, scalar implementations of the lane-wise operations of the fixed-width SIMD proposal
+//!
+//! A `v128` is always handled as its 16 little endian bytes. The helpers in this module split these bytes into lanes
+//! of some type `T`, where the lane width in bytes is the `N` of the type's [`LittleEndianBytes`] implementation.
+//!
+//! See <https://webassembly.github.io/spec/core/exec/numerics.html#vector-operations>
+
+use crate::core::little_endian::LittleEndianBytes;
+
+fn lane<const N: usize, T: LittleEndianBytes<N>>(bytes: &[u8]) -> T {
+    T::from_le_bytes(bytes.try_into().expect("lanes to be exactly N bytes wide"))
+}
+
+/// Read the lane `lane_idx` of `v`
+pub(crate) fn extract_lane<const N: usize, T: LittleEndianBytes<N>>(
+    v: [u8; 16],
+    lane_idx: u8,
+) -> T {
+    let start = usize::from(lane_idx) * N;
+    lane(&v[start..start + N])
+}
+
+/// Overwrite the lane `lane_idx` of `v` with `x`
+pub(crate) fn replace_lane<const N: usize, T: LittleEndianBytes<N>>(
+    mut v: [u8; 16],
+    lane_idx: u8,
+    x: T,
+) -> [u8; 16] {
+    let start = usize::from(lane_idx) * N;
+    v[start..start + N].copy_from_slice(&x.to_le_bytes());
+    v
+}
+
+/// Create a vector with all lanes set to `x`
+pub(crate) fn splat<const N: usize, T: LittleEndianBytes<N> + Copy>(x: T) -> [u8; 16] {
+    let mut res = [0; 16];
+    for res_lane in res.chunks_exact_mut(N) {
+        res_lane.copy_from_slice(&x.to_le_bytes());
+    }
+    res
+}
+
+/// Apply `f` to every lane, the lanes of the result must have the same width as the input lanes
+pub(crate) fn map<const N: usize, T: LittleEndianBytes<N>, U: LittleEndianBytes<N>>(
+    v: [u8; 16],
+    f: impl Fn(T) -> U,
+) -> [u8; 16] {
+    let mut res = [0; 16];
+    for (res_lane, v_lane) in res.chunks_exact_mut(N).zip(v.chunks_exact(N)) {
+        res_lane.copy_from_slice(&f(lane(v_lane)).to_le_bytes());
+    }
+    res
+}
+
+/// Apply `f` to every pair of corresponding lanes of `v1` and `v2`
+pub(crate) fn zip_map<const N: usize, T: LittleEndianBytes<N>>(
+    v1: [u8; 16],
+    v2: [u8; 16],
+    f: impl Fn(T, T) -> T,
+) -> [u8; 16] {
+    let mut res = [0; 16];
+    for ((res_lane, v1_lane), v2_lane) in res
+        .chunks_exact_mut(N)
+        .zip(v1.chunks_exact(N))
+        .zip(v2.chunks_exact(N))
+    {
+        res_lane.copy_from_slice(&f(lane(v1_lane), lane(v2_lane)).to_le_bytes());
+    }
+    res
+}
+
+/// Compare every pair of corresponding lanes of `v1` and `v2`, resulting in a lane with all bits set if `f` holds
+pub(crate) fn compare<const N: usize, T: LittleEndianBytes<N>>(
+    v1: [u8; 16],
+    v2: [u8; 16],
+    f: impl Fn(T, T) -> bool,
+) -> [u8; 16] {
+    let mut res = [0; 16];
+    for ((res_lane, v1_lane), v2_lane) in res
+        .chunks_exact_mut(N)
+        .zip(v1.chunks_exact(N))
+        .zip(v2.chunks_exact(N))
+    {
+        if f(lane(v1_lane), lane(v2_lane)) {
+            res_lane.fill(0xFF);
+        }
+    }
+    res
+}
+
+/// Check whether all lanes are non-zero
+pub(crate) fn all_true<const N: usize>(v: [u8; 16]) -> bool {
+    v.chunks_exact(N)
+        .all(|v_lane| v_lane.iter().any(|byte| *byte != 0))
+}
+
+/// Collect the most significant bit of every lane, with the first lane ending up in the least significant bit
+pub(crate) fn bitmask<const N: usize>(v: [u8; 16]) -> u32 {
+    v.chunks_exact(N)
+        .enumerate()
+        .map(|(i, v_lane)| u32::from(v_lane[N - 1] >> 7) << i)
+        .fold(0, |mask, bit| mask | bit)
+}
+
+/// Convert the lanes of `v1` followed by the lanes of `v2` into lanes of half the width
+///
+/// Also used for conversions that write only the lower half of the result and zero the upper one, by passing a zero
+/// vector as `v2`.
+pub(crate) fn narrow<
+    const N: usize,
+    const M: usize,
+    T: LittleEndianBytes<N>,
+    U: LittleEndianBytes<M>,
+>(
+    v1: [u8; 16],
+    v2: [u8; 16],
+    f: impl Fn(T) -> U,
+) -> [u8; 16] {
+    let mut res = [0; 16];
+    for (res_lane, v_lane) in res
+        .chunks_exact_mut(M)
+        .zip(v1.chunks_exact(N).chain(v2.chunks_exact(N)))
+    {
+        res_lane.copy_from_slice(&f(lane(v_lane)).to_le_bytes());
+    }
+    res
+}
+
+/// Convert the lanes of the lower or upper half of `v` into lanes of twice the width
+pub(crate) fn extend<
+    const N: usize,
+    const M: usize,
+    T: LittleEndianBytes<N>,
+    U: LittleEndianBytes<M>,
+>(
+    v: [u8; 16],
+    high: bool,
+    f: impl Fn(T) -> U,
+) -> [u8; 16] {
+    let half = if high { &v[8..] } else { &v[..8] };
+    let mut res = [0; 16];
+    for (res_lane, v_lane) in res.chunks_exact_mut(M).zip(half.chunks_exact(N)) {
+        res_lane.copy_from_slice(&f(lane(v_lane)).to_le_bytes());
+    }
+    res
+}
+
+/// Combine the corresponding lanes of the lower or upper halves of `v1` and `v2` into lanes of twice the width
+pub(crate) fn extend_zip<
+    const N: usize,
+    const M: usize,
+    T: LittleEndianBytes<N>,
+    U: LittleEndianBytes<M>,
+>(
+    v1: [u8; 16],
+    v2: [u8; 16],
+    high: bool,
+    f: impl Fn(T, T) -> U,
+) -> [u8; 16] {
+    let (half1, half2) = if high {
+        (&v1[8..], &v2[8..])
+    } else {
+        (&v1[..8], &v2[..8])
+    };
+    let mut res = [0; 16];
+    for ((res_lane, v1_lane), v2_lane) in res
+        .chunks_exact_mut(M)
+        .zip(half1.chunks_exact(N))
+        .zip(half2.chunks_exact(N))
+    {
+        res_lane.copy_from_slice(&f(lane(v1_lane), lane(v2_lane)).to_le_bytes());
+    }
+    res
+}
+
+/// Combine every two adjacent lanes of `v1` and the corresponding lanes of `v2` into one lane of twice the width
+///
+/// `f` receives the lanes `2i` and `2i + 1` of both vectors as `(v1[2i], v1[2i + 1], v2[2i], v2[2i + 1])`.
+pub(crate) fn pairwise<
+    const N: usize,
+    const M: usize,
+    T: LittleEndianBytes<N>,
+    U: LittleEndianBytes<M>,
+>(
+    v1: [u8; 16],
+    v2: [u8; 16],
+    f: impl Fn(T, T, T, T) -> U,
+) -> [u8; 16] {
+    let mut res = [0; 16];
+    for ((res_lane, v1_lanes), v2_lanes) in res
+        .chunks_exact_mut(M)
+        .zip(v1.chunks_exact(M))
+        .zip(v2.chunks_exact(M))
+    {
+        let x = f(
+            lane(&v1_lanes[..N]),
+            lane(&v1_lanes[N..]),
+            lane(&v2_lanes[..N]),
+            lane(&v2_lanes[N..]),
+        );
+        res_lane.copy_from_slice(&x.to_le_bytes());
+    }
+    res
+}
+
+/// Select bytes from the concatenation of `v1` and `v2`, the validator checked that all indices are below 32
+pub(crate) fn shuffle(v1: [u8; 16], v2: [u8; 16], lane_idxs: [u8; 16]) -> [u8; 16] {
+    lane_idxs.map(|lane_idx| match lane_idx {
+        0..=15 => v1[usize::from(lane_idx)],
+        _ => v2[usize::from(lane_idx) - 16],
+    })
+}
+
+/// Select bytes from `v1` by the indices in `v2`, indices which are out of bounds select zero
+pub(crate) fn swizzle(v1: [u8; 16], v2: [u8; 16]) -> [u8; 16] {
+    v2.map(|lane_idx| v1.get(usize::from(lane_idx)).copied().unwrap_or(0))
+}
+
+/// The saturating, rounding Q15 multiplication of `i16x8.q15mulr_sat_s`
+pub(crate) fn q15mulr_sat(x: i16, y: i16) -> i16 {
+    let res = (i32::from(x) * i32::from(y) + 0x4000) >> 15;
+    res.clamp(i16::MIN.into(), i16::MAX.into()) as i16
+}
+
+/// The pseudo-minimum of `f32x4.pmin`/`f64x2.pmin`, which is `y < x ? y : x`
+pub(crate) fn pmin<T: PartialOrd>(x: T, y: T) -> T {
+    if y < x {
+        y
+    } else {
+        x
+    }
+}
+
+/// The pseudo-maximum of `f32x4.pmax`/`f64x2.pmax`, which is `x < y ? y : x`
+pub(crate) fn pmax<T: PartialOrd>(x: T, y: T) -> T {
+    if x < y {
+        y
+    } else {
+        x
+    }
+}
@@ -1,14 +1,22 @@
+use core::cell::{Cell, RefCell};
+
+use alloc::boxed::Box;
+use alloc::collections::TryReserveError;
+use alloc::rc::Rc;
 use alloc::string::String;
+use alloc::sync::Arc;
 use alloc::vec;
 use alloc::vec::Vec;
 
+use crate::core::error::StoreInstantiationError;
 use crate::core::indices::TypeIdx;
 use crate::core::reader::span::Span;
 use crate::core::reader::types::export::Export;
-use crate::core::reader::types::global::Global;
-use crate::core::reader::types::{MemType, TableType, ValType};
+use crate::core::reader::types::global::GlobalType;
+use crate::core::reader::types::{FuncType, MemType, TableType, ValType};
 use crate::core::sidetable::Sidetable;
-use crate::execution::value::{Ref, Value};
+use crate::execution::host_function::HostFunction;
+use crate::execution::value::{ExnAddr, Ref, Value};
 use crate::linear_memory::LinearMemory;
 use crate::RefType;
 
@@ -26,6 +34,28 @@ pub struct Store {
     pub elements: Vec<ElemInst>,
     pub passive_elem_indexes: Vec<usize>,
     pub exports: Vec<Export>,
+    pub tags: Vec<TagInst>,
+    /// The exceptions thrown by functions of this module which are referenced by [ExnAddr]s, as they were caught by
+    /// reference or not caught at all
+    ///
+    /// Slots of exceptions which are no longer referenced are freed when exceptions are collected, see
+    /// [store_exception](crate::execution::exceptions::store_exception), and reused for later exceptions.
+    pub exceptions: Vec<ExnSlot>,
+    /// The indices of the free slots of [Store::exceptions]
+    pub free_exceptions: Vec<usize>,
+    /// The number of slots of [Store::exceptions] up to which new slots are added without collecting exceptions first
+    pub exception_collection_threshold: usize,
+}
+
+impl Store {
+    /// The exception referenced by `exn_addr`, if it is an address of this store which was not reclaimed yet
+    pub fn exception(&self, exn_addr: ExnAddr) -> Option<&ExnInst> {
+        let slot = self.exceptions.get(exn_addr.addr?)?;
+        if slot.generation != exn_addr.generation {
+            return None;
+        }
+        slot.exn.as_ref()
+    }
 }
 
 #[derive(Debug)]
@@ -72,6 +102,24 @@ impl FuncInst {
     }
 }
 
+/// A function provided by the embedder. Unlike [FuncInst]s, these do not belong to the [Store] of any module, but to
+/// the [RuntimeInstance](crate::RuntimeInstance) they were registered with.
+/// <https://webassembly.github.io/spec/core/exec/runtime.html#function-instances>
+pub struct HostFuncInst {
+    pub ty: FuncType,
+    pub module_name: String,
+    pub function_name: String,
+    pub func: Box<dyn HostFunction>,
+}
+
+/// A shared memory provided by the embedder. Like [HostFuncInst]s, these belong to the
+/// [RuntimeInstance](crate::RuntimeInstance) they were registered with.
+pub struct HostMemInst {
+    pub module_name: String,
+    pub name: String,
+    pub mem: MemInst,
+}
+
 #[derive(Clone, Debug)]
 /// <https://webassembly.github.io/spec/core/exec/runtime.html#element-instances>
 pub struct ElemInst {
@@ -88,57 +136,137 @@ impl ElemInst {
     }
 }
 
-#[derive(Debug)]
+/// A table instance
+///
+/// Like [MemInst]s, cloned [TableInst]s share the same elements. This is how tables are shared between the exporting
+/// and importing modules.
+#[derive(Debug, Clone)]
 pub struct TableInst {
     pub ty: TableType,
-    pub elem: Vec<Ref>,
+    pub elem: Rc<RefCell<Vec<Ref>>>,
 }
 
 impl TableInst {
     pub fn len(&self) -> usize {
-        self.elem.len()
+        self.elem.borrow().len()
     }
 
     pub fn is_empty(&self) -> bool {
-        self.elem.is_empty()
+        self.elem.borrow().is_empty()
     }
 
     pub fn new(ty: TableType) -> Self {
         Self {
             ty,
-            elem: vec![Ref::default_from_ref_type(ty.et); ty.lim.min as usize],
+            elem: Rc::new(RefCell::new(vec![
+                Ref::default_from_ref_type(ty.et);
+                ty.lim.min as usize
+            ])),
         }
     }
 }
 
+/// A memory instance
+///
+/// Cloning a [MemInst] does not copy the memory, instead both instances refer to the same [LinearMemory]. This is
+/// how memories are shared between the exporting and importing modules. Shared memories may furthermore be accessed by
+/// multiple threads, see [SharedMemory](crate::execution::threads::SharedMemory).
+#[derive(Clone)]
 pub struct MemInst {
-    #[allow(warnings)]
     pub ty: MemType,
-    pub mem: LinearMemory,
+    pub mem: Arc<LinearMemory>,
 }
 
 impl MemInst {
-    pub fn new(ty: MemType) -> Self {
-        Self {
+    /// Fails if the host can not provide the minimum size of the memory, which for 64-bit memories may even exceed the
+    /// address space of the host
+    pub fn new(ty: MemType) -> Result<Self, StoreInstantiationError> {
+        let pages = usize::try_from(ty.limits.min)
+            .ok()
+            .filter(|pages| {
+                pages
+                    .checked_mul(crate::Limits::MEM_PAGE_SIZE as usize)
+                    .is_some()
+            })
+            .ok_or(StoreInstantiationError::OutOfMemory(ty.limits.min))?;
+        let mem = LinearMemory::new_with_initial_pages(pages)
+            .map_err(|_| StoreInstantiationError::OutOfMemory(ty.limits.min))?;
+
+        Ok(Self {
             ty,
-            mem: LinearMemory::new_with_initial_pages(ty.limits.min.try_into().unwrap()),
-        }
+            mem: Arc::new(mem),
+        })
     }
 
-    pub fn grow(&mut self, delta_pages: usize) {
-        self.mem.grow(delta_pages.try_into().unwrap())
+    /// Fails without changing the memory if the host can not provide the additional memory
+    pub fn grow(&self, delta_pages: usize) -> Result<(), TryReserveError> {
+        self.mem.grow(delta_pages)
     }
 
-    /// Can never be bigger than 65,356 pages
+    /// The current size in pages, which can never be bigger than the maximum of the memory's
+    /// [AddrType](crate::core::reader::types::AddrType)
     pub fn size(&self) -> usize {
         self.mem.len() / (crate::Limits::MEM_PAGE_SIZE as usize)
     }
 }
 
+/// A global instance
+///
+/// Like [MemInst]s, cloned [GlobalInst]s share the same value. This is how mutable globals are shared between the
+/// exporting and importing modules.
+#[derive(Clone)]
 pub struct GlobalInst {
-    pub global: Global,
+    pub ty: GlobalType,
     /// Must be of the same type as specified in `ty`
-    pub value: Value,
+    pub value: Rc<Cell<Value>>,
+}
+
+impl GlobalInst {
+    pub fn new(ty: GlobalType, value: Value) -> Self {
+        Self {
+            ty,
+            value: Rc::new(Cell::new(value)),
+        }
+    }
+}
+
+/// A tag instance
+///
+/// Like [GlobalInst]s, cloned [TagInst]s refer to the same tag. Two tags are only the same if they originate from the
+/// same tag definition, even if their types are equal, so the identity of a tag is the allocation of its type.
+/// <https://webassembly.github.io/exception-handling/core/exec/runtime.html#tag-instances>
+#[derive(Clone, Debug)]
+pub struct TagInst {
+    pub ty: Rc<FuncType>,
+}
+
+impl TagInst {
+    pub fn new(ty: FuncType) -> Self {
+        Self { ty: Rc::new(ty) }
+    }
+
+    /// Check whether both instances refer to the same tag
+    pub fn is_same(&self, other: &TagInst) -> bool {
+        Rc::ptr_eq(&self.ty, &other.ty)
+    }
+}
+
+/// An exception instance, created by `throw`
+/// <https://webassembly.github.io/exception-handling/core/exec/runtime.html#exception-instances>
+#[derive(Clone, Debug)]
+pub struct ExnInst {
+    pub tag: TagInst,
+    /// Must be of the types of the parameters of the tag's type
+    pub payload: Vec<Value>,
+}
+
+/// A slot of [Store::exceptions]
+#[derive(Debug)]
+pub struct ExnSlot {
+    /// See [ExnAddr::generation]
+    pub generation: u32,
+    /// The stored exception, or `None` if the slot is free
+    pub exn: Option<ExnInst>,
 }
 
 pub struct DataInst {
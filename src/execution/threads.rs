@@ -0,0 +1,97 @@
+//! Support for the threads proposal, which allows multiple threads to access the same shared memories
+//!
+//! The interpreter does not spawn threads itself. Instead, every host thread uses its own
+//! [RuntimeInstance](crate::RuntimeInstance). A [SharedMemory] exported by a module of one instance can be sent to other
+//! threads, where it is registered via [RuntimeInstance::add_shared_memory](crate::RuntimeInstance::add_shared_memory)
+//! before adding the modules importing it.
+//!
+//! Suspending a thread requires support by the operating system, so `memory.atomic.wait32`, `memory.atomic.wait64` and
+//! `memory.atomic.notify` are delegated to a [Waiter] provided by the embedder. All instances accessing the same shared
+//! memory must use the same [Waiter].
+//!
+//! See <https://webassembly.github.io/threads/core/>
+
+use alloc::sync::Arc;
+use core::time::Duration;
+
+use crate::core::indices::MemIdx;
+use crate::execution::store::MemInst;
+
+/// A handle to a shared memory, which can be sent to other threads
+///
+/// Cloning a [SharedMemory] does not copy the memory, all clones refer to the same memory.
+#[derive(Clone)]
+pub struct SharedMemory {
+    pub(crate) mem: MemInst,
+}
+
+impl SharedMemory {
+    /// The current size in pages
+    pub fn size(&self) -> usize {
+        self.mem.size()
+    }
+}
+
+/// Identifies the address within a shared memory, on which a thread waits or which is notified
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WaitKey {
+    memory: usize,
+    address: MemIdx,
+}
+
+impl WaitKey {
+    pub(crate) fn new(mem_inst: &MemInst, address: MemIdx) -> Self {
+        Self {
+            memory: Arc::as_ptr(&mem_inst.mem) as usize,
+            address,
+        }
+    }
+}
+
+/// The outcome of [Waiter::wait], which is returned to the WASM code as `0`, `1` or `2` respectively
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WaitOutcome {
+    /// The thread was woken up by a notification
+    Woken,
+    /// The loaded value did not match the expected value, so the thread did not wait at all
+    NotEqual,
+    /// The timeout elapsed before the thread was woken up
+    TimedOut,
+}
+
+impl From<WaitOutcome> for i32 {
+    fn from(outcome: WaitOutcome) -> Self {
+        match outcome {
+            WaitOutcome::Woken => 0,
+            WaitOutcome::NotEqual => 1,
+            WaitOutcome::TimedOut => 2,
+        }
+    }
+}
+
+/// Suspends and wakes up threads waiting on a shared memory
+///
+/// An implementation on top of `std` typically keeps a queue of waiting threads per [WaitKey] behind a
+/// `std::sync::Mutex`, and suspends the waiting threads on a `std::sync::Condvar`.
+///
+/// See <https://webassembly.github.io/threads/core/exec/runtime.html#waiter-lists>
+pub trait Waiter: Send + Sync {
+    /// Suspend the current thread until it is woken up by [Waiter::notify] for the same `key`, or until `timeout`
+    /// elapsed, if it is `Some`
+    ///
+    /// `still_expected` loads the value at the address and compares it to the expected value. It must be called while
+    /// holding the lock which is also held by [Waiter::notify], so that no notification is missed between the
+    /// comparison and suspending the thread. If it returns `false`, [WaitOutcome::NotEqual] must be returned
+    /// immediately.
+    fn wait(
+        &self,
+        key: WaitKey,
+        timeout: Option<Duration>,
+        still_expected: &mut dyn FnMut() -> bool,
+    ) -> WaitOutcome;
+
+    /// Wake up at most `count` threads waiting for `key`, in the order in which they started waiting
+    ///
+    /// Returns the number of threads that were woken up.
+    fn notify(&self, key: WaitKey, count: u32) -> u32;
+}
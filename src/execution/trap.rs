@@ -0,0 +1,153 @@
+//! Traps together with the WASM call stack at the time they occurred
+//!
+//! Invoking a function with [RuntimeInstance::invoke](crate::execution::RuntimeInstance::invoke) or any of its
+//! variants fails with a [Trap], which can be compared to the [RuntimeError] it carries.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::{Display, Formatter};
+
+use crate::core::indices::FuncIdx;
+use crate::execution::execution_info::ExecutionInfo;
+use crate::execution::value_stack::Stack;
+use crate::{Error, RuntimeError, SourceLocation};
+
+/// A [RuntimeError] raised while executing a function, together with the functions that were active at that time
+///
+/// Errors that occur before the invoked function starts executing, e.g. an invalid
+/// [FunctionRef](crate::execution::function_ref::FunctionRef), have no active functions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trap {
+    pub error: RuntimeError,
+    /// The active functions, the trapping one first and the invoked one last, or none if no function was executing
+    pub frames: Vec<TrapFrame>,
+}
+
+/// A function that was active when a [Trap] occurred
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrapFrame {
+    /// The name under which the function's module was added to the
+    /// [RuntimeInstance](crate::execution::RuntimeInstance)
+    pub module_name: String,
+    /// The index of the function in its module, including the imported functions
+    pub function_idx: FuncIdx,
+    /// The name of the function in the name section of its module, if there is one
+    pub function_name: Option<String>,
+    /// The offset of the function's current instruction within the module binary
+    ///
+    /// For the trapping function this is the trapping instruction, for all other functions it is the instruction
+    /// following the call that is still active.
+    pub code_offset: usize,
+    /// The location in the source code, if the module contains DWARF debug information for it
+    ///
+    /// For all but the trapping function, this is the location of the call that is still active.
+    pub source_location: Option<SourceLocation>,
+}
+
+impl Trap {
+    /// Capture the stackframes of `stack`, where `pc` is the position of the trapping instruction
+    pub(crate) fn new(
+        error: RuntimeError,
+        modules: &[ExecutionInfo],
+        stack: &Stack,
+        pc: usize,
+    ) -> Self {
+        Trap {
+            error,
+            frames: frames(modules, stack, pc),
+        }
+    }
+}
+
+impl From<RuntimeError> for Trap {
+    fn from(error: RuntimeError) -> Self {
+        Trap {
+            error,
+            frames: Vec::new(),
+        }
+    }
+}
+
+impl From<Trap> for RuntimeError {
+    fn from(trap: Trap) -> Self {
+        trap.error
+    }
+}
+
+impl From<Trap> for Error {
+    fn from(trap: Trap) -> Self {
+        Error::RuntimeError(trap.error)
+    }
+}
+
+impl PartialEq<RuntimeError> for Trap {
+    fn eq(&self, other: &RuntimeError) -> bool {
+        self.error == *other
+    }
+}
+
+impl PartialEq<Trap> for RuntimeError {
+    fn eq(&self, other: &Trap) -> bool {
+        *self == other.error
+    }
+}
+
+impl Display for Trap {
+    /// Formats the error followed by one line per frame, e.g.
+    /// `0: func 3 ($div) in module "main" at offset 0x4f (sensor.c:142:7)`
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.error)?;
+        for (i, frame) in self.frames.iter().enumerate() {
+            write!(f, "\n  {i}: {frame}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for TrapFrame {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        write!(f, "func {}", self.function_idx)?;
+        if let Some(name) = &self.function_name {
+            write!(f, " (${name})")?;
+        }
+        write!(
+            f,
+            " in module {:?} at offset {:#x}",
+            self.module_name, self.code_offset
+        )?;
+        if let Some(source_location) = &self.source_location {
+            write!(f, " ({source_location})")?;
+        }
+        Ok(())
+    }
+}
+
+/// Describe the stackframes of `stack`, the current one first, where `pc` is the position of the current instruction
+pub(crate) fn frames(modules: &[ExecutionInfo], stack: &Stack, pc: usize) -> Vec<TrapFrame> {
+    let call_frames = stack.call_frames();
+
+    call_frames
+        .iter()
+        .enumerate()
+        .rev()
+        .map(|(i, frame)| {
+            // every stackframe stores the position to continue at in its caller
+            let module = &modules[frame.module_idx];
+            // the position preceding a return address still belongs to the call
+            let (code_offset, source_location) = match call_frames.get(i + 1) {
+                Some(callee) => (
+                    callee.return_addr,
+                    module.dwarf.lookup(callee.return_addr - 1),
+                ),
+                None => (pc, module.dwarf.lookup(pc)),
+            };
+            TrapFrame {
+                module_name: module.name.clone(),
+                function_idx: frame.func_idx,
+                function_name: module.names.function_name(frame.func_idx).map(String::from),
+                code_offset,
+                source_location,
+            }
+        })
+        .collect()
+}
@@ -304,15 +304,14 @@ impl F64 {
 /// A value at runtime. This is essentially a duplicate of [ValType] just with additional values.
 ///
 /// See <https://webassembly.github.io/spec/core/exec/runtime.html#values>
-// TODO implement missing variants
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Value {
     I32(u32),
     I64(u64),
     F32(F32),
     F64(F64),
-    // F64,
-    // V128,
+    /// A 128-bit vector, stored as its little endian bytes
+    V128([u8; 16]),
     Ref(Ref),
 }
 
@@ -320,6 +319,7 @@ pub enum Value {
 pub enum Ref {
     Func(FuncAddr),
     Extern(ExternAddr),
+    Exn(ExnAddr),
 }
 
 impl Ref {
@@ -327,6 +327,7 @@ impl Ref {
         match rref {
             RefType::ExternRef => Self::Extern(ExternAddr::default()),
             RefType::FuncRef => Self::Func(FuncAddr::default()),
+            RefType::ExnRef => Self::Exn(ExnAddr::default()),
         }
     }
 
@@ -334,6 +335,7 @@ impl Ref {
         match self {
             Self::Extern(extern_addr) => extern_addr.addr.is_none(),
             Self::Func(func_addr) => func_addr.addr.is_none(),
+            Self::Exn(exn_addr) => exn_addr.addr.is_none(),
         }
     }
 
@@ -350,6 +352,7 @@ impl Display for Ref {
         match self {
             Ref::Func(func_addr) => write!(f, "FuncRef({:?})", func_addr),
             Ref::Extern(extern_addr) => write!(f, "ExternRef({:?})", extern_addr),
+            Ref::Exn(exn_addr) => write!(f, "ExnRef({:?})", exn_addr),
         }
     }
 }
@@ -363,29 +366,51 @@ impl Display for Ref {
 /// [`FuncAddr`] provides a unified representation for both types. Internally,
 /// the address corresponds to an index in a combined function namespace,
 /// typically represented as a vector.
+///
+/// As references can be passed between modules, e.g. through shared tables, a
+/// [`FuncAddr`] also carries the index of the module whose function namespace
+/// the address belongs to.
 #[derive(Clone, Copy, PartialEq)]
 pub struct FuncAddr {
     pub addr: Option<usize>,
+    /// The index of the module owning the function, always `0` for null references
+    pub module_idx: usize,
 }
 
 impl Debug for FuncAddr {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         match self.addr.is_none() {
-            false => write!(f, "FuncAddr {{\n\taddr: {}\n}}", self.addr.unwrap()),
+            false => write!(
+                f,
+                "FuncAddr {{\n\taddr: {}\n\tmodule_idx: {}\n}}",
+                self.addr.unwrap(),
+                self.module_idx
+            ),
             true => write!(f, "FuncAddr {{ NULL }}"),
         }
     }
 }
 
 impl FuncAddr {
+    /// Create a reference to a function of the first module, see [`FuncAddr::new_in_module`]
     pub fn new(addr: Option<usize>) -> Self {
+        Self::new_in_module(0, addr)
+    }
+    /// Create a reference to the function `addr` of the module `module_idx`, or a null reference if `addr` is `None`
+    pub fn new_in_module(module_idx: usize, addr: Option<usize>) -> Self {
         match addr {
             None => Self::null(),
-            Some(u) => Self { addr: Some(u) },
+            Some(u) => Self {
+                addr: Some(u),
+                module_idx,
+            },
         }
     }
     pub fn null() -> Self {
-        Self { addr: None }
+        Self {
+            addr: None,
+            module_idx: 0,
+        }
     }
     pub fn is_null(&self) -> bool {
         self.addr.is_none()
@@ -429,6 +454,43 @@ impl Default for ExternAddr {
     }
 }
 
+/// Represents the address of an exception, which was thrown by a function of the module `module_idx`
+///
+/// Internally, [`ExnAddr`] corresponds to an index into the exceptions of that module's store.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExnAddr {
+    pub addr: Option<usize>,
+    /// The index of the module owning the exception, always `0` for null references
+    pub module_idx: usize,
+    /// The generation of the exception's slot in the store, which is incremented whenever the slot is freed
+    ///
+    /// Addresses of a past generation refer to an exception which was reclaimed.
+    pub generation: u32,
+}
+
+impl ExnAddr {
+    pub fn new_in_module(module_idx: usize, addr: usize, generation: u32) -> Self {
+        Self {
+            addr: Some(addr),
+            module_idx,
+            generation,
+        }
+    }
+    pub fn null() -> Self {
+        Self {
+            addr: None,
+            module_idx: 0,
+            generation: 0,
+        }
+    }
+}
+
+impl Default for ExnAddr {
+    fn default() -> Self {
+        Self::null()
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum RefValueTy {
     Func,
@@ -442,11 +504,10 @@ impl Value {
             ValType::NumType(NumType::I64) => Self::I64(0),
             ValType::NumType(NumType::F32) => Self::F32(F32(0.0)),
             ValType::NumType(NumType::F64) => Self::F64(F64(0.0_f64)),
+            ValType::VecType => Self::V128([0; 16]),
             ValType::RefType(RefType::ExternRef) => Self::Ref(Ref::Extern(ExternAddr::null())),
             ValType::RefType(RefType::FuncRef) => Self::Ref(Ref::Func(FuncAddr::null())),
-            other => {
-                todo!("cannot determine type for {other:?} because this value is not supported yet")
-            }
+            ValType::RefType(RefType::ExnRef) => Self::Ref(Ref::Exn(ExnAddr::null())),
         }
     }
 
@@ -456,9 +517,11 @@ impl Value {
             Value::I64(_) => ValType::NumType(NumType::I64),
             Value::F32(_) => ValType::NumType(NumType::F32),
             Value::F64(_) => ValType::NumType(NumType::F64),
+            Value::V128(_) => ValType::VecType,
             Value::Ref(rref) => match rref {
                 Ref::Extern(_) => ValType::RefType(RefType::ExternRef),
                 Ref::Func(_) => ValType::RefType(RefType::FuncRef),
+                Ref::Exn(_) => ValType::RefType(RefType::ExnRef),
             },
         }
     }
@@ -622,6 +685,40 @@ impl InteropValue for f64 {
     }
 }
 
+impl InteropValue for u128 {
+    const TY: ValType = ValType::VecType;
+
+    #[allow(warnings)]
+    fn into_value(self) -> Value {
+        Value::V128(self.to_le_bytes())
+    }
+
+    #[allow(warnings)]
+    fn from_value(value: Value) -> Self {
+        match value {
+            Value::V128(bytes) => u128::from_le_bytes(bytes),
+            _ => unreachable_validated!(),
+        }
+    }
+}
+
+impl InteropValue for i128 {
+    const TY: ValType = ValType::VecType;
+
+    #[allow(warnings)]
+    fn into_value(self) -> Value {
+        Value::V128(self.to_le_bytes())
+    }
+
+    #[allow(warnings)]
+    fn from_value(value: Value) -> Self {
+        match value {
+            Value::V128(bytes) => i128::from_le_bytes(bytes),
+            _ => unreachable_validated!(),
+        }
+    }
+}
+
 #[derive(PartialEq, Debug, Copy, Clone)]
 pub struct FuncRefForInteropValue {
     rref: Ref,
@@ -634,6 +731,10 @@ impl FuncRefForInteropValue {
                 RefType::ExternRef,
                 RefType::FuncRef,
             )),
+            Ref::Exn(_) => Err(Error::WrongRefTypeForInteropValue(
+                RefType::ExnRef,
+                RefType::FuncRef,
+            )),
             Ref::Func(_) => Ok(Self { rref }),
         }
     }
@@ -784,6 +885,23 @@ impl_value_conversion!(u64);
 impl_value_conversion!(i64);
 impl_value_conversion!(F32);
 impl_value_conversion!(F64);
+impl_value_conversion!(u128);
+impl_value_conversion!(i128);
+
+impl From<[u8; 16]> for Value {
+    fn from(bytes: [u8; 16]) -> Self {
+        Self::V128(bytes)
+    }
+}
+
+impl From<Value> for [u8; 16] {
+    fn from(value: Value) -> Self {
+        match value {
+            Value::V128(bytes) => bytes,
+            _ => unreachable_validated!(),
+        }
+    }
+}
 
 impl From<Ref> for Value {
     fn from(value: Ref) -> Self {
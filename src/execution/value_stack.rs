@@ -1,11 +1,12 @@
 use alloc::vec::{Drain, Vec};
+use core::ops::Range;
 
 use crate::core::indices::{FuncIdx, LocalIdx};
 use crate::core::reader::types::{FuncType, ValType};
 use crate::execution::assert_validated::UnwrapValidatedExt;
 use crate::execution::value::Value;
 use crate::locals::Locals;
-use crate::unreachable_validated;
+use crate::{unreachable_validated, RuntimeError};
 
 use super::value::Ref;
 
@@ -15,7 +16,9 @@ use super::value::Ref;
 /// 3. Activations
 ///
 /// See <https://webassembly.github.io/spec/core/exec/runtime.html#stack>
-#[derive(Default)]
+///
+/// Both the number of values, including the locals of all stackframes, and the number of stackframes are bounded.
+/// Exceeding either bound traps with [RuntimeError::StackSmash].
 pub(crate) struct Stack {
     /// WASM values on the stack, i.e. the actual data that instructions operate on
     values: Vec<Value>,
@@ -24,11 +27,45 @@ pub(crate) struct Stack {
     ///
     /// Each time a function is called, a new frame is pushed, whenever a function returns, a frame is popped
     frames: Vec<CallFrame>,
+
+    /// The catch clauses of all entered `try_table`s, the innermost one on top
+    ///
+    /// Handlers are not removed when their `try_table` is left, but skipped as soon as they are found to be inactive,
+    /// see [`Self::is_handler_active`].
+    handlers: Vec<Handler>,
+
+    /// The number of locals, including parameters, of all stackframes on [`Self::frames`]
+    locals_count: usize,
+
+    /// The maximum number of values on [`Self::values`] plus [`Self::locals_count`]
+    max_value_stack_size: usize,
+
+    /// The maximum number of stackframes on [`Self::frames`]
+    max_call_depth: usize,
+}
+
+impl Default for Stack {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Stack {
+    /// Create a stack without any bounds on the number of values or stackframes
     pub fn new() -> Self {
-        Self::default()
+        Self::new_with_limits(usize::MAX, usize::MAX)
+    }
+
+    /// Create a stack which holds at most `max_value_stack_size` values and locals and `max_call_depth` stackframes
+    pub fn new_with_limits(max_value_stack_size: usize, max_call_depth: usize) -> Self {
+        Self {
+            values: Vec::new(),
+            frames: Vec::new(),
+            handlers: Vec::new(),
+            locals_count: 0,
+            max_value_stack_size,
+            max_call_depth,
+        }
     }
 
     pub fn drop_value(&mut self) {
@@ -116,15 +153,20 @@ impl Stack {
         self.values.last().copied()
     }
 
-    /// Push a value to the value stack
-    pub fn push_value(&mut self, value: Value) {
+    /// Push a value to the value stack, trapping if the value stack is full
+    pub fn push_value(&mut self, value: Value) -> Result<(), RuntimeError> {
+        if self.values.len() + self.locals_count >= self.max_value_stack_size {
+            return Err(RuntimeError::StackSmash);
+        }
+
         self.values.push(value);
+        Ok(())
     }
 
     /// Copy a local variable to the top of the value stack
-    pub fn get_local(&mut self, idx: LocalIdx) {
-        let local_value = self.frames.last().unwrap_validated().locals.get(idx);
-        self.values.push(*local_value);
+    pub fn get_local(&mut self, idx: LocalIdx) -> Result<(), RuntimeError> {
+        let local_value = *self.frames.last().unwrap_validated().locals.get(idx);
+        self.push_value(local_value)
     }
 
     /// Pop value from the top of the value stack, writing it to the given local
@@ -160,16 +202,19 @@ impl Stack {
         self.frames.last_mut().unwrap_validated()
     }
 
-    /// Pop a [`CallFrame`] from the call stack, returning the module id, return address, and the return stp
-    pub fn pop_stackframe(&mut self) -> (usize, usize, usize) {
+    /// Pop a [`CallFrame`] from the call stack, returning the return address and the return stp
+    ///
+    /// The module to return to is the one of the [`CallFrame`] which is current after popping.
+    pub fn pop_stackframe(&mut self) -> (usize, usize) {
         let CallFrame {
-            module_idx,
+            locals,
             return_addr,
             value_stack_base_idx,
             return_value_count,
             return_stp,
             ..
         } = self.frames.pop().unwrap_validated();
+        self.locals_count -= locals.values().len();
 
         let truncation_top = self.values.len() - return_value_count;
         let _ = self.values.drain(value_stack_base_idx..truncation_top);
@@ -180,10 +225,11 @@ impl Stack {
             "after a function call finished, the stack must have exactly as many values as it had before calling the function plus the number of function return values"
         );
 
-        (module_idx, return_addr, return_stp)
+        (return_addr, return_stp)
     }
 
-    /// Push a stackframe to the call stack
+    /// Push a stackframe to the call stack, trapping if the maximum call depth is reached or its locals do not fit
+    /// onto the value stack
     ///
     /// Takes the current [`Self::values`]'s length as [`CallFrame::value_stack_base_idx`].
     pub fn push_stackframe(
@@ -194,7 +240,15 @@ impl Stack {
         locals: Locals,
         return_addr: usize,
         return_stp: usize,
-    ) {
+    ) -> Result<(), RuntimeError> {
+        if self.frames.len() >= self.max_call_depth
+            || self.values.len() + self.locals_count + locals.values().len()
+                > self.max_value_stack_size
+        {
+            return Err(RuntimeError::StackSmash);
+        }
+
+        self.locals_count += locals.values().len();
         self.frames.push(CallFrame {
             module_idx,
             func_idx,
@@ -203,7 +257,58 @@ impl Stack {
             value_stack_base_idx: self.values.len(),
             return_value_count: func_ty.returns.valtypes.len(),
             return_stp,
-        })
+        });
+        Ok(())
+    }
+
+    /// Replace the current [`CallFrame`] by a stackframe for the callee of a tail call
+    ///
+    /// All values of the current stackframe are dropped, so the parameters of the callee must already be moved into
+    /// its `locals`. The return address and return stp are kept, as the callee returns directly to the caller of the
+    /// replaced function. The validator checked that both functions return the same types. Traps if the locals of the
+    /// callee do not fit onto the value stack.
+    pub fn replace_stackframe(
+        &mut self,
+        module_idx: usize,
+        func_idx: FuncIdx,
+        locals: Locals,
+    ) -> Result<(), RuntimeError> {
+        let frame = self.frames.last_mut().unwrap_validated();
+        self.values.truncate(frame.value_stack_base_idx);
+        let locals_count = self.locals_count - frame.locals.values().len() + locals.values().len();
+        if self.values.len() + locals_count > self.max_value_stack_size {
+            return Err(RuntimeError::StackSmash);
+        }
+
+        frame.module_idx = module_idx;
+        frame.func_idx = func_idx;
+        frame.locals = locals;
+        self.locals_count = locals_count;
+        Ok(())
+    }
+
+    /// The values pushed by the function of the [`CallFrame`] at `frame_idx` in [`Stack::call_frames`], the topmost
+    /// one last
+    ///
+    /// For all but the current function, these are the values it pushed before its active call.
+    pub fn frame_values(&self, frame_idx: usize) -> &[Value] {
+        let end = self
+            .frames
+            .get(frame_idx + 1)
+            .map_or(self.values.len(), |callee| callee.value_stack_base_idx);
+        &self.values[self.frames[frame_idx].value_stack_base_idx..end]
+    }
+
+    /// All values on the stack, including the locals of all [`CallFrame`]s
+    pub fn all_values(&self) -> impl Iterator<Item = &Value> {
+        self.values
+            .iter()
+            .chain(self.frames.iter().flat_map(|frame| frame.locals.values()))
+    }
+
+    /// All [`CallFrame`]s on the call stack, the outermost one first
+    pub fn call_frames(&self) -> &[CallFrame] {
+        &self.frames
     }
 
     /// Returns how many stackframes are on the stack, in total.
@@ -221,10 +326,113 @@ impl Stack {
         self.values.drain(start..)
     }
 
-    // TODO change this interface
-    pub fn pop_n_values(&mut self, n: usize) {
-        self.values.truncate(self.values.len() - n);
+    /// Remove the `n` values directly below the `keep` topmost values of the value stack
+    pub fn remove_below_top(&mut self, keep: usize, n: usize) {
+        let end = self.values.len() - keep;
+        self.values.drain(end - n..end);
     }
+
+    /// Push a [`Handler`] for a `try_table` at `pc` within the function of the current [`CallFrame`]
+    ///
+    /// `body` is the range of instructions covered by its `catch_count` catch clauses, whose sidetable entries start at
+    /// `stp`. The `param_count` topmost values are the parameters of the `try_table`.
+    pub fn push_handler(
+        &mut self,
+        body: Range<usize>,
+        catch_count: usize,
+        stp: usize,
+        param_count: usize,
+        pc: usize,
+    ) {
+        // Remove the handlers of the `try_table`s which were left since they were entered
+        while self
+            .handlers
+            .last()
+            .is_some_and(|handler| !self.is_handler_active(handler, pc))
+        {
+            self.handlers.pop();
+        }
+
+        let frame = self.current_stackframe();
+        self.handlers.push(Handler {
+            frame_count: self.frames.len(),
+            module_idx: frame.module_idx,
+            func_idx: frame.func_idx,
+            body,
+            catch_count,
+            stp,
+            height: self.values.len() - param_count,
+        });
+    }
+
+    /// Pop the innermost [`Handler`] whose `try_table` was not left yet, where `pc` is the program counter within the
+    /// function of the current [`CallFrame`]
+    pub fn pop_handler(&mut self, pc: usize) -> Option<Handler> {
+        while let Some(handler) = self.handlers.pop() {
+            if self.is_handler_active(&handler, pc) {
+                return Some(handler);
+            }
+        }
+        None
+    }
+
+    /// Drop all stackframes and values which were pushed after the `try_table` of `handler` was entered
+    pub fn unwind_to_handler(&mut self, handler: &Handler) {
+        for frame in self.frames.drain(handler.frame_count..) {
+            self.locals_count -= frame.locals.values().len();
+        }
+        self.values.truncate(handler.height);
+    }
+
+    /// Check whether the `try_table` of `handler` was not left yet
+    ///
+    /// This is the case if the stackframe of the `try_table` still executes the same function and the program counter
+    /// within that function is still inside the `try_table`. The latter is the return address of the next stackframe,
+    /// or `pc` if there is none. Code inside a `try_table` can only be reached by entering it, which pushes a new
+    /// handler, so handlers of earlier executions of the same `try_table` are removed before their range is reached.
+    fn is_handler_active(&self, handler: &Handler, pc: usize) -> bool {
+        let Some(frame) = self.frames.get(handler.frame_count - 1) else {
+            return false;
+        };
+        if frame.module_idx != handler.module_idx || frame.func_idx != handler.func_idx {
+            return false;
+        }
+
+        let frame_pc = self
+            .frames
+            .get(handler.frame_count)
+            .map_or(pc, |callee| callee.return_addr);
+        handler.body.contains(&frame_pc)
+    }
+}
+
+/// The catch clauses of an entered `try_table`
+///
+/// See <https://webassembly.github.io/exception-handling/core/exec/runtime.html#exception-handlers>
+pub(crate) struct Handler {
+    /// The number of stackframes when the `try_table` was entered, the last one executing its function
+    pub frame_count: usize,
+
+    /// The module of the function containing the `try_table`
+    pub module_idx: usize,
+
+    /// The function containing the `try_table`, within the module it originates in
+    pub func_idx: FuncIdx,
+
+    /// The instructions covered by the catch clauses, from the first instruction after the catch clauses up to and
+    /// including the `try_table`'s END
+    pub body: Range<usize>,
+
+    /// The number of catch clauses
+    pub catch_count: usize,
+
+    /// The index of the first catch clause's sidetable entry, which holds the resolved [`CatchClause`]
+    ///
+    /// [`CatchClause`]: crate::core::reader::types::CatchClause
+    pub stp: usize,
+
+    /// The number of values on [`Stack::values`] below the parameters of the `try_table`
+    pub height: usize,
 }
 
 /// The [WASM spec](https://webassembly.github.io/spec/core/exec/runtime.html#stack) calls this `Activations`, however it refers to the call frames of functions.
@@ -232,7 +440,7 @@ pub(crate) struct CallFrame {
     /// Index to the module idx the function originates in.
     pub module_idx: usize,
 
-    /// Index to the function of this [`CallFrame`]
+    /// Index to the function of this [`CallFrame`], within the module it originates in
     pub func_idx: FuncIdx,
 
     /// Local variables such as parameters for this [`CallFrame`]'s function
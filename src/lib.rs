@@ -3,8 +3,11 @@ extern crate alloc;
 #[macro_use]
 extern crate log;
 
-pub use core::error::{Error, Result, RuntimeError};
-pub use core::reader::types::{Limits, NumType, RefType, ValType};
+pub use core::error::{Error, Result, RuntimeError, StoreInstantiationError};
+pub use core::reader::types::custom_section::{CustomSection, NameSection};
+pub use core::reader::types::dwarf::{DwarfInfo, SourceLocation};
+pub use core::reader::types::proposal::{Proposal, ProposalSet};
+pub use core::reader::types::{opcode, FuncType, Limits, NumType, RefType, ResultType, ValType};
 pub use core::rw_spinlock;
 pub use execution::value::Value;
 pub use execution::*;
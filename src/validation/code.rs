@@ -4,18 +4,21 @@ use alloc::vec::Vec;
 use core::iter;
 
 use crate::core::indices::{
-    DataIdx, ElemIdx, FuncIdx, GlobalIdx, LabelIdx, LocalIdx, MemIdx, TableIdx, TypeIdx,
+    DataIdx, ElemIdx, FuncIdx, GlobalIdx, LabelIdx, LocalIdx, MemIdx, TableIdx, TagIdx, TypeIdx,
 };
 use crate::core::reader::section_header::{SectionHeader, SectionTy};
 use crate::core::reader::span::Span;
 use crate::core::reader::types::element::ElemType;
-use crate::core::reader::types::global::Global;
+use crate::core::reader::types::global::GlobalType;
 use crate::core::reader::types::memarg::MemArg;
-use crate::core::reader::types::{BlockType, FuncType, MemType, NumType, TableType, ValType};
+use crate::core::reader::types::proposal::{Proposal, ProposalSet};
+use crate::core::reader::types::{
+    AddrType, BlockType, CatchClause, FuncType, MemType, NumType, TableType, TagType, ValType,
+};
 use crate::core::reader::{WasmReadable, WasmReader};
 use crate::core::sidetable::{Sidetable, SidetableEntry};
 use crate::validation_stack::{LabelInfo, ValidationStack};
-use crate::{Error, RefType, Result};
+use crate::{Error, RefType, Result, ValidationConfig};
 
 #[allow(clippy::too_many_arguments)]
 pub fn validate_code_section(
@@ -24,12 +27,15 @@ pub fn validate_code_section(
     fn_types: &[FuncType],
     type_idx_of_fn: &[usize],
     num_imported_funcs: usize,
-    globals: &[Global],
+    globals: &[GlobalType],
     memories: &[MemType],
+    tags: &[TagType],
     data_count: &Option<u32>,
     tables: &[TableType],
     elements: &[ElemType],
     referenced_functions: &BTreeSet<u32>,
+    used_proposals: &mut ProposalSet,
+    config: &ValidationConfig,
 ) -> Result<Vec<(Span, Sidetable)>> {
     assert_eq!(section_header.ty, SectionTy::Code);
 
@@ -47,7 +53,17 @@ pub fn validate_code_section(
 
         let locals = {
             let params = func_ty.params.valtypes.iter().cloned();
-            let declared_locals = read_declared_locals(wasm)?;
+            // the parameters count towards the configured maximum, but not towards the maximum of the spec
+            let max_declared_locals = config
+                .max_locals
+                .map_or(usize::MAX, |max| max.saturating_sub(params.len()))
+                .min(i32::MAX as usize);
+            let declared_locals = read_bounded_declared_locals(wasm, max_declared_locals)?;
+            used_proposals.extend(
+                declared_locals
+                    .iter()
+                    .filter_map(|ty| super::valtype_proposal(*ty)),
+            );
             params.chain(declared_locals).collect::<Vec<ValType>>()
         };
 
@@ -63,10 +79,13 @@ pub fn validate_code_section(
             fn_types,
             type_idx_of_fn,
             memories,
+            tags,
             data_count,
             tables,
             elements,
             referenced_functions,
+            used_proposals,
+            config,
         )?;
 
         // Check if there were unread trailing instructions after the last END
@@ -88,6 +107,24 @@ pub fn validate_code_section(
 }
 
 pub fn read_declared_locals(wasm: &mut WasmReader) -> Result<Vec<ValType>> {
+    read_bounded_declared_locals(wasm, i32::MAX as usize)
+}
+
+/// Read the type of a block, of which only those referring to a function type require the multi-value proposal
+fn read_block_type(
+    wasm: &mut WasmReader,
+    fn_types: &[FuncType],
+    used_proposals: &mut ProposalSet,
+) -> Result<FuncType> {
+    let block_ty = BlockType::read(wasm)?;
+    if let BlockType::Type(_) = block_ty {
+        used_proposals.insert(Proposal::MultiValue);
+    }
+    block_ty.as_func_type(fn_types)
+}
+
+/// Read the declared locals of a function, of which there must not be more than `max_locals`
+fn read_bounded_declared_locals(wasm: &mut WasmReader, max_locals: usize) -> Result<Vec<ValType>> {
     let locals = wasm.read_vec(|wasm| {
         let n = wasm.read_var_u32()? as usize;
         let valtype = ValType::read(wasm)?;
@@ -102,7 +139,7 @@ pub fn read_declared_locals(wasm: &mut WasmReader) -> Result<Vec<ValType>> {
     let mut total_no_of_locals: usize = 0;
     for local in &locals {
         let temp = local.0;
-        if temp > i32::MAX as usize {
+        if temp > max_locals {
             return Err(Error::TooManyLocals(total_no_of_locals));
         };
         total_no_of_locals = match total_no_of_locals.checked_add(temp) {
@@ -111,7 +148,7 @@ pub fn read_declared_locals(wasm: &mut WasmReader) -> Result<Vec<ValType>> {
         }
     }
 
-    if total_no_of_locals > i32::MAX as usize {
+    if total_no_of_locals > max_locals {
         return Err(Error::TooManyLocals(total_no_of_locals));
     }
 
@@ -144,6 +181,7 @@ fn generate_unbackpatched_sidetable_entry(
         delta_stp: stp_here as isize,
         popcnt,
         valcnt,
+        catch: None,
     });
 
     match label_info {
@@ -198,16 +236,26 @@ fn read_instructions(
     stack: &mut ValidationStack,
     sidetable: &mut Sidetable,
     locals: &[ValType],
-    globals: &[Global],
+    globals: &[GlobalType],
     fn_types: &[FuncType],
     type_idx_of_fn: &[usize],
     memories: &[MemType],
+    tags: &[TagType],
     data_count: &Option<u32>,
     tables: &[TableType],
     elements: &[ElemType],
     referenced_functions: &BTreeSet<u32>,
+    used_proposals: &mut ProposalSet,
+    config: &ValidationConfig,
 ) -> Result<()> {
     loop {
+        // the block of the function itself does not count as nesting
+        if let Some(max_nesting_depth) = config.max_nesting_depth {
+            if stack.ctrl_stack.len() - 1 > max_nesting_depth {
+                return Err(Error::NestingTooDeep(max_nesting_depth));
+            }
+        }
+
         let Ok(first_instr_byte) = wasm.read_u8() else {
             // TODO only do this if EOF
             return Err(Error::ExprMissingEnd);
@@ -220,19 +268,52 @@ fn read_instructions(
         trace!("Read instruction byte {first_instr_byte:#04X?} ({first_instr_byte}) at wasm_binary[{}]", wasm.pc);
 
         use crate::core::reader::types::opcode::*;
+
+        // prefixed instructions are identified by the sub-opcode following the prefix byte
+        let opcode = match first_instr_byte {
+            FC_EXTENSIONS | FD_EXTENSIONS | FE_EXTENSIONS => {
+                let Ok(second_instr) = wasm.read_var_u32() else {
+                    // TODO only do this if EOF
+                    return Err(Error::ExprMissingEnd);
+                };
+                Opcode::Prefixed(first_instr_byte, second_instr)
+            }
+            _ => Opcode::Single(first_instr_byte),
+        };
+        let Some(info) = opcode.info() else {
+            return Err(match opcode {
+                Opcode::Single(byte) => Error::InvalidInstr(byte),
+                Opcode::Prefixed(prefix, second_instr) => {
+                    Error::InvalidMultiByteInstr(prefix, second_instr)
+                }
+            });
+        };
+        if let Some(proposal) = info.proposal {
+            used_proposals.insert(proposal);
+        }
+
+        // instructions without immediates, whose types do not depend on the module, are validated by their signature
+        if let (Immediates::None, Some(signature)) = (info.immediates, info.signature) {
+            for &param in signature.params.iter().rev() {
+                stack.assert_pop_val_type(param)?;
+            }
+            for &result in signature.results {
+                stack.push_valtype(result);
+            }
+            continue;
+        }
+
         match first_instr_byte {
-            // nop: [] -> []
-            NOP => {}
             // block: [] -> [t*2]
             BLOCK => {
-                let block_ty = BlockType::read(wasm)?.as_func_type(fn_types)?;
+                let block_ty = read_block_type(wasm, fn_types, used_proposals)?;
                 let label_info = LabelInfo::Block {
                     stps_to_backpatch: Vec::new(),
                 };
                 stack.assert_push_ctrl(label_info, block_ty)?;
             }
             LOOP => {
-                let block_ty = BlockType::read(wasm)?.as_func_type(fn_types)?;
+                let block_ty = read_block_type(wasm, fn_types, used_proposals)?;
                 let label_info = LabelInfo::Loop {
                     ip: wasm.pc,
                     stp: sidetable.len(),
@@ -240,7 +321,7 @@ fn read_instructions(
                 stack.assert_push_ctrl(label_info, block_ty)?;
             }
             IF => {
-                let block_ty = BlockType::read(wasm)?.as_func_type(fn_types)?;
+                let block_ty = read_block_type(wasm, fn_types, used_proposals)?;
 
                 stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
 
@@ -250,6 +331,7 @@ fn read_instructions(
                     delta_stp: stp_here as isize,
                     popcnt: 0,
                     valcnt: block_ty.params.valtypes.len(),
+                    catch: None,
                 });
 
                 let label_info = LabelInfo::If {
@@ -275,6 +357,7 @@ fn read_instructions(
                         delta_stp: stp_here as isize,
                         popcnt: 0,
                         valcnt: block_ty.returns.valtypes.len(),
+                        catch: None,
                     });
                     stps_to_backpatch.push(stp_here);
 
@@ -457,6 +540,123 @@ fn read_instructions(
                     stack.push_valtype(*typ);
                 }
             }
+            // return_call [t1*] -> [t2*], where the callee and the current function return [t3*]
+            RETURN_CALL => {
+                let func_to_call_idx = wasm.read_var_u32()? as FuncIdx;
+                let func_ty = type_idx_of_fn
+                    .get(func_to_call_idx)
+                    .map(|type_idx| &fn_types[*type_idx])
+                    .ok_or(Error::FunctionIsNotDefined(func_to_call_idx))?;
+
+                if func_ty.returns != stack.ctrl_stack[0].block_ty.returns {
+                    return Err(Error::TailCallTypeMismatch);
+                }
+
+                for typ in func_ty.params.valtypes.iter().rev() {
+                    stack.assert_pop_val_type(*typ)?;
+                }
+
+                stack.make_unspecified()?;
+            }
+            RETURN_CALL_INDIRECT => {
+                let type_idx = wasm.read_var_u32()? as TypeIdx;
+
+                let table_idx = wasm.read_var_u32()? as TableIdx;
+
+                if tables.len() <= table_idx {
+                    return Err(Error::TableIsNotDefined(table_idx));
+                }
+
+                let tab = &tables[table_idx];
+
+                if tab.et != RefType::FuncRef {
+                    return Err(Error::WrongRefTypeForInteropValue(tab.et, RefType::FuncRef));
+                }
+
+                if type_idx >= fn_types.len() {
+                    return Err(Error::FunctionTypeIsNotDefined(type_idx));
+                }
+
+                let func_ty = &fn_types[type_idx];
+
+                if func_ty.returns != stack.ctrl_stack[0].block_ty.returns {
+                    return Err(Error::TailCallTypeMismatch);
+                }
+
+                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+
+                for typ in func_ty.params.valtypes.iter().rev() {
+                    stack.assert_pop_val_type(*typ)?;
+                }
+
+                stack.make_unspecified()?;
+            }
+            // try_table bt catch* instr* end: [t1*] -> [t2*]
+            TRY_TABLE => {
+                let block_ty = read_block_type(wasm, fn_types, used_proposals)?;
+                let catches = wasm.read_vec(CatchClause::read)?;
+
+                // The first entry marks the end of the try_table, as exceptions are only caught before it
+                let mut label_info = LabelInfo::Block {
+                    stps_to_backpatch: Vec::new(),
+                };
+                generate_unbackpatched_sidetable_entry(wasm, sidetable, 0, 0, &mut label_info);
+                stack.assert_push_ctrl(label_info, block_ty)?;
+
+                // Catching an exception unwinds the stack to the height of the try_table, then branches like `br`
+                let height = stack.ctrl_stack.last().unwrap().height;
+                for catch in catches {
+                    let label_idx = catch.label_idx();
+                    let mut catch_types = match catch.tag_idx() {
+                        Some(tag_idx) => {
+                            let tag = tags.get(tag_idx).ok_or(Error::TagIsNotDefined(tag_idx))?;
+                            fn_types[tag.type_idx].params.valtypes.clone()
+                        }
+                        None => Vec::new(),
+                    };
+                    if catch.passes_exnref() {
+                        catch_types.push(ValType::RefType(RefType::ExnRef));
+                    }
+
+                    // The labels of catch clauses are relative to the block around the try_table
+                    let target = stack
+                        .ctrl_stack
+                        .len()
+                        .checked_sub(label_idx + 2)
+                        .map(|idx| &mut stack.ctrl_stack[idx])
+                        .ok_or(Error::InvalidLabelIdx(label_idx))?;
+                    if target.label_types() != catch_types.as_slice() {
+                        return Err(Error::CatchTypeMismatch(label_idx));
+                    }
+
+                    let popcnt = height - target.height;
+                    generate_unbackpatched_sidetable_entry(
+                        wasm,
+                        sidetable,
+                        catch_types.len(),
+                        popcnt,
+                        &mut target.label_info,
+                    );
+                    // the catch clause is resolved here, so that throwing only consults the sidetable
+                    sidetable.last_mut().unwrap().catch = Some(catch);
+                }
+            }
+            // throw x: [t1* t*] -> [t2*], where the tag x has the parameters [t*]
+            THROW => {
+                let tag_idx = wasm.read_var_u32()? as TagIdx;
+                let tag = tags.get(tag_idx).ok_or(Error::TagIsNotDefined(tag_idx))?;
+
+                for typ in fn_types[tag.type_idx].params.valtypes.iter().rev() {
+                    stack.assert_pop_val_type(*typ)?;
+                }
+
+                stack.make_unspecified()?;
+            }
+            // throw_ref: [t1* exnref] -> [t2*]
+            THROW_REF => {
+                stack.assert_pop_val_type(ValType::RefType(RefType::ExnRef))?;
+                stack.make_unspecified()?;
+            }
             // unreachable: [t1*] -> [t2*]
             UNREACHABLE => {
                 stack.make_unspecified()?;
@@ -502,7 +702,7 @@ fn read_instructions(
                     .get(global_idx)
                     .ok_or(Error::InvalidGlobalIdx(global_idx))?;
 
-                stack.push_valtype(global.ty.ty);
+                stack.push_valtype(global.ty);
             }
             // global.set [t] -> []
             GLOBAL_SET => {
@@ -511,11 +711,11 @@ fn read_instructions(
                     .get(global_idx)
                     .ok_or(Error::InvalidGlobalIdx(global_idx))?;
 
-                if !global.ty.is_mut {
+                if !global.is_mut {
                     return Err(Error::GlobalIsConst);
                 }
 
-                stack.assert_pop_val_type(global.ty.ty)?;
+                stack.assert_pop_val_type(global.ty)?;
             }
             TABLE_GET => {
                 let table_idx = wasm.read_var_u32()? as TableIdx;
@@ -542,282 +742,227 @@ fn read_instructions(
                 stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
             }
             I32_LOAD => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 4 {
                     return Err(Error::ErroneousAlignment(memarg.align, 4));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::I32));
             }
             I64_LOAD => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 8 {
                     return Err(Error::ErroneousAlignment(memarg.align, 8));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::I64));
             }
             F32_LOAD => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 4 {
                     return Err(Error::ErroneousAlignment(memarg.align, 4));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::F32));
             }
             F64_LOAD => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 8 {
                     return Err(Error::ErroneousAlignment(memarg.align, 8));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::F64));
             }
             I32_LOAD8_S => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 1 {
                     return Err(Error::ErroneousAlignment(memarg.align, 1));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::I32));
             }
             I32_LOAD8_U => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 1 {
                     return Err(Error::ErroneousAlignment(memarg.align, 1));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::I32));
             }
             I32_LOAD16_S => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 2 {
                     return Err(Error::ErroneousAlignment(memarg.align, 2));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::I32));
             }
             I32_LOAD16_U => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 2 {
                     return Err(Error::ErroneousAlignment(memarg.align, 2));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::I32));
             }
             I64_LOAD8_S => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 1 {
                     return Err(Error::ErroneousAlignment(memarg.align, 1));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::I64));
             }
             I64_LOAD8_U => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 1 {
                     return Err(Error::ErroneousAlignment(memarg.align, 1));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::I64));
             }
             I64_LOAD16_S => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 2 {
                     return Err(Error::ErroneousAlignment(memarg.align, 2));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::I64));
             }
             I64_LOAD16_U => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 2 {
                     return Err(Error::ErroneousAlignment(memarg.align, 2));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::I64));
             }
             I64_LOAD32_S => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 4 {
                     return Err(Error::ErroneousAlignment(memarg.align, 4));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::I64));
             }
             I64_LOAD32_U => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 4 {
                     return Err(Error::ErroneousAlignment(memarg.align, 4));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
                 stack.push_valtype(ValType::NumType(NumType::I64));
             }
             I32_STORE => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 4 {
                     return Err(Error::ErroneousAlignment(memarg.align, 4));
                 }
                 stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
             }
             I64_STORE => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 8 {
                     return Err(Error::ErroneousAlignment(memarg.align, 8));
                 }
                 stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
             }
             F32_STORE => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 4 {
                     return Err(Error::ErroneousAlignment(memarg.align, 4));
                 }
                 stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
             }
             F64_STORE => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 8 {
                     return Err(Error::ErroneousAlignment(memarg.align, 8));
                 }
                 stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
             }
             I32_STORE8 => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 1 {
                     return Err(Error::ErroneousAlignment(memarg.align, 1));
                 }
                 stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
             }
             I32_STORE16 => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 2 {
                     return Err(Error::ErroneousAlignment(memarg.align, 2));
                 }
                 stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
             }
             I64_STORE8 => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 1 {
                     return Err(Error::ErroneousAlignment(memarg.align, 1));
                 }
                 stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
             }
             I64_STORE16 => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 2 {
                     return Err(Error::ErroneousAlignment(memarg.align, 2));
                 }
                 stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
             }
             I64_STORE32 => {
-                if memories.is_empty() {
-                    return Err(Error::MemoryIsNotDefined(0));
-                }
                 let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
                 if memarg.align > 4 {
                     return Err(Error::ErroneousAlignment(memarg.align, 4));
                 }
                 stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                stack.assert_pop_val_type(addr_ty)?;
             }
             MEMORY_SIZE => {
-                let mem_idx = wasm.read_u8()? as MemIdx;
-                if mem_idx != 0 {
-                    return Err(Error::UnsupportedProposal(
-                        crate::core::error::Proposal::MultipleMemories,
-                    ));
-                }
+                let mem_idx = wasm.read_var_u32()? as MemIdx;
                 if memories.len() <= mem_idx {
                     return Err(Error::MemoryIsNotDefined(mem_idx));
                 }
-                stack.push_valtype(ValType::NumType(NumType::I32));
+                stack.push_valtype(memories[mem_idx].addr_type.into());
             }
             MEMORY_GROW => {
-                let mem_idx = wasm.read_u8()? as MemIdx;
-                if mem_idx != 0 {
-                    return Err(Error::UnsupportedProposal(
-                        crate::core::error::Proposal::MultipleMemories,
-                    ));
-                }
+                let mem_idx = wasm.read_var_u32()? as MemIdx;
                 if memories.len() <= mem_idx {
                     return Err(Error::MemoryIsNotDefined(mem_idx));
                 }
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-                stack.push_valtype(ValType::NumType(NumType::I32));
+                let addr_ty = memories[mem_idx].addr_type.into();
+                stack.assert_pop_val_type(addr_ty)?;
+                stack.push_valtype(addr_ty);
             }
             // i32.const: [] -> [i32]
             I32_CONST => {
@@ -836,170 +981,6 @@ fn read_instructions(
                 let _num = wasm.read_var_f64()?;
                 stack.push_valtype(ValType::NumType(NumType::F64));
             }
-            I32_EQZ => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-            I32_EQ | I32_NE | I32_LT_S | I32_LT_U | I32_GT_S | I32_GT_U | I32_LE_S | I32_LE_U
-            | I32_GE_S | I32_GE_U => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-            I64_EQZ => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-            I64_EQ | I64_NE | I64_LT_S | I64_LT_U | I64_GT_S | I64_GT_U | I64_LE_S | I64_LE_U
-            | I64_GE_S | I64_GE_U => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-            F32_EQ | F32_NE | F32_LT | F32_GT | F32_LE | F32_GE => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-            F64_EQ | F64_NE | F64_LT | F64_GT | F64_LE | F64_GE => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-            F32_ABS | F32_NEG | F32_CEIL | F32_FLOOR | F32_TRUNC | F32_NEAREST | F32_SQRT => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::F32));
-            }
-            F32_ADD | F32_SUB | F32_MUL | F32_DIV | F32_MIN | F32_MAX | F32_COPYSIGN => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::F32));
-            }
-            F64_ABS | F64_NEG | F64_CEIL | F64_FLOOR | F64_TRUNC | F64_NEAREST | F64_SQRT => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::F64));
-            }
-            F64_ADD | F64_SUB | F64_MUL | F64_DIV | F64_MIN | F64_MAX | F64_COPYSIGN => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::F64));
-            }
-            I32_ADD | I32_SUB | I32_MUL | I32_DIV_S | I32_DIV_U | I32_REM_S => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-            // i32.clz: [i32] -> [i32]
-            I32_CLZ | I32_CTZ | I32_POPCNT => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-            I32_REM_U | I32_AND | I32_OR | I32_XOR | I32_SHL | I32_SHR_S | I32_SHR_U | I32_ROTL
-            | I32_ROTR => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-            I64_CLZ | I64_CTZ | I64_POPCNT => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I64));
-            }
-
-            I64_ADD | I64_SUB | I64_MUL | I64_DIV_S | I64_DIV_U | I64_REM_S | I64_REM_U
-            | I64_AND | I64_OR | I64_XOR | I64_SHL | I64_SHR_S | I64_SHR_U | I64_ROTL
-            | I64_ROTR => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I64));
-            }
-
-            I32_WRAP_I64 => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-
-            I32_TRUNC_F32_S | I32_TRUNC_F32_U | I32_REINTERPRET_F32 => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-
-            I32_TRUNC_F64_S | I32_TRUNC_F64_U => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-
-            I64_EXTEND_I32_S | I64_EXTEND_I32_U => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I64));
-            }
-
-            I64_TRUNC_F32_S | I64_TRUNC_F32_U => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I64));
-            }
-
-            I64_TRUNC_F64_S | I64_TRUNC_F64_U | I64_REINTERPRET_F64 => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I64));
-            }
-
-            F32_CONVERT_I32_S | F32_CONVERT_I32_U | F32_REINTERPRET_I32 => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::F32));
-            }
-
-            F32_CONVERT_I64_S | F32_CONVERT_I64_U => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::F32));
-            }
-
-            F32_DEMOTE_F64 => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::F32));
-            }
-
-            F64_CONVERT_I32_S | F64_CONVERT_I32_U => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::F64));
-            }
-
-            F64_CONVERT_I64_S | F64_CONVERT_I64_U | F64_REINTERPRET_I64 => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
-
-                stack.push_valtype(ValType::NumType(NumType::F64));
-            }
-
-            F64_PROMOTE_F32 => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::F64));
-            }
-
             REF_NULL => {
                 let reftype = RefType::read(wasm)?;
                 // at validation-time we don't really care if it's null or not
@@ -1031,54 +1012,18 @@ fn read_instructions(
             }
 
             FC_EXTENSIONS => {
-                let Ok(second_instr_byte) = wasm.read_u8() else {
-                    // TODO only do this if EOF
-                    return Err(Error::ExprMissingEnd);
+                let Opcode::Prefixed(_, second_instr) = opcode else {
+                    unreachable!("the sub-opcode was read together with the prefix")
                 };
+                // all known sub-opcodes fit into a single byte
+                let second_instr_byte = second_instr as u8;
                 trace!("Read instruction byte {second_instr_byte:#04X?} ({second_instr_byte}) at wasm_binary[{}]", wasm.pc);
 
                 use crate::core::reader::types::opcode::fc_extensions::*;
                 match second_instr_byte {
-                    I32_TRUNC_SAT_F32_S => {
-                        stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-                        stack.push_valtype(ValType::NumType(NumType::I32));
-                    }
-                    I32_TRUNC_SAT_F32_U => {
-                        stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-                        stack.push_valtype(ValType::NumType(NumType::I32));
-                    }
-                    I32_TRUNC_SAT_F64_S => {
-                        stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-                        stack.push_valtype(ValType::NumType(NumType::I32));
-                    }
-                    I32_TRUNC_SAT_F64_U => {
-                        stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-                        stack.push_valtype(ValType::NumType(NumType::I32));
-                    }
-                    I64_TRUNC_SAT_F32_S => {
-                        stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-                        stack.push_valtype(ValType::NumType(NumType::I64));
-                    }
-                    I64_TRUNC_SAT_F32_U => {
-                        stack.assert_pop_val_type(ValType::NumType(NumType::F32))?;
-                        stack.push_valtype(ValType::NumType(NumType::I64));
-                    }
-                    I64_TRUNC_SAT_F64_S => {
-                        stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-                        stack.push_valtype(ValType::NumType(NumType::I64));
-                    }
-                    I64_TRUNC_SAT_F64_U => {
-                        stack.assert_pop_val_type(ValType::NumType(NumType::F64))?;
-                        stack.push_valtype(ValType::NumType(NumType::I64));
-                    }
                     MEMORY_INIT => {
                         let data_idx = wasm.read_var_u32()? as DataIdx;
-                        let mem_idx = wasm.read_u8()? as MemIdx;
-                        if mem_idx != 0 {
-                            return Err(Error::UnsupportedProposal(
-                                crate::core::error::Proposal::MultipleMemories,
-                            ));
-                        }
+                        let mem_idx = wasm.read_var_u32()? as MemIdx;
                         if memories.len() <= mem_idx {
                             return Err(Error::MemoryIsNotDefined(mem_idx));
                         }
@@ -1090,7 +1035,7 @@ fn read_instructions(
                         }
                         stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
                         stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-                        stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                        stack.assert_pop_val_type(memories[mem_idx].addr_type.into())?;
                     }
                     DATA_DROP => {
                         if data_count.is_none() {
@@ -1102,32 +1047,36 @@ fn read_instructions(
                         }
                     }
                     MEMORY_COPY => {
-                        let (dst, src) = (wasm.read_u8()? as usize, wasm.read_u8()? as usize);
-                        if dst != 0 || src != 0 {
-                            return Err(Error::UnsupportedProposal(
-                                crate::core::error::Proposal::MultipleMemories,
-                            ));
+                        let (dst, src) = (
+                            wasm.read_var_u32()? as MemIdx,
+                            wasm.read_var_u32()? as MemIdx,
+                        );
+                        if memories.len() <= dst {
+                            return Err(Error::MemoryIsNotDefined(dst));
                         }
-                        if memories.is_empty() {
-                            return Err(Error::MemoryIsNotDefined(0));
+                        if memories.len() <= src {
+                            return Err(Error::MemoryIsNotDefined(src));
                         }
-                        stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-                        stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-                        stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                        let (dst_addr_type, src_addr_type) =
+                            (memories[dst].addr_type, memories[src].addr_type);
+                        // The count is only 64-bit if both memories are
+                        let n_addr_type = match (dst_addr_type, src_addr_type) {
+                            (AddrType::I64, AddrType::I64) => AddrType::I64,
+                            _ => AddrType::I32,
+                        };
+                        stack.assert_pop_val_type(n_addr_type.into())?;
+                        stack.assert_pop_val_type(src_addr_type.into())?;
+                        stack.assert_pop_val_type(dst_addr_type.into())?;
                     }
                     MEMORY_FILL => {
-                        let mem_idx = wasm.read_u8()? as MemIdx;
-                        if mem_idx != 0 {
-                            return Err(Error::UnsupportedProposal(
-                                crate::core::error::Proposal::MultipleMemories,
-                            ));
-                        }
+                        let mem_idx = wasm.read_var_u32()? as MemIdx;
                         if memories.len() <= mem_idx {
                             return Err(Error::MemoryIsNotDefined(mem_idx));
                         }
+                        let addr_ty = memories[mem_idx].addr_type.into();
+                        stack.assert_pop_val_type(addr_ty)?;
                         stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-                        stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-                        stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+                        stack.assert_pop_val_type(addr_ty)?;
                     }
                     TABLE_INIT => {
                         let elem_idx = wasm.read_var_u32()? as ElemIdx;
@@ -1222,39 +1171,289 @@ fn read_instructions(
                     _ => {
                         return Err(Error::InvalidMultiByteInstr(
                             first_instr_byte,
-                            second_instr_byte,
+                            second_instr_byte.into(),
                         ))
                     }
                 }
             }
 
-            I32_EXTEND8_S => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
-
-                stack.push_valtype(ValType::NumType(NumType::I32));
+            FD_EXTENSIONS => {
+                let Opcode::Prefixed(_, second_instr) = opcode else {
+                    unreachable!("the sub-opcode was read together with the prefix")
+                };
+                trace!("Read vector instruction {second_instr:#04X?} ({second_instr}) at wasm_binary[{}]", wasm.pc);
+
+                const V128: ValType = ValType::VecType;
+                const I32: ValType = ValType::NumType(NumType::I32);
+                const I64: ValType = ValType::NumType(NumType::I64);
+                const F32: ValType = ValType::NumType(NumType::F32);
+                const F64: ValType = ValType::NumType(NumType::F64);
+
+                use crate::core::reader::types::opcode::fd_extensions::*;
+                match second_instr {
+                    // v128.load: [at] -> [v128]
+                    V128_LOAD | V128_LOAD8X8_S | V128_LOAD8X8_U | V128_LOAD16X4_S
+                    | V128_LOAD16X4_U | V128_LOAD32X2_S | V128_LOAD32X2_U | V128_LOAD8_SPLAT
+                    | V128_LOAD16_SPLAT | V128_LOAD32_SPLAT | V128_LOAD64_SPLAT
+                    | V128_LOAD32_ZERO | V128_LOAD64_ZERO => {
+                        let max_align = match second_instr {
+                            V128_LOAD => 16,
+                            V128_LOAD8_SPLAT => 1,
+                            V128_LOAD16_SPLAT => 2,
+                            V128_LOAD32_SPLAT | V128_LOAD32_ZERO => 4,
+                            _ => 8,
+                        };
+                        let memarg = MemArg::read(wasm)?;
+                        let addr_ty = validate_memarg(&memarg, memories)?;
+                        if memarg.align > max_align {
+                            return Err(Error::ErroneousAlignment(memarg.align, max_align));
+                        }
+                        stack.assert_pop_val_type(addr_ty)?;
+                        stack.push_valtype(V128);
+                    }
+                    // v128.store: [at v128] -> []
+                    V128_STORE => {
+                        let memarg = MemArg::read(wasm)?;
+                        let addr_ty = validate_memarg(&memarg, memories)?;
+                        if memarg.align > 16 {
+                            return Err(Error::ErroneousAlignment(memarg.align, 16));
+                        }
+                        stack.assert_pop_val_type(V128)?;
+                        stack.assert_pop_val_type(addr_ty)?;
+                    }
+                    // v128.load8_lane: [at v128] -> [v128]
+                    // v128.store8_lane: [at v128] -> []
+                    V128_LOAD8_LANE | V128_LOAD16_LANE | V128_LOAD32_LANE | V128_LOAD64_LANE
+                    | V128_STORE8_LANE | V128_STORE16_LANE | V128_STORE32_LANE
+                    | V128_STORE64_LANE => {
+                        let lane_size: u8 = match second_instr {
+                            V128_LOAD8_LANE | V128_STORE8_LANE => 1,
+                            V128_LOAD16_LANE | V128_STORE16_LANE => 2,
+                            V128_LOAD32_LANE | V128_STORE32_LANE => 4,
+                            _ => 8,
+                        };
+                        let memarg = MemArg::read(wasm)?;
+                        let addr_ty = validate_memarg(&memarg, memories)?;
+                        if memarg.align > lane_size.into() {
+                            return Err(Error::ErroneousAlignment(memarg.align, lane_size.into()));
+                        }
+                        read_lane_idx(wasm, 16 / lane_size)?;
+                        stack.assert_pop_val_type(V128)?;
+                        stack.assert_pop_val_type(addr_ty)?;
+                        if second_instr <= V128_LOAD64_LANE {
+                            stack.push_valtype(V128);
+                        }
+                    }
+                    // v128.const: [] -> [v128]
+                    V128_CONST => {
+                        let _bytes = wasm.strip_bytes::<16>()?;
+                        stack.push_valtype(V128);
+                    }
+                    // i8x16.shuffle: [v128 v128] -> [v128]
+                    I8X16_SHUFFLE => {
+                        for _ in 0..16 {
+                            read_lane_idx(wasm, 32)?;
+                        }
+                        stack.assert_pop_val_type(V128)?;
+                        stack.assert_pop_val_type(V128)?;
+                        stack.push_valtype(V128);
+                    }
+                    // i8x16.extract_lane_s: [v128] -> [i32]
+                    I8X16_EXTRACT_LANE_S | I8X16_EXTRACT_LANE_U | I16X8_EXTRACT_LANE_S
+                    | I16X8_EXTRACT_LANE_U | I32X4_EXTRACT_LANE | I64X2_EXTRACT_LANE
+                    | F32X4_EXTRACT_LANE | F64X2_EXTRACT_LANE => {
+                        let (num_lanes, scalar_ty) = match second_instr {
+                            I8X16_EXTRACT_LANE_S | I8X16_EXTRACT_LANE_U => (16, I32),
+                            I16X8_EXTRACT_LANE_S | I16X8_EXTRACT_LANE_U => (8, I32),
+                            I32X4_EXTRACT_LANE => (4, I32),
+                            I64X2_EXTRACT_LANE => (2, I64),
+                            F32X4_EXTRACT_LANE => (4, F32),
+                            _ => (2, F64),
+                        };
+                        read_lane_idx(wasm, num_lanes)?;
+                        stack.assert_pop_val_type(V128)?;
+                        stack.push_valtype(scalar_ty);
+                    }
+                    // i8x16.replace_lane: [v128 i32] -> [v128]
+                    I8X16_REPLACE_LANE | I16X8_REPLACE_LANE | I32X4_REPLACE_LANE
+                    | I64X2_REPLACE_LANE | F32X4_REPLACE_LANE | F64X2_REPLACE_LANE => {
+                        let (num_lanes, scalar_ty) = match second_instr {
+                            I8X16_REPLACE_LANE => (16, I32),
+                            I16X8_REPLACE_LANE => (8, I32),
+                            I32X4_REPLACE_LANE => (4, I32),
+                            I64X2_REPLACE_LANE => (2, I64),
+                            F32X4_REPLACE_LANE => (4, F32),
+                            _ => (2, F64),
+                        };
+                        read_lane_idx(wasm, num_lanes)?;
+                        stack.assert_pop_val_type(scalar_ty)?;
+                        stack.assert_pop_val_type(V128)?;
+                        stack.push_valtype(V128);
+                    }
+                    _ => return Err(Error::InvalidMultiByteInstr(first_instr_byte, second_instr)),
+                }
             }
-            I32_EXTEND16_S => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I32))?;
+            FE_EXTENSIONS => {
+                let Opcode::Prefixed(_, second_instr) = opcode else {
+                    unreachable!("the sub-opcode was read together with the prefix")
+                };
+                trace!("Read atomic instruction {second_instr:#04X?} ({second_instr}) at wasm_binary[{}]", wasm.pc);
 
-                stack.push_valtype(ValType::NumType(NumType::I32));
-            }
-            I64_EXTEND8_S => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
+                const I32: ValType = ValType::NumType(NumType::I32);
+                const I64: ValType = ValType::NumType(NumType::I64);
 
-                stack.push_valtype(ValType::NumType(NumType::I64));
-            }
-            I64_EXTEND16_S => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
+                use crate::core::reader::types::opcode::fe_extensions::*;
+                if second_instr == ATOMIC_FENCE {
+                    // atomic.fence: [] -> []
+                    if wasm.read_u8()? != 0x00 {
+                        return Err(Error::InvalidMultiByteInstr(first_instr_byte, second_instr));
+                    }
+                    continue;
+                }
+
+                // the width in bytes of the accessed datum and the type of the operands
+                let (width, ty) = match second_instr {
+                    MEMORY_ATOMIC_NOTIFY
+                    | MEMORY_ATOMIC_WAIT32
+                    | I32_ATOMIC_LOAD
+                    | I32_ATOMIC_STORE
+                    | I32_ATOMIC_RMW_ADD
+                    | I32_ATOMIC_RMW_SUB
+                    | I32_ATOMIC_RMW_AND
+                    | I32_ATOMIC_RMW_OR
+                    | I32_ATOMIC_RMW_XOR
+                    | I32_ATOMIC_RMW_XCHG
+                    | I32_ATOMIC_RMW_CMPXCHG => (4, I32),
+                    MEMORY_ATOMIC_WAIT64
+                    | I64_ATOMIC_LOAD
+                    | I64_ATOMIC_STORE
+                    | I64_ATOMIC_RMW_ADD
+                    | I64_ATOMIC_RMW_SUB
+                    | I64_ATOMIC_RMW_AND
+                    | I64_ATOMIC_RMW_OR
+                    | I64_ATOMIC_RMW_XOR
+                    | I64_ATOMIC_RMW_XCHG
+                    | I64_ATOMIC_RMW_CMPXCHG => (8, I64),
+                    I32_ATOMIC_LOAD8_U
+                    | I32_ATOMIC_STORE8
+                    | I32_ATOMIC_RMW8_ADD_U
+                    | I32_ATOMIC_RMW8_SUB_U
+                    | I32_ATOMIC_RMW8_AND_U
+                    | I32_ATOMIC_RMW8_OR_U
+                    | I32_ATOMIC_RMW8_XOR_U
+                    | I32_ATOMIC_RMW8_XCHG_U
+                    | I32_ATOMIC_RMW8_CMPXCHG_U => (1, I32),
+                    I32_ATOMIC_LOAD16_U
+                    | I32_ATOMIC_STORE16
+                    | I32_ATOMIC_RMW16_ADD_U
+                    | I32_ATOMIC_RMW16_SUB_U
+                    | I32_ATOMIC_RMW16_AND_U
+                    | I32_ATOMIC_RMW16_OR_U
+                    | I32_ATOMIC_RMW16_XOR_U
+                    | I32_ATOMIC_RMW16_XCHG_U
+                    | I32_ATOMIC_RMW16_CMPXCHG_U => (2, I32),
+                    I64_ATOMIC_LOAD8_U
+                    | I64_ATOMIC_STORE8
+                    | I64_ATOMIC_RMW8_ADD_U
+                    | I64_ATOMIC_RMW8_SUB_U
+                    | I64_ATOMIC_RMW8_AND_U
+                    | I64_ATOMIC_RMW8_OR_U
+                    | I64_ATOMIC_RMW8_XOR_U
+                    | I64_ATOMIC_RMW8_XCHG_U
+                    | I64_ATOMIC_RMW8_CMPXCHG_U => (1, I64),
+                    I64_ATOMIC_LOAD16_U
+                    | I64_ATOMIC_STORE16
+                    | I64_ATOMIC_RMW16_ADD_U
+                    | I64_ATOMIC_RMW16_SUB_U
+                    | I64_ATOMIC_RMW16_AND_U
+                    | I64_ATOMIC_RMW16_OR_U
+                    | I64_ATOMIC_RMW16_XOR_U
+                    | I64_ATOMIC_RMW16_XCHG_U
+                    | I64_ATOMIC_RMW16_CMPXCHG_U => (2, I64),
+                    I64_ATOMIC_LOAD32_U
+                    | I64_ATOMIC_STORE32
+                    | I64_ATOMIC_RMW32_ADD_U
+                    | I64_ATOMIC_RMW32_SUB_U
+                    | I64_ATOMIC_RMW32_AND_U
+                    | I64_ATOMIC_RMW32_OR_U
+                    | I64_ATOMIC_RMW32_XOR_U
+                    | I64_ATOMIC_RMW32_XCHG_U
+                    | I64_ATOMIC_RMW32_CMPXCHG_U => (4, I64),
+                    _ => return Err(Error::InvalidMultiByteInstr(first_instr_byte, second_instr)),
+                };
 
-                stack.push_valtype(ValType::NumType(NumType::I64));
-            }
-            I64_EXTEND32_S => {
-                stack.assert_pop_val_type(ValType::NumType(NumType::I64))?;
+                // Unlike other memory instructions, atomic ones must declare exactly their natural alignment
+                let memarg = MemArg::read(wasm)?;
+                let addr_ty = validate_memarg(&memarg, memories)?;
+                if memarg.align != width {
+                    return Err(Error::ErroneousAlignment(memarg.align, width));
+                }
 
-                stack.push_valtype(ValType::NumType(NumType::I64));
+                match second_instr {
+                    // memory.atomic.notify: [at i32] -> [i32]
+                    MEMORY_ATOMIC_NOTIFY => {
+                        stack.assert_pop_val_type(I32)?;
+                        stack.assert_pop_val_type(addr_ty)?;
+                        stack.push_valtype(I32);
+                    }
+                    // memory.atomic.wait32: [at i32 i64] -> [i32]
+                    MEMORY_ATOMIC_WAIT32 | MEMORY_ATOMIC_WAIT64 => {
+                        stack.assert_pop_val_type(I64)?;
+                        stack.assert_pop_val_type(ty)?;
+                        stack.assert_pop_val_type(addr_ty)?;
+                        stack.push_valtype(I32);
+                    }
+                    // i32.atomic.load: [at] -> [i32]
+                    I32_ATOMIC_LOAD..=I64_ATOMIC_LOAD32_U => {
+                        stack.assert_pop_val_type(addr_ty)?;
+                        stack.push_valtype(ty);
+                    }
+                    // i32.atomic.store: [at i32] -> []
+                    I32_ATOMIC_STORE..=I64_ATOMIC_STORE32 => {
+                        stack.assert_pop_val_type(ty)?;
+                        stack.assert_pop_val_type(addr_ty)?;
+                    }
+                    // i32.atomic.rmw.cmpxchg: [at i32 i32] -> [i32]
+                    I32_ATOMIC_RMW_CMPXCHG..=I64_ATOMIC_RMW32_CMPXCHG_U => {
+                        stack.assert_pop_val_type(ty)?;
+                        stack.assert_pop_val_type(ty)?;
+                        stack.assert_pop_val_type(addr_ty)?;
+                        stack.push_valtype(ty);
+                    }
+                    // i32.atomic.rmw.add: [at i32] -> [i32]
+                    _ => {
+                        stack.assert_pop_val_type(ty)?;
+                        stack.assert_pop_val_type(addr_ty)?;
+                        stack.push_valtype(ty);
+                    }
+                }
             }
 
             _ => return Err(Error::InvalidInstr(first_instr_byte)),
         }
     }
 }
+
+/// Check that the memory accessed by a load or store instruction is defined and that the offset fits its address type
+///
+/// Returns the type of the address operand.
+fn validate_memarg(memarg: &MemArg, memories: &[MemType]) -> Result<ValType> {
+    let mem_type = memories
+        .get(memarg.memory)
+        .ok_or(Error::MemoryIsNotDefined(memarg.memory))?;
+
+    if mem_type.addr_type == AddrType::I32 && memarg.offset > u32::MAX.into() {
+        return Err(Error::MemArgOffsetTooBig(memarg.offset));
+    }
+
+    Ok(mem_type.addr_type.into())
+}
+
+/// Read the lane index immediate of a vector instruction and check that it addresses one of `num_lanes` lanes
+fn read_lane_idx(wasm: &mut WasmReader, num_lanes: u8) -> Result<u8> {
+    let lane_idx = wasm.read_u8()?;
+    if lane_idx >= num_lanes {
+        return Err(Error::InvalidLaneIdx(lane_idx, num_lanes));
+    }
+    Ok(lane_idx)
+}
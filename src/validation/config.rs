@@ -0,0 +1,42 @@
+use crate::ProposalSet;
+
+/// Restricts the modules accepted by [validate_with_config](crate::validate_with_config) to a subset of WebAssembly
+/// and to explicit resource bounds
+///
+/// The default configuration enables all proposals and sets no bounds, i.e. it accepts the same modules as
+/// [validate](crate::validate).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValidationConfig {
+    /// Modules using any other proposal are rejected with [crate::Error::UnsupportedProposal]
+    pub enabled_proposals: ProposalSet,
+    /// The maximum size of the module in bytes
+    pub max_module_size: Option<usize>,
+    /// The maximum number of functions, including the imported ones
+    pub max_functions: Option<usize>,
+    /// The maximum number of locals of a single function, including its parameters
+    pub max_locals: Option<usize>,
+    /// The maximum number of elements a table can grow to
+    ///
+    /// Tables without a declared maximum size can grow to `2^32 - 1` elements.
+    pub max_table_size: Option<u64>,
+    /// The maximum number of pages a memory can grow to
+    ///
+    /// Memories without a declared maximum size can grow to the maximum number of pages of their address type.
+    pub max_memory_pages: Option<u64>,
+    /// The maximum number of blocks, loops, ifs and try_tables nested within each other in a function body
+    pub max_nesting_depth: Option<usize>,
+}
+
+impl Default for ValidationConfig {
+    fn default() -> Self {
+        Self {
+            enabled_proposals: ProposalSet::all(),
+            max_module_size: None,
+            max_functions: None,
+            max_locals: None,
+            max_table_size: None,
+            max_memory_pages: None,
+            max_nesting_depth: None,
+        }
+    }
+}
@@ -12,10 +12,13 @@ use crate::Result;
 /// The global section is a vector of global variables. Each [Global] variable is composed of a [GlobalType] and an
 /// initialization expression represented by a constant expression.
 ///
+/// The initialization expressions may only refer to the immutable ones of the `imported_globals`.
+///
 /// See [`read_constant_expression`] for more information.
 pub(super) fn validate_global_section(
     wasm: &mut WasmReader,
     section_header: SectionHeader,
+    imported_globals: &[GlobalType],
 ) -> Result<Vec<Global>> {
     assert_eq!(section_header.ty, SectionTy::Global);
 
@@ -25,7 +28,7 @@ pub(super) fn validate_global_section(
             wasm,
             &mut ValidationStack::new(),
             Some(ty.ty),
-            Some(&[/* todo!(imported globals tpyes) */]),
+            Some(imported_globals),
             // we can't refer to any functions
             None,
         )?;
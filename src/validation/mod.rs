@@ -1,24 +1,35 @@
 use alloc::collections::btree_set;
+use alloc::string::ToString;
 use alloc::vec::Vec;
 
 use crate::core::indices::{FuncIdx, TypeIdx};
 use crate::core::reader::section_header::{SectionHeader, SectionTy};
 use crate::core::reader::span::Span;
-use crate::core::reader::types::data::DataSegment;
-use crate::core::reader::types::element::ElemType;
+use crate::core::reader::types::custom_section::{CustomSection, NameSection};
+use crate::core::reader::types::data::{DataMode, DataSegment};
+use crate::core::reader::types::dwarf::DwarfInfo;
+use crate::core::reader::types::element::{ElemItems, ElemMode, ElemType};
 use crate::core::reader::types::export::Export;
-use crate::core::reader::types::global::Global;
+use crate::core::reader::types::global::{Global, GlobalType};
 use crate::core::reader::types::import::{Import, ImportDesc};
-use crate::core::reader::types::{FuncType, MemType, TableType};
+use crate::core::reader::types::opcode::{
+    Opcode, I32_ADD, I32_MUL, I32_SUB, I64_ADD, I64_MUL, I64_SUB, REF_FUNC, REF_NULL,
+};
+use crate::core::reader::types::proposal::{Proposal, ProposalSet};
+use crate::core::reader::types::{AddrType, FuncType, MemType, TableType, TagType, ValType};
 use crate::core::reader::{WasmReadable, WasmReader};
 use crate::core::sidetable::Sidetable;
-use crate::{Error, Result};
+use crate::execution::assert_validated::UnwrapValidatedExt;
+use crate::{Error, RefType, Result};
 
 pub(crate) mod code;
+mod config;
 pub(crate) mod globals;
 pub(crate) mod read_constant_expression;
 pub(crate) mod validation_stack;
 
+pub use config::ValidationConfig;
+
 /// Information collected from validating a module.
 /// This can be used to create a [crate::RuntimeInstance].
 pub struct ValidationInfo<'bytecode> {
@@ -28,6 +39,7 @@ pub struct ValidationInfo<'bytecode> {
     pub(crate) functions: Vec<TypeIdx>,
     pub(crate) tables: Vec<TableType>,
     pub(crate) memories: Vec<MemType>,
+    pub(crate) tags: Vec<TagType>,
     pub(crate) globals: Vec<Global>,
     #[allow(dead_code)]
     pub(crate) exports: Vec<Export>,
@@ -37,9 +49,74 @@ pub struct ValidationInfo<'bytecode> {
     /// The start function which is automatically executed during instantiation
     pub(crate) start: Option<FuncIdx>,
     pub(crate) elements: Vec<ElemType>,
+    /// The proposals used by the module, see [ValidationInfo::used_proposals]
+    pub(crate) used_proposals: ProposalSet,
+    pub(crate) custom_sections: Vec<CustomSection<'bytecode>>,
+    /// The names of the name section, or no names if the module has no valid name section
+    pub(crate) names: NameSection<'bytecode>,
+    /// The DWARF debug information, or none if the module has no valid DWARF sections
+    pub(crate) dwarf: DwarfInfo,
 }
 
-pub fn validate(wasm: &[u8]) -> Result<ValidationInfo> {
+impl<'bytecode> ValidationInfo<'bytecode> {
+    /// All custom sections of the module, in the order they appear in
+    pub fn custom_sections(&self) -> &[CustomSection<'bytecode>] {
+        &self.custom_sections
+    }
+
+    /// The custom sections with the given name, in the order they appear in
+    pub fn custom_sections_by_name<'a>(
+        &'a self,
+        name: &'a str,
+    ) -> impl Iterator<Item = &'a CustomSection<'bytecode>> + 'a {
+        self.custom_sections
+            .iter()
+            .filter(move |section| section.name == name)
+    }
+
+    /// The debug names of the module, parsed from its name section
+    pub fn names(&self) -> &NameSection<'bytecode> {
+        &self.names
+    }
+
+    /// The DWARF debug information of the module, parsed from its `.debug_*` custom sections
+    pub fn dwarf(&self) -> &DwarfInfo {
+        &self.dwarf
+    }
+
+    /// The proposals a module uses on top of the MVP, detected from its instructions and types
+    pub fn used_proposals(&self) -> ProposalSet {
+        self.used_proposals
+    }
+
+    /// Check that the module only uses the `enabled` proposals
+    ///
+    /// Returns [Error::UnsupportedProposal] with the first proposal used, but not enabled.
+    pub fn check_proposals(&self, enabled: ProposalSet) -> Result<()> {
+        match self
+            .used_proposals
+            .iter()
+            .find(|used| !enabled.contains(*used))
+        {
+            Some(proposal) => Err(Error::UnsupportedProposal(proposal)),
+            None => Ok(()),
+        }
+    }
+}
+
+pub fn validate(wasm: &[u8]) -> Result<ValidationInfo<'_>> {
+    validate_with_config(wasm, &ValidationConfig::default())
+}
+
+/// Validate a module like [validate], but only accept it if it satisfies the restrictions of the `config`
+pub fn validate_with_config<'wasm>(
+    wasm: &'wasm [u8],
+    config: &ValidationConfig,
+) -> Result<ValidationInfo<'wasm>> {
+    if config.max_module_size.is_some_and(|max| wasm.len() > max) {
+        return Err(Error::ModuleTooLarge(wasm.len()));
+    }
+
     let mut wasm = WasmReader::new(wasm);
     trace!("Starting validation of bytecode");
 
@@ -57,56 +134,60 @@ pub fn validate(wasm: &[u8]) -> Result<ValidationInfo> {
     let mut header = None;
     read_next_header(&mut wasm, &mut header)?;
 
-    let skip_section = |wasm: &mut WasmReader, section_header: &mut Option<SectionHeader>| {
-        handle_section(wasm, section_header, SectionTy::Custom, |wasm, h| {
-            use alloc::string::*;
-            // customsec ::= section_0(custom)
-            // custom ::= name byte*
-            // name ::= b*:vec(byte) => name (if utf8(name) = b*)
-            // vec(B) ::= n:u32 (x:B)^n => x^n
-
-            if h.contents.len() == 0 {
-                return Ok(());
-            }
-            let _name = wasm.read_name()?;
-
-            let remaining_bytes = match h
-                .contents
-                .from()
-                .checked_add(h.contents.len())
-                .and_then(|res| res.checked_sub(wasm.pc))
+    // Custom sections may appear between any other sections. They are retained, but do not affect validation.
+    let mut custom_sections = Vec::new();
+    let mut read_custom_sections =
+        |wasm: &mut WasmReader<'wasm>, section_header: &mut Option<SectionHeader>| -> Result<()> {
+            while let Some(custom_section) =
+                handle_section(wasm, section_header, SectionTy::Custom, |wasm, h| {
+                    // customsec ::= section_0(custom)
+                    // custom ::= name byte*
+                    // name ::= b*:vec(byte) => name (if utf8(name) = b*)
+                    // vec(B) ::= n:u32 (x:B)^n => x^n
+
+                    if h.contents.len() == 0 {
+                        return Ok(None);
+                    }
+                    let name = wasm.read_name()?;
+
+                    let remaining_bytes = match h
+                        .contents
+                        .from()
+                        .checked_add(h.contents.len())
+                        .and_then(|res| res.checked_sub(wasm.pc))
+                    {
+                        None => Err(Error::InvalidSection(
+                            SectionTy::Custom,
+                            "Remaining bytes less than 0 after reading name!".to_string(),
+                        )),
+                        Some(remaining_bytes) => Ok(remaining_bytes),
+                    }?;
+
+                    let contents = &wasm.full_wasm_binary[wasm.pc..wasm.pc + remaining_bytes];
+                    wasm.skip(remaining_bytes)?;
+                    Ok(Some(CustomSection { name, contents }))
+                })?
             {
-                None => Err(Error::InvalidSection(
-                    SectionTy::Custom,
-                    "Remaining bytes less than 0 after reading name!".to_string(),
-                )),
-                Some(remaining_bytes) => Ok(remaining_bytes),
-            }?;
-
-            // TODO: maybe do something with these remaining bytes?
-            let mut _bytes = Vec::new();
-            for _ in 0..remaining_bytes {
-                _bytes.push(wasm.read_u8()?)
+                custom_sections.extend(custom_section);
             }
             Ok(())
-        })
-    };
+        };
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    read_custom_sections(&mut wasm, &mut header)?;
 
     let types = handle_section(&mut wasm, &mut header, SectionTy::Type, |wasm, _| {
         wasm.read_vec(FuncType::read)
     })?
     .unwrap_or_default();
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    read_custom_sections(&mut wasm, &mut header)?;
 
     let imports = handle_section(&mut wasm, &mut header, SectionTy::Import, |wasm, _| {
         wasm.read_vec(Import::read)
     })?
     .unwrap_or_default();
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    read_custom_sections(&mut wasm, &mut header)?;
 
     // The `Function` section only covers module-level (or "local") functions.
     // Imported functions have their types known in the `import` section. Both
@@ -129,45 +210,124 @@ pub fn validate(wasm: &[u8]) -> Result<ValidationInfo> {
         .clone()
         .chain(local_functions.iter().cloned())
         .collect::<Vec<TypeIdx>>();
+    if config
+        .max_functions
+        .is_some_and(|max| all_functions.len() > max)
+    {
+        return Err(Error::TooManyFunctions(all_functions.len()));
+    }
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    read_custom_sections(&mut wasm, &mut header)?;
 
     let tables = handle_section(&mut wasm, &mut header, SectionTy::Table, |wasm, _| {
         wasm.read_vec(TableType::read)
     })?
     .unwrap_or_default();
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    // Imported tables precede the local ones in the table index space
+    let all_tables = imports
+        .iter()
+        .filter_map(|import| match &import.desc {
+            ImportDesc::Table(table_type) => Some(*table_type),
+            _ => None,
+        })
+        .chain(tables.iter().copied())
+        .collect::<Vec<TableType>>();
+    if let Some(max_table_size) = config.max_table_size {
+        let too_large = |table: &TableType| table.lim.max.map_or(true, |max| max > max_table_size);
+        if let Some(table_idx) = all_tables.iter().position(too_large) {
+            return Err(Error::TableTooLarge(table_idx));
+        }
+    }
+
+    read_custom_sections(&mut wasm, &mut header)?;
 
     let memories = handle_section(&mut wasm, &mut header, SectionTy::Memory, |wasm, _| {
         wasm.read_vec(MemType::read)
     })?
     .unwrap_or_default();
-    if memories.len() > 1 {
-        return Err(Error::MoreThanOneMemory);
+
+    // Just like functions, imported memories precede the local ones in the memory index space
+    let all_memories = imports
+        .iter()
+        .filter_map(|import| match &import.desc {
+            ImportDesc::Mem(mem_type) => Some(*mem_type),
+            _ => None,
+        })
+        .chain(memories.iter().copied())
+        .collect::<Vec<MemType>>();
+    if let Some(max_memory_pages) = config.max_memory_pages {
+        let too_large =
+            |memory: &MemType| memory.limits.max.map_or(true, |max| max > max_memory_pages);
+        if let Some(mem_idx) = all_memories.iter().position(too_large) {
+            return Err(Error::MemoryTooLarge(mem_idx));
+        }
+    }
+
+    read_custom_sections(&mut wasm, &mut header)?;
+
+    // https://webassembly.github.io/exception-handling/core/binary/modules.html#tag-section
+    let tags = handle_section(&mut wasm, &mut header, SectionTy::Tag, |wasm, _| {
+        wasm.read_vec(TagType::read)
+    })?
+    .unwrap_or_default();
+
+    // Imported tags precede the local ones in the tag index space
+    let all_tags = imports
+        .iter()
+        .filter_map(|import| match &import.desc {
+            ImportDesc::Tag(tag_type) => Some(*tag_type),
+            _ => None,
+        })
+        .chain(tags.iter().copied())
+        .collect::<Vec<TagType>>();
+
+    // Exceptions only carry values, so the types of tags must not have results
+    for tag_type in &all_tags {
+        let func_type = types
+            .get(tag_type.type_idx)
+            .ok_or(Error::FunctionTypeIsNotDefined(tag_type.type_idx))?;
+        if !func_type.returns.valtypes.is_empty() {
+            return Err(Error::TagTypeHasResults(tag_type.type_idx));
+        }
     }
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    read_custom_sections(&mut wasm, &mut header)?;
+
+    // Imported globals precede the local ones in the global index space
+    let imported_globals = imports
+        .iter()
+        .filter_map(|import| match &import.desc {
+            ImportDesc::Global(global_type) => Some(*global_type),
+            _ => None,
+        })
+        .collect::<Vec<GlobalType>>();
 
     let globals = handle_section(&mut wasm, &mut header, SectionTy::Global, |wasm, h| {
-        globals::validate_global_section(wasm, h)
+        globals::validate_global_section(wasm, h, &imported_globals)
     })?
     .unwrap_or_default();
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    let all_globals = imported_globals
+        .iter()
+        .copied()
+        .chain(globals.iter().map(|global| global.ty))
+        .collect::<Vec<GlobalType>>();
+
+    read_custom_sections(&mut wasm, &mut header)?;
 
     let exports = handle_section(&mut wasm, &mut header, SectionTy::Export, |wasm, _| {
         wasm.read_vec(Export::read)
     })?
     .unwrap_or_default();
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    read_custom_sections(&mut wasm, &mut header)?;
 
     let start = handle_section(&mut wasm, &mut header, SectionTy::Start, |wasm, _| {
         wasm.read_var_u32().map(|idx| idx as FuncIdx)
     })?;
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    read_custom_sections(&mut wasm, &mut header)?;
 
     let mut referenced_functions = btree_set::BTreeSet::new();
     let elements: Vec<ElemType> =
@@ -175,13 +335,14 @@ pub fn validate(wasm: &[u8]) -> Result<ValidationInfo> {
             ElemType::read_from_wasm(
                 wasm,
                 &all_functions,
+                &imported_globals,
                 &mut referenced_functions,
-                tables.len(),
+                all_tables.len(),
             )
         })?
         .unwrap_or_default();
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    read_custom_sections(&mut wasm, &mut header)?;
 
     // https://webassembly.github.io/spec/core/binary/modules.html#data-count-section
     // As per the official documentation:
@@ -195,22 +356,31 @@ pub fn validate(wasm: &[u8]) -> Result<ValidationInfo> {
         trace!("data count: {}", data_count.unwrap());
     }
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    read_custom_sections(&mut wasm, &mut header)?;
 
+    // The proposals used by instructions are collected while validating the code section
+    let mut used_proposals =
+        module_proposals(&types, &all_tables, &all_memories, &all_tags, &all_globals);
+    // DWARF addresses are relative to the contents of the code section
+    let mut code_section_start = 0;
     let func_blocks_sidetables =
         handle_section(&mut wasm, &mut header, SectionTy::Code, |wasm, h| {
+            code_section_start = h.contents.from();
             code::validate_code_section(
                 wasm,
                 h,
                 &types,
                 &all_functions,
                 imported_functions.count(),
-                &globals,
-                &memories,
+                &all_globals,
+                &all_memories,
+                &all_tags,
                 &data_count,
-                &tables,
+                &all_tables,
                 &elements,
                 &referenced_functions,
+                &mut used_proposals,
+                config,
             )
         })?
         .unwrap_or_default();
@@ -221,10 +391,10 @@ pub fn validate(wasm: &[u8]) -> Result<ValidationInfo> {
         "these should be equal"
     ); // TODO check if this is in the spec
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    read_custom_sections(&mut wasm, &mut header)?;
 
     let data_section = handle_section(&mut wasm, &mut header, SectionTy::Data, |wasm, _| {
-        wasm.read_vec(DataSegment::read)
+        wasm.read_vec(|wasm| DataSegment::read_from_wasm(wasm, &all_memories, &imported_globals))
     })?
     .unwrap_or_default();
 
@@ -233,28 +403,165 @@ pub fn validate(wasm: &[u8]) -> Result<ValidationInfo> {
         assert_eq!(data_count.unwrap() as usize, data_section.len());
     }
 
-    while (skip_section(&mut wasm, &mut header)?).is_some() {}
+    used_proposals.extend(
+        segment_proposals(wasm.full_wasm_binary, &globals, &elements, &data_section).iter(),
+    );
+
+    read_custom_sections(&mut wasm, &mut header)?;
 
     // All sections should have been handled
     if let Some(header) = header {
         return Err(Error::SectionOutOfOrder(header.ty));
     }
 
-    debug!("Validation was successful");
-    Ok(ValidationInfo {
+    // A malformed name section does not make the module invalid, its names are just not available
+    let names = custom_sections
+        .iter()
+        .filter(|section| section.name == NameSection::SECTION_NAME)
+        .find_map(|section| match NameSection::read(section.contents) {
+            Ok(names) => Some(names),
+            Err(err) => {
+                warn!("Ignoring the name section: {err}");
+                None
+            }
+        })
+        .unwrap_or_default();
+
+    let dwarf = DwarfInfo::read(&custom_sections, code_section_start).unwrap_or_else(|err| {
+        warn!("Ignoring the DWARF debug information: {err}");
+        DwarfInfo::default()
+    });
+
+    let validation_info = ValidationInfo {
         wasm: wasm.into_inner(),
         types,
         imports,
         functions: local_functions,
         tables,
         memories,
+        tags,
         globals,
         exports,
         func_blocks: func_blocks_sidetables,
         data: data_section,
         start,
         elements,
-    })
+        used_proposals,
+        custom_sections,
+        names,
+        dwarf,
+    };
+    validation_info.check_proposals(config.enabled_proposals)?;
+
+    debug!("Validation was successful");
+    Ok(validation_info)
+}
+
+/// Collect the proposals required by the types of a module, independently of the instructions of its functions
+fn module_proposals(
+    types: &[FuncType],
+    tables: &[TableType],
+    memories: &[MemType],
+    tags: &[TagType],
+    globals: &[GlobalType],
+) -> ProposalSet {
+    let mut proposals = ProposalSet::empty();
+
+    let valtypes = types
+        .iter()
+        .flat_map(|ty| ty.params.valtypes.iter().chain(&ty.returns.valtypes))
+        .chain(globals.iter().map(|global| &global.ty));
+    proposals.extend(valtypes.filter_map(|valtype| valtype_proposal(*valtype)));
+    // the MVP allows at most one result, also for the block types referring to function types
+    if types.iter().any(|ty| ty.returns.valtypes.len() > 1) {
+        proposals.insert(Proposal::MultiValue);
+    }
+
+    if tables.len() > 1 || tables.iter().any(|table| table.et != RefType::FuncRef) {
+        proposals.insert(Proposal::ReferenceTypes);
+    }
+    if memories.len() > 1 {
+        proposals.insert(Proposal::MultiMemory);
+    }
+    if memories
+        .iter()
+        .any(|memory| memory.addr_type == AddrType::I64)
+    {
+        proposals.insert(Proposal::Memory64);
+    }
+    if memories.iter().any(|memory| memory.shared) {
+        proposals.insert(Proposal::Threads);
+    }
+    if !tags.is_empty() {
+        proposals.insert(Proposal::ExceptionHandling);
+    }
+
+    proposals
+}
+
+/// The proposal introducing a value type, or `None` for the value types of the MVP
+fn valtype_proposal(valtype: ValType) -> Option<Proposal> {
+    match valtype {
+        ValType::VecType => Some(Proposal::Simd),
+        ValType::RefType(RefType::FuncRef | RefType::ExternRef) => Some(Proposal::ReferenceTypes),
+        ValType::RefType(RefType::ExnRef) => Some(Proposal::ExceptionHandling),
+        ValType::NumType(_) => None,
+    }
+}
+
+/// Collect the proposals required by the segments of a module and by its constant expressions, i.e. the initializers
+/// of its globals and the offsets and items of its segments
+fn segment_proposals(
+    wasm: &[u8],
+    globals: &[Global],
+    elements: &[ElemType],
+    data: &[DataSegment],
+) -> ProposalSet {
+    let mut proposals = ProposalSet::empty();
+    let mut const_exprs = Vec::new();
+
+    const_exprs.extend(globals.iter().map(|global| global.init_expr));
+    for elem in elements {
+        match &elem.mode {
+            ElemMode::Passive => proposals.insert(Proposal::BulkMemory),
+            ElemMode::Declarative => proposals.insert(Proposal::ReferenceTypes),
+            ElemMode::Active(active_elem) => {
+                if active_elem.table_idx != 0 {
+                    proposals.insert(Proposal::ReferenceTypes);
+                }
+                const_exprs.push(active_elem.init_expr);
+            }
+        }
+        if let ElemItems::Exprs(_, exprs) = &elem.init {
+            proposals.insert(Proposal::ReferenceTypes);
+            const_exprs.extend(exprs.iter().copied());
+        }
+    }
+    for segment in data {
+        match &segment.mode {
+            DataMode::Passive => proposals.insert(Proposal::BulkMemory),
+            DataMode::Active(active_data) => const_exprs.push(active_data.offset),
+        }
+    }
+
+    for expr in const_exprs {
+        let mut wasm = WasmReader::new(wasm);
+        wasm.move_start_to(expr).unwrap_validated();
+        while wasm.pc < expr.from() + expr.len() {
+            let opcode = Opcode::read_unvalidated(&mut wasm);
+            let info = opcode.info().unwrap_validated();
+            match opcode {
+                Opcode::Single(I32_ADD | I32_SUB | I32_MUL | I64_ADD | I64_SUB | I64_MUL) => {
+                    proposals.insert(Proposal::ExtendedConst)
+                }
+                Opcode::Single(REF_NULL | REF_FUNC) => proposals.insert(Proposal::ReferenceTypes),
+                _ => proposals.extend(info.proposal),
+            }
+            info.immediates.read_unvalidated(&mut wasm);
+        }
+    }
+
+    proposals
 }
 
 fn read_next_header(wasm: &mut WasmReader, header: &mut Option<SectionHeader>) -> Result<()> {
@@ -265,8 +572,8 @@ fn read_next_header(wasm: &mut WasmReader, header: &mut Option<SectionHeader>) -
 }
 
 #[inline(always)]
-fn handle_section<T, F: FnOnce(&mut WasmReader, SectionHeader) -> Result<T>>(
-    wasm: &mut WasmReader,
+fn handle_section<'wasm, T, F: FnOnce(&mut WasmReader<'wasm>, SectionHeader) -> Result<T>>(
+    wasm: &mut WasmReader<'wasm>,
     header: &mut Option<SectionHeader>,
     section_ty: SectionTy,
     handler: F,
@@ -1,3 +1,4 @@
+use crate::core::indices::GlobalIdx;
 use crate::core::reader::span::Span;
 use crate::core::reader::types::global::GlobalType;
 use crate::core::reader::{WasmReadable, WasmReader};
@@ -76,16 +77,16 @@ use super::validation_stack::ValidationStack;
 ///     i32.add (i32.const 1) (global.get 0)
 /// ```
 ///
+/// The same rules apply to the offsets of active data and element segments, as well as to the initialization
+/// expressions of element segments.
+///
 /// # Note
-/// The following instructions are not yet supported:
-/// - `ref.null`
-/// - `ref.func`
-/// - `global.get`
+/// `ref.func` is only allowed if `funcs` is given, which is not the case for the initialization expressions of globals.
 pub fn read_constant_expression(
     wasm: &mut WasmReader,
     stack: &mut ValidationStack,
     this_global_valtype: Option<ValType>,
-    _globals_ty: Option<&[GlobalType]>,
+    globals_ty: Option<&[GlobalType]>,
     funcs: Option<&[usize]>,
 ) -> Result<Span> {
     let start_pc = wasm.pc;
@@ -98,7 +99,6 @@ pub fn read_constant_expression(
 
         use crate::core::reader::types::opcode::*;
         match first_instr_byte {
-            // Missing: ref.null, ref.func, global.get
             END => {
                 // The stack must only contain the global's valtype
                 if this_global_valtype.is_some() {
@@ -134,9 +134,31 @@ pub fn read_constant_expression(
 
                 stack.push_valtype(ValType::NumType(NumType::I64));
             }
+            FD_EXTENSIONS => {
+                use crate::core::reader::types::opcode::fd_extensions::V128_CONST;
+                let second_instr = wasm.read_var_u32()?;
+                if second_instr != V128_CONST {
+                    return Err(Error::InvalidMultiByteInstr(first_instr_byte, second_instr));
+                }
+
+                let _bytes = wasm.strip_bytes::<16>()?;
+                stack.push_valtype(ValType::VecType);
+            }
             REF_NULL => {
                 stack.push_valtype(ValType::RefType(RefType::read(wasm)?));
             }
+            GLOBAL_GET => {
+                let global_idx = wasm.read_var_u32()? as GlobalIdx;
+                let global = globals_ty
+                    .and_then(|globals_ty| globals_ty.get(global_idx))
+                    .ok_or(Error::InvalidGlobalIdx(global_idx))?;
+
+                if global.is_mut {
+                    return Err(Error::GlobalIsMutable);
+                }
+
+                stack.push_valtype(global.ty);
+            }
             REF_FUNC => {
                 let func_idx = wasm.read_var_u32()? as usize;
                 match funcs {
@@ -0,0 +1,90 @@
+#![cfg(feature = "hooks")]
+
+use wasm::{coverage::Coverage, validate, RuntimeInstance, DEFAULT_MODULE};
+
+const MODULE: &str = r#"
+(module
+    (func $classify (export "classify") (param $x i32) (result i32)
+        (block $two
+            (block $one
+                (block $zero
+                    (br_table $zero $one $two (local.get $x))
+                )
+                (return (i32.const 10))
+            )
+            (return (i32.const 11))
+        )
+        (if (result i32) (i32.gt_u (local.get $x) (i32.const 5))
+            (then (i32.const 100))
+            (else (i32.const 12))
+        )
+    )
+    (func $unused (param $x i32)
+        (block
+            (br_if 0 (local.get $x))
+        )
+    )
+)"#;
+
+#[test_log::test]
+pub fn coverage() {
+    let wasm_bytes = wat::parse_str(MODULE).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_with_hooks(DEFAULT_MODULE, &validation_info, Coverage::default())
+            .expect("instantiation failed");
+    let classify = instance
+        .get_function_by_name(DEFAULT_MODULE, "classify")
+        .unwrap();
+
+    for (x, expected) in [(0, 10), (1, 11), (7, 100), (3, 12)] {
+        assert_eq!(Ok(expected), instance.invoke::<i32, i32>(&classify, x));
+    }
+
+    let functions = instance.hook_set.functions(&instance);
+    assert_eq!(2, functions.len());
+    let (classify, unused) = (&functions[0], &functions[1]);
+    assert_eq!(Some("classify"), classify.function_name.as_deref());
+    assert_eq!(4, classify.calls());
+    assert_eq!(0, unused.calls());
+
+    // the br_table takes its distinct targets, the default last, and the if its then arm, then its else arm
+    let directions: Vec<_> = classify
+        .branches
+        .iter()
+        .map(|branch| branch.directions.clone())
+        .collect();
+    assert_eq!(vec![vec![1, 1, 2], vec![1, 1]], directions);
+    let br_table_pc = classify.branches[0].pc;
+    assert_eq!(4, instance.hook_set.instruction_count(0, br_table_pc));
+    let covered = classify
+        .instructions
+        .iter()
+        .filter(|(_, count)| *count > 0)
+        .count();
+    assert_eq!(classify.instructions.len(), covered);
+
+    // unexecuted branches are marked with `-`, and lines are the positions of the instructions
+    let lcov = instance.hook_set.lcov(&instance);
+    let br_if_pc = unused.branches[0].pc;
+    let records: Vec<&str> = lcov.lines().collect();
+    assert_eq!(format!("SF:{DEFAULT_MODULE}"), records[0]);
+    for record in [
+        format!("FN:{},classify", classify.instructions[0].0),
+        "FNDA:4,classify".to_owned(),
+        "FNDA:0,unused".to_owned(),
+        "FNF:2".to_owned(),
+        "FNH:1".to_owned(),
+        format!("BRDA:{br_table_pc},0,2,2"),
+        format!("BRDA:{br_if_pc},0,0,-"),
+        format!("BRDA:{br_if_pc},0,1,-"),
+        "BRF:7".to_owned(),
+        "BRH:5".to_owned(),
+        format!("DA:{br_table_pc},4"),
+        format!("DA:{br_if_pc},0"),
+        format!("LH:{}", classify.instructions.len()),
+    ] {
+        assert!(records.contains(&record.as_str()), "missing {record}");
+    }
+    assert_eq!(Some(&"end_of_record"), records.last());
+}
@@ -0,0 +1,89 @@
+use wasm::{validate, NameSection, RuntimeInstance, DEFAULT_MODULE};
+
+#[test_log::test]
+pub fn name_section() {
+    let wasm_bytes = wat::parse_str(
+        r#"
+        (module $calculator
+            (import "env" "log" (func $log (param i32)))
+            (func $add (export "add") (param $lhs i32) (param $rhs i32) (result i32)
+                (local $sum i32)
+                (local.set $sum (i32.add (local.get $lhs) (local.get $rhs)))
+                (local.get $sum)
+            )
+            (func (param i32))
+        )"#,
+    )
+    .unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let names = validation_info.names();
+    assert_eq!(Some("calculator"), names.module);
+    assert_eq!(Some("log"), names.function_name(0));
+    assert_eq!(Some("add"), names.function_name(1));
+    assert_eq!(None, names.function_name(2));
+    assert_eq!(Some("rhs"), names.local_name(1, 1));
+    assert_eq!(Some("sum"), names.local_name(1, 2));
+    assert_eq!(None, names.local_name(2, 0));
+
+    let mut instance = RuntimeInstance::new_empty();
+    instance
+        .add_module(DEFAULT_MODULE, &validation_info)
+        .expect("instantiation failed");
+    assert_eq!(Some("add"), instance.function_debug_name(DEFAULT_MODULE, 1));
+    assert_eq!(None, instance.function_debug_name(DEFAULT_MODULE, 2));
+    assert_eq!(None, instance.function_debug_name("other", 1));
+}
+
+#[test_log::test]
+pub fn custom_sections() {
+    let wasm_bytes = wat::parse_str(
+        r#"
+        (module
+            (@custom "producers" (before first) "clang")
+            (@custom "metadata" (after func) "\01\02")
+            (@custom "producers" (after last) "rustc")
+            (func)
+        )"#,
+    )
+    .unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let names: Vec<&str> = validation_info
+        .custom_sections()
+        .iter()
+        .map(|section| section.name)
+        .collect();
+    assert_eq!(vec!["producers", "metadata", "producers"], names);
+
+    let producers: Vec<&[u8]> = validation_info
+        .custom_sections_by_name("producers")
+        .map(|section| section.contents)
+        .collect();
+    assert_eq!(vec![&b"clang"[..], &b"rustc"[..]], producers);
+
+    let metadata = validation_info
+        .custom_sections_by_name("metadata")
+        .next()
+        .unwrap();
+    assert_eq!(&[1, 2], metadata.contents);
+
+    // no name section, so no names
+    assert_eq!(&NameSection::default(), validation_info.names());
+}
+
+#[test_log::test]
+pub fn malformed_name_section() {
+    // the function subsection precedes the module subsection
+    let name_section = [0x01, 0x01, 0x00, 0x00, 0x02, 0x01, b'm'];
+    assert!(NameSection::read(&name_section).is_err());
+
+    // malformed name sections do not make a module invalid
+    let mut wasm_bytes = b"\0asm\x01\0\0\0".to_vec();
+    wasm_bytes.extend([0x00, 1 + 4 + name_section.len() as u8, 4]);
+    wasm_bytes.extend(b"name");
+    wasm_bytes.extend(name_section);
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    assert_eq!(None, validation_info.names().module);
+    assert_eq!(1, validation_info.custom_sections().len());
+}
@@ -0,0 +1,155 @@
+use wasm::{
+    debugger::Step,
+    resumable::{InvocationState, Resumable, SuspendReason},
+    validate, RuntimeError, RuntimeInstance, Value, DEFAULT_MODULE,
+};
+
+const SUM_OF_SQUARES: &str = r#"
+(module
+    (global $calls (mut i32) (i32.const 0))
+    (memory 1)
+    (func $square (param $x i32) (result i32)
+        (global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+        (i32.mul (local.get $x) (local.get $x))
+    )
+    (func (export "sum_of_squares") (param $a i32) (param $b i32) (result i32)
+        (i32.store (i32.const 0) (local.get $a))
+        (i32.add (call $square (local.get $a)) (call $square (local.get $b)))
+    )
+)"#;
+
+fn expect_suspended(state: InvocationState, reason: SuspendReason) -> Resumable {
+    match state {
+        InvocationState::Suspended(resumable) => {
+            assert_eq!(Some(reason), resumable.suspend_reason());
+            resumable
+        }
+        InvocationState::Finished(_) => panic!("expected the invocation to be suspended"),
+    }
+}
+
+#[test_log::test]
+pub fn breakpoints() {
+    let wasm_bytes = wat::parse_str(SUM_OF_SQUARES).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let sum_of_squares = instance
+        .get_function_by_name(DEFAULT_MODULE, "sum_of_squares")
+        .unwrap();
+
+    let square_pc = instance.set_function_breakpoint(DEFAULT_MODULE, 0).unwrap();
+    assert_eq!(
+        Err(RuntimeError::FunctionNotFound),
+        instance.set_function_breakpoint(DEFAULT_MODULE, 2)
+    );
+    assert_eq!(
+        Err(RuntimeError::ModuleNotFound),
+        instance.set_breakpoint("other", square_pc)
+    );
+
+    let state = instance
+        .invoke_resumable(&sum_of_squares, vec![Value::I32(3), Value::I32(4)], None)
+        .unwrap();
+    let resumable = expect_suspended(state, SuspendReason::Breakpoint);
+    assert_eq!(square_pc, resumable.pc());
+    let frame = instance.debug_frame(&resumable);
+    assert_eq!(DEFAULT_MODULE, frame.module_name());
+    assert_eq!(0, frame.function_idx());
+    assert_eq!(Some("square"), frame.function_name());
+    assert_eq!(&[Value::I32(3)], frame.locals());
+    assert!(frame.value_stack().is_empty());
+    assert_eq!(Some(Value::I32(0)), frame.global(0));
+    assert_eq!(None, frame.global(1));
+    assert_eq!(Some(vec![3, 0, 0, 0]), frame.read_memory(0, 0, 4));
+    assert_eq!(None, frame.read_memory(0, 65535, 2));
+    let backtrace = instance.backtrace(&resumable);
+    assert_eq!(2, backtrace.len());
+    assert_eq!(Some("square"), backtrace[0].function_name.as_deref());
+    assert_eq!(1, backtrace[1].function_idx);
+
+    // resuming continues with the instruction of the breakpoint, until the breakpoint is hit again
+    let state = instance.resume(resumable).unwrap();
+    let resumable = expect_suspended(state, SuspendReason::Breakpoint);
+    let frame = instance.debug_frame(&resumable);
+    assert_eq!(&[Value::I32(4)], frame.locals());
+    assert_eq!(Some(Value::I32(1)), frame.global(0));
+
+    let InvocationState::Finished(values) = instance.resume(resumable).unwrap() else {
+        panic!("expected the invocation to finish");
+    };
+    assert_eq!(vec![Value::I32(25)], values);
+
+    // invocations which run to completion are not stopped
+    assert_eq!(
+        Ok(25),
+        instance.invoke::<(i32, i32), i32>(&sum_of_squares, (3, 4))
+    );
+
+    assert!(instance.remove_breakpoint(DEFAULT_MODULE, square_pc));
+    assert!(!instance.remove_breakpoint(DEFAULT_MODULE, square_pc));
+    let state = instance
+        .invoke_resumable(&sum_of_squares, vec![Value::I32(3), Value::I32(4)], None)
+        .unwrap();
+    assert!(matches!(state, InvocationState::Finished(_)));
+}
+
+#[test_log::test]
+pub fn stepping() {
+    let wasm_bytes = wat::parse_str(SUM_OF_SQUARES).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let sum_of_squares = instance
+        .get_function_by_name(DEFAULT_MODULE, "sum_of_squares")
+        .unwrap();
+    instance.set_function_breakpoint(DEFAULT_MODULE, 0).unwrap();
+
+    let state = instance
+        .invoke_resumable(&sum_of_squares, vec![Value::I32(3), Value::I32(4)], None)
+        .unwrap();
+    let resumable = expect_suspended(state, SuspendReason::Breakpoint);
+
+    // `global.get $calls`
+    let state = instance.step(resumable, Step::Into).unwrap();
+    let resumable = expect_suspended(state, SuspendReason::Step);
+    let frame = instance.debug_frame(&resumable);
+    assert_eq!(0, frame.function_idx());
+    assert_eq!(&[Value::I32(0)], frame.value_stack());
+
+    // the rest of `$square`
+    let state = instance.step(resumable, Step::Out).unwrap();
+    let resumable = expect_suspended(state, SuspendReason::Step);
+    let frame = instance.debug_frame(&resumable);
+    assert_eq!(1, frame.function_idx());
+    assert_eq!(&[Value::I32(9)], frame.value_stack());
+
+    // `local.get $b`
+    let state = instance.step(resumable, Step::Over).unwrap();
+    let resumable = expect_suspended(state, SuspendReason::Step);
+    let frame = instance.debug_frame(&resumable);
+    assert_eq!(&[Value::I32(9), Value::I32(4)], frame.value_stack());
+
+    // stepping over `call $square` is interrupted by its breakpoint
+    let state = instance.step(resumable, Step::Over).unwrap();
+    let resumable = expect_suspended(state, SuspendReason::Breakpoint);
+    assert_eq!(&[Value::I32(4)], instance.debug_frame(&resumable).locals());
+
+    instance.clear_breakpoints();
+    let state = instance.step(resumable, Step::Out).unwrap();
+    let resumable = expect_suspended(state, SuspendReason::Step);
+    let frame = instance.debug_frame(&resumable);
+    assert_eq!(&[Value::I32(9), Value::I32(16)], frame.value_stack());
+
+    // `i32.add`
+    let state = instance.step(resumable, Step::Into).unwrap();
+    let resumable = expect_suspended(state, SuspendReason::Step);
+    assert_eq!(
+        &[Value::I32(25)],
+        instance.debug_frame(&resumable).value_stack()
+    );
+
+    // `end` of the invoked function
+    let InvocationState::Finished(values) = instance.step(resumable, Step::Into).unwrap() else {
+        panic!("expected the invocation to finish");
+    };
+    assert_eq!(vec![Value::I32(25)], values);
+}
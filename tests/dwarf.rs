@@ -0,0 +1,225 @@
+use wasm::{validate, RuntimeError, RuntimeInstance, SourceLocation, DEFAULT_MODULE};
+
+/// The code section of this module contains, relative to its start:
+/// - 2: the body of `$div`
+/// - 3: `local.get 0`
+/// - 5: `local.get 1`
+/// - 7: `i32.div_s`
+/// - 8: `end`
+const MODULE: &str = r#"
+(module
+    (func $div (export "div") (param i32 i32) (result i32)
+        local.get 0
+        local.get 1
+        i32.div_s
+    )
+)"#;
+
+const DW_LNS_COPY: u8 = 0x01;
+const DW_LNS_ADVANCE_PC: u8 = 0x02;
+const DW_LNS_ADVANCE_LINE: u8 = 0x03;
+const DW_LNS_SET_FILE: u8 = 0x04;
+const DW_LNS_SET_COLUMN: u8 = 0x05;
+const DW_LNE_END_SEQUENCE: [u8; 3] = [0x00, 0x01, 0x01];
+const DW_LNE_SET_ADDRESS: [u8; 3] = [0x00, 0x05, 0x02];
+
+/// The header fields following the header length, up to the directories, as emitted by clang
+const LINE_HEADER_PARAMETERS: [u8; 18] =
+    [1, 1, 1, 0xfb, 14, 13, 0, 1, 1, 1, 1, 0, 0, 0, 1, 0, 0, 1];
+
+/// Encode `sections` as custom sections and append them to `wasm_bytes`
+fn with_custom_sections(mut wasm_bytes: Vec<u8>, sections: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    for (name, contents) in sections {
+        let mut section = vec![name.len() as u8];
+        section.extend_from_slice(name.as_bytes());
+        section.extend_from_slice(contents);
+        wasm_bytes.push(0);
+        wasm_bytes.push(section.len() as u8);
+        wasm_bytes.extend(section);
+    }
+    wasm_bytes
+}
+
+/// Prefix `contents` with their length, as a unit of 32-bit DWARF
+fn unit(contents: Vec<u8>) -> Vec<u8> {
+    let mut unit = (contents.len() as u32).to_le_bytes().to_vec();
+    unit.extend(contents);
+    unit
+}
+
+/// The position of the code section's contents, which DWARF addresses are relative to
+fn code_section_start(wasm_bytes: &[u8]) -> usize {
+    let mut pc = 8;
+    loop {
+        let id = wasm_bytes[pc];
+        let size = wasm_bytes[pc + 1] as usize;
+        assert!(size < 0x80, "the test module only contains small sections");
+        pc += 2;
+        if id == 10 {
+            return pc;
+        }
+        pc += size;
+    }
+}
+
+#[test_log::test]
+pub fn dwarf_4() {
+    let mut header = LINE_HEADER_PARAMETERS.to_vec();
+    header.extend(b"src\0\0");
+    header.extend(b"sensor.c\0\x01\x00\x00\0");
+    let mut program = vec![];
+    program.extend(DW_LNE_SET_ADDRESS);
+    program.extend(2_u32.to_le_bytes());
+    program.extend([DW_LNS_ADVANCE_LINE, 9, DW_LNS_COPY]);
+    program.extend([DW_LNS_ADVANCE_PC, 5, DW_LNS_ADVANCE_LINE, 2]);
+    program.extend([DW_LNS_SET_COLUMN, 5, DW_LNS_COPY]);
+    program.extend([DW_LNS_ADVANCE_PC, 2]);
+    program.extend(DW_LNE_END_SEQUENCE);
+    let mut debug_line = vec![4, 0];
+    debug_line.extend((header.len() as u32).to_le_bytes());
+    debug_line.extend(header);
+    debug_line.extend(program);
+
+    // a compile unit with a name, containing a function with name, low pc and size
+    let debug_abbrev = vec![
+        1, 0x11, 1, 0x03, 0x08, 0, 0, //
+        2, 0x2e, 0, 0x11, 0x01, 0x12, 0x06, 0x03, 0x08, 0, 0, //
+        0,
+    ];
+    let mut debug_info = vec![4, 0, 0, 0, 0, 0, 4];
+    debug_info.extend(b"\x01sensor.c\0");
+    debug_info.push(2);
+    debug_info.extend(2_u32.to_le_bytes());
+    debug_info.extend(7_u32.to_le_bytes());
+    debug_info.extend(b"divide\0\0");
+
+    let wasm_bytes = with_custom_sections(
+        wat::parse_str(MODULE).unwrap(),
+        &[
+            (".debug_line", unit(debug_line)),
+            (".debug_abbrev", debug_abbrev),
+            (".debug_info", unit(debug_info)),
+        ],
+    );
+    let code_start = code_section_start(&wasm_bytes);
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let dwarf = validation_info.dwarf();
+    assert!(!dwarf.is_empty());
+
+    let location = |line, column| {
+        Some(SourceLocation {
+            file: "src/sensor.c".to_owned(),
+            line,
+            column,
+            function: Some("divide".to_owned()),
+        })
+    };
+    assert_eq!(None, dwarf.lookup(code_start + 1));
+    assert_eq!(location(10, 0), dwarf.lookup(code_start + 3));
+    assert_eq!(location(12, 5), dwarf.lookup(code_start + 7));
+    assert_eq!(location(12, 5), dwarf.lookup(code_start + 8));
+    assert_eq!(None, dwarf.lookup(code_start + 9));
+    assert_eq!(
+        "src/sensor.c:10",
+        dwarf.lookup(code_start + 3).unwrap().to_string()
+    );
+
+    // traps are located in the source code
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let div = instance
+        .get_function_by_name(DEFAULT_MODULE, "div")
+        .unwrap();
+    let trap = instance
+        .invoke::<(i32, i32), i32>(&div, (1, 0))
+        .unwrap_err();
+    assert_eq!(RuntimeError::DivideBy0, trap.error);
+    assert_eq!(code_start + 7, trap.frames[0].code_offset);
+    assert_eq!(location(12, 5), trap.frames[0].source_location);
+    assert!(trap.to_string().ends_with("(src/sensor.c:12:5)"));
+
+    assert_eq!(
+        location(10, 0),
+        instance.source_location(DEFAULT_MODULE, code_start + 3)
+    );
+    assert_eq!(None, instance.source_location("other", code_start + 3));
+}
+
+#[test_log::test]
+pub fn dwarf_5() {
+    // paths and names are referenced from the string sections
+    let debug_line_str = b"/work\0sensor.c\0".to_vec();
+    let debug_str = b"divide\0".to_vec();
+    let mut debug_str_offsets = unit(vec![5, 0, 0, 0]);
+    debug_str_offsets.extend(0_u32.to_le_bytes());
+    let mut debug_addr = unit(vec![5, 0, 4, 0]);
+    debug_addr.extend(3_u32.to_le_bytes());
+
+    // the directory and file tables, whose entries consist of paths in `.debug_line_str` and directory indices
+    let mut header = LINE_HEADER_PARAMETERS.to_vec();
+    header.extend([1, 0x01, 0x1f, 1, 0, 0, 0, 0]);
+    header.extend([2, 0x01, 0x1f, 0x02, 0x0b, 1, 6, 0, 0, 0, 0]);
+    let mut program = vec![DW_LNS_SET_FILE, 0];
+    program.extend(DW_LNE_SET_ADDRESS);
+    program.extend(3_u32.to_le_bytes());
+    program.extend([DW_LNS_ADVANCE_LINE, 0x8c, 0x01, DW_LNS_COPY]);
+    // a special opcode advancing the address by 4 and the line by 1
+    program.extend([DW_LNS_SET_COLUMN, 7, 75]);
+    program.extend([DW_LNS_ADVANCE_PC, 1]);
+    program.extend(DW_LNE_END_SEQUENCE);
+    let mut debug_line = vec![5, 0, 4, 0];
+    debug_line.extend((header.len() as u32).to_le_bytes());
+    debug_line.extend(header);
+    debug_line.extend(program);
+
+    // the compile unit holds the bases of the indexed strings and addresses of the function
+    let debug_abbrev = vec![
+        1, 0x11, 1, 0x72, 0x17, 0x73, 0x17, 0, 0, //
+        2, 0x2e, 0, 0x11, 0x1b, 0x12, 0x06, 0x03, 0x25, 0, 0, //
+        0,
+    ];
+    let mut debug_info = vec![5, 0, 0x01, 4, 0, 0, 0, 0, 1];
+    debug_info.extend(8_u32.to_le_bytes());
+    debug_info.extend(8_u32.to_le_bytes());
+    debug_info.extend([2, 0]);
+    debug_info.extend(5_u32.to_le_bytes());
+    debug_info.extend([0, 0]);
+
+    let wasm_bytes = with_custom_sections(
+        wat::parse_str(MODULE).unwrap(),
+        &[
+            (".debug_line", unit(debug_line)),
+            (".debug_line_str", debug_line_str),
+            (".debug_abbrev", debug_abbrev),
+            (".debug_info", unit(debug_info)),
+            (".debug_str", debug_str),
+            (".debug_str_offsets", debug_str_offsets),
+            (".debug_addr", debug_addr),
+        ],
+    );
+    let code_start = code_section_start(&wasm_bytes);
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+
+    let location = |line, column| {
+        Some(SourceLocation {
+            file: "/work/sensor.c".to_owned(),
+            line,
+            column,
+            function: Some("divide".to_owned()),
+        })
+    };
+    let dwarf = validation_info.dwarf();
+    assert_eq!(location(141, 0), dwarf.lookup(code_start + 3));
+    assert_eq!(location(142, 7), dwarf.lookup(code_start + 7));
+    assert_eq!(None, dwarf.lookup(code_start + 8));
+}
+
+#[test_log::test]
+pub fn malformed_dwarf() {
+    // malformed debug information does not make a module invalid, it is just not available
+    let wasm_bytes = with_custom_sections(
+        wat::parse_str(MODULE).unwrap(),
+        &[(".debug_line", unit(vec![1, 0]))],
+    );
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    assert!(validation_info.dwarf().is_empty());
+}
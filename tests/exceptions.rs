@@ -0,0 +1,267 @@
+use wasm::exceptions::Exception;
+use wasm::{
+    validate, Error, RefType, RuntimeError, RuntimeInstance, ValType, Value, DEFAULT_MODULE,
+};
+
+const EXCEPTIONS: &str = r#"
+(module
+    (tag $e (export "e") (param i32))
+    (tag $other (param i32))
+    (tag $empty)
+
+    (func $throw_if_nonzero (param i32)
+        (if (local.get 0) (then (throw $e (local.get 0))))
+    )
+
+    ;; returns 100 plus the payload of the caught exception, or 0 if nothing was thrown
+    (func (export "catch") (param i32) (result i32)
+        (i32.add
+            (i32.const 100)
+            (block $handler (result i32)
+                (try_table (result i32) (catch $other $handler) (catch $e $handler)
+                    (i32.const 9)
+                    (call $throw_if_nonzero (local.get 0))
+                    (drop)
+                    (i32.const -100)
+                )
+            )
+        )
+    )
+
+    ;; catches the exception by reference, rethrows it, and catches it again
+    (func (export "rethrow") (param i32) (result i32)
+        (block $outer (result i32)
+            (try_table (catch $e $outer)
+                (block $inner (result exnref)
+                    (try_table (catch_all_ref $inner)
+                        (throw $e (local.get 0))
+                    )
+                    (unreachable)
+                )
+                (throw_ref)
+            )
+            (unreachable)
+        )
+    )
+
+    ;; counts the odd numbers up to `n`, by throwing an exception for each of them
+    (func (export "count_odd") (param $n i32) (result i32)
+        (local $caught i32)
+        (loop $continue
+            (block $done
+                (block $handler
+                    (try_table (catch_all $handler)
+                        (call $throw_if_nonzero (i32.and (local.get $n) (i32.const 1)))
+                    )
+                    (br $done)
+                )
+                (local.set $caught (i32.add (local.get $caught) (i32.const 1)))
+            )
+            (br_if $continue (local.tee $n (i32.sub (local.get $n) (i32.const 1))))
+        )
+        (local.get $caught)
+    )
+
+;; catches `n` exceptions by reference, keeps the first one in a global and finally returns its payload
+    (global $kept (mut exnref) (ref.null exn))
+    (func (export "catch_ref_loop") (param $n i32) (result i32)
+        (local $exn exnref)
+        (loop $continue
+            (block $handler (result i32 exnref)
+                (try_table (catch_ref $e $handler)
+                    (throw $e (local.get $n))
+                )
+                (unreachable)
+            )
+            (local.set $exn)
+            (drop)
+            (if (ref.is_null (global.get $kept)) (then (global.set $kept (local.get $exn))))
+            (br_if $continue (local.tee $n (i32.sub (local.get $n) (i32.const 1))))
+        )
+        (block $outer (result i32)
+            (try_table (catch $e $outer)
+                (throw_ref (global.get $kept))
+            )
+            (unreachable)
+        )
+    )
+
+    (func (export "catch_ref") (param i32) (result exnref)
+        (block $handler (result exnref)
+            (try_table (catch_all_ref $handler)
+                (throw $e (local.get 0))
+            )
+            (unreachable)
+        )
+    )
+
+    (func (export "throw_ref") (param exnref)
+        (throw_ref (local.get 0))
+    )
+
+        (func (export "uncaught") (param i32)
+        (try_table (catch $empty 0)
+            (throw $e (local.get 0))
+        )
+    )
+
+    (func (export "throw_null")
+        (throw_ref (ref.null exn))
+    )
+)"#;
+
+#[test_log::test]
+pub fn catch_exceptions() {
+    let wasm_bytes = wat::parse_str(EXCEPTIONS).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let catch = instance
+        .get_function_by_name(DEFAULT_MODULE, "catch")
+        .unwrap();
+    assert_eq!(0, instance.invoke::<i32, i32>(&catch, 0).unwrap());
+    assert_eq!(105, instance.invoke::<i32, i32>(&catch, 5).unwrap());
+
+    let rethrow = instance
+        .get_function_by_name(DEFAULT_MODULE, "rethrow")
+        .unwrap();
+    assert_eq!(42, instance.invoke::<i32, i32>(&rethrow, 42).unwrap());
+
+    // the try_table is entered again in every iteration
+    let count_odd = instance
+        .get_function_by_name(DEFAULT_MODULE, "count_odd")
+        .unwrap();
+    assert_eq!(5, instance.invoke::<i32, i32>(&count_odd, 10).unwrap());
+    assert_eq!(500, instance.invoke::<i32, i32>(&count_odd, 999).unwrap());
+}
+
+#[test_log::test]
+pub fn uncaught_exceptions() {
+    let wasm_bytes = wat::parse_str(EXCEPTIONS).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let uncaught = instance
+        .get_function_by_name(DEFAULT_MODULE, "uncaught")
+        .unwrap();
+    let expected = Exception {
+        tag: instance.get_tag_by_name(DEFAULT_MODULE, "e").unwrap(),
+        payload: vec![Value::I32(7)],
+    };
+    let RuntimeError::UncaughtException(exn_addr) =
+        instance.invoke::<i32, ()>(&uncaught, 7).unwrap_err().error
+    else {
+        panic!("expected an uncaught exception");
+    };
+    assert_eq!(Some(expected), instance.exception(exn_addr));
+
+    let throw_null = instance
+        .get_function_by_name(DEFAULT_MODULE, "throw_null")
+        .unwrap();
+    assert_eq!(
+        RuntimeError::NullExceptionReference,
+        instance.invoke::<(), ()>(&throw_null, ()).unwrap_err()
+    );
+
+    assert_eq!(
+        RuntimeError::TagNotFound,
+        instance
+            .get_tag_by_name(DEFAULT_MODULE, "other")
+            .unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn caught_exceptions_are_not_retained() {
+    let wasm_bytes = wat::parse_str(EXCEPTIONS).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    // exceptions caught without a reference to them are dropped
+    let count_odd = instance
+        .get_function_by_name(DEFAULT_MODULE, "count_odd")
+        .unwrap();
+    assert_eq!(5000, instance.invoke::<i32, i32>(&count_odd, 9999).unwrap());
+    assert!(instance.modules[0].store.exceptions.is_empty());
+
+    // only exceptions passed as exnref are kept, rethrowing them does not copy them
+    let rethrow = instance
+        .get_function_by_name(DEFAULT_MODULE, "rethrow")
+        .unwrap();
+    assert_eq!(42, instance.invoke::<i32, i32>(&rethrow, 42).unwrap());
+    assert_eq!(1, instance.modules[0].store.exceptions.len());
+}
+
+#[test_log::test]
+pub fn unreferenced_exceptions_are_reclaimed() {
+    let wasm_bytes = wat::parse_str(EXCEPTIONS).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let catch_ref = instance
+        .get_function_by_name(DEFAULT_MODULE, "catch_ref")
+        .unwrap();
+    let throw_ref = instance
+        .get_function_by_name(DEFAULT_MODULE, "throw_ref")
+        .unwrap();
+    let exnref = instance
+        .invoke_dynamic(
+            &catch_ref,
+            vec![Value::I32(1)],
+            &[ValType::RefType(RefType::ExnRef)],
+        )
+        .unwrap();
+
+    // the slots of exceptions whose exnref was dropped are reused, the one kept in the global is not reclaimed
+    let catch_ref_loop = instance
+        .get_function_by_name(DEFAULT_MODULE, "catch_ref_loop")
+        .unwrap();
+    assert_eq!(
+        10000,
+        instance.invoke::<i32, i32>(&catch_ref_loop, 10000).unwrap()
+    );
+    assert!(instance.modules[0].store.exceptions.len() <= 32);
+
+    // exnrefs only held by the embedder are reclaimed as well
+    assert_eq!(
+        RuntimeError::ReclaimedExceptionReference,
+        instance
+            .invoke_dynamic(&throw_ref, exnref, &[])
+            .unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn invalid_exceptions() {
+    // the label expects an i64, but the tag carries an i32
+    let wasm_bytes = wat::parse_str(
+        r#"
+        (module
+            (tag $e (param i32))
+            (func (result i64)
+                (block $handler (result i64)
+                    (try_table (catch $e $handler))
+                    (i64.const 0)
+                )
+            )
+        )"#,
+    )
+    .unwrap();
+    assert_eq!(
+        Some(Error::CatchTypeMismatch(0)),
+        validate(&wasm_bytes).err()
+    );
+
+    let wasm_bytes = wat::parse_str(
+        r#"
+        (module
+            (type $with_results (func (result i32)))
+            (tag (type $with_results))
+        )"#,
+    )
+    .unwrap();
+    assert_eq!(
+        Some(Error::TagTypeHasResults(0)),
+        validate(&wasm_bytes).err()
+    );
+}
@@ -0,0 +1,147 @@
+use wasm::{validate, RuntimeError, RuntimeInstance, Value, DEFAULT_MODULE};
+
+const FUEL_MODULE: &str = r#"
+(module
+    (memory 1)
+    (func (export "get_three") (result i32)
+        i32.const 1
+        i32.const 2
+        i32.add
+    )
+    (func (export "fill") (param i32)
+        i32.const 0
+        i32.const 42
+        local.get 0
+        memory.fill
+    )
+    (func (export "load") (param i32) (result i32)
+        local.get 0
+        i32.load8_u
+    )
+    (func (export "loop_forever")
+        (loop $l
+            br $l
+        )
+    )
+)"#;
+
+#[test_log::test]
+pub fn fuel_is_consumed_per_instruction() {
+    let wasm_bytes = wat::parse_str(FUEL_MODULE).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let get_three = instance
+        .get_function_by_name(DEFAULT_MODULE, "get_three")
+        .unwrap();
+
+    // i32.const, i32.const, i32.add, end
+    assert_eq!(
+        3,
+        instance
+            .invoke_with_fuel::<(), i32>(&get_three, (), Some(4))
+            .unwrap()
+    );
+    assert_eq!(
+        RuntimeError::OutOfFuel,
+        instance
+            .invoke_with_fuel::<(), i32>(&get_three, (), Some(3))
+            .unwrap_err()
+    );
+    assert_eq!(
+        3,
+        instance
+            .invoke_with_fuel::<(), i32>(&get_three, (), None)
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+pub fn fuel_is_consumed_per_byte_for_bulk_instructions() {
+    let wasm_bytes = wat::parse_str(FUEL_MODULE).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let fill = instance
+        .get_function_by_name(DEFAULT_MODULE, "fill")
+        .unwrap();
+    let load = instance
+        .get_function_by_name(DEFAULT_MODULE, "load")
+        .unwrap();
+
+    // i32.const, i32.const, local.get and memory.fill leave only 99 units for the 100 bytes to fill
+    assert_eq!(
+        RuntimeError::OutOfFuel,
+        instance
+            .invoke_with_fuel::<i32, ()>(&fill, 100, Some(103))
+            .unwrap_err()
+    );
+    // the memory must be untouched if the fill could not be paid for
+    assert_eq!(0, instance.invoke::<i32, i32>(&load, 99).unwrap());
+
+    instance
+        .invoke_with_fuel::<i32, ()>(&fill, 100, Some(105))
+        .unwrap();
+    assert_eq!(42, instance.invoke::<i32, i32>(&load, 99).unwrap());
+    assert_eq!(0, instance.invoke::<i32, i32>(&load, 100).unwrap());
+}
+
+#[test_log::test]
+pub fn fuel_of_bulk_instructions_with_non_minimal_sub_opcode() {
+    let mut wasm_bytes = wat::parse_str(
+        r#"
+        (module
+            (memory 1)
+            (func (export "fill") (param i32)
+                i32.const 0
+                i32.const 42
+                local.get 0
+                nop
+                memory.fill
+            )
+        )"#,
+    )
+    .unwrap();
+    // replace `nop; memory.fill` by a `memory.fill` of the same length, whose sub-opcode is a 2 byte LEB128
+    const NOP_MEMORY_FILL: [u8; 4] = [0x01, 0xFC, 0x0B, 0x00];
+    let position = wasm_bytes
+        .windows(NOP_MEMORY_FILL.len())
+        .position(|window| window == NOP_MEMORY_FILL)
+        .unwrap();
+    wasm_bytes[position..position + 4].copy_from_slice(&[0xFC, 0x8B, 0x00, 0x00]);
+
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let fill = instance
+        .get_function_by_name(DEFAULT_MODULE, "fill")
+        .unwrap();
+
+    // i32.const, i32.const, local.get and end leave only 100 units for the 100 bytes to fill
+    assert_eq!(
+        RuntimeError::OutOfFuel,
+        instance
+            .invoke_with_fuel::<i32, ()>(&fill, 100, Some(104))
+            .unwrap_err()
+    );
+    instance
+        .invoke_with_fuel::<i32, ()>(&fill, 100, Some(105))
+        .unwrap();
+}
+
+#[test_log::test]
+pub fn fuel_bounds_infinite_loop() {
+    let wasm_bytes = wat::parse_str(FUEL_MODULE).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let loop_forever = instance
+        .get_function_by_name(DEFAULT_MODULE, "loop_forever")
+        .unwrap();
+
+    assert_eq!(
+        RuntimeError::OutOfFuel,
+        instance
+            .invoke_dynamic_with_fuel(&loop_forever, Vec::<Value>::new(), &[], Some(10_000))
+            .unwrap_err()
+    );
+}
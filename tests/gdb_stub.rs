@@ -0,0 +1,218 @@
+use std::collections::VecDeque;
+
+use wasm::{
+    gdb_stub::{DebugOutcome, GdbStub, Transport},
+    resumable::{InvocationState, Resumable},
+    validate, RuntimeInstance, Value, DEFAULT_MODULE,
+};
+
+const SUM_OF_SQUARES: &str = r#"
+(module
+    (global $calls (mut i32) (i32.const 0))
+    (memory 1)
+    (func $square (param $x i32) (result i32)
+        (global.set $calls (i32.add (global.get $calls) (i32.const 1)))
+        (i32.mul (local.get $x) (local.get $x))
+    )
+    (func (export "sum_of_squares") (param $a i32) (param $b i32) (result i32)
+        (i32.store (i32.const 0) (local.get $a))
+        (i32.add (call $square (local.get $a)) (call $square (local.get $b)))
+    )
+)"#;
+
+const CODE_ADDRESS: u64 = 0x4000_0000_0000_0000;
+
+/// A debugger which sends all its packets at once and records the replies
+struct ScriptedDebugger {
+    input: VecDeque<u8>,
+    output: Vec<u8>,
+}
+
+impl ScriptedDebugger {
+    fn new(packets: &[&str]) -> Self {
+        let mut input = VecDeque::new();
+        for packet in packets {
+            input.extend(encode(packet).bytes());
+        }
+        ScriptedDebugger {
+            input,
+            output: Vec::new(),
+        }
+    }
+}
+
+impl Transport for ScriptedDebugger {
+    type Error = ();
+
+    fn read_byte(&mut self) -> Result<u8, ()> {
+        self.input.pop_front().ok_or(())
+    }
+
+    fn write_all(&mut self, bytes: &[u8]) -> Result<(), ()> {
+        self.output.extend_from_slice(bytes);
+        Ok(())
+    }
+}
+
+fn encode(packet: &str) -> String {
+    let checksum = packet
+        .bytes()
+        .fold(0_u8, |sum, byte| sum.wrapping_add(byte));
+    format!("${packet}#{checksum:02x}")
+}
+
+/// Split the output of the stub into its acknowledgements and the contents of its packets
+fn split_output(output: &[u8]) -> (String, Vec<String>) {
+    let mut output = std::str::from_utf8(output).unwrap();
+    let mut acknowledgements = String::new();
+    let mut packets = Vec::new();
+    while let Some((before, packet)) = output.split_once('$') {
+        acknowledgements.push_str(before);
+        let (packet, rest) = packet.split_once('#').unwrap();
+        assert_eq!(encode(packet), format!("${packet}#{}", &rest[..2]));
+        packets.push(packet.to_owned());
+        output = &rest[2..];
+    }
+    acknowledgements.push_str(output);
+    (acknowledgements, packets)
+}
+
+fn hex_address(address: u64) -> String {
+    address
+        .to_le_bytes()
+        .iter()
+        .map(|byte| format!("{byte:02x}"))
+        .collect()
+}
+
+/// Start an invocation of `sum_of_squares` which is suspended before its first instruction
+fn suspended_invocation(instance: &mut RuntimeInstance) -> Resumable {
+    let sum_of_squares = instance
+        .get_function_by_name(DEFAULT_MODULE, "sum_of_squares")
+        .unwrap();
+    let state = instance
+        .invoke_resumable(&sum_of_squares, vec![Value::I32(3), Value::I32(4)], Some(0))
+        .unwrap();
+    let InvocationState::Suspended(mut resumable) = state else {
+        panic!("expected the invocation to be suspended");
+    };
+    resumable.set_fuel(None);
+    resumable
+}
+
+#[test_log::test]
+pub fn gdb_stub_session() {
+    let wasm_bytes = wat::parse_str(SUM_OF_SQUARES).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let square_pc = instance.set_function_breakpoint(DEFAULT_MODULE, 0).unwrap() as u64;
+    instance.clear_breakpoints();
+    let resumable = suspended_invocation(&mut instance);
+
+    let breakpoint = format!("{:x},1", CODE_ADDRESS | square_pc);
+    let script = [
+        "qSupported:xmlRegisters=i386",
+        "QStartNoAckMode",
+        "?",
+        "qXfer:libraries:read::0,1000",
+        "m4000000000000000,4",
+        &format!("Z0,{breakpoint}"),
+        "c",
+        "qWasmCallStack:1",
+        "qWasmLocal:0;0",
+        "qWasmLocal:1;1",
+        "qWasmLocal:0;5",
+        "qWasmGlobal:0;0",
+        "m0,4",
+        "M0,4:2a000000",
+        "qWasmMem:0;0;4",
+        "m4000000000000000,0",
+        &format!("z0,{breakpoint}"),
+        "s",
+        "qWasmStackValue:0;0",
+        "vCont?",
+        "c",
+    ];
+    let mut input = String::from("+$?#00");
+    for packet in script {
+        input.push_str(&encode(packet));
+    }
+    let mut stub = GdbStub::new(ScriptedDebugger {
+        input: input.into_bytes().into(),
+        output: Vec::new(),
+    });
+
+    let Ok(DebugOutcome::Finished(values)) = stub.serve(&mut instance, resumable) else {
+        panic!("expected the invocation to finish");
+    };
+    assert_eq!(vec![Value::I32(25)], values);
+
+    // packets with a wrong checksum are rejected, all others acknowledged until the debugger disables this
+    let (acknowledgements, replies) = split_output(&stub.into_transport().output);
+    assert_eq!("-++", acknowledgements);
+    assert_eq!(
+        "PacketSize=1000;QStartNoAckMode+;qXfer:libraries:read+",
+        replies[0]
+    );
+    assert_eq!("OK", replies[1]);
+    let replies = &replies[2..];
+
+    let square_pc = hex_address(CODE_ADDRESS | square_pc);
+    let stopped_at_square = format!("T05thread:1;00:{square_pc};");
+    assert!(replies[0].starts_with("T05thread:1;00:"));
+    assert_eq!(
+        "l<library-list><library name=\"__interpreter_default__\"><section address=\"0x4000000000000000\"/>\
+        </library></library-list>",
+        replies[1]
+    );
+    assert_eq!("0061736d", replies[2]);
+    assert_eq!("OK", replies[3]);
+    assert_eq!(stopped_at_square, replies[4]);
+    assert_eq!(2 * 16, replies[5].len());
+    assert!(replies[5].starts_with(&square_pc));
+    assert_eq!("03000000", replies[6]);
+    assert_eq!("04000000", replies[7]);
+    assert_eq!("E01", replies[8]);
+    assert_eq!("00000000", replies[9]);
+    assert_eq!("03000000", replies[10]);
+    assert_eq!("OK", replies[11]);
+    assert_eq!("2a000000", replies[12]);
+    assert_eq!("", replies[13]);
+    assert_eq!("OK", replies[14]);
+    assert!(replies[15].starts_with("T05thread:1;00:"));
+    assert_ne!(stopped_at_square, replies[15]);
+    assert_eq!("00000000", replies[16]);
+    assert_eq!("", replies[17]);
+    assert_eq!("W00", replies[18]);
+    assert_eq!(19, replies.len());
+}
+
+#[test_log::test]
+pub fn gdb_stub_detach() {
+    let wasm_bytes = wat::parse_str(SUM_OF_SQUARES).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let resumable = suspended_invocation(&mut instance);
+
+    let mut stub = GdbStub::new(ScriptedDebugger::new(&["s", "D"]));
+    let Ok(DebugOutcome::Detached(resumable)) = stub.serve(&mut instance, resumable) else {
+        panic!("expected the debugger to detach");
+    };
+    let (_, replies) = split_output(&stub.into_transport().output);
+    assert!(replies[0].starts_with("T05thread:1;00:"));
+    assert_eq!(vec!["OK"], replies[1..]);
+
+    // the invocation continues without the debugger
+    let InvocationState::Finished(values) = instance.resume(resumable).unwrap() else {
+        panic!("expected the invocation to finish");
+    };
+    assert_eq!(vec![Value::I32(25)], values);
+
+    // a debugger which kills the invocation
+    let resumable = suspended_invocation(&mut instance);
+    let mut stub = GdbStub::new(ScriptedDebugger::new(&["k"]));
+    assert!(matches!(
+        stub.serve(&mut instance, resumable),
+        Ok(DebugOutcome::Killed)
+    ));
+}
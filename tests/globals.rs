@@ -84,11 +84,18 @@ fn global_invalid_value_stack() {
     }
 }
 
-#[ignore = "not yet implemented"]
 #[test_log::test]
 fn imported_globals() {
     use wasm::{validate, RuntimeInstance};
 
+    let env_wat = r#"
+    (module
+        (global (export "global") (mut i32) (i32.const 3))
+
+        (func (export "get") (result i32)
+            global.get 0)
+    )
+    "#;
     let wat = r#"
     (module
         (import "env" "global" (global $my_global (mut i32)))
@@ -104,10 +111,16 @@ fn imported_globals() {
             global.get $my_global)
     )
     "#;
+    let env_wasm_bytes = wat::parse_str(env_wat).unwrap();
     let wasm_bytes = wat::parse_str(wat).unwrap();
 
+    let env_validation_info = validate(&env_wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_named("env", &env_validation_info).expect("instantiation failed");
     let validation_info = validate(&wasm_bytes).expect("validation failed");
-    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    instance
+        .add_module(DEFAULT_MODULE, &validation_info)
+        .expect("instantiation failed");
 
     // Set global to 17. 3 is returned as previous (default) value.
     assert_eq!(
@@ -134,4 +147,180 @@ fn imported_globals() {
             )
             .unwrap()
     );
+
+    // The exporting module observes the change as well
+    assert_eq!(
+        17,
+        instance
+            .invoke(&instance.get_function_by_name("env", "get").unwrap(), ())
+            .unwrap()
+    );
+}
+
+/// Immutable imported globals can be used in the initialization of other globals
+#[test_log::test]
+fn imported_global_in_constant_expression() {
+    use wasm::{validate, RuntimeInstance};
+
+    let env_wat = r#"
+    (module
+        (global (export "base") i64 (i64.const 1024))
+    )
+    "#;
+    let wat = r#"
+    (module
+        (import "env" "base" (global $base i64))
+        (global $my_global i64 (global.get $base))
+
+        (func (export "get") (result i64)
+            global.get $my_global)
+    )
+    "#;
+    let env_wasm_bytes = wat::parse_str(env_wat).unwrap();
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let env_validation_info = validate(&env_wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_named("env", &env_validation_info).expect("instantiation failed");
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    instance
+        .add_module(DEFAULT_MODULE, &validation_info)
+        .expect("instantiation failed");
+
+    assert_eq!(
+        1024_i64,
+        instance
+            .invoke(
+                &instance
+                    .get_function_by_name(DEFAULT_MODULE, "get")
+                    .unwrap(),
+                ()
+            )
+            .unwrap()
+    );
+}
+
+/// Position-independent code, e.g. emitted by LLVM with `-mextended-const`, computes the offsets of its data and
+/// element segments from imported globals
+#[test_log::test]
+fn imported_global_in_segment_offsets() {
+    use wasm::{validate, RuntimeInstance};
+
+    let env_wat = r#"
+    (module
+        (global (export "memory_base") i32 (i32.const 16))
+        (global (export "table_base") i32 (i32.const 1))
+    )
+    "#;
+    let wat = r#"
+    (module
+        (import "env" "memory_base" (global $memory_base i32))
+        (import "env" "table_base" (global $table_base i32))
+        (memory 1)
+        (table 4 funcref)
+
+        (global $data_end i32 (i32.add (global.get $memory_base) (i32.const 5)))
+        (data (offset (i32.add (global.get $memory_base) (i32.const 4))) "\2a")
+        (elem (offset (i32.mul (global.get $table_base) (i32.const 2))) func $seven)
+
+        (func $seven (result i32)
+            i32.const 7)
+
+        (func (export "load") (param i32) (result i32)
+            (i32.load8_u (local.get 0)))
+
+        (func (export "call") (param i32) (result i32)
+            (call_indirect (result i32) (local.get 0)))
+
+        (func (export "data_end") (result i32)
+            global.get $data_end)
+    )
+    "#;
+    let env_wasm_bytes = wat::parse_str(env_wat).unwrap();
+    let wasm_bytes = wat::parse_str(wat).unwrap();
+
+    let env_validation_info = validate(&env_wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_named("env", &env_validation_info).expect("instantiation failed");
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    instance
+        .add_module(DEFAULT_MODULE, &validation_info)
+        .expect("instantiation failed");
+
+    let load = instance
+        .get_function_by_name(DEFAULT_MODULE, "load")
+        .unwrap();
+    assert_eq!(42, instance.invoke::<i32, i32>(&load, 20).unwrap());
+
+    let call = instance
+        .get_function_by_name(DEFAULT_MODULE, "call")
+        .unwrap();
+    assert_eq!(7, instance.invoke::<i32, i32>(&call, 2).unwrap());
+
+    let data_end = instance
+        .get_function_by_name(DEFAULT_MODULE, "data_end")
+        .unwrap();
+    assert_eq!(21, instance.invoke::<(), i32>(&data_end, ()).unwrap());
+}
+
+#[test_log::test]
+fn invalid_global_in_constant_expression() {
+    use wasm::{validate, Error};
+
+    let mutable_import = r#"
+    (module
+        (import "env" "global" (global $imported (mut i32)))
+        (global i32 (global.get $imported))
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(mutable_import).unwrap();
+    assert_eq!(Some(Error::GlobalIsMutable), validate(&wasm_bytes).err());
+
+    let local_global = r#"
+    (module
+        (global $local i32 (i32.const 1))
+        (global i32 (global.get $local))
+    )
+    "#;
+    let wasm_bytes = wat::parse_str(local_global).unwrap();
+    assert_eq!(
+        Some(Error::InvalidGlobalIdx(0)),
+        validate(&wasm_bytes).err()
+    );
+}
+
+#[test_log::test]
+fn incompatible_global_import() {
+    use wasm::{validate, Error, RuntimeInstance, StoreInstantiationError};
+
+    let env_wat = r#"
+    (module
+        (global (export "global") (mut i32) (i32.const 3))
+    )
+    "#;
+    let incompatible_imports = [
+        // the mutability differs
+        r#"(module (import "env" "global" (global i32)))"#,
+        // the value type differs
+        r#"(module (import "env" "global" (global (mut i64))))"#,
+    ]
+    .map(|wat| wat::parse_str(wat).unwrap());
+
+    let env_wasm_bytes = wat::parse_str(env_wat).unwrap();
+    let env_validation_info = validate(&env_wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_named("env", &env_validation_info).expect("instantiation failed");
+
+    for wasm_bytes in &incompatible_imports {
+        let validation_info = validate(wasm_bytes).expect("validation failed");
+        assert_eq!(
+            Error::StoreInstantiationError(StoreInstantiationError::IncompatibleImportType(
+                "env".to_owned(),
+                "global".to_owned()
+            )),
+            instance
+                .add_module(DEFAULT_MODULE, &validation_info)
+                .unwrap_err()
+        );
+    }
 }
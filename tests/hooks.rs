@@ -0,0 +1,324 @@
+#![cfg(feature = "hooks")]
+
+use std::ops::Range;
+
+use wasm::{
+    hooks::{HookAction, HookSet},
+    resumable::{InvocationState, SuspendReason},
+    validate, FuncType, NumType, ResultType, RuntimeError, RuntimeInstance, ValType, Value,
+    DEFAULT_MODULE,
+};
+
+const MODULE: &str = r#"
+(module
+    (memory 1 2)
+    (table 2 funcref)
+    (elem declare func $double)
+    (func $double (param i32) (result i32)
+        (i32.mul (local.get 0) (i32.const 2))
+    )
+    (func (export "run") (param $x i32) (result i32)
+        (i32.store (i32.const 4) (local.get $x))
+        (table.set (i32.const 1) (ref.func $double))
+        (drop (memory.grow (i32.const 1)))
+        (block
+            (br_if 0 (local.get $x))
+            (drop (i32.div_u (i32.const 1) (local.get $x)))
+        )
+        (call $double (i32.load (i32.const 4)))
+    )
+)"#;
+
+#[derive(Debug, PartialEq)]
+enum Event {
+    Call(usize, Vec<Value>),
+    Return(usize, Vec<Value>),
+    Branch(usize, usize),
+    Load(usize, usize, Value),
+    Store(usize, usize, Value),
+    MemoryGrow(usize, usize),
+    Table(usize, Range<usize>),
+    Trap(RuntimeError),
+}
+
+#[derive(Default)]
+struct Recorder {
+    events: Vec<Event>,
+}
+
+impl HookSet for Recorder {
+    fn call_hook(&mut self, _module_idx: usize, func_idx: usize, args: &[Value]) -> HookAction {
+        self.events.push(Event::Call(func_idx, args.to_vec()));
+        HookAction::Continue
+    }
+
+    fn return_hook(
+        &mut self,
+        _module_idx: usize,
+        func_idx: usize,
+        results: &[Value],
+    ) -> HookAction {
+        self.events.push(Event::Return(func_idx, results.to_vec()));
+        HookAction::Continue
+    }
+
+    fn branch_hook(&mut self, _module_idx: usize, pc: usize, target_pc: usize) -> HookAction {
+        self.events.push(Event::Branch(pc, target_pc));
+        HookAction::Continue
+    }
+
+    fn load_hook(
+        &mut self,
+        _: usize,
+        _: usize,
+        address: usize,
+        width: usize,
+        value: Value,
+    ) -> HookAction {
+        self.events.push(Event::Load(address, width, value));
+        HookAction::Continue
+    }
+
+    fn store_hook(
+        &mut self,
+        _: usize,
+        _: usize,
+        address: usize,
+        width: usize,
+        value: Value,
+    ) -> HookAction {
+        self.events.push(Event::Store(address, width, value));
+        HookAction::Continue
+    }
+
+    fn memory_grow_hook(
+        &mut self,
+        _: usize,
+        _: usize,
+        previous_pages: usize,
+        pages: usize,
+    ) -> HookAction {
+        self.events.push(Event::MemoryGrow(previous_pages, pages));
+        HookAction::Continue
+    }
+
+    fn table_hook(
+        &mut self,
+        _module_idx: usize,
+        table_idx: usize,
+        elements: Range<usize>,
+    ) -> HookAction {
+        self.events.push(Event::Table(table_idx, elements));
+        HookAction::Continue
+    }
+
+    fn trap_hook(&mut self, error: &RuntimeError, _module_idx: usize, _pc: usize) {
+        self.events.push(Event::Trap(error.clone()));
+    }
+}
+
+#[test_log::test]
+pub fn hooks() {
+    let wasm_bytes = wat::parse_str(MODULE).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_with_hooks(DEFAULT_MODULE, &validation_info, Recorder::default())
+            .expect("instantiation failed");
+    let run = instance
+        .get_function_by_name(DEFAULT_MODULE, "run")
+        .unwrap();
+
+    assert_eq!(Ok(42), instance.invoke::<i32, i32>(&run, 21));
+    let mut events = std::mem::take(&mut instance.hook_set.events);
+    let Event::Branch(pc, target_pc) = events.remove(4) else {
+        panic!("expected the br_if to be taken");
+    };
+    assert!(pc < target_pc);
+    assert_eq!(
+        vec![
+            Event::Call(1, vec![Value::I32(21)]),
+            Event::Store(4, 4, Value::I32(21)),
+            Event::Table(0, 1..2),
+            Event::MemoryGrow(1, 2),
+            Event::Load(4, 4, Value::I32(21)),
+            Event::Call(0, vec![Value::I32(21)]),
+            Event::Return(0, vec![Value::I32(42)]),
+            Event::Return(1, vec![Value::I32(42)]),
+        ],
+        events
+    );
+
+    // the memory can not grow beyond its maximum, and the branch is not taken
+    assert_eq!(
+        RuntimeError::DivideBy0,
+        instance.invoke::<i32, i32>(&run, 0).unwrap_err()
+    );
+    assert_eq!(
+        vec![
+            Event::Call(1, vec![Value::I32(0)]),
+            Event::Store(4, 4, Value::I32(0)),
+            Event::Table(0, 1..2),
+            Event::MemoryGrow(2, 2),
+            Event::Trap(RuntimeError::DivideBy0),
+        ],
+        instance.hook_set.events
+    );
+}
+
+const HOST_IMPORT: &str = r#"
+(module
+    (import "env" "add_one" (func $add_one (param i32) (result i32)))
+    (table 1 funcref)
+    (elem (i32.const 0) $add_one)
+    (func (export "run") (param i32) (result i32)
+        (call_indirect (param i32) (result i32) (call $add_one (local.get 0)) (i32.const 0))
+    )
+    (func (export "tail") (param i32) (result i32)
+        (return_call $add_one (local.get 0))
+    )
+)"#;
+
+#[test_log::test]
+pub fn host_function_hooks() {
+    let wasm_bytes = wat::parse_str(HOST_IMPORT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_with_hooks(DEFAULT_MODULE, &validation_info, Recorder::default())
+            .expect("instantiation failed");
+    let i32_to_i32 = FuncType {
+        params: ResultType {
+            valtypes: vec![ValType::NumType(NumType::I32)],
+        },
+        returns: ResultType {
+            valtypes: vec![ValType::NumType(NumType::I32)],
+        },
+    };
+    instance.add_host_function("env", "add_one", i32_to_i32, |params: &[Value]| {
+        let x: i32 = params[0].into();
+        vec![Value::from(x + 1)]
+    });
+    let run = instance
+        .get_function_by_name(DEFAULT_MODULE, "run")
+        .unwrap();
+    let tail = instance
+        .get_function_by_name(DEFAULT_MODULE, "tail")
+        .unwrap();
+
+    // the host function is reported as the imported function 0, whether it is called directly or indirectly
+    assert_eq!(Ok(3), instance.invoke::<i32, i32>(&run, 1));
+    assert_eq!(
+        vec![
+            Event::Call(1, vec![Value::I32(1)]),
+            Event::Call(0, vec![Value::I32(1)]),
+            Event::Return(0, vec![Value::I32(2)]),
+            Event::Call(0, vec![Value::I32(2)]),
+            Event::Return(0, vec![Value::I32(3)]),
+            Event::Return(1, vec![Value::I32(3)]),
+        ],
+        std::mem::take(&mut instance.hook_set.events)
+    );
+
+    // a tail call of a host function does not replace the caller, which returns the host function's results
+    assert_eq!(Ok(6), instance.invoke::<i32, i32>(&tail, 5));
+    assert_eq!(
+        vec![
+            Event::Call(2, vec![Value::I32(5)]),
+            Event::Call(0, vec![Value::I32(5)]),
+            Event::Return(0, vec![Value::I32(6)]),
+            Event::Return(2, vec![Value::I32(6)]),
+        ],
+        instance.hook_set.events
+    );
+}
+
+const COUNTER: &str = r#"
+(module
+    (memory 1)
+    (func (export "count") (param $n i32) (result i32)
+        (loop $continue
+            (i32.store (i32.const 0) (i32.add (i32.load (i32.const 0)) (i32.const 1)))
+            (br_if $continue (i32.ne (i32.load (i32.const 0)) (local.get $n)))
+        )
+        (i32.load (i32.const 0))
+    )
+    (func (export "protect") (param $address i32)
+        (i32.store (local.get $address) (i32.const 42))
+    )
+)"#;
+
+/// Forbids writes to the first 16 bytes of memory, except for the counter, and yields after every `yield_interval`
+/// executed instructions
+#[derive(Default)]
+struct Watchdog {
+    yield_interval: usize,
+    executed: usize,
+}
+
+impl HookSet for Watchdog {
+    fn instruction_hook(&mut self, _: usize, _: &[u8], _: usize) -> HookAction {
+        self.executed += 1;
+        if self.yield_interval != 0 && self.executed % self.yield_interval == 0 {
+            HookAction::Yield
+        } else {
+            HookAction::Continue
+        }
+    }
+
+    fn store_hook(
+        &mut self,
+        _: usize,
+        _: usize,
+        address: usize,
+        width: usize,
+        _: Value,
+    ) -> HookAction {
+        if address < 16 && address + width > 4 {
+            HookAction::Trap(format!("write to protected address {address}"))
+        } else {
+            HookAction::Continue
+        }
+    }
+}
+
+#[test_log::test]
+pub fn hooks_control_execution() {
+    let wasm_bytes = wat::parse_str(COUNTER).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_with_hooks(DEFAULT_MODULE, &validation_info, Watchdog::default())
+            .expect("instantiation failed");
+    let count = instance
+        .get_function_by_name(DEFAULT_MODULE, "count")
+        .unwrap();
+    let protect = instance
+        .get_function_by_name(DEFAULT_MODULE, "protect")
+        .unwrap();
+
+    // a trapping store hook prevents the store
+    assert_eq!(
+        RuntimeError::HookTrap("write to protected address 8".to_owned()),
+        instance.invoke::<i32, ()>(&protect, 8).unwrap_err()
+    );
+    assert_eq!(Ok(()), instance.invoke::<i32, ()>(&protect, 16));
+    assert_eq!(Ok(1), instance.invoke::<i32, i32>(&count, 1));
+
+    // yielding suspends resumable invocations, while others continue right away
+    instance.hook_set.yield_interval = 10;
+    assert_eq!(Ok(2), instance.invoke::<i32, i32>(&count, 2));
+    instance.hook_set.executed = 0;
+    let mut state = instance
+        .invoke_resumable(&count, vec![Value::I32(5)], None)
+        .unwrap();
+    let mut yields = 0;
+    while let InvocationState::Suspended(resumable) = state {
+        assert_eq!(Some(SuspendReason::HookYield), resumable.suspend_reason());
+        assert_eq!(0, instance.hook_set.executed % 10);
+        yields += 1;
+        state = instance.resume(resumable).unwrap();
+    }
+    let InvocationState::Finished(values) = state else {
+        unreachable!()
+    };
+    assert_eq!(vec![Value::I32(5)], values);
+    assert_eq!(instance.hook_set.executed / 10, yields);
+}
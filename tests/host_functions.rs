@@ -0,0 +1,182 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use wasm::{
+    validate, FuncType, NumType, ResultType, RuntimeError, RuntimeInstance, ValType, Value,
+    DEFAULT_MODULE,
+};
+
+const HOST_IMPORT: &str = r#"
+(module
+    (import "env" "add_one" (func $add_one (param i32) (result i32)))
+    (import "env" "log" (func $log (param i64)))
+    (type $i32_to_i32 (func (param i32) (result i32)))
+    (table 1 funcref)
+    (elem (i32.const 0) $add_one)
+
+    (func (export "add_two") (param i32) (result i32)
+        local.get 0
+        call $add_one
+        call $add_one
+    )
+
+    (func (export "add_one_indirect") (param i32) (result i32)
+        local.get 0
+        i32.const 0
+        call_indirect (type $i32_to_i32)
+    )
+
+    (func (export "log_twice") (param i64)
+        local.get 0
+        call $log
+        local.get 0
+        i64.const 1
+        i64.add
+        call $log
+    )
+)"#;
+
+fn i32_to_i32() -> FuncType {
+    FuncType {
+        params: ResultType {
+            valtypes: vec![ValType::NumType(NumType::I32)],
+        },
+        returns: ResultType {
+            valtypes: vec![ValType::NumType(NumType::I32)],
+        },
+    }
+}
+
+fn i64_to_void() -> FuncType {
+    FuncType {
+        params: ResultType {
+            valtypes: vec![ValType::NumType(NumType::I64)],
+        },
+        returns: ResultType {
+            valtypes: Vec::new(),
+        },
+    }
+}
+
+#[test_log::test]
+pub fn call_host_function() {
+    let wasm_bytes = wat::parse_str(HOST_IMPORT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let logged = Rc::new(RefCell::new(Vec::new()));
+    let logged_by_host = Rc::clone(&logged);
+
+    instance.add_host_function("env", "add_one", i32_to_i32(), |params: &[Value]| {
+        let x: i32 = params[0].into();
+        vec![Value::from(x + 1)]
+    });
+    instance.add_host_function("env", "log", i64_to_void(), move |params: &[Value]| {
+        logged_by_host.borrow_mut().push(params[0]);
+        Vec::new()
+    });
+
+    let add_two = instance
+        .get_function_by_name(DEFAULT_MODULE, "add_two")
+        .unwrap();
+    assert_eq!(42, instance.invoke::<i32, i32>(&add_two, 40).unwrap());
+
+    let log_twice = instance
+        .get_function_by_name(DEFAULT_MODULE, "log_twice")
+        .unwrap();
+    instance.invoke::<i64, ()>(&log_twice, 7).unwrap();
+    assert_eq!(&[Value::I64(7), Value::I64(8)], logged.borrow().as_slice());
+}
+
+#[test_log::test]
+pub fn call_host_function_indirect() {
+    let wasm_bytes = wat::parse_str(HOST_IMPORT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    instance.add_host_function("env", "add_one", i32_to_i32(), |params: &[Value]| {
+        let x: i32 = params[0].into();
+        vec![Value::from(x + 1)]
+    });
+    instance.add_host_function("env", "log", i64_to_void(), |_: &[Value]| Vec::new());
+
+    let add_one_indirect = instance
+        .get_function_by_name(DEFAULT_MODULE, "add_one_indirect")
+        .unwrap();
+    assert_eq!(
+        -4,
+        instance.invoke::<i32, i32>(&add_one_indirect, -5).unwrap()
+    );
+}
+
+#[test_log::test]
+pub fn host_function_with_wrong_type_is_unmet() {
+    let wasm_bytes = wat::parse_str(HOST_IMPORT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    // `add_one` is imported as `[i32] -> [i32]`, so this does not satisfy the import
+    instance.add_host_function("env", "add_one", i64_to_void(), |_: &[Value]| Vec::new());
+    instance.add_host_function("env", "log", i64_to_void(), |_: &[Value]| Vec::new());
+
+    let add_two = instance
+        .get_function_by_name(DEFAULT_MODULE, "add_two")
+        .unwrap();
+    assert_eq!(
+        RuntimeError::UnmetImport,
+        instance.invoke::<i32, i32>(&add_two, 40).unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn host_function_returning_wrong_values() {
+    let wasm_bytes = wat::parse_str(HOST_IMPORT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    instance.add_host_function("env", "add_one", i32_to_i32(), |_: &[Value]| {
+        vec![Value::I64(1)]
+    });
+    instance.add_host_function("env", "log", i64_to_void(), |_: &[Value]| Vec::new());
+
+    let add_two = instance
+        .get_function_by_name(DEFAULT_MODULE, "add_two")
+        .unwrap();
+    assert_eq!(
+        RuntimeError::HostFunctionSignatureMismatch,
+        instance.invoke::<i32, i32>(&add_two, 40).unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn module_exports_take_precedence_over_host_functions() {
+    const ADDON: &str = r#"
+    (module
+        (func (export "add_one") (param i32) (result i32)
+            local.get 0
+            i32.const 100
+            i32.add
+        )
+    )"#;
+
+    let wasm_bytes = wat::parse_str(HOST_IMPORT).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    instance.add_host_function("env", "add_one", i32_to_i32(), |params: &[Value]| {
+        let x: i32 = params[0].into();
+        vec![Value::from(x + 1)]
+    });
+    instance.add_host_function("env", "log", i64_to_void(), |_: &[Value]| Vec::new());
+
+    let addon_bytes = wat::parse_str(ADDON).unwrap();
+    let addon_validation_info = validate(&addon_bytes).expect("validation failed");
+    instance
+        .add_module("env", &addon_validation_info)
+        .expect("instantiation failed");
+
+    let add_two = instance
+        .get_function_by_name(DEFAULT_MODULE, "add_two")
+        .unwrap();
+    assert_eq!(200, instance.invoke::<i32, i32>(&add_two, 0).unwrap());
+}
@@ -148,3 +148,57 @@ pub fn run_cyclical() {
     // Currently, this passes since we don't allow chained imports.
     assert!(instance.invoke::<(), i32>(&run, ()).unwrap_err() == wasm::RuntimeError::UnmetImport);
 }
+
+#[test_log::test]
+pub fn incompatible_function_import() {
+    use wasm::{Error, StoreInstantiationError};
+
+    let incompatible_imports = [
+        // the parameters differ
+        r#"(module (import "env" "get_one" (func (param i32) (result i32))))"#,
+        // the results differ
+        r#"(module (import "env" "get_one" (func (result i64))))"#,
+    ]
+    .map(|wat| wat::parse_str(wat).unwrap());
+
+    let wasm_bytes = wat::parse_str(SIMPLE_IMPORT_ADDON).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_named("env", &validation_info).expect("instantiation failed");
+
+    for wasm_bytes in &incompatible_imports {
+        let validation_info = validate(wasm_bytes).expect("validation failed");
+        assert_eq!(
+            Error::StoreInstantiationError(StoreInstantiationError::IncompatibleImportType(
+                "env".to_owned(),
+                "get_one".to_owned()
+            )),
+            instance
+                .add_module(DEFAULT_MODULE, &validation_info)
+                .unwrap_err()
+        );
+    }
+
+    // an import of a module added later is not linked to an export of a different type
+    let wasm_bytes = wat::parse_str(
+        r#"(module
+            (import "env" "get_one" (func $get_one (result i64)))
+            (func (export "get_one") (result i64) call $get_one)
+        )"#,
+    )
+    .unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_named("base", &validation_info).expect("instantiation failed");
+    let wasm_bytes = wat::parse_str(SIMPLE_IMPORT_ADDON).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    instance
+        .add_module("env", &validation_info)
+        .expect("instantiation failed");
+
+    let get_one = instance.get_function_by_name("base", "get_one").unwrap();
+    assert_eq!(
+        RuntimeError::UnmetImport,
+        instance.invoke::<(), i64>(&get_one, ()).unwrap_err()
+    );
+}
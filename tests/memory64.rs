@@ -0,0 +1,204 @@
+use wasm::{validate, Error, RuntimeError, RuntimeInstance, DEFAULT_MODULE};
+
+const LOGGER: &str = r#"
+(module
+    (memory $log i64 1 4)
+    (memory $scratch 1)
+    (data (memory $log) (i64.const 16) "\2a")
+
+    (func (export "load") (param i64) (result i64)
+        (i64.load (local.get 0))
+    )
+    (func (export "store") (param i64 i64)
+        (i64.store (local.get 0) (local.get 1))
+    )
+    (func (export "load_far") (result i32)
+        (i32.load offset=0x100000000 (i64.const 0))
+    )
+    (func (export "size") (result i64)
+        memory.size
+    )
+    (func (export "grow") (param i64) (result i64)
+        (memory.grow (local.get 0))
+    )
+    (func (export "fill") (param i64 i32 i64)
+        (memory.fill (local.get 0) (local.get 1) (local.get 2))
+    )
+    (func (export "copy_to_scratch") (param i32 i64 i32)
+        (memory.copy $scratch $log (local.get 0) (local.get 1) (local.get 2))
+    )
+    (func (export "load_scratch") (param i32) (result i32)
+        (i32.load8_u $scratch (local.get 0))
+    )
+)"#;
+
+#[test_log::test]
+pub fn load_and_store_with_64_bit_addresses() {
+    let wasm_bytes = wat::parse_str(LOGGER).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let load = instance
+        .get_function_by_name(DEFAULT_MODULE, "load")
+        .unwrap();
+    let store = instance
+        .get_function_by_name(DEFAULT_MODULE, "store")
+        .unwrap();
+    let load_far = instance
+        .get_function_by_name(DEFAULT_MODULE, "load_far")
+        .unwrap();
+
+    // the active data segment uses a 64-bit offset
+    assert_eq!(42, instance.invoke::<i64, i64>(&load, 16).unwrap());
+
+    instance
+        .invoke::<(i64, i64), ()>(&store, (65528, -1))
+        .unwrap();
+    assert_eq!(-1, instance.invoke::<i64, i64>(&load, 65528).unwrap());
+
+    // addresses beyond 4GiB are not truncated
+    assert_eq!(
+        RuntimeError::MemoryAccessOutOfBounds,
+        instance.invoke::<i64, i64>(&load, 1 << 32).unwrap_err()
+    );
+    assert_eq!(
+        RuntimeError::MemoryAccessOutOfBounds,
+        instance.invoke::<(), i32>(&load_far, ()).unwrap_err()
+    );
+    // neither do they wrap around
+    assert_eq!(
+        RuntimeError::MemoryAccessOutOfBounds,
+        instance.invoke::<i64, i64>(&load, -8).unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn memory_size_and_grow() {
+    let wasm_bytes = wat::parse_str(LOGGER).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let size = instance
+        .get_function_by_name(DEFAULT_MODULE, "size")
+        .unwrap();
+    let grow = instance
+        .get_function_by_name(DEFAULT_MODULE, "grow")
+        .unwrap();
+    let store = instance
+        .get_function_by_name(DEFAULT_MODULE, "store")
+        .unwrap();
+
+    assert_eq!(1, instance.invoke::<(), i64>(&size, ()).unwrap());
+    assert_eq!(1, instance.invoke::<i64, i64>(&grow, 3).unwrap());
+    assert_eq!(4, instance.invoke::<(), i64>(&size, ()).unwrap());
+    assert_eq!(-1, instance.invoke::<i64, i64>(&grow, 1).unwrap());
+    assert_eq!(-1, instance.invoke::<i64, i64>(&grow, 1 << 40).unwrap());
+
+    instance
+        .invoke::<(i64, i64), ()>(&store, (4 * 65536 - 8, 1))
+        .unwrap();
+}
+
+#[test_log::test]
+pub fn growing_is_bound_by_the_host() {
+    let wasm_bytes = wat::parse_str(
+        r#"
+        (module
+            (memory i64 0)
+            (func (export "grow") (param i64) (result i64)
+                (memory.grow (local.get 0))
+            )
+        )"#,
+    )
+    .unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let grow = instance
+        .get_function_by_name(DEFAULT_MODULE, "grow")
+        .unwrap();
+
+    // 2^56 bytes are allowed by the memory type, but no host can provide them
+    assert_eq!(-1, instance.invoke::<i64, i64>(&grow, 1 << 40).unwrap());
+    assert_eq!(0, instance.invoke::<i64, i64>(&grow, 1).unwrap());
+}
+
+#[test_log::test]
+pub fn oversized_memory_fails_to_instantiate() {
+    use wasm::StoreInstantiationError;
+
+    // 2^48 bytes exceed the address space of any host, 2^64 bytes even the size of a `usize`
+    for pages in [1 << 32, 1 << 48] {
+        let wasm_bytes = wat::parse_str(format!("(module (memory i64 {pages}))")).unwrap();
+        let validation_info = validate(&wasm_bytes).expect("validation failed");
+        assert_eq!(
+            Error::StoreInstantiationError(StoreInstantiationError::OutOfMemory(pages)),
+            RuntimeInstance::new(&validation_info).err().unwrap()
+        );
+    }
+}
+
+#[test_log::test]
+pub fn bulk_instructions() {
+    let wasm_bytes = wat::parse_str(LOGGER).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let load = instance
+        .get_function_by_name(DEFAULT_MODULE, "load")
+        .unwrap();
+    let fill = instance
+        .get_function_by_name(DEFAULT_MODULE, "fill")
+        .unwrap();
+    let copy_to_scratch = instance
+        .get_function_by_name(DEFAULT_MODULE, "copy_to_scratch")
+        .unwrap();
+    let load_scratch = instance
+        .get_function_by_name(DEFAULT_MODULE, "load_scratch")
+        .unwrap();
+
+    instance
+        .invoke::<(i64, i32, i64), ()>(&fill, (32, 0x11, 8))
+        .unwrap();
+    assert_eq!(
+        0x1111_1111_1111_1111,
+        instance.invoke::<i64, i64>(&load, 32).unwrap()
+    );
+    assert_eq!(
+        RuntimeError::MemoryAccessOutOfBounds,
+        instance
+            .invoke::<(i64, i32, i64), ()>(&fill, (0, 0, 1 << 32))
+            .unwrap_err()
+    );
+
+    // copying between a 32-bit and a 64-bit memory uses a 32-bit count
+    instance
+        .invoke::<(i32, i64, i32), ()>(&copy_to_scratch, (0, 16, 1))
+        .unwrap();
+    assert_eq!(42, instance.invoke::<i32, i32>(&load_scratch, 0).unwrap());
+}
+
+#[test_log::test]
+pub fn invalid_addresses() {
+    let invalid_modules = [
+        // 32-bit addresses for a 64-bit memory
+        r#"(module (memory i64 1) (func (drop (i32.load (i32.const 0)))))"#,
+        r#"(module (memory i64 1) (data (i32.const 0) ""))"#,
+        // 64-bit addresses for a 32-bit memory
+        r#"(module (memory 1) (func (drop (memory.grow (i64.const 1)))))"#,
+    ];
+
+    for wat in invalid_modules {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        assert!(validate(&wasm_bytes).is_err());
+    }
+
+    let wasm_bytes = wat::parse_str(
+        r#"(module (memory 1) (func (drop (i32.load offset=0x100000000 (i32.const 0)))))"#,
+    )
+    .unwrap();
+    assert_eq!(
+        Some(Error::MemArgOffsetTooBig(1 << 32)),
+        validate(&wasm_bytes).err()
+    );
+}
@@ -32,8 +32,9 @@ macro_rules! assert_result {
 
 macro_rules! assert_error {
     ($instance:expr, $func_name:expr, $arg:expr, $ret_type:ty, $invoke_param_type:ty, $invoke_return_type:ty, $err_type:expr) => {
-        let val: $ret_type =
-            $instance.invoke::<$invoke_param_type, $invoke_return_type>($func_name, $arg);
+        let val: $ret_type = $instance
+            .invoke::<$invoke_param_type, $invoke_return_type>($func_name, $arg)
+            .map_err(RuntimeError::from);
         assert!(val.is_err());
         assert!(val.unwrap_err() == $err_type);
     };
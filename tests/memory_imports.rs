@@ -0,0 +1,116 @@
+use wasm::{validate, Error, RuntimeInstance, StoreInstantiationError};
+
+const CORE: &str = r#"
+(module
+    (memory (export "heap") 1 4)
+    (func (export "load") (param i32) (result i32)
+        local.get 0
+        i32.load
+    )
+    (func (export "size") (result i32)
+        memory.size
+    )
+)"#;
+
+const PLUGIN: &str = r#"
+(module
+    (import "core" "heap" (memory 1))
+    (data (i32.const 8) "\2a")
+    (func (export "store") (param i32 i32)
+        local.get 0
+        local.get 1
+        i32.store
+    )
+    (func (export "grow") (param i32) (result i32)
+        local.get 0
+        memory.grow
+    )
+)"#;
+
+fn instantiate_core(core_bytes: &[u8]) -> RuntimeInstance<'_> {
+    let validation_info = validate(core_bytes).expect("validation failed");
+    RuntimeInstance::new_named("core", &validation_info).expect("instantiation failed")
+}
+
+#[test_log::test]
+pub fn imported_memory_is_shared() {
+    let core_bytes = wat::parse_str(CORE).unwrap();
+    let mut instance = instantiate_core(&core_bytes);
+
+    let plugin_bytes = wat::parse_str(PLUGIN).unwrap();
+    let plugin_validation_info = validate(&plugin_bytes).expect("validation failed");
+    instance
+        .add_module("plugin", &plugin_validation_info)
+        .expect("instantiation failed");
+
+    let load = instance.get_function_by_name("core", "load").unwrap();
+    let size = instance.get_function_by_name("core", "size").unwrap();
+    let store = instance.get_function_by_name("plugin", "store").unwrap();
+    let grow = instance.get_function_by_name("plugin", "grow").unwrap();
+
+    // the plugin's data segment was written to the core's memory
+    assert_eq!(42, instance.invoke::<i32, i32>(&load, 8).unwrap());
+
+    instance
+        .invoke::<(i32, i32), ()>(&store, (16, 1337))
+        .unwrap();
+    assert_eq!(1337, instance.invoke::<i32, i32>(&load, 16).unwrap());
+
+    // growing is bound by the exporter's limits, even though the import does not declare a maximum
+    assert_eq!(1, instance.invoke::<i32, i32>(&grow, 3).unwrap());
+    assert_eq!(4, instance.invoke::<(), i32>(&size, ()).unwrap());
+    assert_eq!(-1, instance.invoke::<i32, i32>(&grow, 1).unwrap());
+}
+
+#[test_log::test]
+pub fn unknown_memory_import() {
+    let core_bytes = wat::parse_str(CORE).unwrap();
+    let mut instance = instantiate_core(&core_bytes);
+
+    let plugin_bytes = wat::parse_str(r#"(module (import "core" "stack" (memory 1)))"#).unwrap();
+    let plugin_validation_info = validate(&plugin_bytes).expect("validation failed");
+    assert_eq!(
+        Error::StoreInstantiationError(StoreInstantiationError::UnknownImport(
+            "core".to_owned(),
+            "stack".to_owned()
+        )),
+        instance
+            .add_module("plugin", &plugin_validation_info)
+            .unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn incompatible_memory_import() {
+    let incompatible_imports = [
+        // the memory is smaller than required
+        ("heap", r#"(module (import "core" "heap" (memory 2)))"#),
+        // the memory may grow larger than allowed
+        ("heap", r#"(module (import "core" "heap" (memory 1 2)))"#),
+        // not a memory at all
+        ("load", r#"(module (import "core" "load" (memory 1)))"#),
+    ]
+    .map(|(name, plugin)| (name, wat::parse_str(plugin).unwrap()));
+
+    let core_bytes = wat::parse_str(CORE).unwrap();
+    let mut instance = instantiate_core(&core_bytes);
+
+    for (name, plugin_bytes) in &incompatible_imports {
+        let plugin_validation_info = validate(plugin_bytes).expect("validation failed");
+        assert_eq!(
+            Error::StoreInstantiationError(StoreInstantiationError::IncompatibleImportType(
+                "core".to_owned(),
+                name.to_string()
+            )),
+            instance
+                .add_module("plugin", &plugin_validation_info)
+                .unwrap_err()
+        );
+    }
+
+    let plugin_bytes = wat::parse_str(r#"(module (import "core" "heap" (memory 0 8)))"#).unwrap();
+    let plugin_validation_info = validate(&plugin_bytes).expect("validation failed");
+    instance
+        .add_module("plugin", &plugin_validation_info)
+        .expect("instantiation failed");
+}
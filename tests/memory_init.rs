@@ -33,8 +33,9 @@ macro_rules! assert_result {
 
 macro_rules! assert_error {
     ($instance:expr, $func:expr, $arg:expr, $ret_type:ty, $invoke_param_type:ty, $invoke_return_type:ty, $err_type:expr) => {
-        let val: $ret_type =
-            $instance.invoke::<$invoke_param_type, $invoke_return_type>($func, $arg);
+        let val: $ret_type = $instance
+            .invoke::<$invoke_param_type, $invoke_return_type>($func, $arg)
+            .map_err(RuntimeError::from);
         assert!(val.is_err());
         assert!(val.unwrap_err() == $err_type);
     };
@@ -0,0 +1,188 @@
+use wasm::{validate, Error, RuntimeError, RuntimeInstance, DEFAULT_MODULE};
+
+const DMA: &str = r#"
+(module
+    (memory $heap 1)
+    (memory $dma 1 2)
+    (data (memory $heap) (i32.const 0) "\01\02\03\04")
+    (data (memory $dma) (i32.const 0) "\2a")
+    (data $packet "\de\ad\be\ef")
+
+    (func (export "load_heap") (param i32) (result i32)
+        (i32.load8_u $heap (local.get 0))
+    )
+    (func (export "load_dma") (param i32) (result i32)
+        (i32.load8_u $dma (local.get 0))
+    )
+    (func (export "store_dma") (param i32 i64)
+        (i64.store $dma (local.get 0) (local.get 1))
+    )
+    (func (export "load_dma_i64") (param i32) (result i64)
+        (i64.load $dma (local.get 0))
+    )
+    (func (export "receive") (param i32)
+        (memory.init $dma $packet (local.get 0) (i32.const 0) (i32.const 4))
+    )
+    (func (export "copy_to_heap") (param i32 i32 i32)
+        (memory.copy $heap $dma (local.get 0) (local.get 1) (local.get 2))
+    )
+    (func (export "clear_dma") (param i32 i32)
+        (memory.fill $dma (local.get 0) (i32.const 0) (local.get 1))
+    )
+    (func (export "grow_dma") (param i32) (result i32)
+        (memory.grow $dma (local.get 0))
+    )
+    (func (export "sizes") (result i32 i32)
+        (memory.size $heap)
+        (memory.size $dma)
+    )
+)"#;
+
+#[test_log::test]
+pub fn memories_are_isolated() {
+    let wasm_bytes = wat::parse_str(DMA).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let load_heap = instance
+        .get_function_by_name(DEFAULT_MODULE, "load_heap")
+        .unwrap();
+    let load_dma = instance
+        .get_function_by_name(DEFAULT_MODULE, "load_dma")
+        .unwrap();
+    let store_dma = instance
+        .get_function_by_name(DEFAULT_MODULE, "store_dma")
+        .unwrap();
+    let load_dma_i64 = instance
+        .get_function_by_name(DEFAULT_MODULE, "load_dma_i64")
+        .unwrap();
+
+    // active data segments were written to their respective memory
+    assert_eq!(1, instance.invoke::<i32, i32>(&load_heap, 0).unwrap());
+    assert_eq!(42, instance.invoke::<i32, i32>(&load_dma, 0).unwrap());
+    assert_eq!(0, instance.invoke::<i32, i32>(&load_dma, 1).unwrap());
+
+    instance
+        .invoke::<(i32, i64), ()>(&store_dma, (8, 0x1122_3344_5566_7788))
+        .unwrap();
+    assert_eq!(
+        0x1122_3344_5566_7788,
+        instance.invoke::<i32, i64>(&load_dma_i64, 8).unwrap()
+    );
+    assert_eq!(0, instance.invoke::<i32, i32>(&load_heap, 8).unwrap());
+}
+
+#[test_log::test]
+pub fn bulk_instructions_on_multiple_memories() {
+    let wasm_bytes = wat::parse_str(DMA).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let load_heap = instance
+        .get_function_by_name(DEFAULT_MODULE, "load_heap")
+        .unwrap();
+    let load_dma = instance
+        .get_function_by_name(DEFAULT_MODULE, "load_dma")
+        .unwrap();
+    let receive = instance
+        .get_function_by_name(DEFAULT_MODULE, "receive")
+        .unwrap();
+    let copy_to_heap = instance
+        .get_function_by_name(DEFAULT_MODULE, "copy_to_heap")
+        .unwrap();
+    let clear_dma = instance
+        .get_function_by_name(DEFAULT_MODULE, "clear_dma")
+        .unwrap();
+
+    instance.invoke::<i32, ()>(&receive, 16).unwrap();
+    assert_eq!(0xad, instance.invoke::<i32, i32>(&load_dma, 17).unwrap());
+
+    instance
+        .invoke::<(i32, i32, i32), ()>(&copy_to_heap, (100, 16, 4))
+        .unwrap();
+    instance
+        .invoke::<(i32, i32), ()>(&clear_dma, (16, 4))
+        .unwrap();
+    assert_eq!(0xef, instance.invoke::<i32, i32>(&load_heap, 103).unwrap());
+    assert_eq!(0, instance.invoke::<i32, i32>(&load_dma, 19).unwrap());
+
+    // bounds are checked against the memory actually accessed
+    assert_eq!(
+        RuntimeError::MemoryAccessOutOfBounds,
+        instance
+            .invoke::<(i32, i32, i32), ()>(&copy_to_heap, (0, 65534, 4))
+            .unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn memory_size_and_grow() {
+    let wasm_bytes = wat::parse_str(DMA).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let grow_dma = instance
+        .get_function_by_name(DEFAULT_MODULE, "grow_dma")
+        .unwrap();
+    let sizes = instance
+        .get_function_by_name(DEFAULT_MODULE, "sizes")
+        .unwrap();
+
+    assert_eq!(
+        (1, 1),
+        instance.invoke::<(), (i32, i32)>(&sizes, ()).unwrap()
+    );
+    assert_eq!(1, instance.invoke::<i32, i32>(&grow_dma, 1).unwrap());
+    assert_eq!(-1, instance.invoke::<i32, i32>(&grow_dma, 1).unwrap());
+    assert_eq!(
+        (1, 2),
+        instance.invoke::<(), (i32, i32)>(&sizes, ()).unwrap()
+    );
+}
+
+#[test_log::test]
+pub fn imported_memory_next_to_local_memory() {
+    let core_bytes = wat::parse_str(r#"(module (memory (export "heap") 1))"#).unwrap();
+    let driver_bytes = wat::parse_str(
+        r#"
+        (module
+            (import "core" "heap" (memory $heap 1))
+            (memory $dma 1)
+            (data (memory $dma) (i32.const 0) "\07")
+            (func (export "forward") (result i32)
+                (i32.store8 $heap (i32.const 0) (i32.load8_u $dma (i32.const 0)))
+                (i32.load8_u $heap (i32.const 0))
+            )
+        )"#,
+    )
+    .unwrap();
+
+    let core_validation_info = validate(&core_bytes).expect("validation failed");
+    let driver_validation_info = validate(&driver_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_named("core", &core_validation_info).expect("instantiation failed");
+    instance
+        .add_module("driver", &driver_validation_info)
+        .expect("instantiation failed");
+
+    let forward = instance.get_function_by_name("driver", "forward").unwrap();
+    assert_eq!(7, instance.invoke::<(), i32>(&forward, ()).unwrap());
+}
+
+#[test_log::test]
+pub fn undefined_memory_index() {
+    let invalid_modules = [
+        r#"(module (memory 1) (func (drop (i32.load 1 (i32.const 0)))))"#,
+        r#"(module (memory 1) (func (drop (memory.size 1))))"#,
+        r#"(module (memory 1) (func (memory.copy 0 1 (i32.const 0) (i32.const 0) (i32.const 0))))"#,
+        r#"(module (memory 1) (data (memory 1) (i32.const 0) ""))"#,
+    ];
+
+    for wat in invalid_modules {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        assert_eq!(
+            Some(Error::MemoryIsNotDefined(1)),
+            validate(&wasm_bytes).err()
+        );
+    }
+}
@@ -0,0 +1,115 @@
+use wasm::{
+    profiler::SamplingProfiler,
+    resumable::{InvocationState, Resumable, SuspendReason},
+    validate, RuntimeInstance, Value, DEFAULT_MODULE,
+};
+
+/// `$square` executes 4 instructions, `$sum_of_squares` 9 without the ones of its callees
+const SUM_OF_SQUARES: &str = r#"
+(module
+    (memory 1)
+    (func $square (param $x i32) (result i32)
+        (i32.mul (local.get $x) (local.get $x))
+    )
+    (func $sum_of_squares (export "sum_of_squares") (param $a i32) (param $b i32) (result i32)
+        (i32.store (i32.const 0) (local.get $a))
+        (i32.add (call $square (local.get $a)) (call $square (local.get $b)))
+    )
+    (func (export "clear")
+        (memory.fill (i32.const 0) (i32.const 0) (i32.const 16))
+    )
+)"#;
+
+/// Start an invocation which is suspended before its first instruction
+fn suspended_invocation(
+    instance: &mut RuntimeInstance,
+    name: &str,
+    params: Vec<Value>,
+) -> Resumable {
+    let function = instance.get_function_by_name(DEFAULT_MODULE, name).unwrap();
+    let state = instance
+        .invoke_resumable(&function, params, Some(0))
+        .unwrap();
+    let InvocationState::Suspended(resumable) = state else {
+        panic!("expected the invocation to be suspended");
+    };
+    resumable
+}
+
+#[cfg(feature = "hooks")]
+#[test_log::test]
+pub fn instrumenting_profiler() {
+    use wasm::profiler::Profiler;
+
+    let wasm_bytes = wat::parse_str(SUM_OF_SQUARES).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_with_hooks(DEFAULT_MODULE, &validation_info, Profiler::default())
+            .expect("instantiation failed");
+    let sum_of_squares = instance
+        .get_function_by_name(DEFAULT_MODULE, "sum_of_squares")
+        .unwrap();
+
+    for _ in 0..2 {
+        assert_eq!(
+            Ok(25),
+            instance.invoke::<(i32, i32), i32>(&sum_of_squares, (3, 4))
+        );
+    }
+
+    let profile = instance.hook_set.profile();
+    assert_eq!(
+        vec![(&[(0, 1)][..], 18), (&[(0, 1), (0, 0)][..], 16)],
+        profile.stacks().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "sum_of_squares 18\nsum_of_squares;square 16\n",
+        profile.folded(&instance)
+    );
+}
+
+#[test_log::test]
+pub fn sampling_profiler() {
+    let wasm_bytes = wat::parse_str(SUM_OF_SQUARES).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let mut profiler = SamplingProfiler::new(1);
+
+    // the fuel of the invocation still bounds its execution
+    let mut resumable = suspended_invocation(
+        &mut instance,
+        "sum_of_squares",
+        vec![Value::I32(3), Value::I32(4)],
+    );
+    resumable.set_fuel(Some(5));
+    let InvocationState::Suspended(mut resumable) =
+        profiler.resume(&mut instance, resumable).unwrap()
+    else {
+        panic!("expected the invocation to run out of fuel");
+    };
+    assert_eq!(Some(SuspendReason::OutOfFuel), resumable.suspend_reason());
+    assert_eq!(Some(0), resumable.fuel());
+    assert_eq!(5, profiler.profile().total());
+
+    // every instruction but the last one is sampled
+    resumable.set_fuel(None);
+    let InvocationState::Finished(values) = profiler.resume(&mut instance, resumable).unwrap()
+    else {
+        panic!("expected the invocation to finish");
+    };
+    assert_eq!(vec![Value::I32(25)], values);
+    assert_eq!(16, profiler.profile().total());
+    let folded = profiler.profile().folded(&instance);
+    assert!(folded.contains("sum_of_squares;square "));
+
+    // instructions which cost more fuel than the interval are executed nonetheless, in a slice of the fuel they require
+    let mut resumable = suspended_invocation(&mut instance, "clear", Vec::new());
+    resumable.set_fuel(None);
+    let mut profiler = SamplingProfiler::new(1);
+    assert!(matches!(
+        profiler.resume(&mut instance, resumable),
+        Ok(InvocationState::Finished(_))
+    ));
+    // the three constants and the fill of 16 bytes are sampled, the final end is not
+    assert_eq!(3 + 17, profiler.profile().total());
+}
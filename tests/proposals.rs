@@ -0,0 +1,165 @@
+use wasm::opcode::{self, Immediates, Opcode};
+use wasm::{validate, Error, NumType, Proposal, ProposalSet, ValType};
+
+#[test_log::test]
+pub fn opcode_registry() {
+    // the registry is sorted, which is required for looking up opcodes
+    let opcodes: Vec<Opcode> = opcode::all_opcodes().map(|info| info.opcode).collect();
+    assert!(opcodes.windows(2).all(|pair| pair[0] < pair[1]));
+    for opcode in opcodes {
+        assert_eq!(Some(opcode), opcode.info().map(|info| info.opcode));
+    }
+
+    let i32_add = Opcode::Single(opcode::I32_ADD).info().unwrap();
+    assert_eq!("i32.add", i32_add.name);
+    assert_eq!(Immediates::None, i32_add.immediates);
+    assert_eq!(None, i32_add.proposal);
+    let signature = i32_add.signature.unwrap();
+    assert_eq!(&[ValType::NumType(NumType::I32); 2], signature.params);
+    assert_eq!(&[ValType::NumType(NumType::I32)], signature.results);
+
+    let call = Opcode::Single(opcode::CALL).info().unwrap();
+    assert_eq!(Immediates::FuncIdx, call.immediates);
+    assert_eq!(None, call.signature);
+
+    let memory_copy = Opcode::Prefixed(
+        opcode::FC_EXTENSIONS,
+        opcode::fc_extensions::MEMORY_COPY.into(),
+    );
+    let memory_copy = memory_copy.info().unwrap();
+    assert_eq!("memory.copy", memory_copy.name);
+    assert_eq!(Some(Proposal::BulkMemory), memory_copy.proposal);
+
+    let rmw = Opcode::Prefixed(
+        opcode::FE_EXTENSIONS,
+        opcode::fe_extensions::I64_ATOMIC_RMW16_XCHG_U,
+    );
+    assert_eq!("i64.atomic.rmw16.xchg_u", rmw.info().unwrap().name);
+
+    assert_eq!(None, Opcode::Single(0x06).info());
+    assert_eq!(None, Opcode::Prefixed(opcode::FD_EXTENSIONS, 0x1000).info());
+}
+
+#[test_log::test]
+pub fn used_proposals() {
+    let wasm_bytes = wat::parse_str(
+        r#"
+        (module
+            (memory 1)
+            (func $f (param i32) (result i32)
+                (i32.extend8_s (local.get 0))
+            )
+            (func (param v128) (result i32)
+                (memory.fill (i32.const 0) (i32.const 0) (i32.const 1))
+                (return_call $f (i32x4.extract_lane 0 (local.get 0)))
+            )
+        )"#,
+    )
+    .unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let expected: ProposalSet = [
+        Proposal::SignExtension,
+        Proposal::BulkMemory,
+        Proposal::Simd,
+        Proposal::TailCall,
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(expected, validation_info.used_proposals());
+
+    assert_eq!(Ok(()), validation_info.check_proposals(ProposalSet::all()));
+    assert_eq!(Ok(()), validation_info.check_proposals(expected));
+    let mut without_simd = expected;
+    without_simd.remove(Proposal::Simd);
+    assert_eq!(
+        Err(Error::UnsupportedProposal(Proposal::Simd)),
+        validation_info.check_proposals(without_simd)
+    );
+
+    // proposals are also detected from the types of a module
+    let wasm_bytes = wat::parse_str(
+        r#"
+        (module
+            (memory i64 1)
+            (memory 1 1 shared)
+            (table 1 externref)
+        )"#,
+    )
+    .unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let used: Vec<Proposal> = validation_info.used_proposals().iter().collect();
+    assert_eq!(
+        vec![
+            Proposal::ReferenceTypes,
+            Proposal::Threads,
+            Proposal::Memory64,
+            Proposal::MultiMemory
+        ],
+        used
+    );
+    assert_eq!(
+        Err(Error::UnsupportedProposal(Proposal::ReferenceTypes)),
+        validation_info.check_proposals(ProposalSet::empty())
+    );
+}
+
+#[test_log::test]
+pub fn proposals_of_segments_locals_and_block_types() {
+    let cases = [
+        // passive segments
+        (
+            r#"(module (memory 1) (data "x"))"#,
+            vec![Proposal::BulkMemory],
+        ),
+        (
+            r#"(module (func $f) (elem func $f))"#,
+            vec![Proposal::BulkMemory],
+        ),
+        // element expressions and declarative segments
+        (
+            r#"(module (table 1 funcref) (elem (i32.const 0) funcref (ref.null func)))"#,
+            vec![Proposal::ReferenceTypes],
+        ),
+        (
+            r#"(module (func $f) (elem declare func $f))"#,
+            vec![Proposal::ReferenceTypes],
+        ),
+        // extended constant expressions
+        (
+            r#"(module (global i32 (i32.add (i32.const 1) (i32.const 2))))"#,
+            vec![Proposal::ExtendedConst],
+        ),
+        (
+            r#"(module (memory 1) (data (i32.mul (i32.const 2) (i32.const 8)) "x"))"#,
+            vec![Proposal::ExtendedConst],
+        ),
+        // locals
+        (r#"(module (func (local v128)))"#, vec![Proposal::Simd]),
+        (
+            r#"(module (func (local externref)))"#,
+            vec![Proposal::ReferenceTypes],
+        ),
+        // multiple results of functions and blocks, and blocks with parameters
+        (
+            r#"(module (func (result i32 i32) (i32.const 1) (i32.const 2)))"#,
+            vec![Proposal::MultiValue],
+        ),
+        (
+            r#"(module (func (param i32) (result i32) (local.get 0) (block (param i32) (result i32))))"#,
+            vec![Proposal::MultiValue],
+        ),
+        // none of these are required by the MVP
+        (
+            r#"(module (memory 1) (table 1 funcref) (func $f (result i32) (block (result i32) (i32.const 0)))
+                (global i32 (i32.const 1)) (data (i32.const 0) "x") (elem (i32.const 0) $f))"#,
+            vec![],
+        ),
+    ];
+
+    for (wat, expected) in cases {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        let validation_info = validate(&wasm_bytes).expect("validation failed");
+        let used: Vec<Proposal> = validation_info.used_proposals().iter().collect();
+        assert_eq!(expected, used, "{wat}");
+    }
+}
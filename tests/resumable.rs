@@ -0,0 +1,243 @@
+use wasm::{
+    host_function::HostCallOutcome,
+    resumable::{InvocationState, SuspendReason},
+    validate, FuncType, ResultType, RuntimeInstance, Value, DEFAULT_MODULE,
+};
+
+const FIBONACCI: &str = r#"
+(module
+    (func $fib (export "fib") (param $n i32) (result i32)
+        (if (result i32) (i32.lt_u (local.get $n) (i32.const 2))
+            (then (local.get $n))
+            (else
+                (i32.add
+                    (call $fib (i32.sub (local.get $n) (i32.const 1)))
+                    (call $fib (i32.sub (local.get $n) (i32.const 2)))
+                )
+            )
+        )
+    )
+)"#;
+
+const YIELDING: &str = r#"
+(module
+    (import "env" "yield" (func $yield))
+    (func (export "count_to") (param $n i32) (result i32)
+        (local $i i32)
+        (block $done
+            (loop $continue
+                (br_if $done (i32.ge_u (local.get $i) (local.get $n)))
+                (call $yield)
+                (local.set $i (i32.add (local.get $i) (i32.const 1)))
+                (br $continue)
+            )
+        )
+        local.get $i
+    )
+)"#;
+
+const MULTI_MODULE_BASE: &str = r#"
+(module
+    (import "addon" "sum_to" (func $sum_to (param i32) (result i32)))
+    (func (export "sum_to_twice") (param i32) (result i32)
+        (i32.add (call $sum_to (local.get 0)) (call $sum_to (local.get 0)))
+    )
+)"#;
+
+const MULTI_MODULE_ADDON: &str = r#"
+(module
+    (func $unused (result i32) i32.const 0)
+    (func (export "sum_to") (param $n i32) (result i32)
+        (local $sum i32)
+        (block $done
+            (loop $continue
+                (br_if $done (i32.eqz (local.get $n)))
+                (local.set $sum (i32.add (local.get $sum) (local.get $n)))
+                (local.set $n (i32.sub (local.get $n) (i32.const 1)))
+                (br $continue)
+            )
+        )
+        local.get $sum
+    )
+)"#;
+
+const BULK: &str = r#"
+(module
+    (memory 1)
+    (func (export "fill_and_load") (param i32) (result i32)
+        (memory.fill (i32.const 0) (i32.const 7) (local.get 0))
+        (i32.load8_u (i32.const 0))
+    )
+)"#;
+
+/// Keep resuming with `fuel_per_slice` until the invocation finishes, returning the results and the number of times
+/// execution was suspended
+fn run_in_slices(
+    instance: &mut RuntimeInstance,
+    mut state: InvocationState,
+    fuel_per_slice: u64,
+) -> (Vec<Value>, usize) {
+    let mut suspensions = 0;
+    loop {
+        match state {
+            InvocationState::Finished(values) => return (values, suspensions),
+            InvocationState::Suspended(mut resumable) => {
+                assert_eq!(Some(SuspendReason::OutOfFuel), resumable.suspend_reason());
+                suspensions += 1;
+                resumable.set_fuel(Some(fuel_per_slice));
+                state = resumable.resume(instance).unwrap();
+            }
+        }
+    }
+}
+
+#[test_log::test]
+pub fn resume_after_fuel_exhaustion() {
+    let wasm_bytes = wat::parse_str(FIBONACCI).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let fib = instance
+        .get_function_by_name(DEFAULT_MODULE, "fib")
+        .unwrap();
+
+    let state = instance
+        .invoke_resumable(&fib, vec![Value::I32(15)], Some(7))
+        .unwrap();
+    let (values, suspensions) = run_in_slices(&mut instance, state, 7);
+
+    assert_eq!(vec![Value::I32(610)], values);
+    assert!(suspensions > 100);
+
+    // the instance is still usable for normal invocations
+    assert_eq!(610, instance.invoke::<i32, i32>(&fib, 15).unwrap());
+}
+
+#[test_log::test]
+pub fn resume_without_fuel_bound() {
+    let wasm_bytes = wat::parse_str(FIBONACCI).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let fib = instance
+        .get_function_by_name(DEFAULT_MODULE, "fib")
+        .unwrap();
+
+    let InvocationState::Suspended(mut resumable) = instance
+        .invoke_resumable(&fib, vec![Value::I32(10)], Some(0))
+        .unwrap()
+    else {
+        panic!("expected the invocation to be suspended");
+    };
+    assert_eq!(Some(0), resumable.fuel());
+
+    resumable.set_fuel(None);
+    let InvocationState::Finished(values) = instance.resume(resumable).unwrap() else {
+        panic!("expected the invocation to finish");
+    };
+    assert_eq!(vec![Value::I32(55)], values);
+}
+
+#[test_log::test]
+pub fn resume_after_host_yield() {
+    let wasm_bytes = wat::parse_str(YIELDING).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let void_to_void = FuncType {
+        params: ResultType {
+            valtypes: Vec::new(),
+        },
+        returns: ResultType {
+            valtypes: Vec::new(),
+        },
+    };
+    instance.add_host_function("env", "yield", void_to_void, |_: &[Value]| {
+        HostCallOutcome::Yield(Vec::new())
+    });
+
+    let count_to = instance
+        .get_function_by_name(DEFAULT_MODULE, "count_to")
+        .unwrap();
+
+    let mut state = instance
+        .invoke_resumable(&count_to, vec![Value::I32(3)], None)
+        .unwrap();
+    let mut yields = 0;
+    let values = loop {
+        match state {
+            InvocationState::Finished(values) => break values,
+            InvocationState::Suspended(resumable) => {
+                assert_eq!(Some(SuspendReason::HostYield), resumable.suspend_reason());
+                yields += 1;
+                state = instance.resume(resumable).unwrap();
+            }
+        }
+    };
+    assert_eq!(3, yields);
+    assert_eq!(vec![Value::I32(3)], values);
+
+    // a normal invocation does not suspend on yields
+    assert_eq!(5, instance.invoke::<i32, i32>(&count_to, 5).unwrap());
+}
+
+#[test_log::test]
+pub fn resume_inside_imported_function() {
+    let base_bytes = wat::parse_str(MULTI_MODULE_BASE).unwrap();
+    let base_validation_info = validate(&base_bytes).expect("validation failed");
+    let addon_bytes = wat::parse_str(MULTI_MODULE_ADDON).unwrap();
+    let addon_validation_info = validate(&addon_bytes).expect("validation failed");
+
+    let mut instance = RuntimeInstance::new(&base_validation_info).expect("instantiation failed");
+    instance
+        .add_module("addon", &addon_validation_info)
+        .expect("instantiation failed");
+
+    let sum_to_twice = instance
+        .get_function_by_name(DEFAULT_MODULE, "sum_to_twice")
+        .unwrap();
+
+    let state = instance
+        .invoke_resumable(&sum_to_twice, vec![Value::I32(100)], Some(3))
+        .unwrap();
+    let (values, suspensions) = run_in_slices(&mut instance, state, 3);
+
+    assert_eq!(vec![Value::I32(10100)], values);
+    assert!(suspensions > 100);
+}
+
+#[test_log::test]
+pub fn resume_before_bulk_instruction() {
+    let wasm_bytes = wat::parse_str(BULK).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let fill_and_load = instance
+        .get_function_by_name(DEFAULT_MODULE, "fill_and_load")
+        .unwrap();
+
+    // enough for everything but the 1000 bytes of the fill
+    let InvocationState::Suspended(mut resumable) = instance
+        .invoke_resumable(&fill_and_load, vec![Value::I32(1000)], Some(10))
+        .unwrap()
+    else {
+        panic!("expected the invocation to be suspended");
+    };
+    // the fuel for the `memory.fill` itself was not consumed
+    assert_eq!(Some(7), resumable.fuel());
+    assert_eq!(Some(1001), resumable.required_fuel());
+
+    // the fill can not be executed before enough fuel is refilled
+    resumable.set_fuel(Some(1000));
+    let InvocationState::Suspended(mut resumable) = instance.resume(resumable).unwrap() else {
+        panic!("expected the invocation to be suspended");
+    };
+    assert_eq!(Some(1000), resumable.fuel());
+    assert_eq!(Some(1001), resumable.required_fuel());
+
+    resumable.set_fuel(Some(1004));
+    let InvocationState::Finished(values) = instance.resume(resumable).unwrap() else {
+        panic!("expected the invocation to finish");
+    };
+    assert_eq!(vec![Value::I32(7)], values);
+}
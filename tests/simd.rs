@@ -0,0 +1,309 @@
+use wasm::{validate, Error, RuntimeError, RuntimeInstance, DEFAULT_MODULE};
+
+const FILTER: &str = r#"
+(module
+    (memory 1)
+    ;; coefficients of a 4-tap FIR filter
+    (data (i32.const 0) "\00\00\80\3e\00\00\80\3e\00\00\80\3e\00\00\80\3e")
+
+    ;; out[i] = sum(coeffs[k] * in[i + k]) for 4 consecutive outputs, `in` and `out` are f32 arrays
+    (func (export "fir4") (param $in i32) (param $out i32)
+        (local $acc v128)
+        (local $k i32)
+        (loop $taps
+            (local.set $acc
+                (f32x4.add
+                    (local.get $acc)
+                    (f32x4.mul
+                        (v128.load (i32.add (local.get $in) (i32.shl (local.get $k) (i32.const 2))))
+                        (f32x4.splat (f32.load (i32.shl (local.get $k) (i32.const 2))))
+                    )
+                )
+            )
+            (local.set $k (i32.add (local.get $k) (i32.const 1)))
+            (br_if $taps (i32.lt_u (local.get $k) (i32.const 4)))
+        )
+        (v128.store (local.get $out) (local.get $acc))
+    )
+    (func (export "store_f32") (param i32 f32)
+        (f32.store (local.get 0) (local.get 1))
+    )
+    (func (export "load_f32") (param i32) (result f32)
+        (f32.load (local.get 0))
+    )
+    (func (export "load_v128") (param i32) (result v128)
+        (v128.load (local.get 0))
+    )
+    (func (export "load_lane") (param i32) (result v128)
+        (v128.load8_lane 15 (local.get 0) (v128.const i64x2 0 0))
+    )
+)"#;
+
+#[test_log::test]
+pub fn fir_filter() {
+    let wasm_bytes = wat::parse_str(FILTER).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let fir4 = instance
+        .get_function_by_name(DEFAULT_MODULE, "fir4")
+        .unwrap();
+    let store_f32 = instance
+        .get_function_by_name(DEFAULT_MODULE, "store_f32")
+        .unwrap();
+    let load_f32 = instance
+        .get_function_by_name(DEFAULT_MODULE, "load_f32")
+        .unwrap();
+
+    let input = [4.0_f32, 8.0, 12.0, 16.0, 20.0, 24.0, 28.0];
+    for (i, x) in input.into_iter().enumerate() {
+        instance
+            .invoke::<(i32, f32), ()>(&store_f32, (64 + 4 * i as i32, x))
+            .unwrap();
+    }
+
+    instance.invoke::<(i32, i32), ()>(&fir4, (64, 128)).unwrap();
+
+    // averaging four samples of a linear ramp
+    let output: Vec<f32> = (0..4)
+        .map(|i| instance.invoke::<i32, f32>(&load_f32, 128 + 4 * i).unwrap())
+        .collect();
+    assert_eq!(vec![10.0, 14.0, 18.0, 22.0], output);
+}
+
+#[test_log::test]
+pub fn memory_accesses() {
+    let wasm_bytes = wat::parse_str(FILTER).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let load_v128 = instance
+        .get_function_by_name(DEFAULT_MODULE, "load_v128")
+        .unwrap();
+    let load_lane = instance
+        .get_function_by_name(DEFAULT_MODULE, "load_lane")
+        .unwrap();
+
+    assert_eq!(
+        0x3e80_0000_3e80_0000_3e80_0000_3e80_0000,
+        instance.invoke::<i32, u128>(&load_v128, 0).unwrap()
+    );
+    assert_eq!(
+        0x3e00_0000_0000_0000_0000_0000_0000_0000,
+        instance.invoke::<i32, u128>(&load_lane, 3).unwrap()
+    );
+    assert_eq!(
+        RuntimeError::MemoryAccessOutOfBounds,
+        instance.invoke::<i32, u128>(&load_v128, 65521).unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn integer_lanes() {
+    let wasm_bytes = wat::parse_str(
+        r#"
+        (module
+            (func (export "add_sat") (param v128 v128) (result v128)
+                (i8x16.add_sat_s (local.get 0) (local.get 1))
+            )
+            (func (export "narrow") (param v128 v128) (result v128)
+                (i8x16.narrow_i16x8_u (local.get 0) (local.get 1))
+            )
+            (func (export "dot") (param v128 v128) (result v128)
+                (i32x4.dot_i16x8_s (local.get 0) (local.get 1))
+            )
+            (func (export "shuffle") (param v128 v128) (result v128)
+                (i8x16.shuffle 16 1 18 3 20 5 22 7 24 9 26 11 28 13 30 15 (local.get 0) (local.get 1))
+            )
+            (func (export "extract") (param v128) (result i32 i32 i64)
+                (i8x16.extract_lane_s 1 (local.get 0))
+                (i16x8.extract_lane_u 1 (local.get 0))
+                (i64x2.extract_lane 1 (local.get 0))
+            )
+            (func (export "bitmask") (param v128) (result i32 i32 i32)
+                (i32x4.bitmask (local.get 0))
+                (i32x4.all_true (local.get 0))
+                (v128.any_true (local.get 0))
+            )
+            (func (export "shr") (param v128 i32) (result v128)
+                (i16x8.shr_s (local.get 0) (local.get 1))
+            )
+        )"#,
+    )
+    .unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let add_sat = instance
+        .get_function_by_name(DEFAULT_MODULE, "add_sat")
+        .unwrap();
+    let narrow = instance
+        .get_function_by_name(DEFAULT_MODULE, "narrow")
+        .unwrap();
+    let dot = instance
+        .get_function_by_name(DEFAULT_MODULE, "dot")
+        .unwrap();
+    let shuffle = instance
+        .get_function_by_name(DEFAULT_MODULE, "shuffle")
+        .unwrap();
+    let extract = instance
+        .get_function_by_name(DEFAULT_MODULE, "extract")
+        .unwrap();
+    let bitmask = instance
+        .get_function_by_name(DEFAULT_MODULE, "bitmask")
+        .unwrap();
+    let shr = instance
+        .get_function_by_name(DEFAULT_MODULE, "shr")
+        .unwrap();
+
+    assert_eq!(
+        0x80_7f_02,
+        instance
+            .invoke::<(u128, u128), u128>(&add_sat, (0x80_70_01, 0xff_10_01))
+            .unwrap()
+    );
+    assert_eq!(
+        0xff00_ff00 << 96,
+        instance
+            .invoke::<(u128, u128), u128>(&narrow, (0, 0x0100_ff00_0fff_8000 << 64))
+            .unwrap()
+    );
+    // 1 * 3 + 2 * -1 and -32768 * -32768 * 2, which wraps around
+    assert_eq!(
+        0x8000_0000_0000_0001,
+        instance
+            .invoke::<(u128, u128), u128>(&dot, (0x8000_8000_0002_0001, 0x8000_8000_ffff_0003))
+            .unwrap()
+    );
+    assert_eq!(
+        0x0f1e_0d1c_0b1a_0918_0716_0514_0312_0110,
+        instance
+            .invoke::<(u128, u128), u128>(
+                &shuffle,
+                (
+                    0x0f0e_0d0c_0b0a_0908_0706_0504_0302_0100,
+                    0x1f1e_1d1c_1b1a_1918_1716_1514_1312_1110
+                )
+            )
+            .unwrap()
+    );
+    assert_eq!(
+        (-1, 0xffff, 0x1234),
+        instance
+            .invoke::<u128, (i32, i32, i64)>(&extract, 0x1234 << 64 | 0xffff_ff00)
+            .unwrap()
+    );
+    assert_eq!(
+        (0b1010, 1, 1),
+        instance
+            .invoke::<u128, (i32, i32, i32)>(&bitmask, 0x8000_0000_0000_0001_ffff_ffff_0000_0001)
+            .unwrap()
+    );
+    assert_eq!(
+        (0, 0, 0),
+        instance
+            .invoke::<u128, (i32, i32, i32)>(&bitmask, 0)
+            .unwrap()
+    );
+    // the shift amount is taken modulo the lane width
+    assert_eq!(
+        0xfff0_0001,
+        instance
+            .invoke::<(u128, i32), u128>(&shr, (0xff00_0010, 20))
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+pub fn float_lanes() {
+    let wasm_bytes = wat::parse_str(
+        r#"
+        (module
+            (global $bias v128 (v128.const f32x4 0.5 0.5 0.5 0.5))
+            (func (export "min_nearest") (param f32 f32) (result f32 f32)
+                (local $v v128)
+                (local.set $v
+                    (f32x4.min
+                        (f32x4.replace_lane 1 (f32x4.splat (local.get 0)) (local.get 1))
+                        (global.get $bias)
+                    )
+                )
+                (f32x4.extract_lane 1 (local.get $v))
+                (f32x4.extract_lane 0 (f32x4.nearest (f32x4.splat (local.get 0))))
+            )
+            (func (export "trunc_sat") (param f64 f64) (result v128)
+                (i32x4.trunc_sat_f64x2_s_zero (f64x2.replace_lane 1 (f64x2.splat (local.get 0)) (local.get 1)))
+            )
+            (func (export "compare") (param f32) (result v128)
+                (f32x4.ne (f32x4.splat (local.get 0)) (f32x4.splat (local.get 0)))
+            )
+        )"#,
+    )
+    .unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let min_nearest = instance
+        .get_function_by_name(DEFAULT_MODULE, "min_nearest")
+        .unwrap();
+    let trunc_sat = instance
+        .get_function_by_name(DEFAULT_MODULE, "trunc_sat")
+        .unwrap();
+    let compare = instance
+        .get_function_by_name(DEFAULT_MODULE, "compare")
+        .unwrap();
+
+    assert_eq!(
+        (-1.0, 2.0),
+        instance
+            .invoke::<(f32, f32), (f32, f32)>(&min_nearest, (2.5, -1.0))
+            .unwrap()
+    );
+    let (min, _) = instance
+        .invoke::<(f32, f32), (f32, f32)>(&min_nearest, (0.0, f32::NAN))
+        .unwrap();
+    assert!(min.is_nan());
+
+    // saturating, NaN turns into zero and the upper two lanes are zeroed
+    assert_eq!(
+        0x0000_0000_7fff_ffff,
+        instance
+            .invoke::<(f64, f64), u128>(&trunc_sat, (1e10, f64::NAN))
+            .unwrap()
+    );
+    assert_eq!(
+        u128::MAX,
+        instance.invoke::<f32, u128>(&compare, f32::NAN).unwrap()
+    );
+    assert_eq!(0, instance.invoke::<f32, u128>(&compare, 1.0).unwrap());
+}
+
+#[test_log::test]
+pub fn invalid_vector_instructions() {
+    let invalid_modules = [
+        // the operand is not a vector
+        r#"(module (func (drop (i32x4.add (i32.const 0) (v128.const i64x2 0 0)))))"#,
+        // the result is not a vector
+        r#"(module (func (result v128) (i8x16.all_true (v128.const i64x2 0 0))))"#,
+        // the alignment exceeds the width of the access
+        r#"(module (memory 1) (func (drop (v128.load8_splat align=2 (i32.const 0)))))"#,
+    ];
+
+    for wat in invalid_modules {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        assert!(validate(&wasm_bytes).is_err());
+    }
+
+    // `wat` rejects out-of-bounds lane indices, so the binary is patched instead
+    let mut wasm_bytes = wat::parse_str(
+        r#"(module (func (result i32) (i32x4.extract_lane 3 (v128.const i64x2 0 0))))"#,
+    )
+    .unwrap();
+    let lane_idx = wasm_bytes.len() - 2;
+    assert_eq!(3, wasm_bytes[lane_idx]);
+    wasm_bytes[lane_idx] = 4;
+    assert_eq!(
+        Some(Error::InvalidLaneIdx(4, 4)),
+        validate(&wasm_bytes).err()
+    );
+}
@@ -9,6 +9,7 @@ mod files;
 mod reports;
 mod run;
 mod test_errors;
+mod threads;
 
 #[test_log::test]
 pub fn spec_tests() {
@@ -1,8 +1,11 @@
 use std::error::Error;
 use std::panic::catch_unwind;
 use std::panic::AssertUnwindSafe;
+use std::sync::Arc;
+use std::thread::ScopedJoinHandle;
 
 use wasm::function_ref::FunctionRef;
+use wasm::threads::{SharedMemory, Waiter};
 use wasm::RuntimeError;
 use wasm::Value;
 use wasm::DEFAULT_MODULE;
@@ -14,6 +17,7 @@ use wast::WastArg;
 
 use crate::specification::reports::*;
 use crate::specification::test_errors::*;
+use crate::specification::threads::{run_thread, CondvarWaiter, ThreadResult};
 
 pub fn to_wasm_testsuite_string(runtime_error: RuntimeError) -> Result<String, Box<dyn Error>> {
     let not_represented = Err(GenericError::new_boxed(
@@ -24,7 +28,7 @@ pub fn to_wasm_testsuite_string(runtime_error: RuntimeError) -> Result<String, B
         RuntimeError::DivideBy0 => Ok("integer divide by zero"),
         RuntimeError::UnrepresentableResult => Ok("integer overflow"),
         RuntimeError::FunctionNotFound => not_represented,
-        RuntimeError::StackSmash => not_represented,
+        RuntimeError::StackSmash => Ok("call stack exhausted"),
         RuntimeError::BadConversionToInteger => Ok("invalid conversion to integer"),
 
         RuntimeError::MemoryAccessOutOfBounds => Ok("out of bounds memory access"),
@@ -38,6 +42,17 @@ pub fn to_wasm_testsuite_string(runtime_error: RuntimeError) -> Result<String, B
         RuntimeError::UndefinedTableIndex => Ok("undefined element"),
         RuntimeError::ModuleNotFound => Ok("module not found"),
         RuntimeError::UnmetImport => Ok("unmet import"),
+        RuntimeError::HostFunctionSignatureMismatch => not_represented,
+        RuntimeError::OutOfFuel => not_represented,
+        RuntimeError::UnalignedAtomic => Ok("unaligned atomic"),
+        RuntimeError::ExpectedSharedMemory => Ok("expected shared memory"),
+        RuntimeError::WaiterNotConfigured => not_represented,
+        RuntimeError::MemoryNotFound => not_represented,
+        RuntimeError::UncaughtException(_) => Ok("uncaught exception"),
+        RuntimeError::TagNotFound => not_represented,
+        RuntimeError::NullExceptionReference => Ok("null exception reference"),
+        RuntimeError::ReclaimedExceptionReference => not_represented,
+        RuntimeError::HookTrap(_) => not_represented,
     }
     .map(|s| s.to_string())
 }
@@ -57,7 +72,7 @@ macro_rules! try_to {
 }
 
 /// Clear the bytes and runtime instance before calling this function
-fn encode(module: &mut wast::QuoteWat) -> Result<Vec<u8>, Box<dyn Error>> {
+pub fn encode(module: &mut wast::QuoteWat) -> Result<Vec<u8>, Box<dyn Error>> {
     match &module {
         QuoteWat::QuoteComponent(..) | QuoteWat::Wat(wast::Wat::Component(..)) => {
             return Err(GenericError::new_boxed(
@@ -117,277 +132,399 @@ pub fn run_spec_test(filepath: &str) -> WastTestReport {
     // -=-= Testing & Compilation =-=-
     let mut asserts = AssertReport::new(filepath);
 
-    // We need to keep the wasm_bytes in-scope for the lifetime of the interpeter.
-    // As such, we hoist the bytes into an Option, and assign it once a module directive is found.
-    #[allow(unused_assignments)]
-    let mut wasm_bytes: Option<Vec<u8>> = None;
-    let mut interpeter = None;
-
-    for directive in wast.directives {
-        match directive {
-            wast::WastDirective::Wat(mut quoted) => {
-                // If we fail to compile or to validate the main module, then we should treat this
-                // as a fatal (compilation) error.
-                wasm_bytes = Some(try_to!(encode(&mut quoted).map_err(|err| {
-                    ScriptError::new(
-                        filepath,
-                        err,
-                        "Module directive (WAT) failed in encoding step.",
-                        get_linenum(&contents, quoted.span()),
-                        get_command(&contents, quoted.span()),
-                    )
-                    .compile_report()
-                })));
-
-                interpeter = Some(try_to!(validate_instantiate(wasm_bytes.as_ref().unwrap())
-                    .map_err(|err| {
+    // All threads share a single waiter, so that they can notify each other
+    let waiter: Arc<dyn Waiter> = Arc::new(CondvarWaiter::default());
+
+    std::thread::scope(|scope| {
+        // We need to keep the wasm_bytes in-scope for the lifetime of the interpeter.
+        // As such, we hoist the bytes into an Option, and assign it once a module directive is found.
+        #[allow(unused_assignments)]
+        let mut wasm_bytes: Option<Vec<u8>> = None;
+        let mut interpeter = None;
+        let mut threads: Vec<(&str, ScopedJoinHandle<Vec<ThreadResult>>)> = Vec::new();
+
+        for directive in wast.directives {
+            match directive {
+                wast::WastDirective::Wat(mut quoted) => {
+                    // If we fail to compile or to validate the main module, then we should treat this
+                    // as a fatal (compilation) error.
+                    wasm_bytes = Some(try_to!(encode(&mut quoted).map_err(|err| {
                         ScriptError::new(
                             filepath,
                             err,
-                            "Module directive (WAT) failed in validation or instantiation.",
+                            "Module directive (WAT) failed in encoding step.",
                             get_linenum(&contents, quoted.span()),
                             get_command(&contents, quoted.span()),
                         )
                         .compile_report()
                     })));
-            }
-            wast::WastDirective::AssertReturn {
-                span,
-                exec,
-                results,
-            } => {
-                if interpeter.is_none() {
-                    return ScriptError::new(
-                        filepath,
-                        GenericError::new_boxed("Attempted to assert before module directive"),
-                        "Assert Return",
-                        get_linenum(&contents, span),
-                        get_command(&contents, span),
-                    )
-                    .compile_report();
+
+                    interpeter = Some(try_to!(validate_instantiate(wasm_bytes.as_ref().unwrap())
+                        .map_err(|err| {
+                            ScriptError::new(
+                                filepath,
+                                err,
+                                "Module directive (WAT) failed in validation or instantiation.",
+                                get_linenum(&contents, quoted.span()),
+                                get_command(&contents, quoted.span()),
+                            )
+                            .compile_report()
+                        })));
+                    interpeter.as_mut().unwrap().set_waiter(waiter.clone());
                 }
+                wast::WastDirective::AssertReturn {
+                    span,
+                    exec,
+                    results,
+                } => {
+                    if interpeter.is_none() {
+                        return ScriptError::new(
+                            filepath,
+                            GenericError::new_boxed("Attempted to assert before module directive"),
+                            "Assert Return",
+                            get_linenum(&contents, span),
+                            get_command(&contents, span),
+                        )
+                        .compile_report();
+                    }
 
-                let interpeter = interpeter.as_mut().unwrap();
+                    let interpeter = interpeter.as_mut().unwrap();
 
-                let err_or_panic = catch_unwind(AssertUnwindSafe(|| {
-                    execute_assert_return(interpeter, exec, results)
-                }))
-                .map_err(PanicError::from_panic_boxed)
-                .and_then(|result| result);
+                    let err_or_panic = catch_unwind(AssertUnwindSafe(|| {
+                        execute_assert_return(interpeter, exec, results)
+                    }))
+                    .map_err(PanicError::from_panic_boxed)
+                    .and_then(|result| result);
 
-                match err_or_panic {
-                    Ok(()) => {
-                        asserts.push_success(WastSuccess::new(
-                            get_linenum(&contents, span),
-                            get_command(&contents, span),
-                        ));
+                    match err_or_panic {
+                        Ok(()) => {
+                            asserts.push_success(WastSuccess::new(
+                                get_linenum(&contents, span),
+                                get_command(&contents, span),
+                            ));
+                        }
+                        Err(inner) => {
+                            asserts.push_error(WastError::new(
+                                inner,
+                                get_linenum(&contents, span),
+                                get_command(&contents, span),
+                            ));
+                        }
                     }
-                    Err(inner) => {
-                        asserts.push_error(WastError::new(
-                            inner,
+                }
+                wast::WastDirective::AssertTrap {
+                    span,
+                    exec,
+                    message,
+                } => {
+                    if interpeter.is_none() {
+                        return ScriptError::new(
+                            filepath,
+                            GenericError::new_boxed("Attempted to assert before module directive"),
+                            "Assert Trap",
                             get_linenum(&contents, span),
                             get_command(&contents, span),
-                        ));
+                        )
+                        .compile_report();
+                    }
+
+                    let interpeter = interpeter.as_mut().unwrap();
+
+                    let err_or_panic: Result<(), Box<dyn Error>> =
+                        catch_unwind(AssertUnwindSafe(|| {
+                            execute_assert_trap(interpeter, exec, message)
+                        }))
+                        .map_err(PanicError::from_panic_boxed)
+                        .and_then(|result| result);
+
+                    match err_or_panic {
+                        Ok(_) => {
+                            asserts.push_success(WastSuccess::new(
+                                get_linenum(&contents, span),
+                                get_command(&contents, span),
+                            ));
+                        }
+                        Err(inner) => {
+                            asserts.push_error(WastError::new(
+                                inner,
+                                get_linenum(&contents, span),
+                                get_command(&contents, span),
+                            ));
+                        }
                     }
                 }
-            }
-            wast::WastDirective::AssertTrap {
-                span,
-                exec,
-                message,
-            } => {
-                if interpeter.is_none() {
-                    return ScriptError::new(
-                        filepath,
-                        GenericError::new_boxed("Attempted to assert before module directive"),
-                        "Assert Trap",
+
+                wast::WastDirective::AssertMalformed {
+                    span,
+                    mut module,
+                    message: _,
+                } => {
+                    let line_number = get_linenum(&contents, span);
+                    let cmd = get_command(&contents, span);
+                    let error = GenericError::new_boxed(
+                        "Module validated and instantiated successfully, when it shouldn't have",
+                    );
+
+                    match encode(&mut module)
+                        .and_then(|bytes| validate_instantiate(&bytes).map(|_| ()))
+                    {
+                        Err(_) => asserts.push_success(WastSuccess::new(line_number, cmd)),
+                        Ok(_) => asserts.push_error(WastError::new(error, line_number, cmd)),
+                    };
+                }
+
+                wast::WastDirective::AssertInvalid {
+                    span,
+                    mut module,
+                    message: _,
+                } => {
+                    let line_number = get_linenum(&contents, span);
+                    let cmd = get_command(&contents, span);
+                    let error = GenericError::new_boxed(
+                        "Module validated and instantiated successfully, when it shouldn't have",
+                    );
+
+                    match encode(&mut module)
+                        .and_then(|bytes| validate_instantiate(&bytes).map(|_| ()))
+                    {
+                        Err(_) => asserts.push_success(WastSuccess::new(line_number, cmd)),
+                        Ok(_) => asserts.push_error(WastError::new(error, line_number, cmd)),
+                    };
+                }
+                wast::WastDirective::Register {
+                    span,
+                    name: _,
+                    module: _,
+                } => {
+                    asserts.push_error(WastError::new(
+                        GenericError::new_boxed("Register directive not yet implemented"),
                         get_linenum(&contents, span),
                         get_command(&contents, span),
-                    )
-                    .compile_report();
+                    ));
                 }
+                wast::WastDirective::AssertExhaustion {
+                    span,
+                    call,
+                    message,
+                } => {
+                    if interpeter.is_none() {
+                        return ScriptError::new(
+                            filepath,
+                            GenericError::new_boxed("Attempted to assert before module directive"),
+                            "Assert Exhaustion",
+                            get_linenum(&contents, span),
+                            get_command(&contents, span),
+                        )
+                        .compile_report();
+                    }
 
-                let interpeter = interpeter.as_mut().unwrap();
-
-                let err_or_panic: Result<(), Box<dyn Error>> =
-                    catch_unwind(AssertUnwindSafe(|| {
-                        execute_assert_trap(interpeter, exec, message)
-                    }))
-                    .map_err(PanicError::from_panic_boxed)
-                    .and_then(|result| result);
+                    let interpeter = interpeter.as_mut().unwrap();
 
-                match err_or_panic {
-                    Ok(_) => {
-                        asserts.push_success(WastSuccess::new(
+                    // Exhaustion is just a particular trap, so it is checked the same way
+                    let err_or_panic: Result<(), Box<dyn Error>> =
+                        catch_unwind(AssertUnwindSafe(|| {
+                            execute_assert_trap(
+                                interpeter,
+                                wast::WastExecute::Invoke(call),
+                                message,
+                            )
+                        }))
+                        .map_err(PanicError::from_panic_boxed)
+                        .and_then(|result| result);
+
+                    match err_or_panic {
+                        Ok(_) => {
+                            asserts.push_success(WastSuccess::new(
+                                get_linenum(&contents, span),
+                                get_command(&contents, span),
+                            ));
+                        }
+                        Err(inner) => {
+                            asserts.push_error(WastError::new(
+                                inner,
+                                get_linenum(&contents, span),
+                                get_command(&contents, span),
+                            ));
+                        }
+                    }
+                }
+                wast::WastDirective::AssertException { span, exec } => {
+                    if interpeter.is_none() {
+                        return ScriptError::new(
+                            filepath,
+                            GenericError::new_boxed("Attempted to assert before module directive"),
+                            "Assert Exception",
                             get_linenum(&contents, span),
                             get_command(&contents, span),
-                        ));
+                        )
+                        .compile_report();
                     }
-                    Err(inner) => {
+
+                    let interpeter = interpeter.as_mut().unwrap();
+
+                    // An uncaught exception is reported like a trap
+                    let err_or_panic: Result<(), Box<dyn Error>> =
+                        catch_unwind(AssertUnwindSafe(|| {
+                            execute_assert_trap(interpeter, exec, "uncaught exception")
+                        }))
+                        .map_err(PanicError::from_panic_boxed)
+                        .and_then(|result| result);
+
+                    match err_or_panic {
+                        Ok(_) => {
+                            asserts.push_success(WastSuccess::new(
+                                get_linenum(&contents, span),
+                                get_command(&contents, span),
+                            ));
+                        }
+                        Err(inner) => {
+                            asserts.push_error(WastError::new(
+                                inner,
+                                get_linenum(&contents, span),
+                                get_command(&contents, span),
+                            ));
+                        }
+                    }
+                }
+                wast::WastDirective::AssertUnlinkable {
+                    span,
+                    module: _,
+                    message: _,
+                } => {
+                    asserts.push_error(WastError::new(
+                        GenericError::new_boxed("Assert directive not yet implemented"),
+                        get_linenum(&contents, span),
+                        get_command(&contents, span),
+                    ));
+                }
+                wast::WastDirective::Wait { span, thread } => {
+                    let Some(idx) = threads.iter().position(|(name, _)| *name == thread.name())
+                    else {
                         asserts.push_error(WastError::new(
-                            inner,
+                            GenericError::new_boxed("Waiting for an unknown thread"),
                             get_linenum(&contents, span),
                             get_command(&contents, span),
                         ));
+                        continue;
+                    };
+
+                    let (_, handle) = threads.swap_remove(idx);
+                    let results = match handle.join() {
+                        Ok(results) => results,
+                        Err(panic) => {
+                            asserts.push_error(WastError::new(
+                                PanicError::from_panic_boxed(panic),
+                                get_linenum(&contents, span),
+                                get_command(&contents, span),
+                            ));
+                            continue;
+                        }
+                    };
+
+                    // the directives of the thread are reported once it is waited for
+                    for (span, result) in results {
+                        match result {
+                            Ok(()) => asserts.push_success(WastSuccess::new(
+                                get_linenum(&contents, span),
+                                get_command(&contents, span),
+                            )),
+                            Err(err) => asserts.push_error(WastError::new(
+                                GenericError::new_boxed(&err),
+                                get_linenum(&contents, span),
+                                get_command(&contents, span),
+                            )),
+                        }
                     }
                 }
-            }
-
-            wast::WastDirective::AssertMalformed {
-                span,
-                mut module,
-                message: _,
-            } => {
-                let line_number = get_linenum(&contents, span);
-                let cmd = get_command(&contents, span);
-                let error = GenericError::new_boxed(
-                    "Module validated and instantiated successfully, when it shouldn't have",
-                );
-
-                match encode(&mut module).and_then(|bytes| validate_instantiate(&bytes).map(|_| ()))
-                {
-                    Err(_) => asserts.push_success(WastSuccess::new(line_number, cmd)),
-                    Ok(_) => asserts.push_error(WastError::new(error, line_number, cmd)),
-                };
-            }
-
-            wast::WastDirective::AssertInvalid {
-                span,
-                mut module,
-                message: _,
-            } => {
-                let line_number = get_linenum(&contents, span);
-                let cmd = get_command(&contents, span);
-                let error = GenericError::new_boxed(
-                    "Module validated and instantiated successfully, when it shouldn't have",
-                );
-
-                match encode(&mut module).and_then(|bytes| validate_instantiate(&bytes).map(|_| ()))
-                {
-                    Err(_) => asserts.push_success(WastSuccess::new(line_number, cmd)),
-                    Ok(_) => asserts.push_error(WastError::new(error, line_number, cmd)),
-                };
-            }
-            wast::WastDirective::Register {
-                span,
-                name: _,
-                module: _,
-            } => {
-                asserts.push_error(WastError::new(
-                    GenericError::new_boxed("Register directive not yet implemented"),
-                    get_linenum(&contents, span),
-                    get_command(&contents, span),
-                ));
-            }
-            wast::WastDirective::AssertExhaustion {
-                span,
-                call: _,
-                message: _,
-            }
-            | wast::WastDirective::AssertUnlinkable {
-                span,
-                module: _,
-                message: _,
-            }
-            | wast::WastDirective::AssertException { span, exec: _ } => {
-                asserts.push_error(WastError::new(
-                    GenericError::new_boxed("Assert directive not yet implemented"),
-                    get_linenum(&contents, span),
-                    get_command(&contents, span),
-                ));
-            }
-            wast::WastDirective::Wait { span, thread: _ } => {
-                asserts.push_error(WastError::new(
-                    GenericError::new_boxed("Wait directive not yet implemented"),
-                    get_linenum(&contents, span),
-                    get_command(&contents, span),
-                ));
-            }
-            wast::WastDirective::Invoke(invoke) => {
-                if interpeter.is_none() {
-                    return ScriptError::new(
-                        filepath,
-                        GenericError::new_boxed(
-                            "Attempted to run invoke directive before interpreter instantiation.",
-                        ),
-                        "Invoke",
-                        get_linenum(&contents, invoke.span),
-                        get_command(&contents, invoke.span),
-                    )
-                    .compile_report();
-                }
-
-                let interpeter = interpeter.as_mut().unwrap();
-
-                let args = invoke
-                    .args
-                    .into_iter()
-                    .map(arg_to_value)
-                    .collect::<Vec<_>>();
-
-                let function_ref_attempt = catch_unwind(AssertUnwindSafe(|| {
-                    interpeter
-                        .get_function_by_name(DEFAULT_MODULE, invoke.name)
-                        .map_err(|err| {
-                            ScriptError::new(
-                                filepath,
-                                WasmInterpreterError::new_boxed(wasm::Error::RuntimeError(err)),
-                                "Invoke directive failed to find function",
-                                get_linenum(&contents, invoke.span),
-                                get_command(&contents, invoke.span),
-                            )
-                            .compile_report()
-                        })
-                }));
-
-                let function_ref = match function_ref_attempt {
-                    Ok(original_result) => try_to!(original_result),
-                    Err(panic) => {
+                wast::WastDirective::Invoke(invoke) => {
+                    if interpeter.is_none() {
                         return ScriptError::new(
                             filepath,
-                            PanicError::from_panic_boxed(panic),
-                            "main module validation panicked",
+                            GenericError::new_boxed(
+                                "Attempted to run invoke directive before interpreter instantiation.",
+                            ),
+                            "Invoke",
                             get_linenum(&contents, invoke.span),
                             get_command(&contents, invoke.span),
                         )
                         .compile_report();
                     }
-                };
 
-                let err_or_panic: Result<_, Box<dyn Error>> =
-                    catch_unwind(AssertUnwindSafe(|| {
-                        interpeter.invoke_dynamic_unchecked_return_ty(&function_ref, args)
-                    }))
-                    .map_err(PanicError::from_panic_boxed)
-                    .and_then(|result| {
-                        result.map_err(|err| {
-                            WasmInterpreterError::new_boxed(wasm::Error::RuntimeError(err))
-                        })
-                    });
-
-                try_to!(err_or_panic.map_err(|inner| ScriptError::new(
-                    filepath,
-                    inner,
-                    "Invoke returned error or panicked",
-                    get_linenum(&contents, invoke.span),
-                    get_command(&contents, invoke.span)
-                )
-                .compile_report()));
-            }
-            wast::WastDirective::Thread(thread) => {
-                asserts.push_error(WastError::new(
-                    GenericError::new_boxed("Thread directive not yet implemented"),
-                    get_linenum(&contents, thread.span),
-                    get_command(&contents, thread.span),
-                ));
+                    let interpeter = interpeter.as_mut().unwrap();
+
+                    let args = invoke
+                        .args
+                        .into_iter()
+                        .map(arg_to_value)
+                        .collect::<Vec<_>>();
+
+                    let function_ref_attempt = catch_unwind(AssertUnwindSafe(|| {
+                        interpeter
+                            .get_function_by_name(DEFAULT_MODULE, invoke.name)
+                            .map_err(|err| {
+                                ScriptError::new(
+                                    filepath,
+                                    WasmInterpreterError::new_boxed(wasm::Error::RuntimeError(err)),
+                                    "Invoke directive failed to find function",
+                                    get_linenum(&contents, invoke.span),
+                                    get_command(&contents, invoke.span),
+                                )
+                                .compile_report()
+                            })
+                    }));
+
+                    let function_ref = match function_ref_attempt {
+                        Ok(original_result) => try_to!(original_result),
+                        Err(panic) => {
+                            return ScriptError::new(
+                                filepath,
+                                PanicError::from_panic_boxed(panic),
+                                "main module validation panicked",
+                                get_linenum(&contents, invoke.span),
+                                get_command(&contents, invoke.span),
+                            )
+                            .compile_report();
+                        }
+                    };
+
+                    let err_or_panic: Result<_, Box<dyn Error>> =
+                        catch_unwind(AssertUnwindSafe(|| {
+                            interpeter.invoke_dynamic_unchecked_return_ty(&function_ref, args)
+                        }))
+                        .map_err(PanicError::from_panic_boxed)
+                        .and_then(|result| {
+                            result.map_err(|err| WasmInterpreterError::new_boxed(err.into()))
+                        });
+
+                    try_to!(err_or_panic.map_err(|inner| ScriptError::new(
+                        filepath,
+                        inner,
+                        "Invoke returned error or panicked",
+                        get_linenum(&contents, invoke.span),
+                        get_command(&contents, invoke.span)
+                    )
+                    .compile_report()));
+                }
+                wast::WastDirective::Thread(thread) => {
+                    // Modules are not tracked by name, so the shared module is assumed to be the current one
+                    let shared = match (&thread.shared_module, &interpeter, &wasm_bytes) {
+                        (Some(_), Some(interpeter), Some(bytes)) => {
+                            exported_shared_memories(interpeter, bytes)
+                        }
+                        _ => Vec::new(),
+                    };
+                    let waiter = waiter.clone();
+                    let handle = scope.spawn(move || run_thread(thread.directives, shared, waiter));
+                    threads.push((thread.name.name(), handle));
+                }
             }
         }
-    }
 
-    asserts.compile_report()
+        asserts.compile_report()
+    })
 }
 
-fn execute_assert_return(
+pub fn execute_assert_return(
     interpeter: &mut RuntimeInstance,
     exec: wast::WastExecute,
     results: Vec<wast::WastRet>,
@@ -413,7 +550,7 @@ fn execute_assert_return(
 
             let actual = interpeter
                 .invoke_dynamic(&func, args, &result_types)
-                .map_err(|err| WasmInterpreterError::new_boxed(wasm::Error::RuntimeError(err)))?;
+                .map_err(|err| WasmInterpreterError::new_boxed(err.into()))?;
 
             AssertEqError::assert_eq(actual, result_vals)?;
             Ok(())
@@ -431,7 +568,7 @@ fn execute_assert_return(
     }
 }
 
-fn execute_assert_trap(
+pub fn execute_assert_trap(
     interpeter: &mut RuntimeInstance,
     exec: wast::WastExecute,
     message: &str,
@@ -462,7 +599,7 @@ fn execute_assert_trap(
             match actual {
                 Ok(_) => Err(GenericError::new_boxed("assert_trap did NOT trap")),
                 Err(e) => {
-                    let actual = to_wasm_testsuite_string(e)?;
+                    let actual = to_wasm_testsuite_string(e.error)?;
                     let expected = message;
 
                     if actual.contains(expected)
@@ -492,6 +629,28 @@ fn execute_assert_trap(
     }
 }
 
+/// All shared memories exported by the module of `interpeter`, along with their names
+fn exported_shared_memories(
+    interpeter: &RuntimeInstance,
+    bytes: &[u8],
+) -> Vec<(String, SharedMemory)> {
+    wasmparser::Parser::new(0)
+        .parse_all(bytes)
+        .filter_map(|payload| match payload {
+            Ok(wasmparser::Payload::ExportSection(reader)) => Some(reader),
+            _ => None,
+        })
+        .flat_map(|reader| reader.into_iter().filter_map(Result::ok))
+        .filter(|export| export.kind == wasmparser::ExternalKind::Memory)
+        .filter_map(|export| {
+            interpeter
+                .get_shared_memory(DEFAULT_MODULE, export.name)
+                .ok()
+                .map(|memory| (export.name.to_owned(), memory))
+        })
+        .collect()
+}
+
 pub fn arg_to_value(arg: WastArg) -> Value {
     match arg {
         WastArg::Core(core_arg) => match core_arg {
@@ -510,6 +669,7 @@ pub fn arg_to_value(arg: WastArg) -> Value {
                     match ty {
                         Func => Value::Ref(Ref::Func(FuncAddr::null())),
                         Extern => Value::Ref(Ref::Extern(ExternAddr::null())),
+                        Exn => Value::Ref(Ref::Exn(ExnAddr::null())),
                         _ => todo!("`GC` proposal not yet implemented"),
                     }
                 }
@@ -567,6 +727,7 @@ fn result_to_value(result: wast::WastRet) -> Value {
                         match ty {
                             Func => Value::Ref(Ref::Func(FuncAddr::null())),
                             Extern => Value::Ref(Ref::Extern(ExternAddr::null())),
+                            Exn => Value::Ref(Ref::Exn(ExnAddr::null())),
                             _ => todo!("`GC` proposal not yet implemented"),
                         }
                     }
@@ -211,7 +211,7 @@ impl std::fmt::Display for PanicError {
     }
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct WasmInterpreterError(pub wasm::Error);
 
 impl WasmInterpreterError {
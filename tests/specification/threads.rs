@@ -0,0 +1,173 @@
+use std::error::Error;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::Duration;
+
+use wasm::threads::{SharedMemory, WaitKey, WaitOutcome, Waiter};
+use wasm::{validate, RuntimeInstance, DEFAULT_MODULE};
+use wast::token::Span;
+
+use crate::specification::run::{arg_to_value, encode, execute_assert_return, execute_assert_trap};
+use crate::specification::test_errors::*;
+
+/// A [Waiter] suspending threads on a [Condvar]. The queue holds the key and a unique ticket of every waiting thread.
+#[derive(Default)]
+pub struct CondvarWaiter {
+    queue: Mutex<(u64, Vec<(WaitKey, u64)>)>,
+    notified: Condvar,
+}
+
+impl Waiter for CondvarWaiter {
+    fn wait(
+        &self,
+        key: WaitKey,
+        timeout: Option<Duration>,
+        still_expected: &mut dyn FnMut() -> bool,
+    ) -> WaitOutcome {
+        let mut queue = self.queue.lock().unwrap();
+        if !still_expected() {
+            return WaitOutcome::NotEqual;
+        }
+        let ticket = (key, queue.0);
+        queue.0 += 1;
+        queue.1.push(ticket);
+
+        let is_queued = |queue: &mut (u64, Vec<(WaitKey, u64)>)| queue.1.contains(&ticket);
+        let mut queue = match timeout {
+            None => self.notified.wait_while(queue, is_queued).unwrap(),
+            Some(timeout) => {
+                self.notified
+                    .wait_timeout_while(queue, timeout, is_queued)
+                    .unwrap()
+                    .0
+            }
+        };
+
+        // `notify` removes the tickets of the threads it wakes up
+        match queue.1.iter().position(|queued| *queued == ticket) {
+            Some(idx) => {
+                queue.1.remove(idx);
+                WaitOutcome::TimedOut
+            }
+            None => WaitOutcome::Woken,
+        }
+    }
+
+    fn notify(&self, key: WaitKey, count: u32) -> u32 {
+        let mut queue = self.queue.lock().unwrap();
+        let mut woken = 0;
+        queue.1.retain(|(queued_key, _)| {
+            let wake = woken < count && *queued_key == key;
+            woken += u32::from(wake);
+            !wake
+        });
+        self.notified.notify_all();
+        woken
+    }
+}
+
+/// The outcome of a directive executed by a thread. Errors are passed as strings, as they have to be sent back to
+/// the main thread.
+pub type ThreadResult = (Span, Result<(), String>);
+
+/// Execute the directives of a `thread` directive, usually on a separate thread
+///
+/// Modules within the thread can import the `shared` memories, which are the shared memories exported by the module
+/// the thread shares, under the names given by `register` directives.
+pub fn run_thread(
+    directives: Vec<wast::WastDirective>,
+    shared: Vec<(String, SharedMemory)>,
+    waiter: Arc<dyn Waiter>,
+) -> Vec<ThreadResult> {
+    let mut results = Vec::new();
+    let mut registered: Vec<(String, String, SharedMemory)> = Vec::new();
+
+    // The bytes have to outlive the interpreter, see `run_spec_test`
+    #[allow(unused_assignments)]
+    let mut wasm_bytes: Option<Vec<u8>> = None;
+    let mut interpeter: Option<RuntimeInstance> = None;
+
+    for directive in directives {
+        let span = directive.span();
+        let result: Result<(), Box<dyn Error>> = match directive {
+            wast::WastDirective::Register { name, .. } => {
+                for (export_name, memory) in &shared {
+                    registered.push((name.to_owned(), export_name.clone(), memory.clone()));
+                }
+                Ok(())
+            }
+            wast::WastDirective::Wat(mut quoted) => match encode(&mut quoted) {
+                Ok(bytes) => {
+                    wasm_bytes = Some(bytes);
+                    match instantiate(wasm_bytes.as_ref().unwrap(), &registered, &waiter) {
+                        Ok(instance) => {
+                            interpeter = Some(instance);
+                            Ok(())
+                        }
+                        Err(err) => {
+                            interpeter = None;
+                            Err(err)
+                        }
+                    }
+                }
+                Err(err) => Err(err),
+            },
+            wast::WastDirective::Invoke(invoke) => match interpeter.as_mut() {
+                None => Err(GenericError::new_boxed("Invoke before module directive")),
+                Some(interpeter) => catch_unwind(AssertUnwindSafe(|| {
+                    let args = invoke.args.into_iter().map(arg_to_value).collect();
+                    interpeter
+                        .get_function_by_name(DEFAULT_MODULE, invoke.name)
+                        .map_err(wasm::trap::Trap::from)
+                        .and_then(|func| interpeter.invoke_dynamic_unchecked_return_ty(&func, args))
+                        .map(|_| ())
+                        .map_err(|err| WasmInterpreterError::new_boxed(err.into()))
+                }))
+                .map_err(PanicError::from_panic_boxed)
+                .and_then(|result| result),
+            },
+            wast::WastDirective::AssertReturn { exec, results, .. } => match interpeter.as_mut() {
+                None => Err(GenericError::new_boxed("Assert before module directive")),
+                Some(interpeter) => catch_unwind(AssertUnwindSafe(|| {
+                    execute_assert_return(interpeter, exec, results)
+                }))
+                .map_err(PanicError::from_panic_boxed)
+                .and_then(|result| result),
+            },
+            wast::WastDirective::AssertTrap { exec, message, .. } => match interpeter.as_mut() {
+                None => Err(GenericError::new_boxed("Assert before module directive")),
+                Some(interpeter) => catch_unwind(AssertUnwindSafe(|| {
+                    execute_assert_trap(interpeter, exec, message)
+                }))
+                .map_err(PanicError::from_panic_boxed)
+                .and_then(|result| result),
+            },
+            _ => Err(GenericError::new_boxed(
+                "Directive not yet implemented within threads",
+            )),
+        };
+
+        results.push((span, result.map_err(|err| err.to_string())));
+    }
+
+    results
+}
+
+/// Instantiate a module within a thread, which may import the shared memories in `registered`
+fn instantiate<'a>(
+    bytes: &'a [u8],
+    registered: &[(String, String, SharedMemory)],
+    waiter: &Arc<dyn Waiter>,
+) -> Result<RuntimeInstance<'a>, Box<dyn Error>> {
+    let validation_info = validate(bytes).map_err(WasmInterpreterError::new_boxed)?;
+
+    let mut instance = RuntimeInstance::new_empty();
+    instance.set_waiter(waiter.clone());
+    for (module_name, name, memory) in registered {
+        instance.add_shared_memory(module_name, name, memory.clone());
+    }
+    instance
+        .add_module(DEFAULT_MODULE, &validation_info)
+        .map_err(WasmInterpreterError::new_boxed)?;
+    Ok(instance)
+}
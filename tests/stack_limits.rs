@@ -0,0 +1,133 @@
+use wasm::{
+    resumable::InvocationState, validate, RuntimeError, RuntimeInstance, Value,
+    DEFAULT_MAX_CALL_DEPTH, DEFAULT_MODULE,
+};
+
+const RECURSION: &str = r#"
+(module
+    (func $runaway (export "runaway")
+        call $runaway
+    )
+    (func $depth (export "depth") (param $n i32) (result i32)
+        (if (result i32) (i32.eqz (local.get $n))
+            (then (i32.const 0))
+            (else (i32.add (i32.const 1) (call $depth (i32.sub (local.get $n) (i32.const 1)))))
+        )
+    )
+)"#;
+
+#[test_log::test]
+pub fn infinite_recursion_exhausts_call_stack() {
+    let wasm_bytes = wat::parse_str(RECURSION).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let runaway = instance
+        .get_function_by_name(DEFAULT_MODULE, "runaway")
+        .unwrap();
+    assert_eq!(
+        RuntimeError::StackSmash,
+        instance.invoke::<(), ()>(&runaway, ()).unwrap_err()
+    );
+
+    // the instance is still usable after the trap
+    let depth = instance
+        .get_function_by_name(DEFAULT_MODULE, "depth")
+        .unwrap();
+    assert_eq!(100, instance.invoke::<i32, i32>(&depth, 100).unwrap());
+}
+
+#[test_log::test]
+pub fn configurable_call_depth() {
+    let wasm_bytes = wat::parse_str(RECURSION).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    assert_eq!(DEFAULT_MAX_CALL_DEPTH, instance.max_call_depth());
+
+    let depth = instance
+        .get_function_by_name(DEFAULT_MODULE, "depth")
+        .unwrap();
+
+    // `depth(n)` requires n + 1 nested calls
+    instance.set_max_call_depth(10);
+    assert_eq!(9, instance.invoke::<i32, i32>(&depth, 9).unwrap());
+    assert_eq!(
+        RuntimeError::StackSmash,
+        instance.invoke::<i32, i32>(&depth, 10).unwrap_err()
+    );
+
+    // resumable invocations are bounded as well
+    let InvocationState::Finished(values) = instance
+        .invoke_resumable(&depth, vec![Value::I32(9)], None)
+        .unwrap()
+    else {
+        panic!("expected the invocation to finish");
+    };
+    assert_eq!(vec![Value::I32(9)], values);
+    assert_eq!(
+        RuntimeError::StackSmash,
+        instance
+            .invoke_resumable(&depth, vec![Value::I32(10)], None)
+            .err()
+            .unwrap()
+    );
+
+    instance.set_max_call_depth(0);
+    assert_eq!(
+        RuntimeError::StackSmash,
+        instance.invoke::<i32, i32>(&depth, 0).unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn configurable_value_stack_size() {
+    let wasm_bytes = wat::parse_str(RECURSION).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let depth = instance
+        .get_function_by_name(DEFAULT_MODULE, "depth")
+        .unwrap();
+
+    // every call keeps its parameter as local and one value for its pending `i32.add` on the stack, computing the
+    // argument of the innermost call needs three more
+    instance.set_max_value_stack_size(201);
+    assert_eq!(99, instance.invoke::<i32, i32>(&depth, 99).unwrap());
+    assert_eq!(
+        RuntimeError::StackSmash,
+        instance.invoke::<i32, i32>(&depth, 100).unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn locals_count_towards_value_stack_size() {
+    let wasm_bytes = wat::parse_str(
+        r#"(module
+            (func (export "many_locals") (param i32) (result i32)
+                (local i64 i64 i64 i64 i64 i64 i64 i64 i64)
+                (local.get 0)
+            )
+        )"#,
+    )
+    .unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let many_locals = instance
+        .get_function_by_name(DEFAULT_MODULE, "many_locals")
+        .unwrap();
+
+    // the parameter and the declared locals take ten slots, the result an eleventh one
+    instance.set_max_value_stack_size(11);
+    assert_eq!(7, instance.invoke::<i32, i32>(&many_locals, 7).unwrap());
+    instance.set_max_value_stack_size(10);
+    assert_eq!(
+        RuntimeError::StackSmash,
+        instance.invoke::<i32, i32>(&many_locals, 7).unwrap_err()
+    );
+    instance.set_max_value_stack_size(9);
+    assert_eq!(
+        RuntimeError::StackSmash,
+        instance.invoke::<i32, i32>(&many_locals, 7).unwrap_err()
+    );
+}
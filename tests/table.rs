@@ -51,6 +51,8 @@ fn table_basic() {
 
 #[test_log::test]
 fn table_basic_2() {
+    let spectest_bytes =
+        wat::parse_str(r#"(module (table (export "table") 10 20 funcref))"#).unwrap();
     let w = r#"
     (module (table 0 funcref) (table 0 funcref))
     (module (table (import "spectest" "table") 0 funcref) (table 0 funcref))
@@ -58,12 +60,18 @@ fn table_basic_2() {
     .split("\n")
     .map(|el| el.trim())
     .filter(|el| !el.is_empty())
-    .collect::<Vec<&str>>();
+    .map(|wat| wat::parse_str(wat).unwrap())
+    .collect::<Vec<Vec<u8>>>();
 
-    w.iter().for_each(|wat| {
-        let wasm_bytes = wat::parse_str(wat).unwrap();
-        let validation_info = validate(&wasm_bytes).expect("validation failed");
-        RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    w.iter().for_each(|wasm_bytes| {
+        let spectest_validation_info = validate(&spectest_bytes).expect("validation failed");
+        let mut instance = RuntimeInstance::new_named("spectest", &spectest_validation_info)
+            .expect("instantiation failed");
+
+        let validation_info = validate(wasm_bytes).expect("validation failed");
+        instance
+            .add_module("module", &validation_info)
+            .expect("instantiation failed");
     });
 }
 
@@ -126,10 +134,11 @@ fn table_elem_test() {
     let wanted: [usize; 2] = [0, 2];
     table
         .elem
+        .borrow()
         .iter()
         .enumerate()
         .for_each(|(i, rref)| match *rref {
-            wasm::value::Ref::Extern(_) => panic!(),
+            wasm::value::Ref::Extern(_) | wasm::value::Ref::Exn(_) => panic!(),
             wasm::value::Ref::Func(func_addr) => {
                 assert!(func_addr.addr.is_some());
                 assert!(wanted[i] == func_addr.addr.unwrap())
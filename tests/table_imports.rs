@@ -0,0 +1,174 @@
+use wasm::{validate, Error, RuntimeInstance, StoreInstantiationError};
+
+const CORE: &str = r#"
+(module
+    (type $binop (func (param i32 i32) (result i32)))
+    (table (export "ops") 4 8 funcref)
+    (func $sub (param i32 i32) (result i32)
+        (i32.sub (local.get 0) (local.get 1))
+    )
+    (elem (i32.const 0) $sub)
+    (func (export "apply") (param i32 i32 i32) (result i32)
+        (call_indirect (type $binop) (local.get 1) (local.get 2) (local.get 0))
+    )
+    (func (export "size") (result i32)
+        table.size
+    )
+)"#;
+
+const PLUGIN: &str = r#"
+(module
+    (import "core" "ops" (table 2 funcref))
+    (global $offset (mut i32) (i32.const 100))
+    (func $add (param i32 i32) (result i32)
+        (i32.add (local.get 0) (local.get 1))
+    )
+    (func $add_offset (param i32 i32) (result i32)
+        (i32.add (global.get $offset) (i32.add (local.get 0) (local.get 1)))
+    )
+    (elem (i32.const 1) $add $add_offset)
+    (func (export "set_offset") (param i32)
+        (global.set $offset (local.get 0))
+    )
+    (func (export "install") (param i32)
+        (table.set (local.get 0) (ref.func $add))
+    )
+    (func (export "grow") (param i32) (result i32)
+        (table.grow (ref.null func) (local.get 0))
+    )
+)"#;
+
+fn instantiate_core(core_bytes: &[u8]) -> RuntimeInstance<'_> {
+    let validation_info = validate(core_bytes).expect("validation failed");
+    RuntimeInstance::new_named("core", &validation_info).expect("instantiation failed")
+}
+
+#[test_log::test]
+pub fn imported_table_is_shared() {
+    let core_bytes = wat::parse_str(CORE).unwrap();
+    let mut instance = instantiate_core(&core_bytes);
+
+    let plugin_bytes = wat::parse_str(PLUGIN).unwrap();
+    let plugin_validation_info = validate(&plugin_bytes).expect("validation failed");
+    instance
+        .add_module("plugin", &plugin_validation_info)
+        .expect("instantiation failed");
+
+    let apply = instance.get_function_by_name("core", "apply").unwrap();
+    let size = instance.get_function_by_name("core", "size").unwrap();
+    let set_offset = instance
+        .get_function_by_name("plugin", "set_offset")
+        .unwrap();
+    let install = instance.get_function_by_name("plugin", "install").unwrap();
+    let grow = instance.get_function_by_name("plugin", "grow").unwrap();
+
+    // the core's own function is still in place
+    assert_eq!(
+        2,
+        instance
+            .invoke::<(i32, i32, i32), i32>(&apply, (0, 5, 3))
+            .unwrap()
+    );
+
+    // the plugin's element segment placed its functions into the core's table, and calls through it are executed in
+    // the plugin's context
+    assert_eq!(
+        8,
+        instance
+            .invoke::<(i32, i32, i32), i32>(&apply, (1, 5, 3))
+            .unwrap()
+    );
+    assert_eq!(
+        108,
+        instance
+            .invoke::<(i32, i32, i32), i32>(&apply, (2, 5, 3))
+            .unwrap()
+    );
+    instance.invoke::<i32, ()>(&set_offset, 1000).unwrap();
+    assert_eq!(
+        1008,
+        instance
+            .invoke::<(i32, i32, i32), i32>(&apply, (2, 5, 3))
+            .unwrap()
+    );
+
+    // references created at runtime refer to the plugin's functions as well
+    instance.invoke::<i32, ()>(&install, 3).unwrap();
+    assert_eq!(
+        8,
+        instance
+            .invoke::<(i32, i32, i32), i32>(&apply, (3, 5, 3))
+            .unwrap()
+    );
+
+    // growing is bound by the exporter's limits, even though the import does not declare a maximum
+    assert_eq!(4, instance.invoke::<i32, i32>(&grow, 4).unwrap());
+    assert_eq!(8, instance.invoke::<(), i32>(&size, ()).unwrap());
+    assert_eq!(-1, instance.invoke::<i32, i32>(&grow, 1).unwrap());
+}
+
+#[test_log::test]
+pub fn unknown_table_import() {
+    let core_bytes = wat::parse_str(CORE).unwrap();
+    let mut instance = instantiate_core(&core_bytes);
+
+    let plugin_bytes =
+        wat::parse_str(r#"(module (import "core" "callbacks" (table 1 funcref)))"#).unwrap();
+    let plugin_validation_info = validate(&plugin_bytes).expect("validation failed");
+    assert_eq!(
+        Error::StoreInstantiationError(StoreInstantiationError::UnknownImport(
+            "core".to_owned(),
+            "callbacks".to_owned()
+        )),
+        instance
+            .add_module("plugin", &plugin_validation_info)
+            .unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn incompatible_table_import() {
+    let incompatible_imports = [
+        // the table is smaller than required
+        ("ops", r#"(module (import "core" "ops" (table 5 funcref)))"#),
+        // the table may grow larger than allowed
+        (
+            "ops",
+            r#"(module (import "core" "ops" (table 4 6 funcref)))"#,
+        ),
+        // the element type differs
+        (
+            "ops",
+            r#"(module (import "core" "ops" (table 4 externref)))"#,
+        ),
+        // not a table at all
+        (
+            "apply",
+            r#"(module (import "core" "apply" (table 4 funcref)))"#,
+        ),
+    ]
+    .map(|(name, plugin)| (name, wat::parse_str(plugin).unwrap()));
+
+    let core_bytes = wat::parse_str(CORE).unwrap();
+    let mut instance = instantiate_core(&core_bytes);
+
+    for (name, plugin_bytes) in &incompatible_imports {
+        let plugin_validation_info = validate(plugin_bytes).expect("validation failed");
+        assert_eq!(
+            Error::StoreInstantiationError(StoreInstantiationError::IncompatibleImportType(
+                "core".to_owned(),
+                name.to_string()
+            )),
+            instance
+                .add_module("plugin", &plugin_validation_info)
+                .unwrap_err()
+        );
+    }
+
+    let plugin_bytes =
+        wat::parse_str(r#"(module (import "core" "ops" (table 0 16 funcref)))"#).unwrap();
+    let plugin_validation_info = validate(&plugin_bytes).expect("validation failed");
+    instance
+        .add_module("plugin", &plugin_validation_info)
+        .expect("instantiation failed");
+}
@@ -0,0 +1,132 @@
+use wasm::{
+    validate, Error, FuncType, NumType, ResultType, RuntimeError, RuntimeInstance, ValType, Value,
+    DEFAULT_MODULE,
+};
+
+const STATE_MACHINE: &str = r#"
+(module
+    (import "env" "double" (func $double (param i64) (result i64)))
+    (type $state (func (param i64 i64) (result i64)))
+    (table 2 funcref)
+    (elem (i32.const 0) $even $odd)
+
+    ;; counts the odd numbers below `n` by alternating between the two states
+    (func $even (export "even") (param $n i64) (param $odd_count i64) (result i64)
+        (if (result i64) (i64.eqz (local.get $n))
+            (then (local.get $odd_count))
+            (else (return_call $odd (i64.sub (local.get $n) (i64.const 1)) (local.get $odd_count)))
+        )
+    )
+    (func $odd (param $n i64) (param $odd_count i64) (result i64)
+        (if (result i64) (i64.eqz (local.get $n))
+            (then (local.get $odd_count))
+            (else
+                (return_call_indirect (type $state)
+                    (i64.sub (local.get $n) (i64.const 1))
+                    (i64.add (local.get $odd_count) (i64.const 1))
+                    (i32.const 0)
+                )
+            )
+        )
+    )
+
+    (func (export "double_via_host") (param i64) (result i64)
+        (return_call $double (local.get 0))
+    )
+)"#;
+
+fn i64_to_i64() -> FuncType {
+    FuncType {
+        params: ResultType {
+            valtypes: vec![ValType::NumType(NumType::I64)],
+        },
+        returns: ResultType {
+            valtypes: vec![ValType::NumType(NumType::I64)],
+        },
+    }
+}
+
+#[test_log::test]
+pub fn tail_calls_do_not_grow_the_call_stack() {
+    let wasm_bytes = wat::parse_str(STATE_MACHINE).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    instance.add_host_function("env", "double", i64_to_i64(), |params: &[Value]| {
+        let x: i64 = params[0].into();
+        vec![Value::from(2 * x)]
+    });
+
+    let even = instance
+        .get_function_by_name(DEFAULT_MODULE, "even")
+        .unwrap();
+
+    // far more transitions than stackframes are available
+    instance.set_max_call_depth(4);
+    assert_eq!(
+        50_000,
+        instance
+            .invoke::<(i64, i64), i64>(&even, (100_000, 0))
+            .unwrap()
+    );
+    assert_eq!(
+        50_001,
+        instance
+            .invoke::<(i64, i64), i64>(&even, (100_003, 0))
+            .unwrap()
+    );
+
+    // the result of the host function is returned by the calling function
+    let double_via_host = instance
+        .get_function_by_name(DEFAULT_MODULE, "double_via_host")
+        .unwrap();
+    assert_eq!(
+        42,
+        instance.invoke::<i64, i64>(&double_via_host, 21).unwrap()
+    );
+}
+
+#[test_log::test]
+pub fn return_call_indirect_checks_the_signature() {
+    // `$add` takes two parameters, but is called with one
+    let wasm_bytes = wat::parse_str(
+        r#"
+        (module
+            (table 1 funcref)
+            (elem (i32.const 0) $add)
+            (func $add (param i64 i64) (result i64)
+                (i64.add (local.get 0) (local.get 1))
+            )
+            (func (export "wrong_signature") (result i64)
+                (return_call_indirect (param i64) (result i64) (i64.const 0) (i32.const 0))
+            )
+        )"#,
+    )
+    .unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    let wrong_signature = instance
+        .get_function_by_name(DEFAULT_MODULE, "wrong_signature")
+        .unwrap();
+    assert_eq!(
+        RuntimeError::SignatureMismatch,
+        instance
+            .invoke::<(), i64>(&wrong_signature, ())
+            .unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn tail_call_with_different_results() {
+    let wasm_bytes = wat::parse_str(
+        r#"
+        (module
+            (func $f (result i32) (i32.const 0))
+            (func (result i64) (return_call $f))
+        )"#,
+    )
+    .unwrap();
+    assert_eq!(
+        Some(Error::TailCallTypeMismatch),
+        validate(&wasm_bytes).err()
+    );
+}
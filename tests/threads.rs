@@ -0,0 +1,385 @@
+use std::sync::{Arc, Condvar, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use wasm::threads::{WaitKey, WaitOutcome, Waiter};
+use wasm::{
+    validate, Error, RuntimeError, RuntimeInstance, StoreInstantiationError, DEFAULT_MODULE,
+};
+
+/// A [Waiter] suspending threads on a [Condvar]. The queue holds the key and a unique ticket of every waiting thread.
+#[derive(Default)]
+struct CondvarWaiter {
+    queue: Mutex<(u64, Vec<(WaitKey, u64)>)>,
+    notified: Condvar,
+}
+
+impl Waiter for CondvarWaiter {
+    fn wait(
+        &self,
+        key: WaitKey,
+        timeout: Option<Duration>,
+        still_expected: &mut dyn FnMut() -> bool,
+    ) -> WaitOutcome {
+        let mut queue = self.queue.lock().unwrap();
+        if !still_expected() {
+            return WaitOutcome::NotEqual;
+        }
+        let ticket = (key, queue.0);
+        queue.0 += 1;
+        queue.1.push(ticket);
+
+        let is_queued = |queue: &mut (u64, Vec<(WaitKey, u64)>)| queue.1.contains(&ticket);
+        let mut queue = match timeout {
+            None => self.notified.wait_while(queue, is_queued).unwrap(),
+            Some(timeout) => {
+                self.notified
+                    .wait_timeout_while(queue, timeout, is_queued)
+                    .unwrap()
+                    .0
+            }
+        };
+
+        // `notify` removes the tickets of the threads it wakes up
+        match queue.1.iter().position(|queued| *queued == ticket) {
+            Some(idx) => {
+                queue.1.remove(idx);
+                WaitOutcome::TimedOut
+            }
+            None => WaitOutcome::Woken,
+        }
+    }
+
+    fn notify(&self, key: WaitKey, count: u32) -> u32 {
+        let mut queue = self.queue.lock().unwrap();
+        let mut woken = 0;
+        queue.1.retain(|(queued_key, _)| {
+            let wake = woken < count && *queued_key == key;
+            woken += u32::from(wake);
+            !wake
+        });
+        self.notified.notify_all();
+        woken
+    }
+}
+
+const COUNTER: &str = r#"
+(module
+    (memory (export "memory") 1 1 shared)
+
+    (func (export "load") (param i32) (result i32)
+        (i32.atomic.load (local.get 0))
+    )
+    (func (export "add") (param i32 i32) (result i32)
+        (i32.atomic.rmw.add (local.get 0) (local.get 1))
+    )
+    (func (export "cmpxchg8") (param i32 i32 i32) (result i32)
+        (i32.atomic.rmw8.cmpxchg_u (local.get 0) (local.get 1) (local.get 2))
+    )
+    (func (export "sub16") (param i32 i64) (result i64)
+        (i64.atomic.rmw16.sub_u (local.get 0) (local.get 1))
+    )
+    (func (export "xchg") (param i32 i64) (result i64)
+        (i64.atomic.rmw.xchg (local.get 0) (local.get 1))
+    )
+    (func (export "wait") (param i32 i32 i64) (result i32)
+        (memory.atomic.wait32 (local.get 0) (local.get 1) (local.get 2))
+    )
+    (func (export "notify") (param i32 i32) (result i32)
+        (memory.atomic.notify (local.get 0) (local.get 1))
+    )
+)"#;
+
+const WORKER: &str = r#"
+(module
+    (import "main" "memory" (memory 1 1 shared))
+
+    ;; increment the counter at address 0 `n` times
+    (func (export "count") (param $n i32)
+        (loop $increment
+            (drop (i32.atomic.rmw.add (i32.const 0) (i32.const 1)))
+            (local.tee $n (i32.sub (local.get $n) (i32.const 1)))
+            (br_if $increment)
+        )
+    )
+
+    ;; wait until the flag at address 8 is set, then return the message at address 16
+    (func (export "receive") (result i64)
+        (loop $wait
+            (if (i32.eqz (i32.atomic.load (i32.const 8)))
+                (then
+                    (drop (memory.atomic.wait32 (i32.const 8) (i32.const 0) (i64.const -1)))
+                    (br $wait)
+                )
+            )
+        )
+        (i64.atomic.load (i32.const 16))
+    )
+)"#;
+
+#[test_log::test]
+pub fn atomic_read_modify_write() {
+    let wasm_bytes = wat::parse_str(COUNTER).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let load = instance
+        .get_function_by_name(DEFAULT_MODULE, "load")
+        .unwrap();
+    let add = instance
+        .get_function_by_name(DEFAULT_MODULE, "add")
+        .unwrap();
+    let cmpxchg8 = instance
+        .get_function_by_name(DEFAULT_MODULE, "cmpxchg8")
+        .unwrap();
+    let sub16 = instance
+        .get_function_by_name(DEFAULT_MODULE, "sub16")
+        .unwrap();
+    let xchg = instance
+        .get_function_by_name(DEFAULT_MODULE, "xchg")
+        .unwrap();
+
+    assert_eq!(
+        0,
+        instance
+            .invoke::<(i32, i32), i32>(&add, (0, 0x1ff))
+            .unwrap()
+    );
+    assert_eq!(
+        0x1ff,
+        instance.invoke::<(i32, i32), i32>(&add, (0, 1)).unwrap()
+    );
+    assert_eq!(0x200, instance.invoke::<i32, i32>(&load, 0).unwrap());
+
+    // the expected value is wrapped to 8 bits before comparing
+    assert_eq!(
+        0,
+        instance
+            .invoke::<(i32, i32, i32), i32>(&cmpxchg8, (0, 0x100, 0x2a))
+            .unwrap()
+    );
+    assert_eq!(0x22a, instance.invoke::<i32, i32>(&load, 0).unwrap());
+    assert_eq!(
+        0x2a,
+        instance
+            .invoke::<(i32, i32, i32), i32>(&cmpxchg8, (0, 0, 0x7))
+            .unwrap()
+    );
+    assert_eq!(0x22a, instance.invoke::<i32, i32>(&load, 0).unwrap());
+
+    // narrow accesses zero-extend the previous value and wrap around
+    assert_eq!(
+        0x22a,
+        instance
+            .invoke::<(i32, i64), i64>(&sub16, (0, 0x22b))
+            .unwrap()
+    );
+    assert_eq!(0xffff, instance.invoke::<i32, i32>(&load, 0).unwrap());
+    assert_eq!(
+        0xffff,
+        instance.invoke::<(i32, i64), i64>(&xchg, (0, -1)).unwrap()
+    );
+    assert_eq!(-1, instance.invoke::<i32, i32>(&load, 4).unwrap());
+
+    assert_eq!(
+        RuntimeError::UnalignedAtomic,
+        instance.invoke::<i32, i32>(&load, 2).unwrap_err()
+    );
+    assert_eq!(
+        RuntimeError::MemoryAccessOutOfBounds,
+        instance.invoke::<i32, i32>(&load, 65536).unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn waiting_requires_a_waiter() {
+    let wasm_bytes = wat::parse_str(COUNTER).unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+
+    let wait = instance
+        .get_function_by_name(DEFAULT_MODULE, "wait")
+        .unwrap();
+    let notify = instance
+        .get_function_by_name(DEFAULT_MODULE, "notify")
+        .unwrap();
+
+    // not waiting at all does not require a waiter
+    assert_eq!(
+        1,
+        instance
+            .invoke::<(i32, i32, i64), i32>(&wait, (0, 1, -1))
+            .unwrap()
+    );
+    assert_eq!(
+        RuntimeError::WaiterNotConfigured,
+        instance
+            .invoke::<(i32, i32, i64), i32>(&wait, (0, 0, -1))
+            .unwrap_err()
+    );
+    assert_eq!(
+        0,
+        instance.invoke::<(i32, i32), i32>(&notify, (0, 1)).unwrap()
+    );
+
+    instance.set_waiter(Arc::new(CondvarWaiter::default()));
+    assert_eq!(
+        2,
+        instance
+            .invoke::<(i32, i32, i64), i32>(&wait, (0, 0, 1_000_000))
+            .unwrap()
+    );
+    assert_eq!(
+        RuntimeError::UnalignedAtomic,
+        instance
+            .invoke::<(i32, i32, i64), i32>(&wait, (1, 0, 0))
+            .unwrap_err()
+    );
+}
+
+#[test_log::test]
+pub fn waiting_on_unshared_memory() {
+    let wasm_bytes = wat::parse_str(
+        r#"
+        (module
+            (memory 1)
+            (func (export "wait") (result i32)
+                (memory.atomic.wait64 (i32.const 0) (i64.const 0) (i64.const 0))
+            )
+            (func (export "notify") (result i32)
+                (memory.atomic.notify (i32.const 0) (i32.const 1))
+            )
+        )"#,
+    )
+    .unwrap();
+    let validation_info = validate(&wasm_bytes).expect("validation failed");
+    let mut instance = RuntimeInstance::new(&validation_info).expect("instantiation failed");
+    instance.set_waiter(Arc::new(CondvarWaiter::default()));
+
+    let wait = instance
+        .get_function_by_name(DEFAULT_MODULE, "wait")
+        .unwrap();
+    let notify = instance
+        .get_function_by_name(DEFAULT_MODULE, "notify")
+        .unwrap();
+
+    assert_eq!(
+        RuntimeError::ExpectedSharedMemory,
+        instance.invoke::<(), i32>(&wait, ()).unwrap_err()
+    );
+    assert_eq!(0, instance.invoke::<(), i32>(&notify, ()).unwrap());
+    assert_eq!(
+        RuntimeError::MemoryNotFound,
+        instance
+            .get_shared_memory(DEFAULT_MODULE, "memory")
+            .err()
+            .unwrap()
+    );
+}
+
+#[test_log::test]
+pub fn threads_increment_a_shared_counter() {
+    let main_bytes = wat::parse_str(COUNTER).unwrap();
+    let worker_bytes = wat::parse_str(WORKER).unwrap();
+    let main_validation_info = validate(&main_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_named("main", &main_validation_info).expect("instantiation failed");
+    let memory = instance.get_shared_memory("main", "memory").unwrap();
+
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            let memory = memory.clone();
+            let worker_bytes = &worker_bytes;
+            scope.spawn(move || {
+                let validation_info = validate(worker_bytes).expect("validation failed");
+                let mut instance = RuntimeInstance::new_empty();
+                instance.add_shared_memory("main", "memory", memory);
+                instance
+                    .add_module("worker", &validation_info)
+                    .expect("instantiation failed");
+
+                let count = instance.get_function_by_name("worker", "count").unwrap();
+                instance.invoke::<i32, ()>(&count, 1000).unwrap();
+            });
+        }
+    });
+
+    let load = instance.get_function_by_name("main", "load").unwrap();
+    assert_eq!(4000, instance.invoke::<i32, i32>(&load, 0).unwrap());
+}
+
+#[test_log::test]
+pub fn wait_for_notification() {
+    let main_bytes = wat::parse_str(COUNTER).unwrap();
+    let worker_bytes = wat::parse_str(WORKER).unwrap();
+    let main_validation_info = validate(&main_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_named("main", &main_validation_info).expect("instantiation failed");
+    let waiter = Arc::new(CondvarWaiter::default());
+    instance.set_waiter(waiter.clone());
+    let memory = instance.get_shared_memory("main", "memory").unwrap();
+
+    let receiver = thread::spawn(move || {
+        let validation_info = validate(&worker_bytes).expect("validation failed");
+        let mut instance = RuntimeInstance::new_empty();
+        instance.set_waiter(waiter);
+        instance.add_shared_memory("main", "memory", memory);
+        instance
+            .add_module("worker", &validation_info)
+            .expect("instantiation failed");
+
+        let receive = instance.get_function_by_name("worker", "receive").unwrap();
+        instance.invoke::<(), i64>(&receive, ()).unwrap()
+    });
+
+    let xchg = instance.get_function_by_name("main", "xchg").unwrap();
+    let add = instance.get_function_by_name("main", "add").unwrap();
+    let notify = instance.get_function_by_name("main", "notify").unwrap();
+
+    instance
+        .invoke::<(i32, i64), i64>(&xchg, (16, 0x1234_5678_9abc))
+        .unwrap();
+    instance.invoke::<(i32, i32), i32>(&add, (8, 1)).unwrap();
+    // the receiver may not have started waiting yet, in which case it sees the flag without waiting
+    let woken = instance.invoke::<(i32, i32), i32>(&notify, (8, 1)).unwrap();
+    assert!(woken <= 1);
+
+    assert_eq!(0x1234_5678_9abc, receiver.join().unwrap());
+}
+
+#[test_log::test]
+pub fn invalid_shared_memories() {
+    let wasm_bytes = wat::parse_str(r#"(module (memory 1 shared))"#).unwrap();
+    assert_eq!(
+        Some(Error::SharedMemoryWithoutMaximum),
+        validate(&wasm_bytes).err()
+    );
+
+    let wasm_bytes = wat::parse_str(
+        r#"(module (memory 1 1 shared) (func (drop (i32.atomic.load align=2 (i32.const 0)))))"#,
+    )
+    .unwrap();
+    assert_eq!(
+        Some(Error::ErroneousAlignment(2, 4)),
+        validate(&wasm_bytes).err()
+    );
+
+    // shared and unshared memories can not be imported as each other
+    let main_bytes = wat::parse_str(COUNTER).unwrap();
+    let main_validation_info = validate(&main_bytes).expect("validation failed");
+    let mut instance =
+        RuntimeInstance::new_named("main", &main_validation_info).expect("instantiation failed");
+
+    let importer_bytes =
+        wat::parse_str(r#"(module (import "main" "memory" (memory 1 1)))"#).unwrap();
+    let importer_validation_info = validate(&importer_bytes).expect("validation failed");
+    assert_eq!(
+        Error::StoreInstantiationError(StoreInstantiationError::IncompatibleImportType(
+            "main".to_owned(),
+            "memory".to_owned()
+        )),
+        instance
+            .add_module("importer", &importer_validation_info)
+            .unwrap_err()
+    );
+}
@@ -0,0 +1,79 @@
+use wasm::{validate, RuntimeError, RuntimeInstance};
+
+const LIB: &str = r#"
+(module
+    (func $div (export "div") (param i32 i32) (result i32)
+        (i32.div_s (local.get 0) (local.get 1))
+    )
+)"#;
+
+const MAIN: &str = r#"
+(module
+    (import "lib" "div" (func $div (param i32 i32) (result i32)))
+    (func $half_of (param i32) (result i32)
+        (call $div (local.get 0) (i32.const 2))
+    )
+    (func $inverse_of (param i32) (result i32)
+        (call $div (i32.const 1) (local.get 0))
+    )
+    (func (export "run") (param i32) (result i32)
+        (call $half_of (local.get 0))
+        (call $inverse_of (local.get 0))
+        i32.add
+    )
+)"#;
+
+#[test_log::test]
+pub fn trap_backtrace() {
+    let lib_bytes = wat::parse_str(LIB).unwrap();
+    let main_bytes = wat::parse_str(MAIN).unwrap();
+    let lib_info = validate(&lib_bytes).expect("validation failed");
+    let main_info = validate(&main_bytes).expect("validation failed");
+
+    let mut instance = RuntimeInstance::new_named("lib", &lib_info).expect("instantiation failed");
+    instance
+        .add_module("main", &main_info)
+        .expect("instantiation failed");
+    let run = instance.get_function_by_name("main", "run").unwrap();
+
+    assert_eq!(Ok(2), instance.invoke::<i32, i32>(&run, 4));
+
+    let trap = instance.invoke::<i32, i32>(&run, 0).unwrap_err();
+    assert_eq!(RuntimeError::DivideBy0, trap.error);
+
+    let frames: Vec<(&str, usize, Option<&str>)> = trap
+        .frames
+        .iter()
+        .map(|frame| {
+            (
+                frame.module_name.as_str(),
+                frame.function_idx,
+                frame.function_name.as_deref(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        vec![
+            ("lib", 0, Some("div")),
+            ("main", 2, Some("inverse_of")),
+            ("main", 3, None),
+        ],
+        frames
+    );
+
+    // the trapping function is located at the trapping instruction, all others right after their active call
+    const I32_DIV_S: u8 = 0x6D;
+    const CALL: u8 = 0x10;
+    assert_eq!(I32_DIV_S, lib_bytes[trap.frames[0].code_offset]);
+    assert_eq!(CALL, main_bytes[trap.frames[1].code_offset - 2]);
+    assert_eq!(CALL, main_bytes[trap.frames[2].code_offset - 2]);
+    assert!(trap.frames[1].code_offset < trap.frames[2].code_offset);
+
+    let trap_log = trap.to_string();
+    assert!(trap_log.starts_with(&RuntimeError::DivideBy0.to_string()));
+    assert!(trap_log.contains("0: func 0 ($div) in module \"lib\" at offset 0x"));
+    assert!(trap_log.contains("2: func 3 in module \"main\" at offset 0x"));
+
+    // the instance stays usable after a trap
+    assert_eq!(Ok(2), instance.invoke::<i32, i32>(&run, 4));
+}
@@ -0,0 +1,153 @@
+use wasm::{validate_with_config, Error, Proposal, ProposalSet, ValidationConfig};
+
+const MODULE: &str = r#"
+(module
+    (table 2 10 funcref)
+    (memory 1 4)
+    (func (param i32) (result i32)
+        (local i64 i64)
+        (block (result i32)
+            (loop (result i32)
+                (if (result i32) (local.get 0)
+                    (then (i32.extend8_s (local.get 0)))
+                    (else (i32.const 0))
+                )
+            )
+        )
+    )
+    (func)
+)"#;
+
+#[test_log::test]
+pub fn default_config_accepts_everything() {
+    let wasm_bytes = wat::parse_str(MODULE).unwrap();
+    assert!(validate_with_config(&wasm_bytes, &ValidationConfig::default()).is_ok());
+
+    // the bounds of the module itself are accepted
+    let config = ValidationConfig {
+        enabled_proposals: [Proposal::SignExtension].into_iter().collect(),
+        max_module_size: Some(wasm_bytes.len()),
+        max_functions: Some(2),
+        max_locals: Some(3),
+        max_table_size: Some(10),
+        max_memory_pages: Some(4),
+        max_nesting_depth: Some(3),
+    };
+    assert!(validate_with_config(&wasm_bytes, &config).is_ok());
+}
+
+#[test_log::test]
+pub fn config_restrictions() {
+    let wasm_bytes = wat::parse_str(MODULE).unwrap();
+    let validate = |config: ValidationConfig| validate_with_config(&wasm_bytes, &config).err();
+
+    assert_eq!(
+        Some(Error::UnsupportedProposal(Proposal::SignExtension)),
+        validate(ValidationConfig {
+            enabled_proposals: ProposalSet::empty(),
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        Some(Error::ModuleTooLarge(wasm_bytes.len())),
+        validate(ValidationConfig {
+            max_module_size: Some(wasm_bytes.len() - 1),
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        Some(Error::TooManyFunctions(2)),
+        validate(ValidationConfig {
+            max_functions: Some(1),
+            ..Default::default()
+        })
+    );
+    assert!(matches!(
+        validate(ValidationConfig {
+            max_locals: Some(2),
+            ..Default::default()
+        }),
+        Some(Error::TooManyLocals(_))
+    ));
+    assert_eq!(
+        Some(Error::TableTooLarge(0)),
+        validate(ValidationConfig {
+            max_table_size: Some(9),
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        Some(Error::MemoryTooLarge(0)),
+        validate(ValidationConfig {
+            max_memory_pages: Some(3),
+            ..Default::default()
+        })
+    );
+    assert_eq!(
+        Some(Error::NestingTooDeep(2)),
+        validate(ValidationConfig {
+            max_nesting_depth: Some(2),
+            ..Default::default()
+        })
+    );
+}
+
+#[test_log::test]
+pub fn memories_without_maximum() {
+    // 32-bit memories can grow to 65536 pages
+    let wasm_bytes = wat::parse_str("(module (memory 0))").unwrap();
+    let config = ValidationConfig {
+        max_memory_pages: Some(u16::MAX.into()),
+        ..Default::default()
+    };
+    assert_eq!(
+        Some(Error::MemoryTooLarge(0)),
+        validate_with_config(&wasm_bytes, &config).err()
+    );
+
+    let config = ValidationConfig {
+        max_memory_pages: Some(1 << 16),
+        ..Default::default()
+    };
+    assert!(validate_with_config(&wasm_bytes, &config).is_ok());
+}
+
+#[test_log::test]
+pub fn mvp_config_rejects_proposals() {
+    let config = ValidationConfig {
+        enabled_proposals: ProposalSet::empty(),
+        ..Default::default()
+    };
+    let cases = [
+        (r#"(module (memory 1) (data "x"))"#, Proposal::BulkMemory),
+        (
+            r#"(module (global i32 (i32.add (i32.const 1) (i32.const 2))))"#,
+            Proposal::ExtendedConst,
+        ),
+        (r#"(module (func (local v128)))"#, Proposal::Simd),
+        (
+            r#"(module (func (block (result i32 i32) (i32.const 1) (i32.const 2)) (drop) (drop)))"#,
+            Proposal::MultiValue,
+        ),
+        (
+            r#"(module (table 1 funcref) (func $f) (elem (i32.const 0) funcref (ref.func $f)))"#,
+            Proposal::ReferenceTypes,
+        ),
+    ];
+
+    for (wat, proposal) in cases {
+        let wasm_bytes = wat::parse_str(wat).unwrap();
+        assert_eq!(
+            Some(Error::UnsupportedProposal(proposal)),
+            validate_with_config(&wasm_bytes, &config).err(),
+            "{wat}"
+        );
+
+        // the module is accepted as soon as the proposal is enabled
+        let config = ValidationConfig {
+            enabled_proposals: [proposal].into_iter().collect(),
+            ..Default::default()
+        };
+        assert!(validate_with_config(&wasm_bytes, &config).is_ok(), "{wat}");
+    }
+}